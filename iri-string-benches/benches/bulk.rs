@@ -0,0 +1,49 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use iri_string::bulk::validate_slice;
+use iri_string::spec::IriSpec;
+use iri_string::validate::iri;
+
+/// Generates rows which look like a real dataset, with some invalid entries.
+fn rows(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| match i % 8 {
+            0 => format!("https://example.com/items/{i}?page={}", i % 100),
+            1 => format!("http://user@sub{}.example.org:8080/a/b/../c", i % 13),
+            2 => format!("urn:uuid:00000000-0000-0000-0000-{i:012}"),
+            3 => format!("https://[2001:db8::{:x}]/\u{03B1}\u{03B2}", i % 0xffff),
+            4 => format!("mailto:user{i}@example.com"),
+            5 => format!("not a valid IRI {i}"),
+            6 => format!("https://example.com/broken%G{i}"),
+            _ => format!("file:///var/data/{i}.csv#row={i}"),
+        })
+        .collect()
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bulk validation");
+    for count in [1_000, 100_000] {
+        let rows = rows(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(
+            BenchmarkId::new("validate_slice", count),
+            &rows,
+            |b, rows| b.iter(|| validate_slice::<IriSpec, _>(rows)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("collect results", count),
+            &rows,
+            |b, rows| {
+                b.iter(|| {
+                    rows.iter()
+                        .map(|s| iri::<IriSpec>(s).is_ok())
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,251 @@
+//! Parsing and construction of `acct:` IRIs.
+//!
+//! See [RFC 7565] for `acct:` URIs. They identify user accounts at service
+//! providers, and are used by WebFinger ([RFC 7033]) and fediverse software.
+//!
+//! An `acct:` IRI consists of the user part and the host, separated by `@`.
+//! The user part is percent-encoded except for unreserved characters and
+//! `sub-delims`, so it can contain `@` (as `%40`).
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::acct::Error;
+//! use iri_string::acct::{account_to_acct, acct_to_account};
+//! use iri_string::types::{IriString, UriString};
+//!
+//! let iri: IriString = account_to_acct("juliet@capulet.example", "shoppingsite.example")?;
+//! assert_eq!(iri, "acct:juliet%40capulet.example@shoppingsite.example");
+//!
+//! let account = acct_to_account(&iri)?;
+//! assert_eq!(account.userpart_str(), "juliet%40capulet.example");
+//! assert_eq!(account.user()?, "juliet@capulet.example");
+//! assert_eq!(account.host_str(), "shoppingsite.example");
+//!
+//! // Non-ASCII characters are kept for IRIs and percent-encoded for URIs.
+//! let iri: IriString = account_to_acct("j\u{FC}rgen", "example.com")?;
+//! assert_eq!(iri, "acct:j\u{FC}rgen@example.com");
+//! let uri: UriString = account_to_acct("j\u{FC}rgen", "example.com")?;
+//! assert_eq!(uri, "acct:j%C3%BCrgen@example.com");
+//! # Ok::<_, Error>(())
+//! ```
+//!
+//! [RFC 7033]: https://datatracker.ietf.org/doc/html/rfc7033
+//! [RFC 7565]: https://datatracker.ietf.org/doc/html/rfc7565
+
+use core::fmt::{self, Write as _};
+
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use crate::parser::char::is_ascii_userinfo_ipvfutureaddr;
+use crate::parser::str::satisfy_chars_with_pct_encoded;
+use crate::percent_decode::decode_bytes;
+use crate::spec::Spec;
+use crate::types::{RiStr, RiString};
+use crate::validate;
+
+/// Error on parsing or construction of `acct:` IRIs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid account or `acct` IRI")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+/// Checks if the ASCII character can appear in the user part without encoding.
+// `userpart = unreserved / sub-delims 0*( unreserved / pct-encoded / sub-delims )`
+#[inline]
+#[must_use]
+fn is_ascii_userpart(c: u8) -> bool {
+    c != b':' && is_ascii_userinfo_ipvfutureaddr(c)
+}
+
+/// Account in an `acct:` IRI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Account<'a> {
+    /// User part, percent-encoded.
+    userpart: &'a str,
+    /// Host.
+    host: &'a str,
+}
+
+impl<'a> Account<'a> {
+    /// Returns the percent-encoded user part.
+    #[inline]
+    #[must_use]
+    pub fn userpart_str(&self) -> &'a str {
+        self.userpart
+    }
+
+    /// Returns the percent-decoded user part.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the decoded user part is not a valid UTF-8 string.
+    pub fn user(&self) -> Result<Cow<'a, str>, Error> {
+        match decode_bytes(self.userpart, false) {
+            Cow::Borrowed(_) => Ok(Cow::Borrowed(self.userpart)),
+            Cow::Owned(bytes) => String::from_utf8(bytes)
+                .map(Cow::Owned)
+                .map_err(|_| Error::new()),
+        }
+    }
+
+    /// Returns the host.
+    ///
+    /// The host is returned as is, i.e. percent-encoded triplets (if any) are
+    /// not decoded.
+    #[inline]
+    #[must_use]
+    pub fn host_str(&self) -> &'a str {
+        self.host
+    }
+}
+
+/// Validates the host of an account.
+///
+/// IP literals are rejected since `[` and `]` cannot appear in the path.
+fn validate_host<S: Spec>(host: &str) -> Result<(), Error> {
+    if host.is_empty() || host.starts_with('[') {
+        return Err(Error::new());
+    }
+    validate::host::<S>(host).map_err(|_| Error::new())
+}
+
+/// Creates an `acct:` IRI from the user and the host.
+///
+/// The user is percent-encoded as necessary. The host should be a valid
+/// `reg-name` or IPv4 address for the spec, i.e. non-ASCII characters in
+/// hosts should be converted into Punycode or percent-encoded beforehand for
+/// URIs.
+///
+/// # Failures
+///
+/// Fails if the user is empty, if the first character of the user should
+/// be percent-encoded (since `userpart` cannot start with a percent-encoded
+/// triplet), or if the host is empty or invalid.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::acct::account_to_acct;
+/// use iri_string::spec::UriSpec;
+/// use iri_string::types::UriString;
+///
+/// assert!(account_to_acct::<UriSpec>("", "example.com").is_err());
+/// assert!(account_to_acct::<UriSpec>("@alice", "example.com").is_err());
+/// assert!(account_to_acct::<UriSpec>("alice", "").is_err());
+/// assert!(account_to_acct::<UriSpec>("alice", "[::1]").is_err());
+///
+/// let uri: UriString = account_to_acct("a:b/c", "example.com").expect("valid account");
+/// assert_eq!(uri, "acct:a%3Ab%2Fc@example.com");
+/// ```
+pub fn account_to_acct<S: Spec>(user: &str, host: &str) -> Result<RiString<S>, Error> {
+    validate_host::<S>(host)?;
+
+    let mut buf = String::with_capacity("acct:@".len() + user.len() + host.len());
+    buf.push_str("acct:");
+    for (i, c) in user.char_indices() {
+        let is_raw = if c.is_ascii() {
+            is_ascii_userpart(c as u8)
+        } else {
+            S::is_nonascii_char_unreserved(c)
+        };
+        if is_raw {
+            buf.push(c);
+        } else if i == 0 {
+            return Err(Error::new());
+        } else {
+            let mut utf8 = [0_u8; 4];
+            for b in c.encode_utf8(&mut utf8).bytes() {
+                write!(buf, "%{:02X}", b).expect("[consistency] writing to a string never fails");
+            }
+        }
+    }
+    if buf.len() == "acct:".len() {
+        return Err(Error::new());
+    }
+    buf.push('@');
+    buf.push_str(host);
+    RiString::try_from(buf).map_err(|_| Error::new())
+}
+
+/// Returns the account in the `acct:` IRI.
+///
+/// # Failures
+///
+/// Fails if the IRI is not an `acct:` IRI, has an authority, a query, or a
+/// fragment, or the user part or the host is invalid.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::acct::Error;
+/// use iri_string::acct::acct_to_account;
+/// use iri_string::types::UriStr;
+///
+/// let uri = UriStr::new("ACCT:alice@example.com").expect("valid URI");
+/// let account = acct_to_account(uri)?;
+/// assert_eq!(account.user()?, "alice");
+/// assert_eq!(account.host_str(), "example.com");
+///
+/// for s in [
+///     "mailto:alice@example.com",
+///     "acct://alice@example.com",
+///     "acct:alice@example.com?q",
+///     "acct:alice",
+///     "acct:%61lice@example.com",
+///     "acct:alice@",
+///     "acct:a:b@example.com",
+/// ] {
+///     let uri = UriStr::new(s).expect("valid URI");
+///     assert!(acct_to_account(uri).is_err(), "{s:?}");
+/// }
+/// # Ok::<_, Error>(())
+/// ```
+pub fn acct_to_account<S: Spec>(iri: &RiStr<S>) -> Result<Account<'_>, Error> {
+    if !iri.scheme_str().eq_ignore_ascii_case("acct")
+        || iri.authority_str().is_some()
+        || iri.query().is_some()
+        || iri.fragment().is_some()
+    {
+        return Err(Error::new());
+    }
+    let path = iri.path_str();
+    let at = path.find('@').ok_or_else(Error::new)?;
+    let (userpart, host) = (&path[..at], &path[(at + 1)..]);
+
+    let is_userpart_valid = !userpart.is_empty()
+        && !userpart.starts_with('%')
+        && satisfy_chars_with_pct_encoded(
+            userpart,
+            is_ascii_userpart,
+            S::is_nonascii_char_unreserved,
+        );
+    if !is_userpart_valid {
+        return Err(Error::new());
+    }
+    validate_host::<S>(host)?;
+
+    Ok(Account { userpart, host })
+}
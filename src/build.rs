@@ -8,16 +8,19 @@ use core::marker::PhantomData;
 #[cfg(feature = "alloc")]
 use alloc::collections::TryReserveError;
 #[cfg(feature = "alloc")]
-use alloc::string::ToString;
+use alloc::string::String;
 
+use crate::escape::EscapeProfile;
 use crate::format::Censored;
 #[cfg(feature = "alloc")]
-use crate::format::{ToDedicatedString, ToStringFallible};
+use crate::format::{try_append_to_string, ToDedicatedString};
 use crate::normalize::{self, NormalizationMode, PathCharacteristic, PctCaseNormalized};
 use crate::parser::str::{find_split, prior_byte2};
 use crate::parser::validate as parser;
 use crate::spec::Spec;
-use crate::types::{RiAbsoluteStr, RiReferenceStr, RiRelativeStr, RiStr};
+use crate::types::{
+    RiAbsoluteStr, RiAuthorityStr, RiHostStr, RiReferenceStr, RiRelativeStr, RiStr,
+};
 #[cfg(feature = "alloc")]
 use crate::types::{RiAbsoluteString, RiReferenceString, RiRelativeString, RiString};
 use crate::validate::Error;
@@ -257,6 +260,50 @@ impl Default for HostRepr<'_> {
     }
 }
 
+/// Components of the IRI reference to build.
+///
+/// This is passed to [`Builder::form`] method, and is useful to emit
+/// absolute or relative forms depending on the context from the same
+/// builder, such as an absolute IRI for `Location` header fields and an
+/// origin-form for HTTP request lines.
+///
+/// When the scheme or the authority is omitted, the path is prefixed as
+/// necessary so that the result is always parsed with the same path:
+///
+/// * a path starting with `//` is prefixed by `/.` when the authority is
+///   absent (otherwise it would be parsed as an authority), and
+/// * a relative path whose first segment contains a colon is prefixed by `./`
+///   when both the scheme and the authority are absent (otherwise the first
+///   segment would be parsed as a scheme).
+///
+/// These prefixes are never added for [`Form::Full`], i.e. such paths are
+/// rejected as usual.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Form {
+    /// All components set to the builder.
+    Full,
+    /// Components except for the scheme (i.e. network-path reference).
+    WithoutScheme,
+    /// Path, query, and fragment (i.e. components except for the scheme and the authority).
+    WithoutOrigin,
+    /// Path and query.
+    ///
+    /// This corresponds to [origin-form] of HTTP request targets. If the
+    /// path is empty, `/` is used instead. Relative paths are rejected, since
+    /// the origin-form requires an absolute path.
+    ///
+    /// [origin-form]: https://www.rfc-editor.org/rfc/rfc9112.html#section-3.2.1
+    PathAndQuery,
+}
+
+impl Default for Form {
+    #[inline]
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
 /// URI/IRI reference builder.
 ///
 /// # Usage
@@ -316,6 +363,17 @@ pub struct Builder<'a> {
     fragment: Option<&'a str>,
     /// Normalization mode.
     normalize: bool,
+    /// Components to build.
+    form: Form,
+    /// Escape profile of the output.
+    escape_profile: EscapeProfile,
+    /// Prefix to be written before the path.
+    ///
+    /// This is set on validation, and is used to keep the path unambiguous
+    /// when some components are omitted by the form.
+    path_prefix: &'static str,
+    /// Capacity to allocate for the owned string of the build result.
+    capacity: usize,
 }
 
 impl<'a> Builder<'a> {
@@ -342,6 +400,49 @@ impl<'a> Builder<'a> {
         Self::default()
     }
 
+    /// Creates a builder with empty data, and with the capacity hint for the result.
+    ///
+    /// When the build result is converted into an owned string, at least
+    /// `capacity` bytes are allocated at once. This is useful to avoid
+    /// repeated reallocations when the size of the resulting IRI is
+    /// predictable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// # #[cfg(feature = "alloc")] {
+    /// use iri_string::build::Builder;
+    /// use iri_string::types::{IriReferenceStr, IriReferenceString};
+    ///
+    /// let mut builder = Builder::with_capacity(64);
+    /// builder.scheme("http");
+    /// builder.host("example.com");
+    ///
+    /// let iri: IriReferenceString = builder.build::<IriReferenceStr>()?.into();
+    /// assert_eq!(iri, "http://example.com");
+    /// assert!(iri.capacity() >= 64);
+    /// # }
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Reserves the capacity hint for the result for at least `additional`
+    /// more bytes.
+    ///
+    /// See [`with_capacity`][`Self::with_capacity`] for the capacity hint.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.capacity = self.capacity.saturating_add(additional);
+    }
+
     /// Writes the authority to the given formatter.
     ///
     /// Don't expose this as public, since this method does not validate.
@@ -371,6 +472,7 @@ impl<'a> Builder<'a> {
             authority.fmt_write_to::<S>(f, self.normalize)?;
         }
 
+        f.write_str(self.path_prefix)?;
         if !self.normalize {
             // No normalization.
             f.write_str(self.path)?;
@@ -442,12 +544,78 @@ impl<'a> Builder<'a> {
     /// # Ok::<_, Error>(())
     /// ```
     #[inline]
-    pub fn build<T>(self) -> Result<Built<'a, T>, Error>
+    pub fn build<T>(mut self) -> Result<Built<'a, T>, Error>
     where
         T: ?Sized + Buildable<'a>,
     {
+        self.apply_form();
         <T as private::Sealed<'a>>::validate_builder(self)
     }
+
+    /// Removes the components omitted by the form.
+    fn apply_form(&mut self) {
+        match self.form {
+            Form::Full => {}
+            Form::WithoutScheme => self.scheme = None,
+            Form::WithoutOrigin => {
+                self.scheme = None;
+                self.authority = None;
+            }
+            Form::PathAndQuery => {
+                self.scheme = None;
+                self.authority = None;
+                self.fragment = None;
+            }
+        }
+    }
+
+    /// Validates the builder and writes the resulting IRI to the given writer.
+    ///
+    /// This is a shorthand for `builder.clone().build::<T>()` followed by
+    /// [`Built::write_to`], and it does not allocate an intermediate string.
+    ///
+    /// Note that the builder itself does not implement [`Display`] since the
+    /// content is not validated until the target type is decided. Use
+    /// [`Built`] returned by [`build`][`Self::build`] for that purpose.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the builder cannot build an IRI of the type `T`, or the
+    /// writer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::build::Builder;
+    /// use iri_string::types::{IriStr, IriRelativeStr};
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.scheme("http");
+    /// builder.host("example.com");
+    /// builder.path("/foo");
+    ///
+    /// # #[cfg(feature = "alloc")] {
+    /// let mut buf = String::from("Location: ");
+    /// builder.write_to::<IriStr, _>(&mut buf)?;
+    /// assert_eq!(buf, "Location: http://example.com/foo");
+    ///
+    /// // Validation error.
+    /// assert!(builder.write_to::<IriRelativeStr, _>(&mut buf).is_err());
+    /// # }
+    /// # Ok::<_, iri_string::build::WriteError>(())
+    /// ```
+    ///
+    /// [`Display`]: `core::fmt::Display`
+    pub fn write_to<T, W>(&self, w: &mut W) -> Result<(), WriteError>
+    where
+        T: ?Sized + Buildable<'a>,
+        Built<'a, T>: fmt::Display,
+        W: ?Sized + fmt::Write,
+    {
+        let built = self.clone().build::<T>()?;
+        write!(w, "{}", built)?;
+        Ok(())
+    }
 }
 
 // Setters does not return `&mut Self` or `Self` since it introduces needless
@@ -566,6 +734,42 @@ impl<'a> Builder<'a> {
         self.authority = None;
     }
 
+    /// Sets the whole authority (i.e. userinfo, host, and port) at once.
+    ///
+    /// This overwrites all of the userinfo, host, and port previously set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::build::Builder;
+    /// use iri_string::types::{IriAuthorityStr, IriReferenceStr};
+    ///
+    /// let authority = IriAuthorityStr::new("user@example.com:8080")?;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.scheme("http");
+    /// builder.authority(authority);
+    ///
+    /// let iri = builder.build::<IriReferenceStr>()?;
+    /// # #[cfg(feature = "alloc")] {
+    /// assert_eq!(iri.to_string(), "http://user@example.com:8080");
+    /// # }
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub fn authority<S: Spec>(&mut self, v: &'a RiAuthorityStr<S>) {
+        let authority = self.authority_builder();
+        authority.userinfo = match v.userinfo() {
+            Some(userinfo) => userinfo.into(),
+            None => UserinfoBuilder::default(),
+        };
+        authority.host = HostRepr::String(v.host().as_str());
+        authority.port = match v.port() {
+            Some(port) => port.into(),
+            None => PortBuilder::default(),
+        };
+    }
+
     /// Sets the userinfo.
     ///
     /// `userinfo` component always have `user` part (but it can be empty).
@@ -685,6 +889,31 @@ impl<'a> Builder<'a> {
         self.authority_builder().host = HostRepr::String(v);
     }
 
+    /// Sets the already validated host.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::build::Builder;
+    /// use iri_string::types::{IriHostStr, IriReferenceStr};
+    ///
+    /// let host = IriHostStr::new("[2001:db8::1]")?;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.validated_host(host);
+    ///
+    /// let iri = builder.build::<IriReferenceStr>()?;
+    /// # #[cfg(feature = "alloc")] {
+    /// assert_eq!(iri.to_string(), "//[2001:db8::1]");
+    /// # }
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub fn validated_host<S: Spec>(&mut self, v: &'a RiHostStr<S>) {
+        self.authority_builder().host = HostRepr::String(v.as_str());
+    }
+
     /// Sets the IP address as a host.
     ///
     /// Note that no methods are provided to "unset" host.
@@ -954,6 +1183,141 @@ impl<'a> Builder<'a> {
     pub fn normalize(&mut self) {
         self.normalize = true;
     }
+
+    /// Sets the components to build.
+    ///
+    /// The components omitted by the form are kept in the builder, and only
+    /// the build result is affected. See [`Form`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::build::{Builder, Form};
+    /// use iri_string::types::{IriReferenceStr, IriStr};
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.scheme("http");
+    /// builder.host("example.com");
+    /// builder.query("q=1");
+    /// builder.fragment("frag");
+    ///
+    /// let absolute = builder.clone().build::<IriStr>()?;
+    /// # #[cfg(feature = "alloc")] {
+    /// assert_eq!(absolute.to_string(), "http://example.com?q=1#frag");
+    /// # }
+    ///
+    /// builder.form(Form::WithoutScheme);
+    /// let network_path = builder.clone().build::<IriReferenceStr>()?;
+    /// # #[cfg(feature = "alloc")] {
+    /// assert_eq!(network_path.to_string(), "//example.com?q=1#frag");
+    /// # }
+    ///
+    /// builder.form(Form::PathAndQuery);
+    /// let request_target = builder.build::<IriReferenceStr>()?;
+    /// # #[cfg(feature = "alloc")] {
+    /// // Empty path is replaced with `/` in the origin-form.
+    /// assert_eq!(request_target.to_string(), "/?q=1");
+    /// # }
+    /// # Ok::<_, Error>(())
+    /// ```
+    ///
+    /// Paths are prefixed as necessary to keep them unambiguous.
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::build::{Builder, Form};
+    /// use iri_string::types::IriReferenceStr;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.scheme("foo");
+    /// builder.path("bar:baz");
+    /// builder.form(Form::WithoutOrigin);
+    ///
+    /// let iri = builder.build::<IriReferenceStr>()?;
+    /// # #[cfg(feature = "alloc")] {
+    /// assert_eq!(iri.to_string(), "./bar:baz");
+    /// # }
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub fn form(&mut self, form: Form) {
+        self.form = form;
+    }
+
+    /// Sets the escape profile of the output.
+    ///
+    /// The components are validated as given, and the profile is applied on
+    /// output. See [`EscapeProfile`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::build::Builder;
+    /// use iri_string::escape::EscapeProfile;
+    /// use iri_string::types::IriStr;
+    ///
+    /// let mut builder = Builder::new();
+    /// builder.scheme("https");
+    /// builder.host("example.com");
+    /// builder.path("/O'Reilly");
+    /// builder.query("sort=name(asc)");
+    /// builder.escape_profile(EscapeProfile::Maximal);
+    ///
+    /// let iri = builder.build::<IriStr>()?;
+    /// # #[cfg(feature = "alloc")] {
+    /// assert_eq!(iri.to_string(), "https://example.com/O%27Reilly?sort=name%28asc%29");
+    /// # }
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub fn escape_profile(&mut self, profile: EscapeProfile) {
+        self.escape_profile = profile;
+    }
+}
+
+/// Error on writing the IRI built by [`Builder::write_to`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteError {
+    /// The builder cannot build the IRI of the requested type.
+    Validation(Error),
+    /// The writer failed.
+    Fmt(fmt::Error),
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Validation(e) => e.fmt(f),
+            Self::Fmt(e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<Error> for WriteError {
+    #[inline]
+    fn from(e: Error) -> Self {
+        Self::Validation(e)
+    }
+}
+
+impl From<fmt::Error> for WriteError {
+    #[inline]
+    fn from(e: fmt::Error) -> Self {
+        Self::Fmt(e)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for WriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Validation(e) => Some(e),
+            Self::Fmt(e) => Some(e),
+        }
+    }
 }
 
 /// [`Display`]-able IRI build result.
@@ -1006,10 +1370,23 @@ macro_rules! impl_stringifiers {
             }
         }
 
+        impl<S: Spec> Built<'_, $borrowed<S>> {
+            /// Writes the IRI to the given writer.
+            ///
+            /// This does not allocate an intermediate string, so this is useful
+            /// to write the IRI directly into buffers such as response bodies.
+            #[inline]
+            pub fn write_to<W: ?Sized + fmt::Write>(&self, w: &mut W) -> fmt::Result {
+                write!(w, "{}", self)
+            }
+        }
+
         impl<S: Spec> fmt::Display for Built<'_, $borrowed<S>> {
             #[inline]
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                self.builder.fmt_write_to::<S>(f, self.path_is_absolute)
+                self.builder.escape_profile.fmt_with(f, |f| {
+                    self.builder.fmt_write_to::<S>(f, self.path_is_absolute)
+                })
             }
         }
 
@@ -1019,7 +1396,9 @@ macro_rules! impl_stringifiers {
 
             #[inline]
             fn try_to_dedicated_string(&self) -> Result<Self::Target, TryReserveError> {
-                let s = self.try_to_string()?;
+                let mut s = String::new();
+                s.try_reserve(self.builder.capacity)?;
+                try_append_to_string(&mut s, self)?;
                 Ok(TryFrom::try_from(s)
                     .expect("[validity] the IRI to be built is already validated"))
             }
@@ -1037,7 +1416,8 @@ macro_rules! impl_stringifiers {
         impl<S: Spec> From<&Built<'_, $borrowed<S>>> for $owned<S> {
             #[inline]
             fn from(builder: &Built<'_, $borrowed<S>>) -> Self {
-                let s = builder.to_string();
+                let mut s = String::with_capacity(builder.builder.capacity);
+                write!(s, "{}", builder).expect("[validity] `Display` for `Built` never fails");
                 Self::try_from(s).expect("[validity] the IRI to be built is already validated")
             }
         }
@@ -1053,8 +1433,8 @@ impl_stringifiers!(RiRelativeStr, RiRelativeString);
 pub trait Buildable<'a>: private::Sealed<'a> {}
 
 impl<'a, S: Spec> private::Sealed<'a> for RiReferenceStr<S> {
-    fn validate_builder(builder: Builder<'a>) -> Result<Built<'a, Self>, Error> {
-        let path_is_absolute = validate_builder_for_iri_reference::<S>(&builder)?;
+    fn validate_builder(mut builder: Builder<'a>) -> Result<Built<'a, Self>, Error> {
+        let path_is_absolute = validate_builder_for_iri_reference::<S>(&mut builder)?;
 
         Ok(Built {
             builder,
@@ -1066,11 +1446,11 @@ impl<'a, S: Spec> private::Sealed<'a> for RiReferenceStr<S> {
 impl<'a, S: Spec> Buildable<'a> for RiReferenceStr<S> {}
 
 impl<'a, S: Spec> private::Sealed<'a> for RiStr<S> {
-    fn validate_builder(builder: Builder<'a>) -> Result<Built<'a, Self>, Error> {
+    fn validate_builder(mut builder: Builder<'a>) -> Result<Built<'a, Self>, Error> {
         if builder.scheme.is_none() {
             return Err(Error::new());
         }
-        let path_is_absolute = validate_builder_for_iri_reference::<S>(&builder)?;
+        let path_is_absolute = validate_builder_for_iri_reference::<S>(&mut builder)?;
 
         Ok(Built {
             builder,
@@ -1082,14 +1462,14 @@ impl<'a, S: Spec> private::Sealed<'a> for RiStr<S> {
 impl<'a, S: Spec> Buildable<'a> for RiStr<S> {}
 
 impl<'a, S: Spec> private::Sealed<'a> for RiAbsoluteStr<S> {
-    fn validate_builder(builder: Builder<'a>) -> Result<Built<'a, Self>, Error> {
+    fn validate_builder(mut builder: Builder<'a>) -> Result<Built<'a, Self>, Error> {
         if builder.scheme.is_none() {
             return Err(Error::new());
         }
         if builder.fragment.is_some() {
             return Err(Error::new());
         }
-        let path_is_absolute = validate_builder_for_iri_reference::<S>(&builder)?;
+        let path_is_absolute = validate_builder_for_iri_reference::<S>(&mut builder)?;
 
         Ok(Built {
             builder,
@@ -1101,11 +1481,11 @@ impl<'a, S: Spec> private::Sealed<'a> for RiAbsoluteStr<S> {
 impl<'a, S: Spec> Buildable<'a> for RiAbsoluteStr<S> {}
 
 impl<'a, S: Spec> private::Sealed<'a> for RiRelativeStr<S> {
-    fn validate_builder(builder: Builder<'a>) -> Result<Built<'a, Self>, Error> {
+    fn validate_builder(mut builder: Builder<'a>) -> Result<Built<'a, Self>, Error> {
         if builder.scheme.is_some() {
             return Err(Error::new());
         }
-        let path_is_absolute = validate_builder_for_iri_reference::<S>(&builder)?;
+        let path_is_absolute = validate_builder_for_iri_reference::<S>(&mut builder)?;
 
         Ok(Built {
             builder,
@@ -1118,8 +1498,9 @@ impl<'a, S: Spec> Buildable<'a> for RiRelativeStr<S> {}
 
 /// Checks whether the builder output is valid IRI reference.
 ///
-/// Returns whether the path is absolute.
-fn validate_builder_for_iri_reference<S: Spec>(builder: &Builder<'_>) -> Result<bool, Error> {
+/// Returns whether the path is absolute. The path prefix of the builder is
+/// also set if necessary.
+fn validate_builder_for_iri_reference<S: Spec>(builder: &mut Builder<'_>) -> Result<bool, Error> {
     if let Some(scheme) = builder.scheme {
         parser::validate_scheme(scheme)?;
     }
@@ -1207,6 +1588,22 @@ fn validate_builder_for_iri_reference<S: Spec>(builder: &Builder<'_>) -> Result<
             prior_byte2(builder.path.as_bytes(), b'/', b':') != Some(b':')
         };
     }
+    builder.path_prefix = "";
+    if builder.form == Form::PathAndQuery && !path_is_absolute && !builder.path.is_empty() {
+        // The origin-form requires an absolute path.
+        return Err(Error::new());
+    }
+    if builder.form != Form::Full {
+        if !is_path_acceptable && builder.authority.is_none() {
+            // The path became ambiguous since some components are omitted.
+            // Note that a path starting with `//` is absolute, and a path
+            // whose first segment has a colon is relative.
+            builder.path_prefix = if path_is_absolute { "/." } else { "./" };
+            is_path_acceptable = true;
+        } else if builder.form == Form::PathAndQuery && builder.path.is_empty() {
+            builder.path_prefix = "/";
+        }
+    }
     if !is_path_acceptable {
         return Err(Error::new());
     }
@@ -0,0 +1,241 @@
+//! Throughput-oriented bulk validation.
+//!
+//! The functions in this module validate many strings at once and record the
+//! results as a compact bit set ([`ValidityBits`]), one bit per entry. This is
+//! intended for data cleaning jobs which process very many rows, where
+//! collecting `Result`s or indices for each row is too costly.
+//!
+//! Entries are processed in chunks of 64, and the result of each entry is
+//! accumulated into a 64-bit word without branching on the validity, so the
+//! cost of the bookkeeping stays small and predictable compared to the
+//! validation itself.
+//!
+//! For the lazy iterator of invalid indices, see
+//! [`validate::validate_all`][`crate::validate::validate_all`].
+//!
+//! # Examples
+//!
+//! ```
+//! use iri_string::bulk::validate_slice;
+//! use iri_string::spec::UriSpec;
+//!
+//! let rows = ["https://example.com/", "not an IRI", "foo:bar", "%GG"];
+//! let bits = validate_slice::<UriSpec, _>(&rows);
+//!
+//! assert_eq!(bits.len(), 4);
+//! assert_eq!(bits.get(0), Some(true));
+//! assert_eq!(bits.get(1), Some(false));
+//! assert_eq!(bits.count_valid(), 2);
+//! assert_eq!(bits.invalid_indices().collect::<Vec<_>>(), [1, 3]);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::spec::Spec;
+use crate::validate::{self, Error};
+
+/// Number of bits in a word.
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Validity of the entries, as a bit set.
+///
+/// The `i`-th bit is set if the `i`-th entry is valid.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidityBits {
+    /// Words of the bits, least significant bit first.
+    ///
+    /// Unused bits in the last word are always zero.
+    words: Vec<u64>,
+    /// Number of the entries.
+    len: usize,
+}
+
+impl ValidityBits {
+    /// Returns the number of the entries.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if there are no entries.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether the entry at the given index is valid.
+    ///
+    /// Returns `None` if the index is out of range.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len {
+            return None;
+        }
+        let word = self.words[index / WORD_BITS];
+        Some((word >> (index % WORD_BITS)) & 1 != 0)
+    }
+
+    /// Returns true if all entries are valid.
+    #[inline]
+    #[must_use]
+    pub fn all_valid(&self) -> bool {
+        self.count_valid() == self.len
+    }
+
+    /// Returns the number of the valid entries.
+    #[must_use]
+    pub fn count_valid(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns the number of the invalid entries.
+    #[inline]
+    #[must_use]
+    pub fn count_invalid(&self) -> usize {
+        self.len - self.count_valid()
+    }
+
+    /// Returns an iterator of the validity of each entry.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |i| (self.words[i / WORD_BITS] >> (i % WORD_BITS)) & 1 != 0)
+    }
+
+    /// Returns an iterator of the indices of the invalid entries, in ascending order.
+    pub fn invalid_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        let len = self.len;
+        self.words.iter().enumerate().flat_map(move |(wi, &word)| {
+            let base = wi * WORD_BITS;
+            let used = (len - base).min(WORD_BITS);
+            let mut invalid = !word & low_bits_mask(used);
+            core::iter::from_fn(move || {
+                if invalid == 0 {
+                    return None;
+                }
+                let bit = invalid.trailing_zeros() as usize;
+                invalid &= invalid - 1;
+                Some(base + bit)
+            })
+        })
+    }
+
+    /// Returns the underlying words, least significant bit first.
+    ///
+    /// The `i`-th entry corresponds to the bit `i % 64` of the word `i / 64`.
+    /// Unused bits in the last word are zero.
+    #[inline]
+    #[must_use]
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Clears the bits, keeping the allocated buffer.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words.clear();
+        self.len = 0;
+    }
+
+    /// Appends the results of the validation to the bits.
+    fn extend_with<T, F>(&mut self, entries: &[T], mut f: F)
+    where
+        T: AsRef<str>,
+        F: FnMut(&str) -> Result<(), Error>,
+    {
+        let rem_bits = self.len % WORD_BITS;
+        let (head, rest) = if rem_bits == 0 {
+            (&entries[..0], entries)
+        } else {
+            entries.split_at((WORD_BITS - rem_bits).min(entries.len()))
+        };
+        if let Some(last) = self.words.last_mut() {
+            for (i, entry) in head.iter().enumerate() {
+                *last |= u64::from(f(entry.as_ref()).is_ok()) << (rem_bits + i);
+            }
+        }
+        self.words.reserve((rest.len() + WORD_BITS - 1) / WORD_BITS);
+        for chunk in rest.chunks(WORD_BITS) {
+            let mut word = 0_u64;
+            for (i, entry) in chunk.iter().enumerate() {
+                word |= u64::from(f(entry.as_ref()).is_ok()) << i;
+            }
+            self.words.push(word);
+        }
+        self.len += entries.len();
+    }
+}
+
+/// Returns a mask with the lowest `n` bits set.
+#[inline]
+#[must_use]
+fn low_bits_mask(n: usize) -> u64 {
+    if n >= WORD_BITS {
+        !0
+    } else {
+        (1 << n) - 1
+    }
+}
+
+/// Validates many [IRIs][iri] at once, and returns the validity of each entry.
+///
+/// This is equivalent to calling [`validate::iri`] for each entry, but the
+/// result is much more compact.
+///
+/// [iri]: https://tools.ietf.org/html/rfc3987#section-2.2
+#[must_use]
+pub fn validate_slice<S: Spec, T: AsRef<str>>(iris: &[T]) -> ValidityBits {
+    validate_slice_with(iris, validate::iri::<S>)
+}
+
+/// Validates many strings at once by the given validator, and returns the validity of each entry.
+///
+/// This is useful to validate the entries as other types than IRIs, for
+/// example as IRI references.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::bulk::validate_slice_with;
+/// use iri_string::spec::UriSpec;
+/// use iri_string::validate::iri_reference;
+///
+/// let rows = ["https://example.com/", "../relative", "%GG"];
+/// let bits = validate_slice_with(&rows, iri_reference::<UriSpec>);
+/// assert_eq!(bits.iter().collect::<Vec<_>>(), [true, true, false]);
+/// ```
+#[must_use]
+pub fn validate_slice_with<T, F>(entries: &[T], validator: F) -> ValidityBits
+where
+    T: AsRef<str>,
+    F: FnMut(&str) -> Result<(), Error>,
+{
+    let mut bits = ValidityBits::default();
+    bits.extend_with(entries, validator);
+    bits
+}
+
+/// Validates many [IRIs][iri] at once, and appends the validity of each entry to the given bits.
+///
+/// This is intended for streaming workloads: validate rows batch by batch
+/// and accumulate the results into one [`ValidityBits`], or reuse the buffer
+/// after [`ValidityBits::clear`].
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::bulk::{validate_slice_into, ValidityBits};
+/// use iri_string::spec::UriSpec;
+///
+/// let mut bits = ValidityBits::default();
+/// validate_slice_into::<UriSpec, _>(&["https://example.com/", "%GG"], &mut bits);
+/// validate_slice_into::<UriSpec, _>(&["foo:bar"], &mut bits);
+/// assert_eq!(bits.iter().collect::<Vec<_>>(), [true, false, true]);
+/// ```
+///
+/// [iri]: https://tools.ietf.org/html/rfc3987#section-2.2
+pub fn validate_slice_into<S: Spec, T: AsRef<str>>(iris: &[T], bits: &mut ValidityBits) {
+    bits.extend_with(iris, validate::iri::<S>);
+}
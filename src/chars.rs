@@ -0,0 +1,166 @@
+//! Character classes of the URI/IRI grammar.
+//!
+//! These predicates are what this crate uses internally, so that downstream
+//! crates writing their own encoders and validators can stay consistent with
+//! the [`Spec`] definitions of this crate.
+//!
+//! Functions with the `i` prefix follow the rule names of [RFC 3987], and
+//! take the spec as a type parameter. With [`UriSpec`][`crate::spec::UriSpec`],
+//! they match the corresponding rules of [RFC 3986] (without the `i` prefix),
+//! i.e. no non-ASCII characters are accepted.
+//!
+//! Note that `pct-encoded` consists of three characters, so it is not
+//! matched by any of these predicates: `%` itself is always rejected. Use
+//! them for the characters that can appear without percent-encoding.
+//!
+//! # Examples
+//!
+//! ```
+//! use iri_string::chars::{is_ipchar, is_iunreserved, is_sub_delim};
+//! use iri_string::spec::{IriSpec, UriSpec};
+//!
+//! assert!(is_iunreserved::<UriSpec>('~'));
+//! assert!(is_iunreserved::<IriSpec>('\u{03B1}'));
+//! assert!(!is_iunreserved::<UriSpec>('\u{03B1}'));
+//!
+//! assert!(is_sub_delim('&'));
+//! assert!(!is_sub_delim('/'));
+//!
+//! assert!(is_ipchar::<UriSpec>(':'));
+//! assert!(is_ipchar::<UriSpec>('@'));
+//! assert!(!is_ipchar::<UriSpec>('/'));
+//! assert!(!is_ipchar::<UriSpec>('%'));
+//! ```
+//!
+//! [RFC 3986]: https://www.rfc-editor.org/rfc/rfc3986.html
+//! [RFC 3987]: https://www.rfc-editor.org/rfc/rfc3987.html
+
+use crate::parser::char as grammar;
+use crate::spec::{IriSpec, Spec};
+
+/// Returns true if the character matches `ALPHA / DIGIT / "+" / "-" / "."`.
+///
+/// These characters can appear in the scheme after the first character,
+/// which should be `ALPHA`.
+#[inline]
+#[must_use]
+pub fn is_scheme_continue(c: char) -> bool {
+    c.is_ascii() && grammar::is_ascii_scheme_continue(c as u8)
+}
+
+/// Returns true if the character matches `gen-delims` rule.
+///
+/// `gen-delims = ":" / "/" / "?" / "#" / "[" / "]" / "@"`.
+#[inline]
+#[must_use]
+pub fn is_gen_delim(c: char) -> bool {
+    c.is_ascii() && grammar::is_ascii_gen_delims(c as u8)
+}
+
+/// Returns true if the character matches `sub-delims` rule.
+///
+/// `sub-delims = "!" / "$" / "&" / "'" / "(" / ")" / "*" / "+" / "," / ";" / "="`.
+#[inline]
+#[must_use]
+pub fn is_sub_delim(c: char) -> bool {
+    c.is_ascii() && grammar::is_ascii_sub_delims(c as u8)
+}
+
+/// Returns true if the character matches `reserved` rule.
+///
+/// `reserved = gen-delims / sub-delims`.
+#[inline]
+#[must_use]
+pub fn is_reserved(c: char) -> bool {
+    c.is_ascii() && grammar::is_ascii_reserved(c as u8)
+}
+
+/// Returns true if the character matches `ucschar` rule of RFC 3987.
+#[inline]
+#[must_use]
+pub fn is_ucschar(c: char) -> bool {
+    grammar::is_ucschar(c)
+}
+
+/// Returns true if the character matches `iprivate` rule of RFC 3987.
+///
+/// These characters are allowed only in the query of IRIs.
+#[inline]
+#[must_use]
+pub fn is_iprivate(c: char) -> bool {
+    !c.is_ascii() && is_nonascii_private::<IriSpec>(c)
+}
+
+/// Returns true if the character matches `iunreserved` (or `unreserved`) rule.
+#[inline]
+#[must_use]
+pub fn is_iunreserved<S: Spec>(c: char) -> bool {
+    grammar::is_unreserved::<S>(c)
+}
+
+/// Returns true if the character matches `ipchar` (or `pchar`) rule, except for `pct-encoded`.
+///
+/// `ipchar = iunreserved / pct-encoded / sub-delims / ":" / "@"`.
+#[inline]
+#[must_use]
+pub fn is_ipchar<S: Spec>(c: char) -> bool {
+    if c.is_ascii() {
+        grammar::is_ascii_pchar(c as u8)
+    } else {
+        S::is_nonascii_char_unreserved(c)
+    }
+}
+
+/// Returns true if the character can appear in `iuserinfo` (or `userinfo`), except for `pct-encoded`.
+#[inline]
+#[must_use]
+pub fn is_iuserinfo_char<S: Spec>(c: char) -> bool {
+    if c.is_ascii() {
+        grammar::is_ascii_userinfo_ipvfutureaddr(c as u8)
+    } else {
+        grammar::is_nonascii_userinfo::<S>(c)
+    }
+}
+
+/// Returns true if the character can appear in `ireg-name` (or `reg-name`), except for `pct-encoded`.
+#[inline]
+#[must_use]
+pub fn is_ireg_name_char<S: Spec>(c: char) -> bool {
+    if c.is_ascii() {
+        grammar::is_ascii_regname(c as u8)
+    } else {
+        grammar::is_nonascii_regname::<S>(c)
+    }
+}
+
+/// Returns true if the character can appear in `iquery` (or `query`), except for `pct-encoded`.
+///
+/// Unlike [`is_ifragment_char`], this accepts [`iprivate`][`is_iprivate`]
+/// characters for IRIs.
+#[inline]
+#[must_use]
+pub fn is_iquery_char<S: Spec>(c: char) -> bool {
+    if c.is_ascii() {
+        grammar::is_ascii_frag_query(c as u8)
+    } else {
+        grammar::is_nonascii_query::<S>(c)
+    }
+}
+
+/// Returns true if the character can appear in `ifragment` (or `fragment`), except for `pct-encoded`.
+#[inline]
+#[must_use]
+pub fn is_ifragment_char<S: Spec>(c: char) -> bool {
+    if c.is_ascii() {
+        grammar::is_ascii_frag_query(c as u8)
+    } else {
+        grammar::is_nonascii_fragment::<S>(c)
+    }
+}
+
+/// Returns true if the non-ASCII character matches `iprivate` rule of the spec.
+#[inline]
+#[must_use]
+fn is_nonascii_private<S: Spec>(c: char) -> bool {
+    S::is_nonascii_char_private(c)
+}
@@ -1,14 +1,32 @@
 //! Components of IRIs.
 
 mod authority;
+mod crawl;
+mod origin;
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "serde")]
+mod ser;
 
+use core::cmp::Ordering;
 use core::num::NonZeroUsize;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
 
 use crate::parser::trusted as trusted_parser;
 use crate::spec::Spec;
 use crate::types::RiReferenceStr;
 
 pub use self::authority::AuthorityComponents;
+pub use self::crawl::{CrawlKey, Partition};
+pub use self::origin::Origin;
+#[cfg(feature = "psl")]
+pub use self::origin::Site;
+#[cfg(feature = "alloc")]
+pub use self::owned::{OwnedAuthority, OwnedComponents};
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use self::ser::ComponentsSer;
 
 /// Components of an IRI reference.
 ///
@@ -66,6 +84,23 @@ impl<'a, S: Spec> RiReferenceComponents<'a, S> {
         (scheme, authority, path, query, fragment)
     }
 
+    /// Returns the byte range of the path in the IRI reference.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub(crate) fn path_range(&self) -> Range<usize> {
+        let s = self.iri.as_str();
+        let start = match (self.authority_end, self.scheme_end) {
+            (Some(end), _) => end.get(),
+            (None, Some(end)) => end.get() + 1,
+            (None, None) => 0,
+        };
+        let end = match (self.query_start, self.fragment_start) {
+            (Some(start), _) | (None, Some(start)) => start.get() - 1,
+            (None, None) => s.len(),
+        };
+        start..end
+    }
+
     /// Returns the IRI reference.
     #[inline]
     #[must_use]
@@ -80,3 +115,20 @@ impl<'a, S: Spec> From<&'a RiReferenceStr<S>> for RiReferenceComponents<'a, S> {
         trusted_parser::decompose_iri_reference(s)
     }
 }
+
+/// Compares IRI references by scheme, authority, path segments, query, and fragment.
+///
+/// Absent components are less than present components.
+#[must_use]
+pub(crate) fn component_cmp<S: Spec>(lhs: &RiReferenceStr<S>, rhs: &RiReferenceStr<S>) -> Ordering {
+    let (l_scheme, l_authority, l_path, l_query, l_fragment) =
+        RiReferenceComponents::from(lhs).to_major();
+    let (r_scheme, r_authority, r_path, r_query, r_fragment) =
+        RiReferenceComponents::from(rhs).to_major();
+    l_scheme
+        .cmp(&r_scheme)
+        .then_with(|| l_authority.cmp(&r_authority))
+        .then_with(|| l_path.split('/').cmp(r_path.split('/')))
+        .then_with(|| l_query.cmp(&r_query))
+        .then_with(|| l_fragment.cmp(&r_fragment))
+}
@@ -11,6 +11,7 @@ use crate::types::RiReferenceStr;
 ///
 /// [`RiStr::authority_components`]: `crate::types::RiStr::authority_components`
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "yoke", derive(yoke::Yokeable))]
 pub struct AuthorityComponents<'a> {
     /// Authority string, excluding the leading `//`.
     pub(crate) authority: &'a str,
@@ -53,6 +54,40 @@ impl<'a> AuthorityComponents<'a> {
         debug_assert_eq!(self.authority.as_bytes()[port_colon], b':');
         Some(&self.authority[(port_colon + 1)..])
     }
+
+    /// Returns the registrable domain (i.e. eTLD+1) of the host, using Public Suffix List.
+    ///
+    /// Returns `None` if the host is an IP address, or the host has no
+    /// registrable domain (for example when the host is a public suffix
+    /// itself). The trailing dot of the host is ignored.
+    ///
+    /// The host is used as is except for ASCII case, i.e. percent-encoded
+    /// hosts are not decoded and IDNs are not converted. Normalize the IRI
+    /// beforehand if necessary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::components::AuthorityComponents;
+    /// use iri_string::types::IriReferenceStr;
+    ///
+    /// let iri = IriReferenceStr::new("https://www.Example.CO.UK/")?;
+    /// let authority = AuthorityComponents::from_iri(iri).expect("has authority");
+    /// assert_eq!(authority.registrable_domain(), Some("Example.CO.UK"));
+    ///
+    /// let suffix = IriReferenceStr::new("https://github.io/")?;
+    /// let authority = AuthorityComponents::from_iri(suffix).expect("has authority");
+    /// assert_eq!(authority.registrable_domain(), None);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "psl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "psl")))]
+    #[inline]
+    #[must_use]
+    pub fn registrable_domain(&self) -> Option<&'a str> {
+        super::origin::registrable_domain(self.host())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,187 @@
+//! Grouping keys for crawl scheduling.
+
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+use crate::components::origin::hash_ascii_lowercase;
+use crate::components::AuthorityComponents;
+use crate::parser::validate::validate_ipv4address;
+use crate::spec::Spec;
+use crate::types::RiReferenceStr;
+
+/// Granularity of [`CrawlKey`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Partition {
+    /// Groups IRIs by the host.
+    Host,
+    /// Groups IRIs by the registrable domain (eTLD+1) of the host.
+    ///
+    /// Hosts without registrable domains (such as IP addresses and public
+    /// suffixes) are used as is.
+    #[cfg(feature = "psl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "psl")))]
+    RegistrableDomain,
+}
+
+/// Key to group IRIs by their hosts, for politeness partitioning of crawlers.
+///
+/// The key ignores the scheme, the userinfo, and the port, since they are
+/// usually served by the same machine. The host is compared ASCII
+/// case-insensitively, and the trailing dot is ignored.
+///
+/// # Ordering
+///
+/// Domain names are ordered by their labels from the rightmost one, so that
+/// hosts in the same domain are adjacent when sorted (for example,
+/// `example.com`, `a.example.com`, and `b.example.com` come in this order,
+/// followed by `example.net`). IP addresses come before domain names, and
+/// are compared as strings.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::components::{CrawlKey, Partition};
+/// use iri_string::types::IriReferenceStr;
+///
+/// let key = |s| CrawlKey::from_iri(IriReferenceStr::new(s).unwrap(), Partition::Host);
+///
+/// let a = key("https://user@Example.COM:8443/foo").expect("has authority");
+/// let b = key("http://example.com./bar").expect("has authority");
+/// assert_eq!(a.host(), "Example.COM");
+/// assert_eq!(a, b);
+///
+/// assert!(key("http://example.com/").unwrap() < key("http://a.example.com/").unwrap());
+/// assert!(key("http://z.example.com/").unwrap() < key("http://example.net/").unwrap());
+/// assert!(key("http://192.0.2.1/").unwrap() < key("http://a.example/").unwrap());
+///
+/// assert_eq!(key("mailto:user@example.com"), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CrawlKey<'a> {
+    /// Host or registrable domain, without the trailing dot.
+    host: &'a str,
+    /// Whether the host is an IP address.
+    is_ip: bool,
+}
+
+impl<'a> CrawlKey<'a> {
+    /// Creates a new key from the IRI.
+    ///
+    /// Returns `None` if the IRI does not have an authority, or the host is
+    /// empty.
+    ///
+    /// The host is used as is except for ASCII case, i.e. percent-encoded
+    /// hosts are not decoded and IDNs are not converted. Normalize the IRI
+    /// beforehand if necessary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "psl")] {
+    /// use iri_string::components::{CrawlKey, Partition};
+    /// use iri_string::types::IriReferenceStr;
+    ///
+    /// let iri = IriReferenceStr::new("https://static.example.co.uk/img.png").unwrap();
+    /// let by_host = CrawlKey::from_iri(iri, Partition::Host).expect("has host");
+    /// assert_eq!(by_host.host(), "static.example.co.uk");
+    /// let by_site = CrawlKey::from_iri(iri, Partition::RegistrableDomain).expect("has host");
+    /// assert_eq!(by_site.host(), "example.co.uk");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn from_iri<S: Spec>(iri: &'a RiReferenceStr<S>, partition: Partition) -> Option<Self> {
+        let host = AuthorityComponents::from_iri(iri)?.host();
+        Self::from_host(host, partition)
+    }
+
+    /// Creates a new key from the host.
+    ///
+    /// The host should be valid as a host of IRIs, not percent-decoded.
+    /// Returns `None` if the host is empty.
+    #[must_use]
+    fn from_host(host: &'a str, partition: Partition) -> Option<Self> {
+        let is_ip = host.starts_with('[') || validate_ipv4address(host).is_ok();
+        let host = match partition {
+            Partition::Host => host,
+            #[cfg(feature = "psl")]
+            Partition::RegistrableDomain => {
+                crate::components::origin::registrable_domain(host).unwrap_or(host)
+            }
+        };
+        let host = if is_ip {
+            host
+        } else {
+            host.strip_suffix('.').unwrap_or(host)
+        };
+        if host.is_empty() {
+            return None;
+        }
+        Some(Self { host, is_ip })
+    }
+
+    /// Returns the host (or the registrable domain) used as the key.
+    ///
+    /// The trailing dot is removed, and the case is preserved.
+    #[inline]
+    #[must_use]
+    pub fn host(&self) -> &'a str {
+        self.host
+    }
+
+    /// Returns true if the host is an IP address.
+    #[inline]
+    #[must_use]
+    pub fn is_ip(&self) -> bool {
+        self.is_ip
+    }
+}
+
+impl PartialEq for CrawlKey<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.host.eq_ignore_ascii_case(other.host)
+    }
+}
+
+impl Eq for CrawlKey<'_> {}
+
+impl PartialOrd for CrawlKey<'_> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CrawlKey<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_ip, other.is_ip) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (true, true) => cmp_ascii_lowercase(self.host, other.host),
+            (false, false) => self
+                .host
+                .rsplit('.')
+                .zip(other.host.rsplit('.'))
+                .map(|(l, r)| cmp_ascii_lowercase(l, r))
+                .find(|&ord| ord != Ordering::Equal)
+                .unwrap_or_else(|| {
+                    let count = |s: &str| s.bytes().filter(|&b| b == b'.').count();
+                    count(self.host).cmp(&count(other.host))
+                }),
+        }
+    }
+}
+
+impl Hash for CrawlKey<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_ascii_lowercase(self.host, state);
+    }
+}
+
+/// Compares the strings ASCII case-insensitively.
+fn cmp_ascii_lowercase(l: &str, r: &str) -> Ordering {
+    l.bytes()
+        .map(|b| b.to_ascii_lowercase())
+        .cmp(r.bytes().map(|b| b.to_ascii_lowercase()))
+}
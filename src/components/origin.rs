@@ -0,0 +1,250 @@
+//! Origin and site of IRIs.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use crate::components::AuthorityComponents;
+use crate::spec::Spec;
+use crate::types::RiReferenceStr;
+
+/// Origin of an IRI, i.e. the scheme, the host, and the port.
+///
+/// See [RFC 6454](https://datatracker.ietf.org/doc/html/rfc6454).
+///
+/// The scheme and the host are compared ASCII case-insensitively. Others are
+/// compared as is; especially, no default ports are assumed (i.e.
+/// `http://example.com` and `http://example.com:80` are different origins).
+/// An empty port is treated as absent.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::components::Origin;
+/// use iri_string::types::IriReferenceStr;
+///
+/// let iri = IriReferenceStr::new("https://user@Example.COM:8443/path?query")?;
+/// let origin = Origin::from_iri(iri).expect("the IRI has an authority");
+/// assert_eq!(origin.scheme(), "https");
+/// assert_eq!(origin.host(), "Example.COM");
+/// assert_eq!(origin.port(), Some("8443"));
+/// assert_eq!(origin.to_string(), "https://Example.COM:8443");
+///
+/// let other = IriReferenceStr::new("HTTPS://example.com:8443/other")?;
+/// assert_eq!(Origin::from_iri(other), Some(origin));
+///
+/// let opaque = IriReferenceStr::new("mailto:user@example.com")?;
+/// assert_eq!(Origin::from_iri(opaque), None);
+/// # Ok::<_, Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Origin<'a> {
+    /// Scheme.
+    scheme: &'a str,
+    /// Host.
+    host: &'a str,
+    /// Non-empty port.
+    port: Option<&'a str>,
+}
+
+impl<'a> Origin<'a> {
+    /// Creates a new `Origin` from the IRI.
+    ///
+    /// Returns `None` if the IRI does not have a scheme or an authority.
+    #[must_use]
+    pub fn from_iri<S: Spec>(iri: &'a RiReferenceStr<S>) -> Option<Self> {
+        let scheme = iri.scheme_str()?;
+        let authority = AuthorityComponents::from_iri(iri)?;
+        Some(Self {
+            scheme,
+            host: authority.host(),
+            port: authority.port().filter(|port| !port.is_empty()),
+        })
+    }
+
+    /// Returns the scheme.
+    #[inline]
+    #[must_use]
+    pub fn scheme(&self) -> &'a str {
+        self.scheme
+    }
+
+    /// Returns the host.
+    #[inline]
+    #[must_use]
+    pub fn host(&self) -> &'a str {
+        self.host
+    }
+
+    /// Returns the port, if non-empty one is specified.
+    #[inline]
+    #[must_use]
+    pub fn port(&self) -> Option<&'a str> {
+        self.port
+    }
+
+    /// Returns the site of the origin, i.e. the scheme and the registrable domain.
+    ///
+    /// If the host has no registrable domain (for example when the host is an
+    /// IP address or a public suffix itself), the host is used as is. This is
+    /// consistent with "obtain a site" algorithm of [HTML Standard].
+    ///
+    /// See [`AuthorityComponents::registrable_domain`] for detail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::components::Origin;
+    /// use iri_string::types::IriReferenceStr;
+    ///
+    /// let a = IriReferenceStr::new("https://www.example.co.uk/")?;
+    /// let b = IriReferenceStr::new("https://static.example.co.uk:8443/")?;
+    /// let site_a = Origin::from_iri(a).expect("has authority").site();
+    /// let site_b = Origin::from_iri(b).expect("has authority").site();
+    /// assert_eq!(site_a.domain(), "example.co.uk");
+    /// assert_eq!(site_a, site_b);
+    /// assert_eq!(site_a.to_string(), "https://example.co.uk");
+    ///
+    /// let ip = IriReferenceStr::new("http://192.0.2.1/")?;
+    /// assert_eq!(Origin::from_iri(ip).expect("has authority").site().domain(), "192.0.2.1");
+    /// # Ok::<_, Error>(())
+    /// ```
+    ///
+    /// [HTML Standard]: https://html.spec.whatwg.org/multipage/browsers.html#obtain-a-site
+    #[cfg(feature = "psl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "psl")))]
+    #[must_use]
+    pub fn site(&self) -> Site<'a> {
+        Site {
+            scheme: self.scheme,
+            domain: registrable_domain(self.host).unwrap_or(self.host),
+        }
+    }
+}
+
+impl fmt::Display for Origin<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", self.scheme, self.host)?;
+        if let Some(port) = self.port {
+            write!(f, ":{}", port)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Origin<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.scheme.eq_ignore_ascii_case(other.scheme)
+            && self.host.eq_ignore_ascii_case(other.host)
+            && (self.port == other.port)
+    }
+}
+
+impl Eq for Origin<'_> {}
+
+impl Hash for Origin<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_ascii_lowercase(self.scheme, state);
+        hash_ascii_lowercase(self.host, state);
+        self.port.hash(state);
+    }
+}
+
+/// Site, i.e. the scheme and the registrable domain.
+///
+/// This is a return type of [`Origin::site`].
+///
+/// The scheme and the domain are compared ASCII case-insensitively.
+#[cfg(feature = "psl")]
+#[cfg_attr(docsrs, doc(cfg(feature = "psl")))]
+#[derive(Debug, Clone, Copy)]
+pub struct Site<'a> {
+    /// Scheme.
+    scheme: &'a str,
+    /// Registrable domain, or the host if unavailable.
+    domain: &'a str,
+}
+
+#[cfg(feature = "psl")]
+impl<'a> Site<'a> {
+    /// Returns the scheme.
+    #[inline]
+    #[must_use]
+    pub fn scheme(&self) -> &'a str {
+        self.scheme
+    }
+
+    /// Returns the registrable domain, or the host if the domain is unavailable.
+    #[inline]
+    #[must_use]
+    pub fn domain(&self) -> &'a str {
+        self.domain
+    }
+}
+
+#[cfg(feature = "psl")]
+impl fmt::Display for Site<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", self.scheme, self.domain)
+    }
+}
+
+#[cfg(feature = "psl")]
+impl PartialEq for Site<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.scheme.eq_ignore_ascii_case(other.scheme)
+            && self.domain.eq_ignore_ascii_case(other.domain)
+    }
+}
+
+#[cfg(feature = "psl")]
+impl Eq for Site<'_> {}
+
+#[cfg(feature = "psl")]
+impl Hash for Site<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_ascii_lowercase(self.scheme, state);
+        hash_ascii_lowercase(self.domain, state);
+    }
+}
+
+/// Feeds the ASCII-lowercased string into the hasher.
+pub(super) fn hash_ascii_lowercase<H: Hasher>(s: &str, state: &mut H) {
+    s.bytes()
+        .for_each(|b| state.write_u8(b.to_ascii_lowercase()));
+    // Terminator, as `str` does.
+    state.write_u8(0xff);
+}
+
+/// Returns the registrable domain (eTLD+1) of the host.
+///
+/// The result is a suffix of the given host, excluding the trailing dot.
+#[cfg(feature = "psl")]
+#[must_use]
+pub(crate) fn registrable_domain(host: &str) -> Option<&str> {
+    use crate::parser::validate::validate_ipv4address;
+
+    /// Maximum length of the host to be lowercased.
+    ///
+    /// This is long enough for DNS names.
+    const MAX_LOWERCASE_LEN: usize = 256;
+
+    if host.starts_with('[') || validate_ipv4address(host).is_ok() {
+        // IP address.
+        return None;
+    }
+    let host = host.strip_suffix('.').unwrap_or(host);
+    let domain_len = if host.bytes().any(|b| b.is_ascii_uppercase()) {
+        // Public Suffix List lookup is case-sensitive.
+        let mut buf = [0_u8; MAX_LOWERCASE_LEN];
+        let buf = buf.get_mut(..host.len())?;
+        buf.copy_from_slice(host.as_bytes());
+        buf.make_ascii_lowercase();
+        psl::domain(buf)?.as_bytes().len()
+    } else {
+        psl::domain(host.as_bytes())?.as_bytes().len()
+    };
+    Some(&host[(host.len() - domain_len)..])
+}
@@ -0,0 +1,396 @@
+//! Owned components of IRIs.
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+
+use crate::components::{AuthorityComponents, RiReferenceComponents};
+use crate::parser::validate::{validate_host, validate_path, validate_scheme, validate_userinfo};
+use crate::spec::Spec;
+use crate::types::{
+    RiFragmentStr, RiFragmentString, RiQueryStr, RiQueryString, RiReferenceStr, RiReferenceString,
+};
+use crate::validate::Error;
+
+/// Owned subcomponents of authority.
+///
+/// This is a part of [`OwnedComponents`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedAuthority {
+    /// Userinfo.
+    userinfo: Option<String>,
+    /// Host.
+    host: String,
+    /// Port.
+    port: Option<String>,
+}
+
+impl OwnedAuthority {
+    /// Returns the `userinfo` part, excluding the following `@`.
+    #[inline]
+    #[must_use]
+    pub fn userinfo(&self) -> Option<&str> {
+        self.userinfo.as_deref()
+    }
+
+    /// Returns the `host` part.
+    #[inline]
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the `port` part, excluding the following `:`.
+    #[inline]
+    #[must_use]
+    pub fn port(&self) -> Option<&str> {
+        self.port.as_deref()
+    }
+
+    /// Returns the userinfo, the host, and the port.
+    #[inline]
+    #[must_use]
+    pub fn into_parts(self) -> (Option<String>, String, Option<String>) {
+        (self.userinfo, self.host, self.port)
+    }
+}
+
+impl<'a> From<AuthorityComponents<'a>> for OwnedAuthority {
+    fn from(v: AuthorityComponents<'a>) -> Self {
+        Self {
+            userinfo: v.userinfo().map(Into::into),
+            host: v.host().into(),
+            port: v.port().map(Into::into),
+        }
+    }
+}
+
+/// Owned components of an IRI reference.
+///
+/// This is a return type of the `into_components` method of the owned string
+/// types (for example [`RiString::into_components`]).
+///
+/// Each component is already known to be valid, so that no validation is
+/// done on the decomposition.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::types::IriString;
+///
+/// let iri = "https://user@example.com:8080/path?query#frag".parse::<IriString>()?;
+/// let components = iri.into_components();
+///
+/// assert_eq!(components.scheme_str(), Some("https"));
+/// let authority = components.authority().expect("the IRI has an authority");
+/// assert_eq!(authority.userinfo(), Some("user"));
+/// assert_eq!(authority.host(), "example.com");
+/// assert_eq!(authority.port(), Some("8080"));
+/// assert_eq!(components.path_str(), "/path");
+/// assert_eq!(components.query().map(|q| q.as_str()), Some("query"));
+/// assert_eq!(components.fragment().map(|f| f.as_str()), Some("frag"));
+///
+/// let (scheme, _authority, path, _query, _fragment) = components.into_parts();
+/// let scheme: String = scheme.expect("the IRI has a scheme");
+/// let path: String = path;
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// Components can be modified and then recomposed into an IRI. Setters
+/// validate the given component, and recomposition only checks the
+/// consistency among components, so no components are parsed again.
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::types::{IriFragmentStr, IriString};
+///
+/// let iri = "http://example.com/path?query#frag".parse::<IriString>()?;
+/// let mut components = iri.into_components();
+/// components.set_scheme(Some("https"))?;
+/// components.set_port(Some("8443"))?;
+/// components.set_fragment(Some(IriFragmentStr::new("top")?));
+///
+/// let iri = IriString::from_components(&components)?;
+/// assert_eq!(iri, "https://example.com:8443/path?query#top");
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// [`RiString::into_components`]: `crate::types::RiString::into_components`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedComponents<S: Spec> {
+    /// Scheme.
+    scheme: Option<String>,
+    /// Authority.
+    authority: Option<OwnedAuthority>,
+    /// Path.
+    path: String,
+    /// Query.
+    query: Option<RiQueryString<S>>,
+    /// Fragment.
+    fragment: Option<RiFragmentString<S>>,
+}
+
+impl<S: Spec> OwnedComponents<S> {
+    /// Decomposes the IRI reference into owned components.
+    #[must_use]
+    pub(crate) fn from_reference(iri: &RiReferenceStr<S>) -> Self {
+        let (scheme, _, path, query, fragment) = RiReferenceComponents::from(iri).to_major();
+        Self {
+            scheme: scheme.map(Into::into),
+            authority: AuthorityComponents::from_iri(iri).map(Into::into),
+            path: path.into(),
+            // SAFETY: the query of the valid IRI reference is a valid query.
+            query: query.map(|query| unsafe { RiQueryString::new_maybe_unchecked(query.into()) }),
+            // SAFETY: the fragment of the valid IRI reference is a valid fragment.
+            fragment: fragment
+                .map(|fragment| unsafe { RiFragmentString::new_maybe_unchecked(fragment.into()) }),
+        }
+    }
+
+    /// Returns the scheme.
+    #[inline]
+    #[must_use]
+    pub fn scheme_str(&self) -> Option<&str> {
+        self.scheme.as_deref()
+    }
+
+    /// Returns the authority.
+    #[inline]
+    #[must_use]
+    pub fn authority(&self) -> Option<&OwnedAuthority> {
+        self.authority.as_ref()
+    }
+
+    /// Returns the path.
+    #[inline]
+    #[must_use]
+    pub fn path_str(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the query.
+    #[inline]
+    #[must_use]
+    pub fn query(&self) -> Option<&RiQueryStr<S>> {
+        self.query.as_deref()
+    }
+
+    /// Returns the fragment.
+    #[inline]
+    #[must_use]
+    pub fn fragment(&self) -> Option<&RiFragmentStr<S>> {
+        self.fragment.as_deref()
+    }
+
+    /// Returns the scheme, the authority, the path, the query, and the fragment.
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    #[must_use]
+    pub fn into_parts(
+        self,
+    ) -> (
+        Option<String>,
+        Option<OwnedAuthority>,
+        String,
+        Option<RiQueryString<S>>,
+        Option<RiFragmentString<S>>,
+    ) {
+        (
+            self.scheme,
+            self.authority,
+            self.path,
+            self.query,
+            self.fragment,
+        )
+    }
+
+    /// Sets the scheme.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the given scheme is invalid.
+    pub fn set_scheme(&mut self, scheme: Option<&str>) -> Result<(), Error> {
+        if let Some(scheme) = scheme {
+            if scheme.is_empty() {
+                return Err(Error::new());
+            }
+            validate_scheme(scheme)?;
+        }
+        self.scheme = scheme.map(ToOwned::to_owned);
+        Ok(())
+    }
+
+    /// Sets the host.
+    ///
+    /// If `None` is given, the whole authority (including the userinfo and
+    /// the port) is removed.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the given host is invalid.
+    pub fn set_host(&mut self, host: Option<&str>) -> Result<(), Error> {
+        let host = match host {
+            Some(v) => v,
+            None => {
+                self.authority = None;
+                return Ok(());
+            }
+        };
+        validate_host::<S>(host)?;
+        match &mut self.authority {
+            Some(authority) => {
+                authority.host.clear();
+                authority.host.push_str(host);
+            }
+            None => {
+                self.authority = Some(OwnedAuthority {
+                    userinfo: None,
+                    host: host.to_owned(),
+                    port: None,
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the userinfo.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the given userinfo is invalid, or if a userinfo is given while
+    /// the authority is absent.
+    pub fn set_userinfo(&mut self, userinfo: Option<&str>) -> Result<(), Error> {
+        match (&mut self.authority, userinfo) {
+            (Some(authority), Some(userinfo)) => {
+                validate_userinfo::<S>(userinfo)?;
+                authority.userinfo = Some(userinfo.to_owned());
+            }
+            (Some(authority), None) => authority.userinfo = None,
+            (None, Some(_)) => return Err(Error::new()),
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    /// Sets the port.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the given port is invalid, or if a port is given while the
+    /// authority is absent.
+    pub fn set_port(&mut self, port: Option<&str>) -> Result<(), Error> {
+        match (&mut self.authority, port) {
+            (Some(authority), Some(port)) => {
+                if !port.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(Error::new());
+                }
+                authority.port = Some(port.to_owned());
+            }
+            (Some(authority), None) => authority.port = None,
+            (None, Some(_)) => return Err(Error::new()),
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    /// Sets the path.
+    ///
+    /// Consistency with other components (for example, a path with an
+    /// authority must be empty or start with a slash) is checked on
+    /// recomposition.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the given path contains invalid characters.
+    pub fn set_path(&mut self, path: &str) -> Result<(), Error> {
+        // `//` at the beginning is allowed as long as the authority is present.
+        let path_without_double_slash = match path.strip_prefix('/') {
+            Some(rest) if rest.starts_with('/') => rest,
+            _ => path,
+        };
+        validate_path::<S>(path_without_double_slash)?;
+        self.path.clear();
+        self.path.push_str(path);
+        Ok(())
+    }
+
+    /// Sets the query.
+    #[inline]
+    pub fn set_query(&mut self, query: Option<&RiQueryStr<S>>) {
+        self.query = query.map(ToOwned::to_owned);
+    }
+
+    /// Sets the fragment.
+    #[inline]
+    pub fn set_fragment(&mut self, fragment: Option<&RiFragmentStr<S>>) {
+        self.fragment = fragment.map(ToOwned::to_owned);
+    }
+
+    /// Recomposes the components into an IRI reference.
+    ///
+    /// This follows [RFC 3986 section 5.3].
+    ///
+    /// # Failures
+    ///
+    /// Fails if the components are inconsistent, i.e. when:
+    ///
+    /// * the authority is present and the path is neither empty nor starting
+    ///   with a slash,
+    /// * the authority is absent and the path starts with `//`, or
+    /// * the scheme and the authority are absent and the first path segment
+    ///   contains a colon.
+    ///
+    /// [RFC 3986 section 5.3]: https://datatracker.ietf.org/doc/html/rfc3986#section-5.3
+    pub fn build(&self) -> Result<RiReferenceString<S>, Error> {
+        let path = self.path.as_str();
+        let is_consistent = match (&self.scheme, &self.authority) {
+            (_, Some(_)) => path.is_empty() || path.starts_with('/'),
+            (Some(_), None) => !path.starts_with("//"),
+            (None, None) => {
+                !path.starts_with("//")
+                    && !path
+                        .split('/')
+                        .next()
+                        .map_or(false, |seg| seg.contains(':'))
+            }
+        };
+        if !is_consistent {
+            return Err(Error::new());
+        }
+
+        let mut buf = String::new();
+        if let Some(scheme) = &self.scheme {
+            buf.push_str(scheme);
+            buf.push(':');
+        }
+        if let Some(authority) = &self.authority {
+            buf.push_str("//");
+            if let Some(userinfo) = &authority.userinfo {
+                buf.push_str(userinfo);
+                buf.push('@');
+            }
+            buf.push_str(&authority.host);
+            if let Some(port) = &authority.port {
+                buf.push(':');
+                buf.push_str(port);
+            }
+        }
+        buf.push_str(path);
+        if let Some(query) = &self.query {
+            buf.push('?');
+            buf.push_str(query.as_str());
+        }
+        if let Some(fragment) = &self.fragment {
+            buf.push('#');
+            buf.push_str(fragment.as_str());
+        }
+
+        debug_assert!(
+            RiReferenceStr::<S>::new(&buf).is_ok(),
+            "[validity] consistent valid components must compose a valid IRI reference"
+        );
+        // SAFETY: each component is valid, and the consistency among
+        // components is checked above.
+        Ok(unsafe { RiReferenceString::new_maybe_unchecked(buf) })
+    }
+}
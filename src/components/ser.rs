@@ -0,0 +1,108 @@
+//! Serializable view of the components.
+
+use serde::Serialize;
+
+use crate::components::{AuthorityComponents, RiReferenceComponents};
+use crate::spec::Spec;
+use crate::types::RiReferenceStr;
+
+/// Serializable view of how an IRI reference is decomposed into components.
+///
+/// This serializes the parsed structure rather than the string itself, so
+/// that debugging tools and test fixtures can record the exact result of the
+/// decomposition. Absent components are serialized as `None` and empty
+/// components as empty strings, so they are distinguishable.
+///
+/// The serialized form is a struct named `Components` with the following
+/// fields:
+///
+/// * `iri`: the whole IRI reference.
+/// * `scheme`: optional string.
+/// * `authority`: optional struct named `Authority` with the fields
+///   `userinfo` (optional string), `host` (string), and `port` (optional
+///   string).
+/// * `path`: string.
+/// * `query`: optional string.
+/// * `fragment`: optional string.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::components::ComponentsSer;
+/// use iri_string::types::IriReferenceStr;
+///
+/// let iri = IriReferenceStr::new("https://user@example.com:8080/a?#")?;
+/// let components = ComponentsSer::new(iri);
+/// assert_eq!(components.iri(), "https://user@example.com:8080/a?#");
+/// // `components` can be serialized by any serde serializer.
+/// # Ok::<_, Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename = "Components")]
+pub struct ComponentsSer<'a> {
+    /// Whole IRI reference.
+    iri: &'a str,
+    /// Scheme.
+    scheme: Option<&'a str>,
+    /// Authority.
+    authority: Option<AuthoritySer<'a>>,
+    /// Path.
+    path: &'a str,
+    /// Query.
+    query: Option<&'a str>,
+    /// Fragment.
+    fragment: Option<&'a str>,
+}
+
+impl<'a> ComponentsSer<'a> {
+    /// Decomposes the IRI reference into the serializable view.
+    #[must_use]
+    pub fn new<S: Spec>(iri: &'a RiReferenceStr<S>) -> Self {
+        let (scheme, authority, path, query, fragment) =
+            RiReferenceComponents::from(iri).to_major();
+        let authority = authority.map(|_| {
+            let authority = AuthorityComponents::from_iri(iri)
+                .expect("[consistency] the authority component is present");
+            AuthoritySer {
+                userinfo: authority.userinfo(),
+                host: authority.host(),
+                port: authority.port(),
+            }
+        });
+        Self {
+            iri: iri.as_str(),
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+
+    /// Returns the whole IRI reference as a string.
+    #[inline]
+    #[must_use]
+    pub fn iri(&self) -> &'a str {
+        self.iri
+    }
+}
+
+impl<'a, S: Spec> From<&'a RiReferenceStr<S>> for ComponentsSer<'a> {
+    #[inline]
+    fn from(iri: &'a RiReferenceStr<S>) -> Self {
+        Self::new(iri)
+    }
+}
+
+/// Serializable view of the authority subcomponents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename = "Authority")]
+struct AuthoritySer<'a> {
+    /// Userinfo.
+    userinfo: Option<&'a str>,
+    /// Host.
+    host: &'a str,
+    /// Port.
+    port: Option<&'a str>,
+}
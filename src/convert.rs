@@ -1,15 +1,28 @@
 //! Conversion between URI/IRI types.
+//!
+//! IRIs are mapped to URIs by [`MappedToUri`] (percent-encoding non-ASCII
+//! characters), and URIs are mapped to IRIs by [`MappedToIri`] (decoding
+//! percent-encoded non-ASCII characters).
 
-use core::fmt;
+use core::fmt::{self, Write as _};
 
 #[cfg(feature = "alloc")]
 use alloc::collections::TryReserveError;
 #[cfg(feature = "alloc")]
 use alloc::string::String;
 
+use crate::chars::is_iprivate;
+use crate::format::{write_to_slice, CapacityOverflowError};
 #[cfg(feature = "alloc")]
 use crate::format::{ToDedicatedString, ToStringFallible};
-use crate::spec::Spec;
+use crate::parser::char::{is_nonascii_query, is_ucschar};
+use crate::parser::trusted::hexdigits_to_byte;
+use crate::spec::{IriSpec, Spec, UriSpec};
+#[cfg(feature = "alloc")]
+use crate::types::{
+    IriAbsoluteString, IriFragmentString, IriQueryString, IriReferenceString, IriRelativeString,
+    IriString,
+};
 use crate::types::{
     RiAbsoluteStr, RiFragmentStr, RiQueryStr, RiReferenceStr, RiRelativeStr, RiStr,
 };
@@ -54,6 +67,13 @@ const HEXDIGITS: [u8; 16] = [
 /// // The type is `MappedToUri<IriStr>`, but you usually don't need to specify.
 /// let mapped = MappedToUri::from(src).to_string();
 /// assert_eq!(mapped, "http://example.com/?alpha=%CE%B1");
+///
+/// // The URI can also be written into a byte buffer, without allocation.
+/// let mut buf = [0_u8; 64];
+/// let uri: &UriStr = MappedToUri::from(src)
+///     .write_to_slice(&mut buf)
+///     .expect("enough buffer");
+/// assert_eq!(uri, "http://example.com/?alpha=%CE%B1");
 /// # Ok::<_, iri_string::validate::Error>(())
 /// ```
 ///
@@ -77,6 +97,22 @@ macro_rules! impl_for_iri {
             }
         }
 
+        impl<S: Spec> MappedToUri<'_, $borrowed<S>> {
+            /// Writes the URI into the byte buffer, without memory allocation.
+            ///
+            /// Returns the written URI, whose length is the number of bytes
+            /// written. Fails if the buffer is too small, and the content of
+            /// the buffer is unspecified in that case.
+            pub fn write_to_slice<'b>(
+                &self,
+                buf: &'b mut [u8],
+            ) -> Result<&'b $borrowed<UriSpec>, CapacityOverflowError> {
+                let s = write_to_slice(buf, self)?;
+                Ok(<$borrowed<UriSpec>>::new(s)
+                    .expect("[validity] the IRI must be encoded into a valid URI"))
+            }
+        }
+
         #[cfg(feature = "alloc")]
         impl<S: Spec> ToDedicatedString for MappedToUri<'_, $borrowed<S>> {
             type Target = $owned_uri;
@@ -112,6 +148,328 @@ impl_for_iri!(RiRelativeStr, RiRelativeString, UriRelativeString);
 impl_for_iri!(RiQueryStr, RiQueryString, UriQueryString);
 impl_for_iri!(RiFragmentStr, RiFragmentString, UriFragmentString);
 
+/// Characters to be decoded on conversion from URIs to IRIs.
+///
+/// This is used by [`MappedToIri`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeMode {
+    /// Decodes characters allowed in IRIs, as [RFC 3987 section 3.2] describes.
+    ///
+    /// Percent-encoded UTF-8 sequences are decoded if they represent
+    /// `ucschar` (or `iprivate` in the query). Bidirectional formatting
+    /// characters are kept encoded, since they must not appear in IRIs (see
+    /// [RFC 3987 section 4.1]).
+    ///
+    /// [RFC 3987 section 3.2]: https://www.rfc-editor.org/rfc/rfc3987.html#section-3.2
+    /// [RFC 3987 section 4.1]: https://www.rfc-editor.org/rfc/rfc3987.html#section-4.1
+    Standard,
+    /// Decodes only characters that are safe to display to end users.
+    ///
+    /// In addition to [`Standard`][`Self::Standard`], the following are kept
+    /// encoded to prevent spoofing:
+    ///
+    /// * U+00A0 (no-break space),
+    /// * non-ASCII whitespace characters,
+    /// * invisible characters (default ignorable code points, such as
+    ///   zero-width spaces, joiners, variation selectors, and tags), and
+    /// * private use characters (even in the query).
+    ///
+    /// Note that this cannot detect confusable characters (homographs).
+    SafeDisplay,
+}
+
+impl Default for DecodeMode {
+    #[inline]
+    fn default() -> Self {
+        Self::Standard
+    }
+}
+
+/// A URI mapped to an IRI of some kind, by decoding percent-encoded non-ASCII characters.
+///
+/// Supported `Src` types are URIs:
+///
+/// * [`UriAbsoluteStr`] (alias of `RiAbsoluteStr<UriSpec>`)
+/// * [`UriReferenceStr`] (alias of `RiReferenceStr<UriSpec>`)
+/// * [`UriRelativeStr`] (alias of `RiRelativeStr<UriSpec>`)
+/// * [`UriStr`] (alias of `RiStr<UriSpec>`)
+/// * [`UriQueryStr`] (alias of `RiQueryStr<UriSpec>`)
+/// * [`UriFragmentStr`] (alias of `RiFragmentStr<UriSpec>`)
+///
+/// Percent-encoded ASCII characters and invalid UTF-8 sequences are kept as
+/// is, so the conversion does not change the meaning of the URI. Which
+/// non-ASCII characters are decoded can be specified by [`DecodeMode`].
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::convert::{DecodeMode, MappedToIri};
+/// use iri_string::types::{IriStr, UriStr};
+///
+/// let src = UriStr::new("http://example.com/%CE%B1%2F?rlo=%E2%80%AE&zwsp=%E2%80%8B&bad=%CE")?;
+/// let mapped = MappedToIri::from(src).to_string();
+/// assert_eq!(mapped, "http://example.com/\u{03B1}%2F?rlo=%E2%80%AE&zwsp=\u{200B}&bad=%CE");
+///
+/// // Invisible characters are not decoded in the safe display mode.
+/// let mapped = MappedToIri::from(src).mode(DecodeMode::SafeDisplay).to_string();
+/// assert_eq!(mapped, "http://example.com/\u{03B1}%2F?rlo=%E2%80%AE&zwsp=%E2%80%8B&bad=%CE");
+///
+/// // The IRI can also be written into a byte buffer, without allocation.
+/// let mut buf = [0_u8; 128];
+/// let iri: &IriStr = MappedToIri::from(src)
+///     .write_to_slice(&mut buf)
+///     .expect("enough buffer");
+/// assert_eq!(iri, "http://example.com/\u{03B1}%2F?rlo=%E2%80%AE&zwsp=\u{200B}&bad=%CE");
+/// # Ok::<_, iri_string::validate::Error>(())
+/// ```
+///
+/// [`UriAbsoluteStr`]: crate::types::UriAbsoluteStr
+/// [`UriFragmentStr`]: crate::types::UriFragmentStr
+/// [`UriQueryStr`]: crate::types::UriQueryStr
+/// [`UriReferenceStr`]: crate::types::UriReferenceStr
+/// [`UriRelativeStr`]: crate::types::UriRelativeStr
+/// [`UriStr`]: crate::types::UriStr
+#[derive(Debug, Clone, Copy)]
+pub struct MappedToIri<'a, Src: ?Sized> {
+    /// Source URI.
+    src: &'a Src,
+    /// Decode mode.
+    mode: DecodeMode,
+}
+
+impl<Src: ?Sized> MappedToIri<'_, Src> {
+    /// Sets the decode mode.
+    #[inline]
+    #[must_use]
+    pub fn mode(self, mode: DecodeMode) -> Self {
+        Self { mode, ..self }
+    }
+}
+
+/// Implement conversions for a URI string type.
+macro_rules! impl_for_uri {
+    ($borrowed:ident, $owned:ident, $owned_iri:ident, $query_range:expr) => {
+        impl fmt::Display for MappedToIri<'_, $borrowed<UriSpec>> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let s = self.src.as_str();
+                let query_range: fn(&str) -> Option<(usize, usize)> = $query_range;
+                write_percent_decoded(f, s, query_range(s), self.mode)
+            }
+        }
+
+        impl MappedToIri<'_, $borrowed<UriSpec>> {
+            /// Writes the IRI into the byte buffer, without memory allocation.
+            ///
+            /// Returns the written IRI, whose length is the number of bytes
+            /// written. Fails if the buffer is too small, and the content of
+            /// the buffer is unspecified in that case.
+            pub fn write_to_slice<'b>(
+                &self,
+                buf: &'b mut [u8],
+            ) -> Result<&'b $borrowed<IriSpec>, CapacityOverflowError> {
+                let s = write_to_slice(buf, self)?;
+                Ok(<$borrowed<IriSpec>>::new(s)
+                    .expect("[validity] the URI must be decoded into a valid IRI"))
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl ToDedicatedString for MappedToIri<'_, $borrowed<UriSpec>> {
+            type Target = $owned_iri;
+
+            fn try_to_dedicated_string(&self) -> Result<Self::Target, TryReserveError> {
+                let s = self.try_to_string()?;
+                Ok(TryFrom::try_from(s)
+                    .expect("[validity] the URI must be decoded into a valid IRI"))
+            }
+        }
+
+        impl<'a> From<&'a $borrowed<UriSpec>> for MappedToIri<'a, $borrowed<UriSpec>> {
+            #[inline]
+            fn from(uri: &'a $borrowed<UriSpec>) -> Self {
+                Self {
+                    src: uri,
+                    mode: DecodeMode::default(),
+                }
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<'a> From<&'a $owned<UriSpec>> for MappedToIri<'a, $borrowed<UriSpec>> {
+            #[inline]
+            fn from(uri: &'a $owned<UriSpec>) -> Self {
+                Self::from(uri.as_slice())
+            }
+        }
+    };
+}
+
+impl_for_uri!(
+    RiReferenceStr,
+    RiReferenceString,
+    IriReferenceString,
+    find_query_range
+);
+impl_for_uri!(RiStr, RiString, IriString, find_query_range);
+impl_for_uri!(
+    RiAbsoluteStr,
+    RiAbsoluteString,
+    IriAbsoluteString,
+    find_query_range
+);
+impl_for_uri!(
+    RiRelativeStr,
+    RiRelativeString,
+    IriRelativeString,
+    find_query_range
+);
+impl_for_uri!(RiQueryStr, RiQueryString, IriQueryString, |s| Some((
+    0,
+    s.len()
+)));
+impl_for_uri!(RiFragmentStr, RiFragmentString, IriFragmentString, |_| None);
+
+/// Returns the byte range of the query in the valid URI reference, if exists.
+fn find_query_range(s: &str) -> Option<(usize, usize)> {
+    // `?` cannot appear before the query, but can appear in the fragment.
+    let fragment_start = s.find('#').unwrap_or(s.len());
+    let query_start = s[..fragment_start].find('?')? + 1;
+    Some((query_start, fragment_start))
+}
+
+/// Returns true if the character should be decoded on conversion to an IRI.
+fn is_decodable(c: char, in_query: bool, mode: DecodeMode) -> bool {
+    let is_allowed = if in_query {
+        is_nonascii_query::<IriSpec>(c)
+    } else {
+        is_ucschar(c)
+    };
+    if !is_allowed || is_bidi_formatting(c) {
+        return false;
+    }
+    match mode {
+        DecodeMode::Standard => true,
+        DecodeMode::SafeDisplay => {
+            c > '\u{A0}' && !is_iprivate(c) && !is_nonascii_whitespace(c) && !is_invisible(c)
+        }
+    }
+}
+
+/// Returns true if the character is a bidirectional formatting character.
+///
+/// See [RFC 3987 section 4.1](https://www.rfc-editor.org/rfc/rfc3987.html#section-4.1).
+/// Isolates (U+2066..=U+2069), which were introduced later, are also included.
+#[inline]
+#[must_use]
+fn is_bidi_formatting(c: char) -> bool {
+    matches!(c, '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Returns true if the character is a non-ASCII whitespace (`White_Space` property).
+#[inline]
+#[must_use]
+fn is_nonascii_whitespace(c: char) -> bool {
+    matches!(
+        c,
+        '\u{85}' | '\u{A0}' | '\u{1680}' | '\u{2000}'
+            ..='\u{200A}' | '\u{2028}' | '\u{2029}' | '\u{202F}' | '\u{205F}' | '\u{3000}'
+    )
+}
+
+/// Returns true if the non-ASCII character is invisible (`Default_Ignorable_Code_Point` property).
+#[inline]
+#[must_use]
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{AD}'
+            | '\u{34F}'
+            | '\u{61C}'
+            | '\u{115F}'..='\u{1160}'
+            | '\u{17B4}'..='\u{17B5}'
+            | '\u{180B}'..='\u{180F}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{206F}'
+            | '\u{3164}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FEFF}'
+            | '\u{FFA0}'
+            | '\u{FFF0}'..='\u{FFF8}'
+            | '\u{1BCA0}'..='\u{1BCA3}'
+            | '\u{1D173}'..='\u{1D17A}'
+            | '\u{E0000}'..='\u{E0FFF}'
+    )
+}
+
+/// Decodes percent-encoded non-ASCII characters and writes the URI string.
+///
+/// `query_range` is the byte range of the query in `s`, if exists.
+fn write_percent_decoded(
+    f: &mut fmt::Formatter<'_>,
+    s: &str,
+    query_range: Option<(usize, usize)>,
+    mode: DecodeMode,
+) -> fmt::Result {
+    let bytes = s.as_bytes();
+    let mut written = 0;
+    let mut pos = 0;
+    while let Some(rel) = s[pos..].find('%') {
+        let start = pos + rel;
+        let (c, len) = match decode_utf8_triplets(&bytes[start..]) {
+            Some(v) => v,
+            None => {
+                // Keep the triplet as is.
+                pos = start + 3;
+                continue;
+            }
+        };
+        let in_query =
+            query_range.map_or(false, |(q_start, q_end)| (q_start..q_end).contains(&start));
+        if is_decodable(c, in_query, mode) {
+            f.write_str(&s[written..start])?;
+            f.write_char(c)?;
+            written = start + len;
+            pos = written;
+        } else {
+            pos = start + 3;
+        }
+    }
+    f.write_str(&s[written..])
+}
+
+/// Decodes a character from the percent-encoded UTF-8 bytes at the beginning of the string.
+///
+/// Returns the non-ASCII character and the number of bytes consumed, or
+/// `None` if the string does not start with a percent-encoded non-ASCII
+/// character.
+///
+/// # Precondition
+///
+/// The string should start with `%`, and every `%` in the string should be
+/// followed by two hexadecimal digits.
+fn decode_utf8_triplets(s: &[u8]) -> Option<(char, usize)> {
+    let mut buf = [0_u8; 4];
+    buf[0] = hexdigits_to_byte([s[1], s[2]]);
+    let len = match buf[0] {
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return None,
+    };
+    for (i, dest) in buf.iter_mut().enumerate().take(len).skip(1) {
+        let triplet = s.get((i * 3)..(i * 3 + 3))?;
+        if triplet[0] != b'%' {
+            return None;
+        }
+        *dest = hexdigits_to_byte([triplet[1], triplet[2]]);
+    }
+    let decoded = core::str::from_utf8(&buf[..len]).ok()?;
+    let c = decoded.chars().next()?;
+    Some((c, len * 3))
+}
+
 /// Percent-encodes and writes the IRI string using the given buffer.
 fn write_percent_encoded(f: &mut fmt::Formatter<'_>, mut s: &str) -> fmt::Result {
     while !s.is_empty() {
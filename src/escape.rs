@@ -0,0 +1,412 @@
+//! Lossless escaping of arbitrary strings into IRI components.
+//!
+//! [`Escaped`] embeds an arbitrary string (or bytes) into a component, and
+//! [`unescape`] extracts it again byte-for-byte. This is intended for
+//! application data such as IDs and names, i.e. "put this string in the
+//! path" without worrying about which characters have special meanings.
+//!
+//! # Policy
+//!
+//! `%` and characters not allowed in the component are always
+//! percent-encoded. In addition, characters which have (or commonly have)
+//! special meanings in the component are percent-encoded, so that the
+//! escaped string never changes the structure of the IRI:
+//!
+//! | Target                                               | Raw ASCII characters other than `unreserved` |
+//! |------------------------------------------------------|----------------------------------------------|
+//! | [`PathSegment`][`EscapeTarget::PathSegment`]         | `! $ & ' ( ) * + , = @`                      |
+//! | [`QueryKeyOrValue`][`EscapeTarget::QueryKeyOrValue`] | `! $ ' ( ) * , ; : @ / ?`                    |
+//! | [`Fragment`][`EscapeTarget::Fragment`]               | `sub-delims` and `: @ / ?`                   |
+//! | [`UserOrPassword`][`EscapeTarget::UserOrPassword`]   | `sub-delims`                                 |
+//!
+//! More specifically:
+//!
+//! * Path segments have `/`, `:` (which can be confused with the scheme in
+//!   relative references), and `;` (which is used for matrix parameters by
+//!   some servers) encoded. Note that `.` and `..` are always dot segments
+//!   (even if percent-encoded) and are removed on reference resolution, so
+//!   applications should avoid such strings, for example by prefixing.
+//! * Query keys and values have `&`, `=`, `+`, and `#` encoded, so the
+//!   result can also be decoded by `application/x-www-form-urlencoded`
+//!   parsers.
+//! * Users and passwords have `:` and `@` encoded.
+//!
+//! Non-ASCII characters are left as is if they are allowed as unreserved
+//! characters by the spec (i.e. for IRIs), and percent-encoded otherwise.
+//!
+//! Unreserved characters are never percent-encoded, so normalization (such
+//! as [`RiStr::normalize`]) keeps the escaped strings as is.
+//!
+//! To percent-encode characters allowed in IRIs when building or normalizing
+//! whole IRIs, use [`EscapeProfile`].
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use iri_string::escape::{unescape, EscapeTarget, Escaped};
+//! use iri_string::spec::UriSpec;
+//!
+//! let id = "a/b:c;d?e#f%20..";
+//! let segment = Escaped::<UriSpec>::new(id, EscapeTarget::PathSegment).to_string();
+//! assert_eq!(segment, "a%2Fb%3Ac%3Bd%3Fe%23f%2520..");
+//! assert_eq!(unescape(&segment).as_deref(), Ok(id));
+//!
+//! let value = Escaped::<UriSpec>::new("1+1=2 & more", EscapeTarget::QueryKeyOrValue).to_string();
+//! assert_eq!(value, "1%2B1%3D2%20%26%20more");
+//! assert_eq!(unescape(&value).as_deref(), Ok("1+1=2 & more"));
+//! # }
+//! ```
+//!
+//! [`RiStr::normalize`]: `crate::types::RiStr::normalize`
+
+use core::fmt::{self, Write as _};
+use core::marker::PhantomData;
+
+#[cfg(feature = "std")]
+use std::error;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::parser::char;
+#[cfg(feature = "alloc")]
+use crate::percent_decode::decode_bytes;
+use crate::spec::Spec;
+
+/// Component to embed strings into.
+///
+/// See [the module documentation][self] for the characters encoded for each
+/// target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EscapeTarget {
+    /// A path segment.
+    PathSegment,
+    /// A key or a value of a query pair.
+    QueryKeyOrValue,
+    /// A fragment.
+    Fragment,
+    /// A user or a password in the userinfo.
+    UserOrPassword,
+}
+
+impl EscapeTarget {
+    /// Returns true if the ASCII character can be written without encoding.
+    #[must_use]
+    fn is_ascii_raw(self, c: u8) -> bool {
+        if char::is_ascii_unreserved(c) {
+            return true;
+        }
+        match self {
+            Self::PathSegment => matches!(
+                c,
+                b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b'=' | b'@'
+            ),
+            Self::QueryKeyOrValue => matches!(
+                c,
+                b'!' | b'$' | b'\'' | b'(' | b')' | b'*' | b',' | b';' | b':' | b'@' | b'/' | b'?'
+            ),
+            Self::Fragment => {
+                char::is_ascii_sub_delims(c) || matches!(c, b':' | b'@' | b'/' | b'?')
+            }
+            Self::UserOrPassword => char::is_ascii_sub_delims(c),
+        }
+    }
+}
+
+/// A proxy to losslessly escape a string into a component.
+///
+/// The original string can be extracted from the escaped string by
+/// [`unescape`] (or [`unescape_bytes`] for arbitrary bytes).
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use iri_string::escape::{unescape, unescape_bytes, EscapeTarget, Escaped};
+/// use iri_string::spec::{IriSpec, UriSpec};
+///
+/// let iri = Escaped::<IriSpec>::new("caf\u{E9} ..", EscapeTarget::Fragment);
+/// assert_eq!(iri.to_string(), "caf\u{E9}%20..");
+/// let uri = Escaped::<UriSpec>::new("caf\u{E9} ..", EscapeTarget::Fragment);
+/// assert_eq!(uri.to_string(), "caf%C3%A9%20..");
+///
+/// let escaped = Escaped::<UriSpec>::from_bytes(b"\xFF:@", EscapeTarget::UserOrPassword);
+/// assert_eq!(escaped.to_string(), "%FF%3A%40");
+/// assert_eq!(unescape_bytes(&escaped.to_string()).as_deref(), Ok(&b"\xFF:@"[..]));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Escaped<'a, S> {
+    /// Raw bytes before being escaped.
+    raw: &'a [u8],
+    /// Target component.
+    target: EscapeTarget,
+    /// Spec.
+    _spec: PhantomData<fn() -> S>,
+}
+
+impl<'a, S: Spec> Escaped<'a, S> {
+    /// Creates a new proxy to escape the string.
+    #[inline]
+    #[must_use]
+    pub fn new(raw: &'a str, target: EscapeTarget) -> Self {
+        Self::from_bytes(raw.as_bytes(), target)
+    }
+
+    /// Creates a new proxy to escape the bytes.
+    ///
+    /// Valid UTF-8 sequences are escaped as [`new`][`Self::new`] does, and
+    /// other bytes are always percent-encoded.
+    #[inline]
+    #[must_use]
+    pub fn from_bytes(raw: &'a [u8], target: EscapeTarget) -> Self {
+        Self {
+            raw,
+            target,
+            _spec: PhantomData,
+        }
+    }
+}
+
+impl<S: Spec> fmt::Display for Escaped<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rest = self.raw;
+        while !rest.is_empty() {
+            let (valid, invalid_len) = match core::str::from_utf8(rest) {
+                Ok(s) => (s, 0),
+                Err(e) => {
+                    let (valid, after_valid) = rest.split_at(e.valid_up_to());
+                    let valid = core::str::from_utf8(valid)
+                        .expect("[validity] the prefix is checked to be valid UTF-8");
+                    (valid, e.error_len().unwrap_or(after_valid.len()))
+                }
+            };
+            for c in valid.chars() {
+                let is_raw = if c.is_ascii() {
+                    self.target.is_ascii_raw(c as u8)
+                } else {
+                    S::is_nonascii_char_unreserved(c)
+                };
+                if is_raw {
+                    f.write_char(c)?;
+                } else {
+                    let mut buf = [0_u8; 4];
+                    c.encode_utf8(&mut buf)
+                        .bytes()
+                        .try_for_each(|b| write!(f, "%{:02X}", b))?;
+                }
+            }
+            let invalid = &rest[valid.len()..(valid.len() + invalid_len)];
+            invalid.iter().try_for_each(|b| write!(f, "%{:02X}", b))?;
+            rest = &rest[(valid.len() + invalid_len)..];
+        }
+        Ok(())
+    }
+}
+
+/// Profile to control how aggressively the characters allowed in IRIs are
+/// percent-encoded on output.
+///
+/// This can be set to the builder ([`Builder::escape_profile`]) and the
+/// normalizer ([`Normalized::and_escape_profile`]).
+///
+/// Characters not allowed in IRIs (such as `|`, `"`, `<`, and `>`) never
+/// appear in valid IRIs, so they are always percent-encoded regardless of the
+/// profile.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// # #[cfg(feature = "alloc")] {
+/// use iri_string::escape::EscapeProfile;
+/// use iri_string::types::IriStr;
+///
+/// let iri = IriStr::new("http://example.com/it's(1)?q=don't#*")?;
+/// assert_eq!(
+///     iri.normalize().and_escape_profile(EscapeProfile::Maximal).to_string(),
+///     "http://example.com/it%27s%281%29?q=don%27t#%2A"
+/// );
+/// # }
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// [`Builder::escape_profile`]: `crate::build::Builder::escape_profile`
+/// [`Normalized::and_escape_profile`]: `crate::normalize::Normalized::and_escape_profile`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EscapeProfile {
+    /// Writes the characters as is.
+    ///
+    /// This is the default.
+    Minimal,
+    /// Percent-encodes `!`, `'`, `(`, `)`, and `*`.
+    ///
+    /// These are `sub-delims` which are not used as delimiters by common
+    /// schemes, but are troublesome when the IRI is embedded into other
+    /// languages, such as HTML attributes quoted by `'`, CSS `url()`, and
+    /// Markdown links. IP literals (such as `[v1.x!]`) are kept as is.
+    ///
+    /// Note that encoding these `sub-delims` can change the meaning of the
+    /// IRI: RFC 3986 (section 2.2) does not treat the percent-encoded
+    /// reserved characters as equivalent to the raw ones, and schemes and
+    /// applications may use them as delimiters. Applications comparing IRIs
+    /// should use the same profile consistently.
+    ///
+    /// `|` is not in the list since it is not allowed in IRIs and never
+    /// appears raw in a valid IRI; it is always percent-encoded regardless
+    /// of the profile.
+    Maximal,
+}
+
+impl Default for EscapeProfile {
+    #[inline]
+    fn default() -> Self {
+        Self::Minimal
+    }
+}
+
+impl EscapeProfile {
+    /// Writes the IRI written by `fmt_raw` with the profile applied.
+    ///
+    /// # Precondition
+    ///
+    /// `fmt_raw` should write a valid IRI reference.
+    pub(crate) fn fmt_with<F>(self, f: &mut fmt::Formatter<'_>, fmt_raw: F) -> fmt::Result
+    where
+        F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+    {
+        /// Displays the value written by the function.
+        struct Raw<F>(F);
+        impl<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result> fmt::Display for Raw<F> {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                (self.0)(f)
+            }
+        }
+
+        match self {
+            Self::Minimal => fmt_raw(f),
+            Self::Maximal => {
+                let mut writer = MaximalWriter {
+                    inner: f,
+                    in_ip_literal: false,
+                };
+                write!(writer, "{}", Raw(fmt_raw))
+            }
+        }
+    }
+}
+
+/// Writer to apply [`EscapeProfile::Maximal`].
+struct MaximalWriter<'a, 'b> {
+    /// Inner formatter.
+    inner: &'a mut fmt::Formatter<'b>,
+    /// Whether the last written `[` is not closed yet.
+    in_ip_literal: bool,
+}
+
+impl fmt::Write for MaximalWriter<'_, '_> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        while !s.is_empty() {
+            if self.in_ip_literal {
+                match s.find(']') {
+                    Some(pos) => {
+                        self.inner.write_str(&s[..=pos])?;
+                        self.in_ip_literal = false;
+                        s = &s[(pos + 1)..];
+                        continue;
+                    }
+                    None => return self.inner.write_str(s),
+                }
+            }
+            let pos = s.find(['!', '\'', '(', ')', '*', '[']).unwrap_or(s.len());
+            self.inner.write_str(&s[..pos])?;
+            match s.as_bytes().get(pos) {
+                None => return Ok(()),
+                Some(b'[') => self.in_ip_literal = true,
+                Some(&b) => {
+                    write!(self.inner, "%{:02X}", b)?;
+                    s = &s[(pos + 1)..];
+                    continue;
+                }
+            }
+            s = &s[pos..];
+        }
+        Ok(())
+    }
+}
+
+/// Error on unescaping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnescapeError(());
+
+impl UnescapeError {
+    /// Creates a new `UnescapeError`.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for UnescapeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid escaped string")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for UnescapeError {}
+
+/// Extracts the original bytes from the escaped string.
+///
+/// All percent-encoded triplets are decoded, and `+` is kept as is. The
+/// string should be a whole escaped string, i.e. a path segment (not a
+/// path), a query key or value (not a whole query), and so on.
+///
+/// # Failures
+///
+/// Fails if `%` is not followed by two hexadecimal digits.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn unescape_bytes(s: &str) -> Result<Cow<'_, [u8]>, UnescapeError> {
+    let bytes = s.as_bytes();
+    let is_valid = bytes.iter().enumerate().all(|(i, &b)| {
+        b != b'%'
+            || matches!(
+                bytes.get((i + 1)..(i + 3)),
+                Some(hex) if hex.iter().all(u8::is_ascii_hexdigit)
+            )
+    });
+    if !is_valid {
+        return Err(UnescapeError::new());
+    }
+    Ok(decode_bytes(s, false))
+}
+
+/// Extracts the original string from the escaped string.
+///
+/// See [`unescape_bytes`] for details.
+///
+/// # Failures
+///
+/// Fails if `%` is not followed by two hexadecimal digits, or the original
+/// string is not valid UTF-8.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn unescape(s: &str) -> Result<Cow<'_, str>, UnescapeError> {
+    match unescape_bytes(s)? {
+        Cow::Borrowed(_) => Ok(Cow::Borrowed(s)),
+        Cow::Owned(bytes) => String::from_utf8(bytes)
+            .map(Cow::Owned)
+            .map_err(|_| UnescapeError::new()),
+    }
+}
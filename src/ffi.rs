@@ -0,0 +1,336 @@
+//! C-compatible validation and parsing API.
+//!
+//! This module provides `extern "C"` functions to validate and decompose
+//! IRIs, so that the validator of this crate can be embedded into C, C++, and
+//! other language runtimes. The parse result ([`FfiComponents`]) borrows the
+//! input buffer, so no strings are copied or allocated.
+//!
+//! Strings are passed as pairs of a pointer to UTF-8 bytes and its length
+//! in bytes (without terminating NUL). Components are returned as
+//! [`FfiStr`], where the null pointer represents an absent component.
+//!
+//! The corresponding C declarations are:
+//!
+//! ```c
+//! #include <stdbool.h>
+//! #include <stddef.h>
+//! #include <stdint.h>
+//!
+//! typedef struct {
+//!     const uint8_t *ptr;
+//!     size_t len;
+//! } iri_string_str;
+//!
+//! typedef struct {
+//!     iri_string_str scheme;
+//!     iri_string_str authority;
+//!     iri_string_str path;
+//!     iri_string_str query;
+//!     iri_string_str fragment;
+//! } iri_string_components;
+//!
+//! bool iri_string_validate_iri(const uint8_t *ptr, size_t len);
+//! bool iri_string_validate_iri_reference(const uint8_t *ptr, size_t len);
+//! bool iri_string_validate_uri(const uint8_t *ptr, size_t len);
+//! bool iri_string_validate_uri_reference(const uint8_t *ptr, size_t len);
+//! bool iri_string_parse_iri_reference(const uint8_t *ptr, size_t len, iri_string_components *out);
+//! bool iri_string_parse_uri_reference(const uint8_t *ptr, size_t len, iri_string_components *out);
+//! ```
+
+use core::marker::PhantomData;
+use core::{ptr, slice};
+
+use crate::spec::{IriSpec, Spec, UriSpec};
+use crate::types::RiReferenceStr;
+use crate::validate;
+
+/// A borrowed string slice, or an absent component.
+///
+/// The null pointer represents an absent component. Note that an empty
+/// component (such as the query of `http://example.com/?`) has a non-null
+/// pointer and zero length.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct FfiStr<'a> {
+    /// Pointer to the first byte, or null if absent.
+    ptr: *const u8,
+    /// Length in bytes.
+    len: usize,
+    /// Lifetime of the borrowed string.
+    _borrowed: PhantomData<&'a str>,
+}
+
+impl<'a> FfiStr<'a> {
+    /// Creates a new value for absent component.
+    #[inline]
+    #[must_use]
+    const fn absent() -> Self {
+        Self {
+            ptr: ptr::null(),
+            len: 0,
+            _borrowed: PhantomData,
+        }
+    }
+
+    /// Creates a new value from the optional string.
+    #[inline]
+    #[must_use]
+    fn new(s: Option<&'a str>) -> Self {
+        match s {
+            Some(s) => Self {
+                ptr: s.as_ptr(),
+                len: s.len(),
+                _borrowed: PhantomData,
+            },
+            None => Self::absent(),
+        }
+    }
+
+    /// Returns the string, or `None` if the component is absent.
+    #[must_use]
+    pub fn as_str(&self) -> Option<&'a str> {
+        if self.ptr.is_null() {
+            return None;
+        }
+        // SAFETY: non-null values are always created from `&'a str`.
+        let s =
+            unsafe { core::str::from_utf8_unchecked(slice::from_raw_parts(self.ptr, self.len)) };
+        Some(s)
+    }
+}
+
+/// Components of an IRI reference, borrowing the input.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::ffi::FfiComponents;
+/// use iri_string::types::IriReferenceStr;
+///
+/// let iri = IriReferenceStr::new("http://example.com/a?")?;
+/// let components = FfiComponents::new(iri);
+/// assert_eq!(components.scheme().as_str(), Some("http"));
+/// assert_eq!(components.authority().as_str(), Some("example.com"));
+/// assert_eq!(components.path().as_str(), Some("/a"));
+/// assert_eq!(components.query().as_str(), Some(""));
+/// assert_eq!(components.fragment().as_str(), None);
+/// # Ok::<_, iri_string::validate::Error>(())
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct FfiComponents<'a> {
+    /// Scheme.
+    scheme: FfiStr<'a>,
+    /// Authority.
+    authority: FfiStr<'a>,
+    /// Path (always present).
+    path: FfiStr<'a>,
+    /// Query.
+    query: FfiStr<'a>,
+    /// Fragment.
+    fragment: FfiStr<'a>,
+}
+
+impl<'a> FfiComponents<'a> {
+    /// Decomposes the IRI reference.
+    #[must_use]
+    pub fn new<S: Spec>(iri: &'a RiReferenceStr<S>) -> Self {
+        Self {
+            scheme: FfiStr::new(iri.scheme_str()),
+            authority: FfiStr::new(iri.authority_str()),
+            path: FfiStr::new(Some(iri.path_str())),
+            query: FfiStr::new(iri.query_str()),
+            fragment: FfiStr::new(iri.fragment().map(|fragment| fragment.as_str())),
+        }
+    }
+
+    /// Returns the scheme.
+    #[inline]
+    #[must_use]
+    pub fn scheme(&self) -> FfiStr<'a> {
+        self.scheme
+    }
+
+    /// Returns the authority.
+    #[inline]
+    #[must_use]
+    pub fn authority(&self) -> FfiStr<'a> {
+        self.authority
+    }
+
+    /// Returns the path.
+    ///
+    /// The path is always present, but can be empty.
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> FfiStr<'a> {
+        self.path
+    }
+
+    /// Returns the query.
+    #[inline]
+    #[must_use]
+    pub fn query(&self) -> FfiStr<'a> {
+        self.query
+    }
+
+    /// Returns the fragment.
+    #[inline]
+    #[must_use]
+    pub fn fragment(&self) -> FfiStr<'a> {
+        self.fragment
+    }
+}
+
+/// Returns the input string, or `None` if it is not valid UTF-8.
+///
+/// # Safety
+///
+/// `ptr` should be valid for reads of `len` bytes during `'a`, or `len`
+/// should be zero.
+unsafe fn input_str<'a>(ptr: *const u8, len: usize) -> Option<&'a str> {
+    if len == 0 {
+        return Some("");
+    }
+    if ptr.is_null() {
+        return None;
+    }
+    // SAFETY: the caller ensures the pointer is valid for reads of `len`
+    // bytes during `'a`.
+    let bytes = unsafe { slice::from_raw_parts(ptr, len) };
+    core::str::from_utf8(bytes).ok()
+}
+
+/// Validates the input with the validator.
+///
+/// # Safety
+///
+/// See [`input_str`].
+unsafe fn validate_input(
+    ptr: *const u8,
+    len: usize,
+    validator: fn(&str) -> Result<(), validate::Error>,
+) -> bool {
+    // SAFETY: the caller ensures the precondition of `input_str`.
+    match unsafe { input_str(ptr, len) } {
+        Some(s) => validator(s).is_ok(),
+        None => false,
+    }
+}
+
+/// Parses the input as an IRI reference, and writes the components to `out`.
+///
+/// # Safety
+///
+/// See [`input_str`]. Additionally, `out` should be valid for writes, or
+/// null.
+unsafe fn parse_input<'a, S: Spec + 'a>(
+    ptr: *const u8,
+    len: usize,
+    out: *mut FfiComponents<'a>,
+) -> bool {
+    // SAFETY: the caller ensures the precondition of `input_str`.
+    let iri = match unsafe { input_str(ptr, len) }.and_then(|s| RiReferenceStr::<S>::new(s).ok()) {
+        Some(iri) => iri,
+        None => return false,
+    };
+    if !out.is_null() {
+        // SAFETY: the caller ensures `out` is valid for writes if non-null.
+        unsafe {
+            out.write(FfiComponents::new(iri));
+        }
+    }
+    true
+}
+
+/// Returns true if the input is a valid IRI.
+///
+/// Returns false if the input is invalid (including the case when it is not
+/// valid UTF-8).
+///
+/// # Safety
+///
+/// `ptr` should be valid for reads of `len` bytes, or `len` should be zero.
+#[no_mangle]
+pub unsafe extern "C" fn iri_string_validate_iri(ptr: *const u8, len: usize) -> bool {
+    // SAFETY: the caller ensures the pointer is valid.
+    unsafe { validate_input(ptr, len, validate::iri::<IriSpec>) }
+}
+
+/// Returns true if the input is a valid IRI reference.
+///
+/// See [`iri_string_validate_iri`] for details.
+///
+/// # Safety
+///
+/// `ptr` should be valid for reads of `len` bytes, or `len` should be zero.
+#[no_mangle]
+pub unsafe extern "C" fn iri_string_validate_iri_reference(ptr: *const u8, len: usize) -> bool {
+    // SAFETY: the caller ensures the pointer is valid.
+    unsafe { validate_input(ptr, len, validate::iri_reference::<IriSpec>) }
+}
+
+/// Returns true if the input is a valid URI.
+///
+/// See [`iri_string_validate_iri`] for details.
+///
+/// # Safety
+///
+/// `ptr` should be valid for reads of `len` bytes, or `len` should be zero.
+#[no_mangle]
+pub unsafe extern "C" fn iri_string_validate_uri(ptr: *const u8, len: usize) -> bool {
+    // SAFETY: the caller ensures the pointer is valid.
+    unsafe { validate_input(ptr, len, validate::iri::<UriSpec>) }
+}
+
+/// Returns true if the input is a valid URI reference.
+///
+/// See [`iri_string_validate_iri`] for details.
+///
+/// # Safety
+///
+/// `ptr` should be valid for reads of `len` bytes, or `len` should be zero.
+#[no_mangle]
+pub unsafe extern "C" fn iri_string_validate_uri_reference(ptr: *const u8, len: usize) -> bool {
+    // SAFETY: the caller ensures the pointer is valid.
+    unsafe { validate_input(ptr, len, validate::iri_reference::<UriSpec>) }
+}
+
+/// Parses the input as an IRI reference.
+///
+/// Returns true and writes the components to `out` (if non-null) if the
+/// input is a valid IRI reference. Otherwise returns false and leaves `out`
+/// untouched. The components borrow the input buffer, so they are valid only
+/// while the input buffer is alive and not modified.
+///
+/// # Safety
+///
+/// `ptr` should be valid for reads of `len` bytes, or `len` should be zero.
+/// `out` should be valid for writes, or null.
+#[no_mangle]
+pub unsafe extern "C" fn iri_string_parse_iri_reference(
+    ptr: *const u8,
+    len: usize,
+    out: *mut FfiComponents<'static>,
+) -> bool {
+    // SAFETY: the caller ensures the pointers are valid.
+    unsafe { parse_input::<IriSpec>(ptr, len, out) }
+}
+
+/// Parses the input as a URI reference.
+///
+/// See [`iri_string_parse_iri_reference`] for details.
+///
+/// # Safety
+///
+/// `ptr` should be valid for reads of `len` bytes, or `len` should be zero.
+/// `out` should be valid for writes, or null.
+#[no_mangle]
+pub unsafe extern "C" fn iri_string_parse_uri_reference(
+    ptr: *const u8,
+    len: usize,
+    out: *mut FfiComponents<'static>,
+) -> bool {
+    // SAFETY: the caller ensures the pointers are valid.
+    unsafe { parse_input::<UriSpec>(ptr, len, out) }
+}
@@ -0,0 +1,244 @@
+//! Conversion from platform paths into `file:` IRIs.
+//!
+//! Paths given by the OS are not always valid UTF-8 strings. The functions in
+//! this module never convert such paths lossily; invalid bytes are
+//! percent-encoded instead.
+//!
+//! * On Unix, paths are arbitrary byte sequences. Bytes not forming valid
+//!   UTF-8 sequences are percent-encoded as is.
+//! * On Windows, paths are sequences of 16-bit code units, possibly with
+//!   unpaired surrogates. The paths are converted into [WTF-8], i.e. unpaired
+//!   surrogates are encoded as 3-byte sequences and then percent-encoded.
+//! * On other platforms, paths must be valid UTF-8 strings.
+//!
+//! [WTF-8]: https://simonsapin.github.io/wtf-8/
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::file::Error;
+//! # #[cfg(unix)] {
+//! use std::path::Path;
+//! use iri_string::file::path_to_file_iri;
+//! use iri_string::types::{IriString, UriString};
+//!
+//! let path = Path::new("/tmp/r\u{E9}sum\u{E9} #1.txt");
+//! let iri: IriString = path_to_file_iri(path)?;
+//! assert_eq!(iri, "file:///tmp/r\u{E9}sum\u{E9}%20%231.txt");
+//! let uri: UriString = path_to_file_iri(path)?;
+//! assert_eq!(uri, "file:///tmp/r%C3%A9sum%C3%A9%20%231.txt");
+//! # }
+//! # Ok::<_, Error>(())
+//! ```
+
+use core::fmt::{self, Write as _};
+
+use std::borrow::Cow;
+use std::error;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::string::String;
+
+use crate::parser::char::is_ascii_pchar_slash;
+use crate::spec::Spec;
+use crate::types::RiString;
+
+/// Error on conversion from a path into a `file:` IRI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("cannot convert the path into a `file` IRI")
+    }
+}
+
+impl error::Error for Error {}
+
+/// Returns the bytes of the OS string, in WTF-8 on Windows.
+///
+/// Returns `None` if the platform is neither Unix nor Windows and the string
+/// is not valid UTF-8.
+#[cfg(unix)]
+#[inline]
+pub(crate) fn os_str_to_bytes(s: &OsStr) -> Option<Cow<'_, [u8]>> {
+    use std::os::unix::ffi::OsStrExt;
+
+    Some(Cow::Borrowed(s.as_bytes()))
+}
+
+/// Returns the bytes of the OS string, in WTF-8 on Windows.
+///
+/// Returns `None` if the platform is neither Unix nor Windows and the string
+/// is not valid UTF-8.
+#[cfg(windows)]
+pub(crate) fn os_str_to_bytes(s: &OsStr) -> Option<Cow<'_, [u8]>> {
+    use std::os::windows::ffi::OsStrExt;
+    use std::vec::Vec;
+
+    if let Some(s) = s.to_str() {
+        return Some(Cow::Borrowed(s.as_bytes()));
+    }
+    let mut buf = Vec::with_capacity(s.len());
+    for c in char::decode_utf16(s.encode_wide()) {
+        match c {
+            Ok(c) => buf.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            Err(e) => {
+                // Generalized UTF-8 encoding of the surrogate (U+D800..=U+DFFF).
+                let surrogate = e.unpaired_surrogate();
+                buf.extend_from_slice(&[
+                    0xE0 | (surrogate >> 12) as u8,
+                    0x80 | ((surrogate >> 6) & 0x3F) as u8,
+                    0x80 | (surrogate & 0x3F) as u8,
+                ]);
+            }
+        }
+    }
+    Some(Cow::Owned(buf))
+}
+
+/// Returns the bytes of the OS string, in WTF-8 on Windows.
+///
+/// Returns `None` if the platform is neither Unix nor Windows and the string
+/// is not valid UTF-8.
+#[cfg(not(any(unix, windows)))]
+#[inline]
+pub(crate) fn os_str_to_bytes(s: &OsStr) -> Option<Cow<'_, [u8]>> {
+    s.to_str().map(|s| Cow::Borrowed(s.as_bytes()))
+}
+
+/// Appends the bytes to the buffer, percent-encoding the characters not
+/// satisfying the given predicate and the bytes not forming valid UTF-8
+/// sequences.
+pub(crate) fn push_bytes_encoded<F>(buf: &mut String, mut bytes: &[u8], mut is_raw: F)
+where
+    F: FnMut(char) -> bool,
+{
+    let mut push_str = |buf: &mut String, s: &str| {
+        for c in s.chars() {
+            if is_raw(c) {
+                buf.push(c);
+            } else {
+                push_pct_encoded(buf, c.encode_utf8(&mut [0; 4]).as_bytes());
+            }
+        }
+    };
+
+    while !bytes.is_empty() {
+        match core::str::from_utf8(bytes) {
+            Ok(s) => {
+                push_str(buf, s);
+                break;
+            }
+            Err(e) => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                let valid = core::str::from_utf8(valid)
+                    .expect("[validity] the prefix is valid UTF-8 sequence");
+                push_str(buf, valid);
+                let (invalid, rest) = rest.split_at(e.error_len().unwrap_or(rest.len()));
+                push_pct_encoded(buf, invalid);
+                bytes = rest;
+            }
+        }
+    }
+}
+
+/// Appends the percent-encoded bytes to the buffer.
+fn push_pct_encoded(buf: &mut String, bytes: &[u8]) {
+    for b in bytes {
+        write!(buf, "%{:02X}", b).expect("[consistency] writing to a string never fails");
+    }
+}
+
+/// Appends the path segment to the buffer with percent-encoding.
+fn push_path_encoded<S: Spec>(buf: &mut String, segment: &OsStr) -> Result<(), Error> {
+    let bytes = os_str_to_bytes(segment).ok_or_else(Error::new)?;
+    push_bytes_encoded(buf, &bytes, |c| {
+        if c.is_ascii() {
+            is_ascii_pchar_slash(c as u8)
+        } else {
+            S::is_nonascii_char_unreserved(c)
+        }
+    });
+    Ok(())
+}
+
+/// Converts the absolute path into a `file:` IRI.
+///
+/// Characters not allowed in the path component (such as spaces, `?`, `#`,
+/// and `%`) are percent-encoded. Non-ASCII characters are kept as is for
+/// IRIs if possible, and percent-encoded for URIs.
+///
+/// On Windows, paths with drive letters (such as `C:\foo`) are converted to
+/// `file:///C:/foo`, and UNC paths (such as `\\server\share\foo`) are
+/// converted to `file://server/share/foo`.
+///
+/// # Failures
+///
+/// Fails if the path is not absolute, or the path cannot be represented in
+/// the IRI (for example, the UNC server name is not a valid host).
+pub fn path_to_file_iri<S: Spec>(path: &Path) -> Result<RiString<S>, Error> {
+    if !path.is_absolute() {
+        return Err(Error::new());
+    }
+    let mut buf = String::from("file://");
+    write_path::<S>(&mut buf, path)?;
+    RiString::try_from(buf).map_err(|_| Error::new())
+}
+
+/// Writes the absolute Unix path.
+#[cfg(not(windows))]
+#[inline]
+fn write_path<S: Spec>(buf: &mut String, path: &Path) -> Result<(), Error> {
+    push_path_encoded::<S>(buf, path.as_os_str())
+}
+
+/// Writes the absolute Windows path.
+#[cfg(windows)]
+fn write_path<S: Spec>(buf: &mut String, path: &Path) -> Result<(), Error> {
+    use std::path::{Component, Prefix};
+
+    let push_segment = |buf: &mut String, segment: &OsStr| {
+        if !buf.ends_with('/') {
+            buf.push('/');
+        }
+        push_path_encoded::<S>(buf, segment)
+    };
+
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => match prefix.kind() {
+                Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                    buf.push('/');
+                    buf.push(char::from(letter));
+                    buf.push(':');
+                }
+                Prefix::UNC(server, share) | Prefix::VerbatimUNC(server, share) => {
+                    let server = server.to_str().ok_or_else(Error::new)?;
+                    if crate::parser::validate::validate_host::<S>(server).is_err() {
+                        return Err(Error::new());
+                    }
+                    buf.push_str(server);
+                    buf.push('/');
+                    push_path_encoded::<S>(buf, share)?;
+                }
+                _ => return Err(Error::new()),
+            },
+            Component::RootDir => buf.push('/'),
+            Component::CurDir => push_segment(buf, OsStr::new("."))?,
+            Component::ParentDir => push_segment(buf, OsStr::new(".."))?,
+            Component::Normal(segment) => push_segment(buf, segment)?,
+        }
+    }
+    Ok(())
+}
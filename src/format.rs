@@ -4,6 +4,7 @@
 //! types.
 
 use core::fmt::{self, Write as _};
+use core::hash::Hasher;
 
 #[cfg(feature = "alloc")]
 use alloc::collections::TryReserveError;
@@ -137,6 +138,180 @@ where
     succeeded && writer.0.is_empty()
 }
 
+/// Returns true if the two equals after they are converted to strings.
+///
+/// This does not allocate. The left hand side is formatted once and compared
+/// in blocks, and the right hand side is formatted once per block. If one side
+/// is available as a string, use [`eq_str_display`] instead.
+pub(crate) fn eq_display<T, U>(lhs: &T, rhs: &U) -> bool
+where
+    T: ?Sized + fmt::Display,
+    U: ?Sized + fmt::Display,
+{
+    /// Block size.
+    const BLOCK_SIZE: usize = 512;
+
+    /// Writer to buffer the formatted `lhs` and compare it block by block.
+    struct CmpWriter<'a, U: ?Sized> {
+        /// Object to compare.
+        rhs: &'a U,
+        /// Buffer.
+        buf: [u8; BLOCK_SIZE],
+        /// Length of the buffered bytes.
+        buf_len: usize,
+        /// Offset of the formatted `rhs` corresponding to the buffer.
+        offset: usize,
+    }
+    impl<U: ?Sized + fmt::Display> fmt::Write for CmpWriter<'_, U> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let mut bytes = s.as_bytes();
+            while !bytes.is_empty() {
+                let len = bytes.len().min(BLOCK_SIZE - self.buf_len);
+                let (head, rest) = bytes.split_at(len);
+                self.buf[self.buf_len..(self.buf_len + len)].copy_from_slice(head);
+                self.buf_len += len;
+                bytes = rest;
+                if self.buf_len == BLOCK_SIZE {
+                    if !eq_display_block(self.rhs, self.offset, &self.buf, false) {
+                        return Err(fmt::Error);
+                    }
+                    self.offset += BLOCK_SIZE;
+                    self.buf_len = 0;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    let mut writer = CmpWriter {
+        rhs,
+        buf: [0; BLOCK_SIZE],
+        buf_len: 0,
+        offset: 0,
+    };
+    if write!(writer, "{}", lhs).is_err() {
+        return false;
+    }
+    eq_display_block(rhs, writer.offset, &writer.buf[..writer.buf_len], true)
+}
+
+/// Returns true if the formatted object has the given bytes at the given offset.
+///
+/// If `is_last` is true, this also checks that the formatted object ends
+/// right after the given bytes.
+fn eq_display_block<U>(d: &U, offset: usize, expected: &[u8], is_last: bool) -> bool
+where
+    U: ?Sized + fmt::Display,
+{
+    /// Dummy writer to compare the part of the formatted object to the given bytes.
+    struct WindowCmpWriter<'a> {
+        /// Number of bytes to skip.
+        skip: usize,
+        /// Expected bytes (not yet compared).
+        expected: &'a [u8],
+        /// Whether the bytes after the expected ones should be rejected.
+        is_last: bool,
+        /// Whether the mismatch is found.
+        mismatch: bool,
+    }
+    impl fmt::Write for WindowCmpWriter<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let mut bytes = s.as_bytes();
+            if self.skip >= bytes.len() {
+                self.skip -= bytes.len();
+                return Ok(());
+            }
+            bytes = &bytes[self.skip..];
+            self.skip = 0;
+            if self.is_last && (bytes.len() > self.expected.len()) {
+                // The formatted object is longer than expected.
+                self.mismatch = true;
+                return Err(fmt::Error);
+            }
+            let len = bytes.len().min(self.expected.len());
+            let (expected, rest) = self.expected.split_at(len);
+            if bytes[..len] != *expected {
+                self.mismatch = true;
+                return Err(fmt::Error);
+            }
+            self.expected = rest;
+            if rest.is_empty() && !self.is_last {
+                // Compared enough. Stop formatting.
+                return Err(fmt::Error);
+            }
+            Ok(())
+        }
+    }
+
+    let mut writer = WindowCmpWriter {
+        skip: offset,
+        expected,
+        is_last,
+        mismatch: false,
+    };
+    // Stops formatting by an error when the comparison is done.
+    let _ = write!(writer, "{}", d);
+    // The rest of the expected bytes means that the formatted object is
+    // shorter than expected.
+    !writer.mismatch && writer.expected.is_empty() && (writer.skip == 0)
+}
+
+/// Feeds the formatted string to the hasher.
+///
+/// The bytes are fed in fixed-size blocks, so the result does not depend on
+/// how the `Display` implementation splits the output into chunks. The result
+/// is consistent among values with the same formatted strings.
+pub(crate) fn hash_display<T, H>(d: &T, state: &mut H)
+where
+    T: ?Sized + fmt::Display,
+    H: Hasher,
+{
+    /// Block size.
+    const BLOCK_SIZE: usize = 64;
+
+    /// Writer to feed the bytes to the hasher.
+    struct HashWriter<'a, H> {
+        /// Hasher.
+        state: &'a mut H,
+        /// Buffer.
+        buf: [u8; BLOCK_SIZE],
+        /// Length of the buffered bytes.
+        buf_len: usize,
+    }
+    impl<H: Hasher> fmt::Write for HashWriter<'_, H> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let mut bytes = s.as_bytes();
+            while !bytes.is_empty() {
+                let len = bytes.len().min(BLOCK_SIZE - self.buf_len);
+                let (head, rest) = bytes.split_at(len);
+                self.buf[self.buf_len..(self.buf_len + len)].copy_from_slice(head);
+                self.buf_len += len;
+                bytes = rest;
+                if self.buf_len == BLOCK_SIZE {
+                    self.state.write(&self.buf);
+                    self.buf_len = 0;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    let mut writer = HashWriter {
+        state,
+        buf: [0; BLOCK_SIZE],
+        buf_len: 0,
+    };
+    write!(writer, "{}", d).expect("[precondition] `Display` should not fail");
+    let HashWriter {
+        state,
+        buf,
+        buf_len,
+    } = writer;
+    state.write(&buf[..buf_len]);
+    // Terminator, as `str` does.
+    state.write_u8(0xff);
+}
+
 /// A debug-printable type to hide the sensitive information.
 #[derive(Clone, Copy)]
 pub(crate) struct Censored;
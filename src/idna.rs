@@ -0,0 +1,236 @@
+//! IDNA (UTS #46) processing of hosts.
+//!
+//! Registrars and browsers differ in the IDNA requirements, so the processing
+//! options of [Unicode IDNA Compatibility Processing] (UTS #46) can be
+//! specified by [`IdnaOptions`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::idna::Error;
+//! use iri_string::idna::IdnaOptions;
+//!
+//! let options = IdnaOptions::new();
+//! assert_eq!(options.domain_to_ascii("B\u{FC}cher.example")?, "xn--bcher-kva.example");
+//! assert_eq!(options.domain_to_unicode("xn--bcher-kva.example")?, "b\u{FC}cher.example");
+//!
+//! // `ß` is mapped to `ss` in transitional processing.
+//! let transitional = IdnaOptions::new().transitional(true);
+//! assert_eq!(transitional.domain_to_ascii("stra\u{DF}e.example")?, "strasse.example");
+//! assert_eq!(options.domain_to_ascii("stra\u{DF}e.example")?, "xn--strae-oqa.example");
+//! # Ok::<_, Error>(())
+//! ```
+//!
+//! [Unicode IDNA Compatibility Processing]: https://www.unicode.org/reports/tr46/
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::string::String;
+
+use crate::parser::validate::validate_ipv4address;
+use crate::percent_decode::decode_bytes;
+use crate::spec::Spec;
+use crate::types::{RiStr, RiString};
+
+/// IDNA processing error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IDNA processing failed")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+/// UTS #46 processing options.
+///
+/// The default options are the ones used by [WHATWG URL Standard], i.e. all
+/// the options are disabled.
+///
+/// [WHATWG URL Standard]: https://url.spec.whatwg.org/#idna
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct IdnaOptions {
+    /// Whether to use transitional processing.
+    transitional: bool,
+    /// Whether to apply `UseSTD3ASCIIRules`.
+    use_std3_ascii_rules: bool,
+    /// Whether to verify the DNS length restrictions.
+    verify_dns_length: bool,
+    /// Whether to check the hyphen positions.
+    check_hyphens: bool,
+}
+
+impl IdnaOptions {
+    /// Creates the default options.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the strict options.
+    ///
+    /// This enables `UseSTD3ASCIIRules`, the DNS length verification, and the
+    /// hyphen position check, and uses nontransitional processing.
+    #[inline]
+    pub fn strict() -> Self {
+        Self {
+            transitional: false,
+            use_std3_ascii_rules: true,
+            verify_dns_length: true,
+            check_hyphens: true,
+        }
+    }
+
+    /// Sets whether to use transitional processing (`Transitional_Processing`).
+    ///
+    /// Transitional processing maps deviation characters (such as `ß` and
+    /// `ς`) as IDNA2003 did, while nontransitional processing keeps them.
+    #[inline]
+    pub fn transitional(mut self, value: bool) -> Self {
+        self.transitional = value;
+        self
+    }
+
+    /// Sets whether to apply the STD3 ASCII rules (`UseSTD3ASCIIRules`).
+    ///
+    /// If enabled, only letters, digits, and hyphens are allowed as ASCII
+    /// characters in labels.
+    #[inline]
+    pub fn use_std3_ascii_rules(mut self, value: bool) -> Self {
+        self.use_std3_ascii_rules = value;
+        self
+    }
+
+    /// Sets whether to verify the DNS length restrictions (`VerifyDnsLength`).
+    ///
+    /// If enabled, the domain name and each label must not be empty or too
+    /// long. This is used only for [`domain_to_ascii`][`Self::domain_to_ascii`].
+    #[inline]
+    pub fn verify_dns_length(mut self, value: bool) -> Self {
+        self.verify_dns_length = value;
+        self
+    }
+
+    /// Sets whether to check the hyphen positions (`CheckHyphens`).
+    ///
+    /// If enabled, labels must not begin or end with a hyphen.
+    #[inline]
+    pub fn check_hyphens(mut self, value: bool) -> Self {
+        self.check_hyphens = value;
+        self
+    }
+
+    /// Returns true if transitional processing is used.
+    #[inline]
+    #[must_use]
+    pub fn is_transitional(&self) -> bool {
+        self.transitional
+    }
+
+    /// Returns true if the STD3 ASCII rules are applied.
+    #[inline]
+    #[must_use]
+    pub fn uses_std3_ascii_rules(&self) -> bool {
+        self.use_std3_ascii_rules
+    }
+
+    /// Returns true if the DNS length restrictions are verified.
+    #[inline]
+    #[must_use]
+    pub fn verifies_dns_length(&self) -> bool {
+        self.verify_dns_length
+    }
+
+    /// Returns true if the hyphen positions are checked.
+    #[inline]
+    #[must_use]
+    pub fn checks_hyphens(&self) -> bool {
+        self.check_hyphens
+    }
+
+    /// Returns the configuration for `idna` crate.
+    fn config(&self) -> ::idna::Config {
+        ::idna::Config::default()
+            .transitional_processing(self.transitional)
+            .use_std3_ascii_rules(self.use_std3_ascii_rules)
+            .verify_dns_length(self.verify_dns_length)
+            .check_hyphens(self.check_hyphens)
+    }
+
+    /// Converts the domain name into the ASCII form (UTS #46 `ToASCII`).
+    ///
+    /// # Failures
+    ///
+    /// Fails if the domain name is invalid under the options.
+    pub fn domain_to_ascii(&self, domain: &str) -> Result<String, Error> {
+        self.config().to_ascii(domain).map_err(|_| Error::new())
+    }
+
+    /// Converts the domain name into the Unicode form (UTS #46 `ToUnicode`).
+    ///
+    /// # Failures
+    ///
+    /// Fails if the domain name is invalid under the options.
+    pub fn domain_to_unicode(&self, domain: &str) -> Result<String, Error> {
+        let (converted, result) = self.config().to_unicode(domain);
+        result.map(|()| converted).map_err(|_| Error::new())
+    }
+
+    /// Returns the IRI with the host converted into the ASCII form.
+    ///
+    /// Percent-encoded hosts are decoded before the conversion. IP addresses
+    /// are returned as is, and the IRI without hosts is returned as is.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the host is invalid under the options, or the host is
+    /// percent-encoded but is not a UTF-8 string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::idna::Error;
+    /// use iri_string::idna::IdnaOptions;
+    /// use iri_string::types::IriStr;
+    ///
+    /// let iri = IriStr::new("https://\u{4F8B}\u{3048}.\u{30C6}\u{30B9}\u{30C8}/path")
+    ///     .expect("valid IRI");
+    /// let converted = IdnaOptions::new().host_to_ascii(iri)?;
+    /// assert_eq!(converted, "https://xn--r8jz45g.xn--zckzah/path");
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub fn host_to_ascii<S: Spec>(&self, iri: &RiStr<S>) -> Result<RiString<S>, Error> {
+        let host = match iri.authority_components() {
+            Some(authority) => authority.host(),
+            None => return Ok(iri.into()),
+        };
+        if host.starts_with('[') || validate_ipv4address(host).is_ok() {
+            return Ok(iri.into());
+        }
+        let decoded = decode_bytes(host, false);
+        let decoded = core::str::from_utf8(&decoded).map_err(|_| Error::new())?;
+        let converted = self.domain_to_ascii(decoded)?;
+        if converted == host {
+            return Ok(iri.into());
+        }
+        iri.with_host(&converted).map_err(|_| Error::new())
+    }
+}
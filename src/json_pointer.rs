@@ -0,0 +1,382 @@
+//! JSON Pointer fragment identifiers.
+//!
+//! See [RFC 6901] for JSON Pointer, and its [section 6] for the fragment
+//! identifier representation used by `application/json` (and JSON Schema
+//! `$ref`). In the fragment, the JSON Pointer string is percent-encoded as
+//! UTF-8.
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::json_pointer::Error;
+//! use iri_string::json_pointer::JsonPointer;
+//! use iri_string::types::UriReferenceStr;
+//!
+//! let uri = UriReferenceStr::new("schema.json#/$defs/a~1b/m%25n").expect("valid URI");
+//! let pointer = JsonPointer::from_fragment(uri.fragment().expect("has fragment"))?;
+//! assert_eq!(pointer.as_str(), "/$defs/a~1b/m%n");
+//! assert!(pointer.tokens().eq(["$defs", "a/b", "m%n"]));
+//! # Ok::<_, Error>(())
+//! ```
+//!
+//! To resolve JSON Schema `$ref` references against `$id` base IRIs, use
+//! [`resolve_ref`].
+//!
+//! [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901.html
+//! [section 6]: https://www.rfc-editor.org/rfc/rfc6901.html#section-6
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+
+use crate::format::ToDedicatedString;
+use crate::percent_decode::decode_bytes;
+use crate::percent_encode::PercentEncoded;
+use crate::spec::Spec;
+use crate::types::{
+    RiAbsoluteStr, RiFragmentStr, RiFragmentString, RiReferenceStr, RiStr, RiString,
+};
+
+/// Error on parsing JSON Pointers or resolving references.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid JSON pointer or unresolvable reference")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+/// JSON Pointer.
+///
+/// This holds the JSON Pointer string, i.e. the reference tokens escaped by
+/// `~0` and `~1` and joined with `/`, but not percent-encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JsonPointer<'a> {
+    /// JSON Pointer string.
+    pointer: Cow<'a, str>,
+}
+
+impl<'a> JsonPointer<'a> {
+    /// Creates a new JSON Pointer from the JSON Pointer string.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the string is not empty and does not start with `/`, or if
+    /// `~` is not followed by `0` or `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::json_pointer::JsonPointer;
+    ///
+    /// assert!(JsonPointer::new("").is_ok());
+    /// assert!(JsonPointer::new("/a b/~0").is_ok());
+    /// assert!(JsonPointer::new("a").is_err());
+    /// assert!(JsonPointer::new("/~2").is_err());
+    /// assert!(JsonPointer::new("/~").is_err());
+    /// ```
+    pub fn new(pointer: &'a str) -> Result<Self, Error> {
+        Self::from_cow(Cow::Borrowed(pointer))
+    }
+
+    /// Creates a new JSON Pointer from the fragment.
+    ///
+    /// Percent-encoded triplets in the fragment are decoded.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the decoded fragment is not valid UTF-8, or not a valid JSON
+    /// Pointer string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::json_pointer::JsonPointer;
+    /// use iri_string::types::IriFragmentStr;
+    ///
+    /// let fragment = IriFragmentStr::new("/caf\u{E9}/%C3%A9").expect("valid fragment");
+    /// let pointer = JsonPointer::from_fragment(fragment).expect("valid pointer");
+    /// assert_eq!(pointer.as_str(), "/caf\u{E9}/\u{E9}");
+    ///
+    /// let anchor = IriFragmentStr::new("anchor").expect("valid fragment");
+    /// assert!(JsonPointer::from_fragment(anchor).is_err());
+    /// let non_utf8 = IriFragmentStr::new("/%FF").expect("valid fragment");
+    /// assert!(JsonPointer::from_fragment(non_utf8).is_err());
+    /// ```
+    pub fn from_fragment<S: Spec>(fragment: &'a RiFragmentStr<S>) -> Result<Self, Error> {
+        let s = fragment.as_str();
+        let pointer = match decode_bytes(s, false) {
+            Cow::Borrowed(_) => Cow::Borrowed(s),
+            Cow::Owned(bytes) => Cow::Owned(String::from_utf8(bytes).map_err(|_| Error::new())?),
+        };
+        Self::from_cow(pointer)
+    }
+
+    /// Creates a new JSON Pointer from the possibly owned string.
+    fn from_cow(pointer: Cow<'a, str>) -> Result<Self, Error> {
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(Error::new());
+        }
+        let bytes = pointer.as_bytes();
+        let is_escape_valid = bytes
+            .iter()
+            .enumerate()
+            .filter(|(_, &b)| b == b'~')
+            .all(|(i, _)| matches!(bytes.get(i + 1), Some(b'0' | b'1')));
+        if !is_escape_valid {
+            return Err(Error::new());
+        }
+        Ok(Self { pointer })
+    }
+
+    /// Returns the JSON Pointer string.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.pointer
+    }
+
+    /// Returns true if the pointer refers to the whole document.
+    #[inline]
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.pointer.is_empty()
+    }
+
+    /// Returns an iterator of the unescaped reference tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::json_pointer::JsonPointer;
+    ///
+    /// let pointer = JsonPointer::new("/a/~01/").expect("valid pointer");
+    /// assert!(pointer.tokens().eq(["a", "~1", ""]));
+    /// assert_eq!(JsonPointer::new("").expect("valid pointer").tokens().count(), 0);
+    /// ```
+    #[must_use]
+    pub fn tokens(&self) -> Tokens<'_> {
+        let mut split = self.pointer.split('/');
+        // Skip the empty string before the leading slash. For the root
+        // pointer, `split` yields only the empty string.
+        split.next();
+        Tokens { split }
+    }
+
+    /// Returns the fragment representation of the pointer.
+    ///
+    /// Characters not allowed in the fragment are percent-encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::json_pointer::JsonPointer;
+    /// use iri_string::types::{IriFragmentString, UriFragmentString};
+    ///
+    /// let pointer = JsonPointer::new("/caf\u{E9}/a b/%").expect("valid pointer");
+    /// let iri: IriFragmentString = pointer.to_fragment();
+    /// assert_eq!(iri, "/caf\u{E9}/a%20b/%25");
+    /// let uri: UriFragmentString = pointer.to_fragment();
+    /// assert_eq!(uri, "/caf%C3%A9/a%20b/%25");
+    /// ```
+    #[must_use]
+    pub fn to_fragment<S: Spec>(&self) -> RiFragmentString<S> {
+        let encoded = PercentEncoded::<_, S>::from_fragment(self.as_str()).to_string();
+        RiFragmentString::try_from(encoded)
+            .expect("[validity] the encoded string should be a valid fragment")
+    }
+
+    /// Converts the pointer into an owned one.
+    #[must_use]
+    pub fn into_owned(self) -> JsonPointer<'static> {
+        JsonPointer {
+            pointer: Cow::Owned(self.pointer.into_owned()),
+        }
+    }
+}
+
+impl fmt::Display for JsonPointer<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Iterator of the unescaped reference tokens of a JSON Pointer.
+///
+/// This is a return type of [`JsonPointer::tokens`].
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+    /// Escaped tokens.
+    split: core::str::Split<'a, char>,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.split.next().map(unescape_token)
+    }
+}
+
+impl core::iter::FusedIterator for Tokens<'_> {}
+
+/// Unescapes `~1` and `~0` in the reference token.
+fn unescape_token(token: &str) -> Cow<'_, str> {
+    if !token.contains('~') {
+        return Cow::Borrowed(token);
+    }
+    let mut buf = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' {
+            buf.push(match chars.next() {
+                Some('0') => '~',
+                Some('1') => '/',
+                _ => unreachable!("[validity] escapes are validated on creation"),
+            });
+        } else {
+            buf.push(c);
+        }
+    }
+    Cow::Owned(buf)
+}
+
+/// Resolves the reference (such as `$ref` of JSON Schema) against the base IRI (such as `$id`).
+///
+/// The fragment of the base IRI is ignored, so `$id` with an empty fragment
+/// (as written by some schemas) can be passed as is. Paths are resolved as
+/// [RFC 3986 section 5.2] specifies, but no other normalization is applied;
+/// normalize the result if documents should be compared more leniently.
+///
+/// # Failures
+///
+/// Fails if the resolution result cannot be represented as an IRI. See
+/// [`Normalized::ensure_rfc3986_normalizable`] for the condition.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::json_pointer::Error;
+/// use iri_string::json_pointer::resolve_ref;
+/// use iri_string::types::{UriReferenceStr, UriStr};
+///
+/// let id = UriStr::new("https://example.com/schemas/root.json#").expect("valid IRI");
+///
+/// let reference = UriReferenceStr::new("defs.json#/$defs/positive%20int").expect("valid reference");
+/// let resolved = resolve_ref(id, reference)?;
+/// assert_eq!(resolved.document(), "https://example.com/schemas/defs.json");
+/// assert!(resolved.pointer()?.tokens().eq(["$defs", "positive int"]));
+///
+/// // Same-document references keep the base document.
+/// let reference = UriReferenceStr::new("#/properties/name").expect("valid reference");
+/// let resolved = resolve_ref(id, reference)?;
+/// assert_eq!(resolved.document(), "https://example.com/schemas/root.json");
+/// assert_eq!(resolved.pointer()?.as_str(), "/properties/name");
+///
+/// // Plain name fragments are anchors, not pointers.
+/// let reference = UriReferenceStr::new("other.json#node").expect("valid reference");
+/// let resolved = resolve_ref(id, reference)?;
+/// assert_eq!(resolved.anchor().map(|a| a.as_str()), Some("node"));
+/// assert!(resolved.pointer().is_err());
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// [RFC 3986 section 5.2]: https://www.rfc-editor.org/rfc/rfc3986.html#section-5.2
+/// [`Normalized::ensure_rfc3986_normalizable`]: `crate::normalize::Normalized::ensure_rfc3986_normalizable`
+pub fn resolve_ref<S: Spec>(
+    base: &RiStr<S>,
+    reference: &RiReferenceStr<S>,
+) -> Result<ResolvedRef<S>, Error> {
+    let base: &RiAbsoluteStr<S> = base.to_absolute();
+    let iri = reference
+        .resolve_against(base)
+        .try_to_dedicated_string()
+        .map_err(|_| Error::new())?;
+    Ok(ResolvedRef { iri })
+}
+
+/// Reference resolved against the base IRI.
+///
+/// This is a return type of [`resolve_ref`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResolvedRef<S: Spec> {
+    /// Resolved IRI.
+    iri: RiString<S>,
+}
+
+impl<S: Spec> ResolvedRef<S> {
+    /// Returns the resolved IRI.
+    #[inline]
+    #[must_use]
+    pub fn as_iri(&self) -> &RiStr<S> {
+        &self.iri
+    }
+
+    /// Returns the resolved IRI.
+    #[inline]
+    #[must_use]
+    pub fn into_iri(self) -> RiString<S> {
+        self.iri
+    }
+
+    /// Returns the IRI of the document, i.e. the resolved IRI without the fragment.
+    #[inline]
+    #[must_use]
+    pub fn document(&self) -> &RiAbsoluteStr<S> {
+        self.iri.to_absolute()
+    }
+
+    /// Returns the fragment.
+    #[inline]
+    #[must_use]
+    pub fn fragment(&self) -> Option<&RiFragmentStr<S>> {
+        self.iri.fragment()
+    }
+
+    /// Returns the JSON Pointer in the fragment.
+    ///
+    /// The absent and empty fragments are the root pointer.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the fragment is not a valid JSON Pointer, including the case
+    /// when the fragment is an [anchor][`Self::anchor`].
+    pub fn pointer(&self) -> Result<JsonPointer<'_>, Error> {
+        match self.fragment() {
+            Some(fragment) => JsonPointer::from_fragment(fragment),
+            None => Ok(JsonPointer {
+                pointer: Cow::Borrowed(""),
+            }),
+        }
+    }
+
+    /// Returns the plain name fragment (such as `$anchor` of JSON Schema).
+    ///
+    /// Returns `None` if the fragment is absent, empty, or starts with `/`
+    /// (i.e. can be a JSON Pointer).
+    #[must_use]
+    pub fn anchor(&self) -> Option<&RiFragmentStr<S>> {
+        self.fragment()
+            .filter(|fragment| !fragment.is_empty() && !fragment.as_str().starts_with('/'))
+    }
+}
@@ -0,0 +1,702 @@
+//! Lenient processing similar to [WHATWG URL Standard].
+//!
+//! Strict RFC 3986 / RFC 3987 decomposition sometimes disagrees with how web
+//! browsers interpret the same string. Utilities in this module let callers
+//! (especially sanitizers) classify valid IRIs the way browsers do, without
+//! changing what strings are accepted by the strict types.
+//!
+//! [WHATWG URL Standard]: https://url.spec.whatwg.org/
+
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::fmt::Write as _;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::components::RiReferenceComponents;
+use crate::format::eq_str_display;
+#[cfg(feature = "alloc")]
+use crate::parser::char::{is_ascii_unreserved_or_reserved, is_nonascii_query};
+use crate::parser::trusted::hexdigits_to_byte;
+use crate::spec::Spec;
+use crate::types::RiReferenceStr;
+#[cfg(feature = "alloc")]
+use crate::types::RiReferenceString;
+#[cfg(feature = "alloc")]
+use crate::validate::Error;
+
+/// Schemes whose content is always interpreted as an opaque path by browsers.
+const OPAQUE_SPECIAL_SCHEMES: &[&str] = &["javascript", "about", "blob", "data"];
+
+/// Returns true if the scheme is handled as an opaque-path scheme in the lenient mode.
+///
+/// The schemes `javascript`, `about`, `blob`, and `data` are recognized.
+/// The comparison is ASCII case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::lenient::is_opaque_special_scheme;
+///
+/// assert!(is_opaque_special_scheme("javascript"));
+/// assert!(is_opaque_special_scheme("DATA"));
+/// assert!(!is_opaque_special_scheme("http"));
+/// ```
+#[must_use]
+pub fn is_opaque_special_scheme(scheme: &str) -> bool {
+    OPAQUE_SPECIAL_SCHEMES
+        .iter()
+        .any(|special| special.eq_ignore_ascii_case(scheme))
+}
+
+/// Components of an IRI reference decomposed in the lenient mode.
+///
+/// For the schemes recognized by [`is_opaque_special_scheme`], the content
+/// after the scheme is never parsed as an authority: everything until the
+/// query or the fragment is the opaque path. For other IRIs, the
+/// decomposition is the same as the strict one.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::lenient::Components;
+/// use iri_string::types::IriReferenceStr;
+///
+/// let iri = IriReferenceStr::new("javascript://example.com/%0Aalert(1)")?;
+/// // Strict decomposition finds an authority...
+/// assert_eq!(iri.authority_str(), Some("example.com"));
+///
+/// // ...but browsers treat the content as an opaque path.
+/// let components = Components::from_iri(iri);
+/// assert!(components.has_opaque_path());
+/// assert_eq!(components.authority(), None);
+/// assert_eq!(components.path(), "//example.com/%0Aalert(1)");
+/// # Ok::<_, Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "yoke", derive(yoke::Yokeable))]
+pub struct Components<'a> {
+    /// Scheme.
+    scheme: Option<&'a str>,
+    /// Authority.
+    authority: Option<&'a str>,
+    /// Path.
+    path: &'a str,
+    /// Query.
+    query: Option<&'a str>,
+    /// Fragment.
+    fragment: Option<&'a str>,
+    /// Whether the path is opaque.
+    has_opaque_path: bool,
+}
+
+impl<'a> Components<'a> {
+    /// Decomposes the IRI reference in the lenient mode.
+    #[must_use]
+    pub fn from_iri<S: Spec>(iri: &'a RiReferenceStr<S>) -> Self {
+        let (scheme, authority, path, query, fragment) =
+            RiReferenceComponents::from(iri).to_major();
+        match scheme {
+            Some(scheme) if is_opaque_special_scheme(scheme) => {
+                let s = iri.as_str();
+                let path_start = scheme.len() + 1;
+                // 1: the leading `?` or `#`.
+                let path_end = s.len()
+                    - query.map_or(0, |query| query.len() + 1)
+                    - fragment.map_or(0, |fragment| fragment.len() + 1);
+                Self {
+                    scheme: Some(scheme),
+                    authority: None,
+                    path: &s[path_start..path_end],
+                    query,
+                    fragment,
+                    has_opaque_path: true,
+                }
+            }
+            _ => Self {
+                scheme,
+                authority,
+                path,
+                query,
+                fragment,
+                has_opaque_path: scheme.is_some() && authority.is_none() && !path.starts_with('/'),
+            },
+        }
+    }
+
+    /// Returns the scheme.
+    #[inline]
+    #[must_use]
+    pub fn scheme(&self) -> Option<&'a str> {
+        self.scheme
+    }
+
+    /// Returns the authority, excluding the leading `//`.
+    ///
+    /// This is always `None` for IRIs with an opaque path.
+    #[inline]
+    #[must_use]
+    pub fn authority(&self) -> Option<&'a str> {
+        self.authority
+    }
+
+    /// Returns the path.
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+
+    /// Returns the query, excluding the leading `?`.
+    #[inline]
+    #[must_use]
+    pub fn query(&self) -> Option<&'a str> {
+        self.query
+    }
+
+    /// Returns the fragment, excluding the leading `#`.
+    #[inline]
+    #[must_use]
+    pub fn fragment(&self) -> Option<&'a str> {
+        self.fragment
+    }
+
+    /// Returns true if the path is opaque in the sense of the WHATWG URL Standard.
+    ///
+    /// This is true for the opaque special schemes (see
+    /// [`is_opaque_special_scheme`]), and for absolute IRIs without authority
+    /// whose path does not start with a slash (such as `mailto:foo@example.com`).
+    #[inline]
+    #[must_use]
+    pub fn has_opaque_path(&self) -> bool {
+        self.has_opaque_path
+    }
+}
+
+/// Cleanup of the input string before the strict validation.
+///
+/// [WHATWG URL Standard] parsers strip leading and trailing C0 controls and
+/// spaces (U+0000 to U+0020), and remove all ASCII tabs and newlines (U+0009,
+/// U+000A, and U+000D) from the input. Values taken from HTML attributes (such
+/// as `href`) are often accepted by browsers thanks to this cleanup, though
+/// they are invalid as IRIs.
+///
+/// By default, both steps are enabled as WHATWG URL Standard does.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::lenient::InputCleanup;
+/// use iri_string::types::IriReferenceString;
+///
+/// let href = "  https://example.com/\n  very/long\t/path  ";
+/// let cleaned = InputCleanup::new().apply(href);
+/// assert_eq!(cleaned, "https://example.com/  very/long/path");
+/// // Spaces in the middle are not removed, so this is still invalid.
+/// assert!(IriReferenceString::try_from(&*cleaned).is_err());
+///
+/// let href = "\thttps://example.com/\n/path\r\n";
+/// let iri = IriReferenceString::try_from(&*InputCleanup::new().apply(href))?;
+/// assert_eq!(iri, "https://example.com//path");
+///
+/// // Only trim the string.
+/// let trim_only = InputCleanup::new().remove_tab_and_newline(false);
+/// assert_eq!(trim_only.apply(" /a\tb "), "/a\tb");
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// [WHATWG URL Standard]: https://url.spec.whatwg.org/#concept-basic-url-parser
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct InputCleanup {
+    /// Whether to strip leading and trailing C0 controls and spaces.
+    trim: bool,
+    /// Whether to remove ASCII tabs and newlines.
+    remove_tab_and_newline: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl Default for InputCleanup {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            trim: true,
+            remove_tab_and_newline: true,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl InputCleanup {
+    /// Creates the cleanup options same as WHATWG URL Standard.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether to strip leading and trailing C0 controls and spaces.
+    #[inline]
+    pub fn trim(mut self, value: bool) -> Self {
+        self.trim = value;
+        self
+    }
+
+    /// Sets whether to remove ASCII tabs and newlines anywhere in the input.
+    #[inline]
+    pub fn remove_tab_and_newline(mut self, value: bool) -> Self {
+        self.remove_tab_and_newline = value;
+        self
+    }
+
+    /// Returns the cleaned string.
+    ///
+    /// The result is borrowed from the input if no characters are removed
+    /// from the middle of the string.
+    #[must_use]
+    pub fn apply<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        /// Returns true if the character is a C0 control or a space.
+        fn is_c0_control_or_space(c: char) -> bool {
+            c <= '\u{20}'
+        }
+        /// Returns true if the character is an ASCII tab or newline.
+        fn is_tab_or_newline(c: char) -> bool {
+            matches!(c, '\t' | '\n' | '\r')
+        }
+
+        let s = if self.trim {
+            s.trim_matches(is_c0_control_or_space)
+        } else {
+            s
+        };
+        if !self.remove_tab_and_newline || !s.contains(is_tab_or_newline) {
+            return Cow::Borrowed(s);
+        }
+        let mut buf = String::with_capacity(s.len());
+        buf.extend(s.chars().filter(|&c| !is_tab_or_newline(c)));
+        Cow::Owned(buf)
+    }
+}
+
+/// Kind of a repair performed by [`sanitize`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FixupKind {
+    /// Whitespaces or control characters are removed.
+    WhitespaceRemoved,
+    /// A backslash is converted into a slash.
+    BackslashConverted,
+    /// A character is percent-encoded.
+    CharacterEncoded,
+}
+
+/// A repair performed by [`sanitize`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fixup {
+    /// Kind of the repair.
+    kind: FixupKind,
+    /// Byte range in the input.
+    span: Range<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl Fixup {
+    /// Returns the kind of the repair.
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> FixupKind {
+        self.kind
+    }
+
+    /// Returns the byte range of the repaired part in the input.
+    #[inline]
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// Report of repairs performed by [`sanitize`].
+///
+/// Repairs are listed in the order of their positions in the input.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixupReport {
+    /// Repairs.
+    fixups: Vec<Fixup>,
+}
+
+#[cfg(feature = "alloc")]
+impl FixupReport {
+    /// Returns the repairs.
+    #[inline]
+    #[must_use]
+    pub fn fixups(&self) -> &[Fixup] {
+        &self.fixups
+    }
+
+    /// Returns true if no repairs are performed, i.e. the input is used as is.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fixups.is_empty()
+    }
+
+    /// Records the repair, merging it with the previous one if adjacent.
+    fn push(&mut self, kind: FixupKind, span: Range<usize>) {
+        if let Some(last) = self.fixups.last_mut() {
+            if last.kind == kind && last.span.end == span.start {
+                last.span.end = span.end;
+                return;
+            }
+        }
+        self.fixups.push(Fixup { kind, span });
+    }
+}
+
+/// Repairs the string into an IRI reference, and reports the repairs.
+///
+/// The following repairs are performed:
+///
+/// * Whitespaces are removed as specified by the `cleanup` option.
+/// * Backslashes before the query and fragment are converted into slashes,
+///   as browsers do for special schemes.
+/// * Characters not allowed at their positions (such as spaces, `"`, `<`,
+///   `%` not followed by two hexadecimal digits, and the second `#`) are
+///   percent-encoded.
+///
+/// The input is used as is if it is already a valid IRI reference without
+/// whitespaces to be removed, and the report is empty in that case.
+///
+/// Adjacent repairs of the same kind are merged into one [`Fixup`].
+///
+/// # Failures
+///
+/// Fails if the repaired string is still not a valid IRI reference (for
+/// example when the scheme or the IP literal is broken).
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::lenient::{sanitize, FixupKind, InputCleanup};
+/// use iri_string::spec::UriSpec;
+///
+/// let (uri, report) = sanitize::<UriSpec>(
+///     " http:\\\\example.com\\a b/100%?q=\u{3042} ",
+///     &InputCleanup::new(),
+/// )?;
+/// assert_eq!(uri, "http://example.com/a%20b/100%25?q=%E3%81%82");
+///
+/// let kinds = report.fixups().iter().map(|f| f.kind()).collect::<Vec<_>>();
+/// assert_eq!(
+///     kinds,
+///     [
+///         FixupKind::WhitespaceRemoved,
+///         FixupKind::BackslashConverted,
+///         FixupKind::BackslashConverted,
+///         FixupKind::CharacterEncoded,
+///         FixupKind::CharacterEncoded,
+///         FixupKind::CharacterEncoded,
+///         FixupKind::WhitespaceRemoved,
+///     ]
+/// );
+/// assert_eq!(report.fixups()[1].span(), 6..8);
+///
+/// // Valid input is kept as is.
+/// let (uri, report) = sanitize::<UriSpec>(
+///     "http://example.com/",
+///     &InputCleanup::new(),
+/// )?;
+/// assert_eq!(uri, "http://example.com/");
+/// assert!(report.is_empty());
+/// # Ok::<_, Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn sanitize<S: Spec>(
+    s: &str,
+    cleanup: &InputCleanup,
+) -> Result<(RiReferenceString<S>, FixupReport), Error> {
+    let mut report = FixupReport::default();
+    let (start, end) = if cleanup.trim {
+        let start = s.len() - s.trim_start_matches(|c| c <= '\u{20}').len();
+        let end = start + s[start..].trim_end_matches(|c| c <= '\u{20}').len();
+        (start, end)
+    } else {
+        (0, s.len())
+    };
+
+    if start != 0 {
+        report.push(FixupKind::WhitespaceRemoved, 0..start);
+    }
+    let mut buf = String::with_capacity(end - start);
+    // Whether the query or the fragment has started.
+    let mut in_query = false;
+    let mut in_fragment = false;
+    for (pos, c) in s[start..end].char_indices() {
+        let pos = start + pos;
+        let span = pos..(pos + c.len_utf8());
+        if cleanup.remove_tab_and_newline && matches!(c, '\t' | '\n' | '\r') {
+            report.push(FixupKind::WhitespaceRemoved, span);
+            continue;
+        }
+        let is_raw = match c {
+            '\\' if !in_query && !in_fragment => {
+                report.push(FixupKind::BackslashConverted, span);
+                buf.push('/');
+                continue;
+            }
+            '#' => !core::mem::replace(&mut in_fragment, true),
+            '?' => {
+                in_query |= !in_fragment;
+                true
+            }
+            '%' => {
+                let rest = &s.as_bytes()[(pos + 1)..end];
+                rest.len() >= 2 && rest[0].is_ascii_hexdigit() && rest[1].is_ascii_hexdigit()
+            }
+            c if c.is_ascii() => is_ascii_unreserved_or_reserved(c as u8),
+            c if in_query && !in_fragment => is_nonascii_query::<S>(c),
+            c => S::is_nonascii_char_unreserved(c),
+        };
+        if is_raw {
+            buf.push(c);
+        } else {
+            report.push(FixupKind::CharacterEncoded, span);
+            for b in c.encode_utf8(&mut [0; 4]).bytes() {
+                write!(buf, "%{:02X}", b).expect("[consistency] writing to a string never fails");
+            }
+        }
+    }
+    if end != s.len() {
+        report.push(FixupKind::WhitespaceRemoved, end..s.len());
+    }
+
+    let iri = RiReferenceString::try_from(buf).map_err(|e| e.validation_error())?;
+    Ok((iri, report))
+}
+
+/// Result of interpreting a host as an IPv4 address in the way browsers do.
+///
+/// This is a return type of [`interpret_ipv4_host`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ipv4Interpretation {
+    /// The host is not interpreted as an IPv4 address (i.e. it is a domain name).
+    NotIpv4,
+    /// The host is interpreted as an IPv4 address.
+    Ipv4(Ipv4Host),
+    /// The host looks like an IPv4 address but is invalid, and browsers reject it.
+    ///
+    /// For example, `256.0.0.1`, `1.2.3.4.5`, and `0x100000000` are invalid.
+    Invalid,
+}
+
+/// IPv4 address host interpreted in the way browsers do.
+///
+/// `Display` prints the address in the dotted-decimal form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ipv4Host {
+    /// Octets of the address.
+    octets: [u8; 4],
+    /// Whether the source string was in the canonical dotted-decimal form.
+    is_canonical: bool,
+}
+
+impl Ipv4Host {
+    /// Returns the octets of the address.
+    #[inline]
+    #[must_use]
+    pub fn octets(&self) -> [u8; 4] {
+        self.octets
+    }
+
+    /// Returns true if the source host was in the canonical dotted-decimal form.
+    ///
+    /// Hosts in non-canonical forms (such as octal, hexadecimal, shorthand,
+    /// a trailing dot, or percent-encoded forms) are not valid as `IPv4address`
+    /// rule in RFC 3986, and might be used to obfuscate IP addresses.
+    #[inline]
+    #[must_use]
+    pub fn is_canonical(&self) -> bool {
+        self.is_canonical
+    }
+}
+
+impl fmt::Display for Ipv4Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.octets;
+        write!(f, "{}.{}.{}.{}", a, b, c, d)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Ipv4Host> for std::net::Ipv4Addr {
+    #[inline]
+    fn from(host: Ipv4Host) -> Self {
+        host.octets.into()
+    }
+}
+
+/// Interprets the host as an IPv4 address in the way [WHATWG URL Standard] does.
+///
+/// In addition to the dotted-decimal form, browsers recognize octal
+/// (`0177.0.0.1`), hexadecimal (`0x7f.0.0.1`), and shorthand (`127.1`,
+/// `2130706433`) forms. Percent-encoded hosts are decoded before
+/// interpretation. Such non-canonical forms are flagged by
+/// [`Ipv4Host::is_canonical`], which is useful to detect obfuscated IP
+/// literals.
+///
+/// Non-ASCII hosts are not interpreted as IPv4 addresses since this function
+/// does not apply IDNA processing.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::lenient::{interpret_ipv4_host, Ipv4Interpretation};
+///
+/// let host = match interpret_ipv4_host("0x7f.1") {
+///     Ipv4Interpretation::Ipv4(host) => host,
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(host.octets(), [127, 0, 0, 1]);
+/// assert!(!host.is_canonical());
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!(host.to_string(), "127.0.0.1");
+///
+/// match interpret_ipv4_host("192.168.0.1") {
+///     Ipv4Interpretation::Ipv4(host) => assert!(host.is_canonical()),
+///     _ => unreachable!(),
+/// }
+///
+/// assert_eq!(interpret_ipv4_host("example.com"), Ipv4Interpretation::NotIpv4);
+/// assert_eq!(interpret_ipv4_host("1.2.3.256"), Ipv4Interpretation::Invalid);
+/// ```
+///
+/// [WHATWG URL Standard]: https://url.spec.whatwg.org/#concept-ipv4-parser
+#[must_use]
+pub fn interpret_ipv4_host(host: &str) -> Ipv4Interpretation {
+    /// Maximum length of the percent-decoded host to interpret.
+    const DECODED_MAX: usize = 256;
+
+    if !host.contains('%') {
+        return interpret_ipv4_host_bytes(host.as_bytes(), host);
+    }
+    let mut buf = [0_u8; DECODED_MAX];
+    let mut len = 0;
+    let mut rest = host.as_bytes();
+    while let Some((&first, after_first)) = rest.split_first() {
+        let (byte, next) = match (first, after_first) {
+            (b'%', [upper, lower, next @ ..])
+                if upper.is_ascii_hexdigit() && lower.is_ascii_hexdigit() =>
+            {
+                (hexdigits_to_byte([*upper, *lower]), next)
+            }
+            _ => (first, after_first),
+        };
+        if len == DECODED_MAX {
+            // Too long to be an IPv4 address in practice.
+            return Ipv4Interpretation::NotIpv4;
+        }
+        buf[len] = byte;
+        len += 1;
+        rest = next;
+    }
+    interpret_ipv4_host_bytes(&buf[..len], host)
+}
+
+/// Interprets the (percent-decoded) host as an IPv4 address.
+///
+/// `source` is the original host string used to check the canonicality.
+fn interpret_ipv4_host_bytes(host: &[u8], source: &str) -> Ipv4Interpretation {
+    let mut parts = host.split(|&b| b == b'.');
+    let num_parts = parts.clone().count();
+    // A trailing dot is ignored.
+    let num_parts = match host.last() {
+        Some(b'.') if num_parts > 1 => num_parts - 1,
+        _ => num_parts,
+    };
+    let last = match host.split(|&b| b == b'.').nth(num_parts - 1) {
+        Some(v) => v,
+        None => return Ipv4Interpretation::NotIpv4,
+    };
+    // "Ends in a number" check.
+    let ends_in_number = (!last.is_empty() && last.iter().all(u8::is_ascii_digit))
+        || parse_ipv4_number(last).is_some() && matches!(last, [b'0', b'x' | b'X', ..]);
+    if !ends_in_number {
+        return Ipv4Interpretation::NotIpv4;
+    }
+    if num_parts > 4 {
+        return Ipv4Interpretation::Invalid;
+    }
+
+    let mut numbers = [0_u64; 4];
+    for (i, number) in numbers.iter_mut().take(num_parts).enumerate() {
+        let part = parts
+            .next()
+            .expect("[consistency] the number of parts is already checked");
+        *number = match parse_ipv4_number(part) {
+            Some(v) if i + 1 == num_parts || v <= 255 => v,
+            _ => return Ipv4Interpretation::Invalid,
+        };
+    }
+    let (last, init) = numbers[..num_parts]
+        .split_last()
+        .expect("[consistency] there is at least one part");
+    let last_bits = 8 * (5 - num_parts as u32);
+    if *last >= (1_u64 << last_bits) {
+        return Ipv4Interpretation::Invalid;
+    }
+    let mut addr = *last as u32;
+    for (i, &number) in init.iter().enumerate() {
+        addr |= (number as u32) << (8 * (3 - i as u32));
+    }
+
+    let octets = addr.to_be_bytes();
+    let is_canonical = {
+        let host = Ipv4Host {
+            octets,
+            is_canonical: false,
+        };
+        eq_str_display(source, &host)
+    };
+    Ipv4Interpretation::Ipv4(Ipv4Host {
+        octets,
+        is_canonical,
+    })
+}
+
+/// Parses the part of the IPv4 address in the way browsers do.
+///
+/// Returns `None` if the part is not a valid number.
+/// Too large numbers are saturated to `u64::MAX`.
+fn parse_ipv4_number(part: &[u8]) -> Option<u64> {
+    let (radix, digits) = match part {
+        [] => return None,
+        [b'0', b'x' | b'X', rest @ ..] => (16, rest),
+        [b'0', rest @ ..] if !rest.is_empty() => (8, rest),
+        _ => (10, part),
+    };
+    digits.iter().try_fold(0_u64, |acc, &b| {
+        let digit = char::from(b).to_digit(radix)?;
+        Some(
+            acc.saturating_mul(u64::from(radix))
+                .saturating_add(u64::from(digit)),
+        )
+    })
+}
@@ -34,10 +34,20 @@
 //! [`percent_encode` module][`percent_encode`] provides a converter to encode
 //! user-provided string into percent-encoded one (if syntax requires so).
 //!
+//! ## Query
+//!
+//! [`query` module][`query`] provides utilities for `key=value` pairs in
+//! queries, such as the decoded pairs iterator.
+//!
 //! ## IRI builder
 //!
 //! [`build` module][`build`] provides IRI builder.
 //!
+//! ## Lenient processing
+//!
+//! [`lenient` module][`lenient`] provides utilities to interpret IRIs the way
+//! web browsers do (as described in WHATWG URL Standard).
+//!
 //! ## URI template (RFC 6570)
 //!
 //! [`template` module][`template`] provides an RFC 6570 URI Template processor.
@@ -64,8 +74,92 @@
 //! * `serde`
 //!     + Enables serde support.
 //!     + Implement `Serailize` and `Deserialize` traits for IRI / URI types.
+//!     + Also enables `components::ComponentsSer` to serialize the parsed
+//!       component structure of IRIs.
+//! * `ffi`
+//!     + Enables `ffi` module providing `extern "C"` functions to validate and
+//!       decompose IRIs without copying strings, for embedding the validator
+//!       into C / C++ and other language runtimes.
+//! * `hmac`
+//!     + Enables `signed_url` module to sign IRIs with expiration using
+//!       HMAC-SHA256, for presigned links.
+//!     + HMAC-SHA256 is provided by `hmac` and `sha2` crates.
+//!     + This automatically enables `alloc` feature.
+//! * `idna`
+//!     + Enables IDNA (UTS #46) processing of hosts with configurable options,
+//!       such as `idna::IdnaOptions::host_to_ascii()`.
+//!     + This automatically enables `alloc` feature.
+//! * `json-pointer`
+//!     + Enables `json_pointer` module to interpret fragments as JSON Pointers
+//!       (RFC 6901), as used by `application/json` and JSON Schema `$ref`.
+//!     + This automatically enables `alloc` feature.
 //! * `memchr`
 //!     + Enables faster internal character search.
+//! * `oxiri`
+//!     + Implements conversions between IRI types and `oxiri::Iri` /
+//!       `oxiri::IriRef`, for interoperability with RDF toolkits.
+//!     + This automatically enables `std` feature.
+//! * `psl`
+//!     + Enables registrable domain (eTLD+1) extraction using Public Suffix
+//!       List, such as `components::AuthorityComponents::registrable_domain()`
+//!       and `components::Origin::site()`.
+//! * `rayon`
+//!     + Enables parallel batch validation, such as `validate::par_validate_all()`.
+//!     + This automatically enables `std` feature.
+//! * `redact-debug`
+//!     + Makes `Debug` implementations of IRI string types mask the userinfo.
+//!       See the `redact` module for details.
+//! * `redact-debug-query`
+//!     + Makes `Debug` implementations of IRI string types mask the query
+//!       values, in addition to the userinfo.
+//!     + This automatically enables `redact-debug` feature.
+//! * `rkyv`
+//!     + Implements `rkyv` traits (`Archive`, `Serialize`, `Deserialize`, and
+//!       `CheckBytes` for archived types) for owned IRI / URI types.
+//!     + Archived values are validated as IRIs when accessed with validation,
+//!       and can be used as borrowed IRI types without parsing or allocation.
+//!     + The archive format (`rkyv/size_16`, `rkyv/size_32`, or
+//!       `rkyv/size_64`) is not selected by this crate. Enable one of them in
+//!       your own dependency on `rkyv`.
+//!     + This automatically enables `alloc` feature.
+//! * `schemars`
+//!     + Implements `schemars::JsonSchema` for IRI / URI types.
+//!     + Types are described as strings with `format` of `iri`, `iri-reference`,
+//!       `uri`, or `uri-reference`.
+//!     + This automatically enables `std` feature.
+//! * `test-vectors`
+//!     + Enables `test_vectors` module providing the test vectors (from RFC
+//!       3986, RFC 3987, W3C test suites, and curated tricky inputs) and the conformance
+//!       harness, to check wrappers and other implementations.
+//! * `tracing`
+//!     + Enables `tracing_value()` methods of IRI types to record IRIs
+//!       (with password masked) as `tracing` field values.
+//!     + `tracing::Value` is not implemented for IRI types, since the trait is
+//!       sealed by `tracing-core`.
+//! * `unicode-normalization`
+//!     + Enables `to_nfc()` methods of IRI types to apply Unicode normalization
+//!       (NFC) to IRIs, as recommended by RFC 3987.
+//!     + This automatically enables `alloc` feature.
+//! * `validate-mutations`
+//!     + Re-validates the whole IRI after every in-place mutation (such as
+//!       `set_query()` and `ensure_trailing_slash()`) even in release build.
+//!       By default, this is done only in debug build, and mutation APIs
+//!       validate only the changed component.
+//! * `wasm`
+//!     + Implements conversions between string types and `js_sys::JsString`,
+//!       for validators running in browsers.
+//!     + This automatically enables `alloc` feature.
+//! * `xpointer`
+//!     + Enables `xpointer` module to interpret fragments as shorthand
+//!       XPointers, as used by XML media types.
+//!     + This automatically enables `alloc` feature.
+//! * `yoke`
+//!     + Implements `stable_deref_trait::StableDeref` for owned IRI types, and
+//!       `yoke::Yokeable` for borrowed components views such as
+//!       `components::AuthorityComponents`.
+//!     + This allows bundling an owned IRI string and a view borrowed from it
+//!       into a single `yoke::Yoke` value.
+//!     + This automatically enables `alloc` feature.
 //!
 //! # Rationale
 //!
@@ -143,17 +237,77 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod acct;
 pub mod build;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod bulk;
+pub mod chars;
 pub mod components;
 pub mod convert;
+pub mod escape;
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod file;
 pub mod format;
+#[cfg(feature = "idna")]
+#[cfg_attr(docsrs, doc(cfg(feature = "idna")))]
+pub mod idna;
+#[cfg(feature = "json-pointer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json-pointer")))]
+pub mod json_pointer;
+pub mod lenient;
+pub mod list;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod mailto;
 pub mod mask_password;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod ni;
 pub mod normalize;
-pub(crate) mod parser;
+pub mod parser;
+pub mod path;
+pub mod pattern;
+pub(crate) mod percent_decode;
 pub mod percent_encode;
+pub mod policy;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod punycode;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod query;
 pub(crate) mod raw;
+pub mod redact;
 pub mod resolve;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod scheme;
+pub mod scp;
+#[cfg(feature = "hmac")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hmac")))]
+pub mod signed_url;
 pub mod spec;
+pub(crate) mod stable_hash;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod suggest;
 pub mod template;
+#[cfg(feature = "test-vectors")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-vectors")))]
+pub mod test_vectors;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod transform;
+pub mod truncate;
 pub mod types;
 pub mod validate;
+#[cfg(feature = "xpointer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xpointer")))]
+pub mod xpointer;
@@ -0,0 +1,249 @@
+//! Tokenizer for lists of IRI references.
+//!
+//! Attribute values and header fields sometimes contain multiple IRI
+//! references, such as `rel` targets, `ping` attribute values in HTML, or
+//! comma-separated header fields. [`ListItems`] iterates over the items
+//! in such values, and yields validated IRI references with their spans.
+//!
+//! # Examples
+//!
+//! ```
+//! use iri_string::list::{ListItems, Separator};
+//! use iri_string::spec::IriSpec;
+//!
+//! let value = " https://example.com/a  /b\t../c ";
+//! let mut items = ListItems::<IriSpec>::new(value, Separator::Whitespace);
+//!
+//! let first = items.next().expect("has an item").expect("valid IRI reference");
+//! assert_eq!(first.as_iri_ref(), "https://example.com/a");
+//! assert_eq!(first.span(), 1..22);
+//! assert_eq!(&value[first.span()], "https://example.com/a");
+//!
+//! let rest = items
+//!     .map(|item| item.expect("valid IRI reference").as_iri_ref().as_str())
+//!     .collect::<Vec<_>>();
+//! assert_eq!(rest, ["/b", "../c"]);
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::spec::Spec;
+use crate::types::RiReferenceStr;
+use crate::validate::Error;
+
+/// Separator of IRI references in a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Separator {
+    /// ASCII whitespaces.
+    ///
+    /// IRI references cannot contain whitespaces, so this separator never
+    /// splits a valid IRI reference.
+    Whitespace,
+    /// Commas, optionally surrounded by ASCII whitespaces.
+    ///
+    /// Note that IRI references can contain commas, so an IRI reference
+    /// containing commas is split into multiple items. Percent-encode commas
+    /// (as `%2C`) in IRI references to be listed with this separator.
+    Comma,
+    /// ASCII whitespaces or commas.
+    ///
+    /// Note that IRI references can contain commas, so an IRI reference
+    /// containing commas is split into multiple items.
+    WhitespaceOrComma,
+}
+
+impl Separator {
+    /// Returns true if the byte is a separator.
+    #[inline]
+    #[must_use]
+    fn is_separator(self, b: u8) -> bool {
+        match self {
+            Self::Whitespace => b.is_ascii_whitespace(),
+            Self::Comma => b == b',',
+            Self::WhitespaceOrComma => b.is_ascii_whitespace() || b == b',',
+        }
+    }
+}
+
+/// An IRI reference in a list, with its position.
+pub struct ListItem<'a, S: Spec> {
+    /// IRI reference.
+    iri_ref: &'a RiReferenceStr<S>,
+    /// Byte position of the IRI reference in the list.
+    start: usize,
+}
+
+impl<'a, S: Spec> ListItem<'a, S> {
+    /// Returns the IRI reference.
+    #[inline]
+    #[must_use]
+    pub fn as_iri_ref(&self) -> &'a RiReferenceStr<S> {
+        self.iri_ref
+    }
+
+    /// Returns the byte range of the IRI reference in the list.
+    #[inline]
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.start..(self.start + self.iri_ref.len())
+    }
+}
+
+impl<S: Spec> Clone for ListItem<'_, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: Spec> Copy for ListItem<'_, S> {}
+
+impl<S: Spec> fmt::Debug for ListItem<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListItem")
+            .field("iri_ref", &self.iri_ref)
+            .field("span", &self.span())
+            .finish()
+    }
+}
+
+/// An invalid item in a list, with its position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidListItem<'a> {
+    /// Invalid item.
+    item: &'a str,
+    /// Byte position of the item in the list.
+    start: usize,
+    /// Validation error.
+    error: Error,
+}
+
+impl<'a> InvalidListItem<'a> {
+    /// Returns the invalid item.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.item
+    }
+
+    /// Returns the byte range of the invalid item in the list.
+    #[inline]
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.start..(self.start + self.item.len())
+    }
+
+    /// Returns the validation error.
+    #[inline]
+    #[must_use]
+    pub fn validation_error(&self) -> Error {
+        self.error
+    }
+}
+
+impl fmt::Display for InvalidListItem<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid IRI reference at {}..{} in the list",
+            self.start,
+            self.start + self.item.len()
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for InvalidListItem<'_> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Iterator over IRI references in a list.
+///
+/// Empty items (such as the ones between consecutive separators) are skipped.
+/// Invalid items are yielded as errors, and the iteration can continue after
+/// them.
+///
+/// Note that the empty string is a valid IRI reference (i.e. the same-document
+/// reference), but it is never yielded by this iterator.
+pub struct ListItems<'a, S: Spec> {
+    /// List.
+    list: &'a str,
+    /// Current byte position.
+    pos: usize,
+    /// Separator.
+    separator: Separator,
+    /// Spec.
+    _spec: PhantomData<&'a RiReferenceStr<S>>,
+}
+
+impl<'a, S: Spec> ListItems<'a, S> {
+    /// Creates a new iterator over IRI references in the list.
+    #[inline]
+    #[must_use]
+    pub fn new(list: &'a str, separator: Separator) -> Self {
+        Self {
+            list,
+            pos: 0,
+            separator,
+            _spec: PhantomData,
+        }
+    }
+}
+
+impl<S: Spec> Clone for ListItems<'_, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list,
+            pos: self.pos,
+            separator: self.separator,
+            _spec: PhantomData,
+        }
+    }
+}
+
+impl<S: Spec> fmt::Debug for ListItems<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListItems")
+            .field("rest", &&self.list[self.pos..])
+            .field("pos", &self.pos)
+            .field("separator", &self.separator)
+            .finish()
+    }
+}
+
+impl<'a, S: Spec> Iterator for ListItems<'a, S> {
+    type Item = Result<ListItem<'a, S>, InvalidListItem<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.list.as_bytes();
+        let is_delimiter = |b: u8| self.separator.is_separator(b) || b.is_ascii_whitespace();
+
+        let start = self.pos + bytes[self.pos..].iter().position(|&b| !is_delimiter(b))?;
+        let end = bytes[start..]
+            .iter()
+            .position(|&b| match self.separator {
+                // Whitespaces inside comma-separated items make them invalid.
+                Separator::Comma => b == b',',
+                _ => is_delimiter(b),
+            })
+            .map_or(bytes.len(), |len| start + len);
+        self.pos = end;
+
+        // All delimiters are ASCII characters, so the positions are char boundaries.
+        let item = self.list[start..end].trim_end_matches(|c: char| c.is_ascii_whitespace());
+        Some(
+            RiReferenceStr::<S>::new(item)
+                .map(|iri_ref| ListItem { iri_ref, start })
+                .map_err(|error| InvalidListItem { item, start, error }),
+        )
+    }
+}
+
+impl<'a, S: Spec> core::iter::FusedIterator for ListItems<'a, S> {}
@@ -0,0 +1,330 @@
+//! Conversion between email addresses and `mailto:` IRIs.
+//!
+//! See [RFC 6068] for `mailto:` IRIs, and [RFC 5321] and [RFC 6531] for
+//! email addresses.
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::mailto::Error;
+//! use iri_string::mailto::{address_to_mailto, mailto_to_addresses, AddressEncoding};
+//! use iri_string::types::{IriString, UriString};
+//!
+//! let iri: IriString = address_to_mailto("\"john doe\"@example.com", AddressEncoding::Smtputf8)?;
+//! assert_eq!(iri, "mailto:%22john%20doe%22@example.com");
+//! assert_eq!(
+//!     mailto_to_addresses(&iri, AddressEncoding::Smtputf8)?,
+//!     ["\"john doe\"@example.com"]
+//! );
+//!
+//! // Non-ASCII characters are kept for IRIs and percent-encoded for URIs.
+//! let address = "user@b\u{FC}cher.example";
+//! let iri: IriString = address_to_mailto(address, AddressEncoding::Smtputf8)?;
+//! assert_eq!(iri, "mailto:user@b\u{FC}cher.example");
+//! let uri: UriString = address_to_mailto(address, AddressEncoding::Smtputf8)?;
+//! assert_eq!(uri, "mailto:user@b%C3%BCcher.example");
+//!
+//! // Domains can be converted into Punycode for servers without SMTPUTF8.
+//! let uri: UriString = address_to_mailto(address, AddressEncoding::Ascii)?;
+//! assert_eq!(uri, "mailto:user@xn--bcher-kva.example");
+//! # Ok::<_, Error>(())
+//! ```
+//!
+//! [RFC 5321]: https://datatracker.ietf.org/doc/html/rfc5321
+//! [RFC 6068]: https://datatracker.ietf.org/doc/html/rfc6068
+//! [RFC 6531]: https://datatracker.ietf.org/doc/html/rfc6531
+
+use core::fmt::{self, Write as _};
+
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::percent_decode::decode_bytes;
+use crate::punycode;
+use crate::spec::Spec;
+use crate::types::{RiStr, RiString};
+
+/// Error on conversion between email addresses and `mailto:` IRIs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid email address or `mailto` IRI")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+/// Representation of non-ASCII email addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressEncoding {
+    /// Keeps non-ASCII characters as is, for SMTPUTF8 ([RFC 6531]) servers.
+    ///
+    /// [RFC 6531]: https://datatracker.ietf.org/doc/html/rfc6531
+    Smtputf8,
+    /// Converts non-ASCII domain labels into Punycode (`xn--`) labels.
+    ///
+    /// Non-ASCII local parts are rejected since they have no ASCII
+    /// representation. Note that encoded-words ([RFC 2047]) are not allowed
+    /// in addresses.
+    ///
+    /// Domain labels are converted as is, without IDNA mapping. Normalize the
+    /// domain beforehand if necessary.
+    ///
+    /// [RFC 2047]: https://datatracker.ietf.org/doc/html/rfc2047
+    Ascii,
+}
+
+/// Checks if the byte is `atext` of RFC 5322.
+#[inline]
+#[must_use]
+fn is_ascii_atext(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-/=?^_`{|}~".contains(&b)
+}
+
+/// Splits the address into the local part and the domain, and validates them.
+fn split_address(address: &str) -> Result<(&str, &str), Error> {
+    let at = address.rfind('@').ok_or_else(Error::new)?;
+    let (local, domain) = (&address[..at], &address[(at + 1)..]);
+
+    let is_local_valid = if let Some(quoted) = local.strip_prefix('"') {
+        // Quoted string. Backslashes are used as escapes.
+        let content = quoted.strip_suffix('"').ok_or_else(Error::new)?;
+        let mut chars = content.chars();
+        let mut is_valid = true;
+        while let Some(c) = chars.next() {
+            let c = if c == '\\' {
+                chars.next().ok_or_else(Error::new)?
+            } else if c == '"' {
+                is_valid = false;
+                break;
+            } else {
+                c
+            };
+            if c.is_ascii_control() && c != '\t' {
+                is_valid = false;
+                break;
+            }
+        }
+        is_valid
+    } else {
+        // Dot-atom.
+        !local.is_empty()
+            && local.split('.').all(|atom| {
+                !atom.is_empty()
+                    && atom
+                        .chars()
+                        .all(|c| !c.is_ascii() || is_ascii_atext(c as u8))
+            })
+    };
+
+    let is_domain_valid = if domain.starts_with('[') {
+        // Address literal.
+        domain.ends_with(']')
+            && domain[1..(domain.len() - 1)]
+                .bytes()
+                .all(|b| b.is_ascii_graphic() && !matches!(b, b'[' | b']' | b'\\'))
+    } else {
+        !domain.is_empty()
+            && domain.split('.').all(|label| {
+                !label.is_empty()
+                    && label
+                        .chars()
+                        .all(|c| !c.is_ascii() || c.is_ascii_alphanumeric() || c == '-')
+            })
+    };
+
+    if is_local_valid && is_domain_valid {
+        Ok((local, domain))
+    } else {
+        Err(Error::new())
+    }
+}
+
+/// Converts the validated address according to the encoding.
+fn encode_address(local: &str, domain: &str, encoding: AddressEncoding) -> Result<String, Error> {
+    let mut buf = String::with_capacity(local.len() + domain.len() + 1);
+    buf.push_str(local);
+    buf.push('@');
+    match encoding {
+        AddressEncoding::Smtputf8 => buf.push_str(domain),
+        AddressEncoding::Ascii => {
+            if !local.is_ascii() {
+                return Err(Error::new());
+            }
+            for (i, label) in domain.split('.').enumerate() {
+                if i != 0 {
+                    buf.push('.');
+                }
+                let label = punycode::to_ascii_label(label).map_err(|_| Error::new())?;
+                buf.push_str(&label);
+            }
+        }
+    }
+    Ok(buf)
+}
+
+/// Writes the address percent-encoded as `addr-spec` of `mailto:` IRIs.
+fn write_encoded_address<S: Spec>(buf: &mut String, address: &str) {
+    let at = address
+        .rfind('@')
+        .expect("[precondition] the address must be validated");
+    for (i, c) in address.char_indices() {
+        // See RFC 6068 section 2.
+        let is_raw = if c.is_ascii() {
+            i == at
+                || c.is_ascii_alphanumeric()
+                || matches!(
+                    c,
+                    '-' | '.' | '_' | '~' | '!' | '$' | '\'' | '(' | ')' | '*' | '+' | ':'
+                )
+        } else {
+            S::is_nonascii_char_unreserved(c)
+        };
+        if is_raw {
+            buf.push(c);
+        } else {
+            let mut utf8 = [0_u8; 4];
+            for b in c.encode_utf8(&mut utf8).bytes() {
+                write!(buf, "%{:02X}", b).expect("[consistency] writing to a string never fails");
+            }
+        }
+    }
+}
+
+/// Converts the email address into a `mailto:` IRI.
+///
+/// # Failures
+///
+/// Fails if the address is invalid, or cannot be represented with the given
+/// encoding.
+pub fn address_to_mailto<S: Spec>(
+    address: &str,
+    encoding: AddressEncoding,
+) -> Result<RiString<S>, Error> {
+    addresses_to_mailto(core::iter::once(address), encoding)
+}
+
+/// Converts the email addresses into a `mailto:` IRI.
+///
+/// # Failures
+///
+/// Fails if any of the addresses are invalid, or cannot be represented with
+/// the given encoding.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::mailto::Error;
+/// use iri_string::mailto::{addresses_to_mailto, AddressEncoding};
+/// use iri_string::types::UriString;
+///
+/// let uri: UriString = addresses_to_mailto(
+///     ["a@example.com", "\"b,c\"@example.com"],
+///     AddressEncoding::Ascii,
+/// )?;
+/// assert_eq!(uri, "mailto:a@example.com,%22b%2Cc%22@example.com");
+/// # Ok::<_, Error>(())
+/// ```
+pub fn addresses_to_mailto<'a, S, I>(
+    addresses: I,
+    encoding: AddressEncoding,
+) -> Result<RiString<S>, Error>
+where
+    S: Spec,
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut buf = String::from("mailto:");
+    for (i, address) in addresses.into_iter().enumerate() {
+        let (local, domain) = split_address(address)?;
+        let address = encode_address(local, domain, encoding)?;
+        if i != 0 {
+            buf.push(',');
+        }
+        write_encoded_address::<S>(&mut buf, &address);
+    }
+    RiString::try_from(buf).map_err(|_| Error::new())
+}
+
+/// Returns the email addresses in the path of the `mailto:` IRI.
+///
+/// The addresses in the query (i.e. `to` header fields) are not returned.
+///
+/// # Failures
+///
+/// Fails if the IRI is not a `mailto:` IRI, or the addresses are invalid or
+/// cannot be represented with the given encoding.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::mailto::Error;
+/// use iri_string::mailto::{mailto_to_addresses, AddressEncoding};
+/// use iri_string::types::UriStr;
+///
+/// let uri = UriStr::new("mailto:a@example.com,b@b%C3%BCcher.example?subject=hi")
+///     .expect("valid URI");
+/// assert_eq!(
+///     mailto_to_addresses(uri, AddressEncoding::Smtputf8)?,
+///     ["a@example.com", "b@b\u{FC}cher.example"]
+/// );
+/// assert_eq!(
+///     mailto_to_addresses(uri, AddressEncoding::Ascii)?,
+///     ["a@example.com", "b@xn--bcher-kva.example"]
+/// );
+/// # Ok::<_, Error>(())
+/// ```
+pub fn mailto_to_addresses<S: Spec>(
+    iri: &RiStr<S>,
+    encoding: AddressEncoding,
+) -> Result<Vec<String>, Error> {
+    if !iri.scheme_str().eq_ignore_ascii_case("mailto") {
+        return Err(Error::new());
+    }
+    let path = iri.path_str();
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    path.split(',')
+        .map(|encoded| {
+            let decoded = decode_bytes(encoded, false);
+            let decoded = core::str::from_utf8(&decoded).map_err(|_| Error::new())?;
+            let (local, domain) = split_address(decoded)?;
+            encode_address(local, domain, encoding)
+        })
+        .collect()
+}
+
+/// Returns the email address in the path of the `mailto:` IRI, if it has exactly one.
+///
+/// # Failures
+///
+/// Fails if the IRI is not a `mailto:` IRI with exactly one address in the
+/// path, or the address is invalid or cannot be represented with the given
+/// encoding.
+pub fn mailto_to_address<S: Spec>(
+    iri: &RiStr<S>,
+    encoding: AddressEncoding,
+) -> Result<String, Error> {
+    let mut addresses = mailto_to_addresses(iri, encoding)?;
+    match addresses.pop() {
+        Some(address) if addresses.is_empty() => Ok(address),
+        _ => Err(Error::new()),
+    }
+}
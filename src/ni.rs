@@ -0,0 +1,398 @@
+//! Parsing and construction of `ni:` IRIs.
+//!
+//! See [RFC 6920] for `ni:` (Named Information) URIs. They identify
+//! contents by their hash digests, and are used by content-addressed storage
+//! systems.
+//!
+//! A `ni:` IRI consists of the optional authority, the hash algorithm name,
+//! and the digest value encoded in base64url without padding, such as
+//! `ni://example.com/sha-256;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk`.
+//! It can also have the query (such as `?ct=text/plain`), but cannot have the
+//! fragment.
+//!
+//! This module does not calculate hash digests. Compute the digest with the
+//! hash library of your choice, and pass it to [`digest_to_ni`] or
+//! [`NamedInfo::verify`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::ni::Error;
+//! use iri_string::ni::{digest_to_ni, ni_to_named_info};
+//! use iri_string::types::UriString;
+//!
+//! // SHA-256 digest of `Hello World!`.
+//! let digest: [u8; 32] = [
+//!     0x7f, 0x83, 0xb1, 0x65, 0x7f, 0xf1, 0xfc, 0x53, 0xb9, 0x2d, 0xc1, 0x81, 0x48, 0xa1, 0xd6,
+//!     0x5d, 0xfc, 0x2d, 0x4b, 0x1f, 0xa3, 0xd6, 0x77, 0x28, 0x4a, 0xdd, 0xd2, 0x00, 0x12, 0x6d,
+//!     0x90, 0x69,
+//! ];
+//! let uri: UriString = digest_to_ni(Some("example.com"), "sha-256", &digest)?;
+//! assert_eq!(uri, "ni://example.com/sha-256;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk");
+//!
+//! let ni = ni_to_named_info(&uri)?;
+//! assert_eq!(ni.authority_str(), Some("example.com"));
+//! assert_eq!(ni.algorithm(), "sha-256");
+//! assert_eq!(ni.digest()?, digest);
+//! assert!(ni.verify(&digest));
+//!
+//! // Truncated hashes are verified against the prefix of the full digest.
+//! let uri: UriString = digest_to_ni(None, "sha-256-32", &digest[..4])?;
+//! assert_eq!(uri, "ni:///sha-256-32;f4OxZQ");
+//! assert!(ni_to_named_info(&uri)?.verify(&digest));
+//! # Ok::<_, Error>(())
+//! ```
+//!
+//! [RFC 6920]: https://datatracker.ietf.org/doc/html/rfc6920
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::parser::char::is_ascii_unreserved;
+use crate::spec::Spec;
+use crate::types::{RiStr, RiString};
+use crate::validate;
+
+/// Hash algorithms and their digest lengths in bytes.
+///
+/// These are registered to the "Named Information Hash Algorithm Registry".
+/// Truncated variants have the length suffix, such as `sha-256-128`.
+const ALGORITHMS: &[(&str, usize)] = &[
+    ("sha-256", 32),
+    ("sha-256-128", 16),
+    ("sha-256-120", 15),
+    ("sha-256-96", 12),
+    ("sha-256-64", 8),
+    ("sha-256-32", 4),
+    ("sha-384", 48),
+    ("sha-512", 64),
+    ("sha3-224", 28),
+    ("sha3-256", 32),
+    ("sha3-384", 48),
+    ("sha3-512", 64),
+    ("blake2s-256", 32),
+    ("blake2b-256", 32),
+    ("blake2b-512", 64),
+];
+
+/// Base64url alphabet.
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Error on parsing or construction of `ni:` IRIs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid digest or `ni` IRI")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+/// Returns the digest length of the known algorithm.
+///
+/// Algorithm names are compared case-insensitively.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::ni::digest_len;
+///
+/// assert_eq!(digest_len("sha-256"), Some(32));
+/// assert_eq!(digest_len("SHA-256-128"), Some(16));
+/// assert_eq!(digest_len("md5"), None);
+/// ```
+#[must_use]
+pub fn digest_len(algorithm: &str) -> Option<usize> {
+    ALGORITHMS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(algorithm))
+        .map(|&(_, len)| len)
+}
+
+/// Named information in a `ni:` IRI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NamedInfo<'a> {
+    /// Authority.
+    authority: Option<&'a str>,
+    /// Hash algorithm name.
+    algorithm: &'a str,
+    /// Base64url-encoded digest value.
+    value: &'a str,
+    /// Query.
+    query: Option<&'a str>,
+}
+
+impl<'a> NamedInfo<'a> {
+    /// Returns the authority, or `None` if it is empty.
+    #[inline]
+    #[must_use]
+    pub fn authority_str(&self) -> Option<&'a str> {
+        self.authority
+    }
+
+    /// Returns the hash algorithm name.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'a str {
+        self.algorithm
+    }
+
+    /// Returns the base64url-encoded digest value.
+    #[inline]
+    #[must_use]
+    pub fn value_str(&self) -> &'a str {
+        self.value
+    }
+
+    /// Returns the query.
+    #[inline]
+    #[must_use]
+    pub fn query_str(&self) -> Option<&'a str> {
+        self.query
+    }
+
+    /// Returns the decoded digest.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the value is not a canonical base64url string without
+    /// padding, or if the algorithm is known and the length of the digest
+    /// does not match.
+    pub fn digest(&self) -> Result<Vec<u8>, Error> {
+        let digest = decode_base64url(self.value)?;
+        match digest_len(self.algorithm) {
+            Some(len) if len != digest.len() => Err(Error::new()),
+            _ => Ok(digest),
+        }
+    }
+
+    /// Returns true if the named information identifies the given digest.
+    ///
+    /// For truncated algorithms (such as `sha-256-32`), the digest is
+    /// compared with the prefix of the given digest, so the full digest of
+    /// the base algorithm (such as `sha-256`) can be passed. Otherwise the
+    /// whole digest is compared.
+    ///
+    /// The digests are compared in constant time with respect to their
+    /// contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::ni::Error;
+    /// use iri_string::ni::ni_to_named_info;
+    /// use iri_string::types::UriStr;
+    ///
+    /// let uri = UriStr::new("ni:///sha-256-32;AAECAw").expect("valid URI");
+    /// let ni = ni_to_named_info(uri)?;
+    /// let mut digest = [0; 32];
+    /// digest[..4].copy_from_slice(&[0, 1, 2, 3]);
+    /// assert!(ni.verify(&digest));
+    /// assert!(ni.verify(&[0, 1, 2, 3]));
+    /// assert!(!ni.verify(&[0, 1, 2]));
+    /// assert!(!ni.verify(&[0, 1, 2, 4]));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[must_use]
+    pub fn verify(&self, digest: &[u8]) -> bool {
+        let expected = match self.digest() {
+            Ok(v) => v,
+            Err(_) => return false,
+        };
+        let is_truncated = self
+            .algorithm
+            .rsplit_once('-')
+            .and_then(|(base, _)| digest_len(base))
+            .is_some();
+        let actual = match digest.get(..expected.len()) {
+            Some(prefix) if is_truncated => prefix,
+            _ => digest,
+        };
+        if actual.len() != expected.len() {
+            return false;
+        }
+        actual
+            .iter()
+            .zip(&expected)
+            .fold(0_u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+    }
+}
+
+/// Creates a `ni:` IRI from the authority, the algorithm name, and the digest.
+///
+/// The authority is omitted if `None` is given.
+///
+/// # Failures
+///
+/// Fails if the authority is invalid, the algorithm name is empty or contains
+/// characters other than `unreserved`, or the algorithm is known and the
+/// length of the digest does not match it.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::ni::digest_to_ni;
+/// use iri_string::spec::UriSpec;
+/// use iri_string::types::UriString;
+///
+/// let uri: UriString = digest_to_ni(None, "x-custom", &[0xfb, 0xff]).expect("valid digest");
+/// assert_eq!(uri, "ni:///x-custom;-_8");
+///
+/// // Wrong digest length for SHA-256.
+/// assert!(digest_to_ni::<UriSpec>(None, "sha-256", &[0; 20]).is_err());
+/// assert!(digest_to_ni::<UriSpec>(None, "sha;256", &[0; 32]).is_err());
+/// assert!(digest_to_ni::<UriSpec>(Some("a/b"), "sha-256", &[0; 32]).is_err());
+/// ```
+pub fn digest_to_ni<S: Spec>(
+    authority: Option<&str>,
+    algorithm: &str,
+    digest: &[u8],
+) -> Result<RiString<S>, Error> {
+    let authority = authority.unwrap_or("");
+    validate::authority::<S>(authority).map_err(|_| Error::new())?;
+    if !is_valid_token(algorithm) || digest.is_empty() {
+        return Err(Error::new());
+    }
+    if matches!(digest_len(algorithm), Some(len) if len != digest.len()) {
+        return Err(Error::new());
+    }
+
+    let mut buf = String::with_capacity(
+        "ni:///;".len() + authority.len() + algorithm.len() + (digest.len() * 4 + 2) / 3,
+    );
+    buf.push_str("ni://");
+    buf.push_str(authority);
+    buf.push('/');
+    buf.push_str(algorithm);
+    buf.push(';');
+    encode_base64url(&mut buf, digest);
+    RiString::try_from(buf).map_err(|_| Error::new())
+}
+
+/// Returns the named information in the `ni:` IRI.
+///
+/// # Failures
+///
+/// Fails if the IRI is not a `ni:` IRI, has no authority, has a fragment, the
+/// path is not `/alg;val` form, or the digest is invalid (see
+/// [`NamedInfo::digest`]).
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::ni::Error;
+/// use iri_string::ni::ni_to_named_info;
+/// use iri_string::types::UriStr;
+///
+/// let uri = UriStr::new("NI:///sha-256-32;f4OxZQ?ct=text/plain").expect("valid URI");
+/// let ni = ni_to_named_info(uri)?;
+/// assert_eq!(ni.authority_str(), None);
+/// assert_eq!(ni.algorithm(), "sha-256-32");
+/// assert_eq!(ni.value_str(), "f4OxZQ");
+/// assert_eq!(ni.query_str(), Some("ct=text/plain"));
+///
+/// for s in [
+///     "urn:sha-256;f4OxZQ",
+///     "ni:sha-256-32;f4OxZQ",
+///     "ni:///sha-256-32;f4OxZQ#frag",
+///     "ni:///sha-256-32",
+///     "ni:///;f4OxZQ",
+///     "ni:///sha-256-32;",
+///     "ni:///sha-256-32;f4OxZQ==",
+///     "ni:///sha-256-32;f4OxZR",
+///     "ni:///sha-256-32;f4Ox",
+///     "ni:///a/sha-256-32;f4OxZQ",
+/// ] {
+///     let uri = UriStr::new(s).expect("valid URI");
+///     assert!(ni_to_named_info(uri).is_err(), "{s:?}");
+/// }
+/// # Ok::<_, Error>(())
+/// ```
+pub fn ni_to_named_info<S: Spec>(iri: &RiStr<S>) -> Result<NamedInfo<'_>, Error> {
+    if !iri.scheme_str().eq_ignore_ascii_case("ni") || iri.fragment().is_some() {
+        return Err(Error::new());
+    }
+    let authority = iri.authority_str().ok_or_else(Error::new)?;
+    let (algorithm, value) = iri
+        .path_str()
+        .strip_prefix('/')
+        .and_then(|alg_val| alg_val.split_once(';'))
+        .ok_or_else(Error::new)?;
+    if !is_valid_token(algorithm) || !is_valid_token(value) {
+        return Err(Error::new());
+    }
+
+    let ni = NamedInfo {
+        authority: Some(authority).filter(|s| !s.is_empty()),
+        algorithm,
+        value,
+        query: iri.query_str(),
+    };
+    ni.digest()?;
+    Ok(ni)
+}
+
+/// Returns true if the string is a non-empty sequence of `unreserved` characters.
+#[must_use]
+fn is_valid_token(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(is_ascii_unreserved)
+}
+
+/// Appends the base64url encoding of the bytes without padding.
+fn encode_base64url(buf: &mut String, bytes: &[u8]) {
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0_u32, |acc, (i, &b)| acc | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..=chunk.len() {
+            let index = (n >> (18 - 6 * i)) & 0x3f;
+            buf.push(char::from(BASE64URL[index as usize]));
+        }
+    }
+}
+
+/// Decodes the canonical base64url string without padding.
+fn decode_base64url(s: &str) -> Result<Vec<u8>, Error> {
+    if s.len() % 4 == 1 {
+        return Err(Error::new());
+    }
+    let mut bytes = Vec::with_capacity(s.len() * 3 / 4);
+    for chunk in s.as_bytes().chunks(4) {
+        let mut n = 0_u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = BASE64URL
+                .iter()
+                .position(|&d| d == c)
+                .ok_or_else(Error::new)?;
+            n |= (value as u32) << (18 - 6 * i);
+        }
+        let len = chunk.len() - 1;
+        // Reject non-canonical encodings with non-zero trailing bits.
+        if n & (0x00ff_ffff >> (8 * len)) != 0 {
+            return Err(Error::new());
+        }
+        bytes.extend_from_slice(&n.to_be_bytes()[1..=len]);
+    }
+    Ok(bytes)
+}
@@ -72,6 +72,9 @@
 //! ```
 
 mod error;
+mod key;
+#[cfg(feature = "unicode-normalization")]
+mod nfc;
 mod path;
 mod pct_case;
 
@@ -82,16 +85,24 @@ use core::marker::PhantomData;
 use alloc::collections::TryReserveError;
 
 use crate::components::RiReferenceComponents;
+use crate::escape::EscapeProfile;
+use crate::format::{write_to_slice, CapacityOverflowError};
 #[cfg(feature = "alloc")]
 use crate::format::{ToDedicatedString, ToStringFallible};
 use crate::parser::str::rfind_split_hole;
 use crate::parser::trusted::is_ascii_only_host;
+use crate::resolve::ResolutionMode;
 use crate::spec::Spec;
 use crate::types::{RiAbsoluteStr, RiReferenceStr, RiStr};
 #[cfg(feature = "alloc")]
 use crate::types::{RiAbsoluteString, RiString};
 
 pub use self::error::Error;
+pub use self::key::NormalizedIri;
+#[cfg(feature = "unicode-normalization")]
+pub(crate) use self::nfc::to_nfc_string;
+#[cfg(feature = "alloc")]
+pub(crate) use self::path::is_dot_segment;
 pub(crate) use self::path::{Path, PathCharacteristic, PathToNormalize};
 pub(crate) use self::pct_case::{
     is_pct_case_normalized, NormalizedAsciiOnlyHost, PctCaseNormalized,
@@ -172,6 +183,7 @@ impl<'a> NormalizationInput<'a> {
     pub(crate) fn with_resolution_params<S: Spec>(
         base_components: &RiReferenceComponents<'a, S>,
         reference: &'a RiReferenceStr<S>,
+        mode: ResolutionMode,
     ) -> Self {
         let b = base_components;
         let r = RiReferenceComponents::from(reference);
@@ -179,6 +191,17 @@ impl<'a> NormalizationInput<'a> {
         let (r_scheme, r_authority, r_path, r_query, r_fragment) = r.to_major();
         let (b_scheme, b_authority, b_path, b_query, _) = b.to_major();
         let b_scheme = b_scheme.expect("[validity] non-relative IRI must have a scheme");
+        // About this, see
+        // <https://datatracker.ietf.org/doc/html/rfc3986#section-5.2.2>.
+        //
+        // > -- A non-strict parser may ignore a scheme in the reference
+        // > -- if it is identical to the base URI's scheme.
+        let r_scheme = match (mode, r_scheme) {
+            (ResolutionMode::Legacy, Some(r_scheme)) if r_scheme.eq_ignore_ascii_case(b_scheme) => {
+                None
+            }
+            (_, r_scheme) => r_scheme,
+        };
 
         Self::create_normalization_input(
             r_scheme,
@@ -343,6 +366,15 @@ impl<'a, S: Spec> From<&'a RiAbsoluteString<S>> for NormalizationInput<'a> {
 }
 
 impl NormalizationInput<'_> {
+    /// Returns true if the dot segments removal attempts to go above the root.
+    #[must_use]
+    pub(crate) fn path_climbs_above_root(&self) -> bool {
+        match self.path {
+            Path::Done(_) => false,
+            Path::NeedsProcessing(path) => path.climbs_above_root(),
+        }
+    }
+
     /// Checks if the path is normalizable by RFC 3986 algorithm.
     ///
     /// Returns `Ok(())` when normalizable, returns `Err(_)` if not.
@@ -542,6 +574,8 @@ pub(crate) fn normalize_fragment<S: Spec>(
 pub struct Normalized<'a, T: ?Sized> {
     /// Spec-agnostic normalization input.
     input: NormalizationInput<'a>,
+    /// Escape profile of the output.
+    profile: EscapeProfile,
     /// Expected result type.
     _ty_str: PhantomData<fn() -> T>,
 }
@@ -550,6 +584,7 @@ impl<T: ?Sized> fmt::Debug for Normalized<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Normalized")
             .field("input", &self.input)
+            .field("profile", &self.profile)
             .finish()
     }
 }
@@ -561,6 +596,7 @@ impl<'a, T: ?Sized> Normalized<'a, T> {
     pub(crate) fn from_input(input: NormalizationInput<'a>) -> Self {
         Self {
             input,
+            profile: EscapeProfile::Minimal,
             _ty_str: PhantomData,
         }
     }
@@ -608,6 +644,24 @@ impl<'a, T: ?Sized> Normalized<'a, T> {
         self
     }
 
+    /// Sets the escape profile of the output.
+    ///
+    /// See [`EscapeProfile`] for details.
+    #[inline]
+    pub fn set_escape_profile(&mut self, profile: EscapeProfile) {
+        self.profile = profile;
+    }
+
+    /// Returns `Self` with the escape profile set.
+    ///
+    /// See [`EscapeProfile`] for details.
+    #[inline]
+    #[must_use]
+    pub fn and_escape_profile(mut self, profile: EscapeProfile) -> Self {
+        self.set_escape_profile(profile);
+        self
+    }
+
     /// Checks if the path is normalizable by RFC 3986 algorithm.
     ///
     /// Returns `Ok(())` when normalizable, returns `Err(_)` if not.
@@ -617,17 +671,82 @@ impl<'a, T: ?Sized> Normalized<'a, T> {
     }
 }
 
+impl<S: Spec> Normalized<'_, RiStr<S>> {
+    /// Writes the normalized IRI into the byte buffer, without memory allocation.
+    ///
+    /// Returns the written normalized IRI, whose length is the number of bytes
+    /// written. Fails if the buffer is too small, and the content of the
+    /// buffer is unspecified in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriStr;
+    ///
+    /// let iri = IriStr::new("HTTP://example.COM/a/../b#f")?;
+    /// let mut buf = [0_u8; 32];
+    /// let normalized = iri.normalize().write_to_slice(&mut buf).expect("enough buffer");
+    /// assert_eq!(normalized, "http://example.com/b#f");
+    /// assert_eq!(normalized.len(), 22);
+    ///
+    /// assert!(iri.normalize().write_to_slice(&mut buf[..21]).is_err());
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub fn write_to_slice<'b>(
+        &self,
+        buf: &'b mut [u8],
+    ) -> Result<&'b RiStr<S>, CapacityOverflowError> {
+        let s = write_to_slice(buf, self)?;
+        Ok(<RiStr<S>>::new(s).expect("[validity] the normalization result must be a valid IRI"))
+    }
+}
+
+impl<S: Spec> Normalized<'_, RiAbsoluteStr<S>> {
+    /// Writes the normalized absolute IRI into the byte buffer, without memory allocation.
+    ///
+    /// Returns the written normalized absolute IRI, whose length is the number of bytes
+    /// written. Fails if the buffer is too small, and the content of the
+    /// buffer is unspecified in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriAbsoluteStr;
+    ///
+    /// let iri = IriAbsoluteStr::new("HTTP://example.COM/a/../b")?;
+    /// let mut buf = [0_u8; 32];
+    /// let normalized = iri.normalize().write_to_slice(&mut buf).expect("enough buffer");
+    /// assert_eq!(normalized, "http://example.com/b");
+    /// assert_eq!(normalized.len(), 20);
+    ///
+    /// assert!(iri.normalize().write_to_slice(&mut buf[..19]).is_err());
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub fn write_to_slice<'b>(
+        &self,
+        buf: &'b mut [u8],
+    ) -> Result<&'b RiAbsoluteStr<S>, CapacityOverflowError> {
+        let s = write_to_slice(buf, self)?;
+        Ok(<RiAbsoluteStr<S>>::new(s)
+            .expect("[validity] the normalization result must be a valid IRI"))
+    }
+}
+
 impl<S: Spec> fmt::Display for Normalized<'_, RiStr<S>> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        NormalizedInner::<S>::from_input(self.input).fmt(f)
+        let inner = NormalizedInner::<S>::from_input(self.input);
+        self.profile.fmt_with(f, |f| inner.fmt(f))
     }
 }
 
 impl<S: Spec> fmt::Display for Normalized<'_, RiAbsoluteStr<S>> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        NormalizedInner::<S>::from_input(self.input).fmt(f)
+        let inner = NormalizedInner::<S>::from_input(self.input);
+        self.profile.fmt_with(f, |f| inner.fmt(f))
     }
 }
 
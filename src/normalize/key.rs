@@ -0,0 +1,140 @@
+//! Wrapper to compare IRIs by their normalized forms.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::format::{eq_display, eq_str_display, hash_display};
+use crate::spec::Spec;
+use crate::types::RiStr;
+
+/// A wrapper of an IRI whose `Eq` and `Hash` use the normalized form.
+///
+/// Two `NormalizedIri`s are equal if and only if the results of
+/// [`RiStr::normalize`] for the wrapped IRIs are equal. This is useful to use
+/// IRIs directly as deduplicating map keys.
+///
+/// Comparison and hashing do not allocate nor materialize the normalized
+/// string. If the IRI is already normalized (which is common), the raw string
+/// is used directly.
+///
+/// The wrapped value can be any type dereferenced to [`RiStr`], such as
+/// `&RiStr<S>`, [`RiString`], and [`RiCow`].
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// # #[cfg(feature = "std")] {
+/// use std::collections::HashSet;
+///
+/// use iri_string::normalize::NormalizedIri;
+/// use iri_string::types::IriStr;
+///
+/// let mut set = HashSet::new();
+/// assert!(set.insert(NormalizedIri::new(IriStr::new("http://example.com/a/b")?)));
+/// assert!(!set.insert(NormalizedIri::new(IriStr::new("HTTP://Example.COM/a/./c/../b")?)));
+/// assert!(!set.insert(NormalizedIri::new(IriStr::new("http://example.com/%61/b")?)));
+/// assert!(set.insert(NormalizedIri::new(IriStr::new("http://example.com/a/B")?)));
+/// assert_eq!(set.len(), 2);
+/// # }
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// [`RiStr::normalize`]: ../types/struct.RiStr.html#method.normalize
+/// [`RiStr`]: ../types/struct.RiStr.html
+/// [`RiString`]: ../types/struct.RiString.html
+/// [`RiCow`]: ../types/enum.RiCow.html
+#[derive(Default, Clone, Copy)]
+#[repr(transparent)]
+pub struct NormalizedIri<T>(T);
+
+impl<T> NormalizedIri<T> {
+    /// Creates a new wrapper.
+    #[inline]
+    #[must_use]
+    pub fn new(iri: T) -> Self {
+        Self(iri)
+    }
+
+    /// Returns the reference to the wrapped IRI.
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns the wrapped IRI.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for NormalizedIri<T> {
+    #[inline]
+    fn from(iri: T) -> Self {
+        Self(iri)
+    }
+}
+
+impl<T> Deref for NormalizedIri<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for NormalizedIri<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for NormalizedIri<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NormalizedIri").field(&self.0).finish()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for NormalizedIri<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<S, T, U> PartialEq<NormalizedIri<U>> for NormalizedIri<T>
+where
+    S: Spec,
+    T: Deref<Target = RiStr<S>>,
+    U: Deref<Target = RiStr<S>>,
+{
+    fn eq(&self, other: &NormalizedIri<U>) -> bool {
+        let (lhs, rhs): (&RiStr<S>, &RiStr<S>) = (&self.0, &other.0);
+        match (lhs.is_normalized(), rhs.is_normalized()) {
+            (true, true) => lhs == rhs,
+            (true, false) => eq_str_display(lhs.as_str(), &rhs.normalize()),
+            (false, true) => eq_str_display(rhs.as_str(), &lhs.normalize()),
+            (false, false) => eq_display(&lhs.normalize(), &rhs.normalize()),
+        }
+    }
+}
+
+impl<S: Spec, T: Deref<Target = RiStr<S>>> Eq for NormalizedIri<T> {}
+
+impl<S: Spec, T: Deref<Target = RiStr<S>>> Hash for NormalizedIri<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let iri: &RiStr<S> = &self.0;
+        if iri.is_normalized() {
+            hash_display(iri, state);
+        } else {
+            hash_display(&iri.normalize(), state);
+        }
+    }
+}
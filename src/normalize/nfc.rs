@@ -0,0 +1,76 @@
+//! Unicode normalization (NFC) of IRI components.
+
+use alloc::string::String;
+
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+use crate::components::{AuthorityComponents, RiReferenceComponents};
+use crate::parser::validate as parser;
+use crate::spec::Spec;
+use crate::types::RiReferenceStr;
+use crate::validate::Error;
+
+/// Applies NFC to the non-ASCII components of the IRI reference.
+///
+/// Returns `None` if the IRI is already in NFC.
+///
+/// Each component is normalized separately, so that delimiters between
+/// components are never affected. If the normalized component is no longer
+/// valid (for example, `U+1FEF GREEK VARIA` is normalized to a backquote which
+/// is not allowed in IRIs), the component is left as is.
+#[must_use]
+pub(crate) fn to_nfc_string<S: Spec>(iri: &RiReferenceStr<S>) -> Option<String> {
+    let s = iri.as_str();
+    if s.is_ascii() || is_nfc_quick(s.chars()) == IsNormalized::Yes {
+        return None;
+    }
+
+    let (scheme, authority, path, query, fragment) = RiReferenceComponents::from(iri).to_major();
+    let mut buf = String::with_capacity(s.len());
+    if let Some(scheme) = scheme {
+        buf.push_str(scheme);
+        buf.push(':');
+    }
+    if authority.is_some() {
+        let authority = AuthorityComponents::from_iri(iri)
+            .expect("[consistency] the IRI has an authority component");
+        buf.push_str("//");
+        if let Some(userinfo) = authority.userinfo() {
+            push_nfc_component(&mut buf, userinfo, parser::validate_userinfo::<S>);
+            buf.push('@');
+        }
+        push_nfc_component(&mut buf, authority.host(), parser::validate_host::<S>);
+        if let Some(port) = authority.port() {
+            buf.push(':');
+            buf.push_str(port);
+        }
+    }
+    push_nfc_component(&mut buf, path, parser::validate_path::<S>);
+    if let Some(query) = query {
+        buf.push('?');
+        push_nfc_component(&mut buf, query, parser::validate_query::<S>);
+    }
+    if let Some(fragment) = fragment {
+        buf.push('#');
+        push_nfc_component(&mut buf, fragment, parser::validate_fragment::<S>);
+    }
+
+    if buf == s {
+        return None;
+    }
+    Some(buf)
+}
+
+/// Pushes the NFC-normalized component if it is valid, or the original component.
+fn push_nfc_component(buf: &mut String, component: &str, validate: fn(&str) -> Result<(), Error>) {
+    if component.is_ascii() {
+        buf.push_str(component);
+        return;
+    }
+    let start = buf.len();
+    buf.extend(component.nfc());
+    if validate(&buf[start..]).is_err() {
+        buf.truncate(start);
+        buf.push_str(component);
+    }
+}
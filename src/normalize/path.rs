@@ -400,6 +400,28 @@ impl PathToNormalize<'_> {
     }
 }
 
+impl PathToNormalize<'_> {
+    /// Returns true if the dot segments removal attempts to go above the root.
+    ///
+    /// RFC 3986 silently discards such excess `..` segments, but strict
+    /// resolvers may want to detect them.
+    #[must_use]
+    pub(crate) fn climbs_above_root(&self) -> bool {
+        let mut depth = 0_usize;
+        for seg in PathSegmentsIter::new(self) {
+            match seg.kind(self) {
+                SegmentKind::Dot => {}
+                SegmentKind::DotDot => match depth.checked_sub(1) {
+                    Some(v) => depth = v,
+                    None => return true,
+                },
+                SegmentKind::Normal => depth += 1,
+            }
+        }
+        false
+    }
+}
+
 /// Characteristic of a path.
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum PathCharacteristic {
@@ -525,6 +547,13 @@ impl SegmentKind {
     }
 }
 
+/// Returns true if the segment is `.` or `..` (possibly percent-encoded).
+#[cfg(feature = "alloc")]
+#[must_use]
+pub(crate) fn is_dot_segment(s: &str) -> bool {
+    SegmentKind::from_segment(s) != SegmentKind::Normal
+}
+
 /// A segment with optional leading slash.
 #[derive(Debug, Clone)]
 struct PathSegment {
@@ -1,6 +1,10 @@
 //! Common stuff for parsing.
+//!
+//! The public part of this module is the low-level API in [`split`] module,
+//! which operates on raw strings without constructing typed wrappers.
 
 pub(crate) mod char;
+pub mod split;
 pub(crate) mod str;
 pub(crate) mod trusted;
 pub(crate) mod validate;
@@ -191,26 +191,26 @@ pub(crate) fn is_unreserved<S: Spec>(c: char) -> bool {
     }
 }
 
-///// Returns `true` if the given ASCII character matches `gen-delims`.
-//#[inline]
-//#[must_use]
-//pub(crate) const fn is_ascii_gen_delims(c: u8) -> bool {
-//    (TABLE[c as usize] & MASK_GEN_DELIMS) != 0
-//}
+/// Returns `true` if the given ASCII character matches `gen-delims`.
+#[inline]
+#[must_use]
+pub(crate) const fn is_ascii_gen_delims(c: u8) -> bool {
+    (TABLE[c as usize] & MASK_GEN_DELIMS) != 0
+}
 
-///// Returns `true` if the given ASCII character matches `sub-delims`.
-//#[inline]
-//#[must_use]
-//pub(crate) const fn is_ascii_sub_delims(c: u8) -> bool {
-//    (TABLE[c as usize] & MASK_SUB_DELIMS) != 0
-//}
+/// Returns `true` if the given ASCII character matches `sub-delims`.
+#[inline]
+#[must_use]
+pub(crate) const fn is_ascii_sub_delims(c: u8) -> bool {
+    (TABLE[c as usize] & MASK_SUB_DELIMS) != 0
+}
 
-///// Returns `true` if the given ASCII character matches `reserved`.
-//#[inline]
-//#[must_use]
-//pub(crate) const fn is_ascii_reserved(c: u8) -> bool {
-//    (TABLE[c as usize] & (MASK_GEN_DELIMS | MASK_SUB_DELIMS)) != 0
-//}
+/// Returns `true` if the given ASCII character matches `reserved`.
+#[inline]
+#[must_use]
+pub(crate) const fn is_ascii_reserved(c: u8) -> bool {
+    (TABLE[c as usize] & (MASK_GEN_DELIMS | MASK_SUB_DELIMS)) != 0
+}
 
 /// Returns `true` if the given ASCII character matches `pchar` modulo `pct-encoded`.
 #[inline]
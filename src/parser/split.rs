@@ -0,0 +1,198 @@
+//! Low-level splitters of IRI reference strings.
+//!
+//! Functions in this module split raw strings into components, without
+//! constructing typed wrappers and without validation. They follow the
+//! regular expression in [RFC 3986 Appendix B], so they never fail and can
+//! be applied to any strings, including invalid IRI references.
+//!
+//! For valid IRI references, the results are identical to the ones returned
+//! by the accessors of the string types (such as [`RiReferenceStr::scheme_str`]).
+//! For invalid strings, the results are still well-defined but are not
+//! guaranteed to be valid components. Validate the string beforehand (or
+//! validate the components afterwards) if necessary.
+//!
+//! [RFC 3986 Appendix B]: https://www.rfc-editor.org/rfc/rfc3986.html#appendix-B
+//! [`RiReferenceStr::scheme_str`]: `crate::types::RiReferenceStr::scheme_str`
+//!
+//! # Examples
+//!
+//! ```
+//! use iri_string::parser::split::{
+//!     split_authority, split_path, split_query_fragment, split_scheme,
+//! };
+//!
+//! let s = "https://example.com/a/b?q=1#frag";
+//! let (scheme, rest) = split_scheme(s);
+//! assert_eq!(scheme, Some("https"));
+//! let (authority, rest) = split_authority(rest);
+//! assert_eq!(authority, Some("example.com"));
+//! let (path, rest) = split_path(rest);
+//! assert_eq!(path, "/a/b");
+//! assert_eq!(split_query_fragment(rest), (Some("q=1"), Some("frag")));
+//! ```
+
+use crate::parser::str::{
+    find_split2, find_split3, find_split4_hole, find_split_hole, rfind_split2,
+};
+
+/// Splits the string into the scheme (without the trailing `:`) and the rest.
+///
+/// The scheme is the non-empty prefix preceding the first `:` if no `/`,
+/// `?`, and `#` characters appear before it.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::parser::split::split_scheme;
+///
+/// assert_eq!(split_scheme("http://example.com"), (Some("http"), "//example.com"));
+/// assert_eq!(split_scheme("urn:isbn:0451450523"), (Some("urn"), "isbn:0451450523"));
+/// assert_eq!(split_scheme("a/b:c"), (None, "a/b:c"));
+/// assert_eq!(split_scheme(":a"), (None, ":a"));
+/// ```
+#[must_use]
+pub fn split_scheme(s: &str) -> (Option<&str>, &str) {
+    match find_split4_hole(s, b':', b'/', b'?', b'#') {
+        Some((scheme, b':', rest)) if !scheme.is_empty() => (Some(scheme), rest),
+        _ => (None, s),
+    }
+}
+
+/// Splits the string after the scheme into the authority (without the leading
+/// `//`) and the rest.
+///
+/// The authority exists if the string starts with `//`, and it is terminated
+/// by the next `/`, `?`, or `#` character or by the end of the string.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::parser::split::split_authority;
+///
+/// assert_eq!(split_authority("//example.com/a"), (Some("example.com"), "/a"));
+/// assert_eq!(split_authority("//?q"), (Some(""), "?q"));
+/// assert_eq!(split_authority("/a"), (None, "/a"));
+/// ```
+#[must_use]
+pub fn split_authority(s: &str) -> (Option<&str>, &str) {
+    let after_slashes = match s.strip_prefix("//") {
+        Some(v) => v,
+        None => return (None, s),
+    };
+    match find_split3(after_slashes, b'/', b'?', b'#') {
+        Some((authority, rest)) => (Some(authority), rest),
+        None => (Some(after_slashes), ""),
+    }
+}
+
+/// Splits the string after the authority into the path and the rest.
+///
+/// The path is terminated by the first `?` or `#` character or by the end
+/// of the string. The rest starts with `?` or `#`, or is empty.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::parser::split::split_path;
+///
+/// assert_eq!(split_path("/a/b?q#f"), ("/a/b", "?q#f"));
+/// assert_eq!(split_path("#f"), ("", "#f"));
+/// assert_eq!(split_path("a:b"), ("a:b", ""));
+/// ```
+#[must_use]
+pub fn split_path(s: &str) -> (&str, &str) {
+    match find_split2(s, b'?', b'#') {
+        Some((path, rest)) => (path, rest),
+        None => (s, ""),
+    }
+}
+
+/// Splits the string after the path into the query and the fragment.
+///
+/// The leading `?` of the query and the leading `#` of the fragment are
+/// removed. The string is expected to start with `?` or `#`, or to be empty;
+/// otherwise the prefix before the first `?` or `#` is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::parser::split::split_query_fragment;
+///
+/// assert_eq!(split_query_fragment("?q=1#f"), (Some("q=1"), Some("f")));
+/// assert_eq!(split_query_fragment("?#"), (Some(""), Some("")));
+/// assert_eq!(split_query_fragment("#f?x"), (None, Some("f?x")));
+/// assert_eq!(split_query_fragment(""), (None, None));
+/// ```
+#[must_use]
+pub fn split_query_fragment(s: &str) -> (Option<&str>, Option<&str>) {
+    let (_, rest) = split_path(s);
+    let (before_fragment, fragment) = match find_split_hole(rest, b'#') {
+        Some((before, fragment)) => (before, Some(fragment)),
+        None => (rest, None),
+    };
+    (before_fragment.strip_prefix('?'), fragment)
+}
+
+/// Splits the authority into the userinfo, the host, and the port.
+///
+/// The trailing `@` of the userinfo and the leading `:` of the port are
+/// removed. The userinfo is the prefix before the first `@`, and the port is
+/// the suffix after the last `:` unless it is followed by `]`.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::parser::split::split_userinfo_host_port;
+///
+/// assert_eq!(
+///     split_userinfo_host_port("user:pw@example.com:8080"),
+///     (Some("user:pw"), "example.com", Some("8080"))
+/// );
+/// assert_eq!(split_userinfo_host_port("[::1]"), (None, "[::1]", None));
+/// assert_eq!(split_userinfo_host_port("[::1]:"), (None, "[::1]", Some("")));
+/// ```
+#[must_use]
+pub fn split_userinfo_host_port(authority: &str) -> (Option<&str>, &str, Option<&str>) {
+    let (userinfo, host_port) = match find_split_hole(authority, b'@') {
+        Some((userinfo, rest)) => (Some(userinfo), rest),
+        None => (None, authority),
+    };
+    match rfind_split2(host_port, b':', b']') {
+        Some((host, colon_port)) if colon_port.starts_with(':') => {
+            (userinfo, host, Some(&colon_port[1..]))
+        }
+        _ => (userinfo, host_port, None),
+    }
+}
+
+/// Splits the string into the scheme, the authority, the path, the query, and
+/// the fragment.
+///
+/// This is a shorthand of [`split_scheme`], [`split_authority`],
+/// [`split_path`], and [`split_query_fragment`].
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::parser::split::split_iri_reference;
+///
+/// assert_eq!(
+///     split_iri_reference("http://example.com/a?q#f"),
+///     (Some("http"), Some("example.com"), "/a", Some("q"), Some("f"))
+/// );
+/// assert_eq!(
+///     split_iri_reference("../a"),
+///     (None, None, "../a", None, None)
+/// );
+/// ```
+#[allow(clippy::type_complexity)]
+#[must_use]
+pub fn split_iri_reference(
+    s: &str,
+) -> (Option<&str>, Option<&str>, &str, Option<&str>, Option<&str>) {
+    let (scheme, rest) = split_scheme(s);
+    let (authority, rest) = split_authority(rest);
+    let (path, rest) = split_path(rest);
+    let (query, fragment) = split_query_fragment(rest);
+    (scheme, authority, path, query, fragment)
+}
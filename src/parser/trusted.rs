@@ -235,6 +235,18 @@ pub(crate) fn extract_path_relative(i: &str) -> &str {
     until_query(i).1
 }
 
+/// Extracts the part after `authority` (i.e. path, query, and fragment) from an IRI reference.
+///
+/// # Precondition
+///
+/// The given string must be a valid IRI reference.
+#[inline]
+#[must_use]
+pub(crate) fn extract_after_authority(i: &str) -> &str {
+    let (i, _scheme) = scheme_colon_opt(i);
+    slash_slash_authority_opt(i).0
+}
+
 /// Extracts `query` part from an IRI reference.
 ///
 /// # Precondition
@@ -366,6 +378,11 @@ pub(crate) fn is_normalized<S: Spec>(i: &str, mode: NormalizednessCheckMode) ->
             return false;
         }
 
+        // Check `port`: empty port should be removed.
+        if authority_components.port() == Some("") {
+            return false;
+        }
+
         // Check pencent encodings in `userinfo`.
         if let Some(userinfo) = authority_components.userinfo() {
             if !is_pct_case_normalized::<S>(userinfo) {
@@ -418,7 +435,7 @@ pub(crate) fn is_normalized<S: Spec>(i: &str, mode: NormalizednessCheckMode) ->
 ///
 /// The parameters `upper` and `lower` should be an ASCII hexadecimal digit.
 #[must_use]
-pub(super) fn hexdigits_to_byte([upper, lower]: [u8; 2]) -> u8 {
+pub(crate) fn hexdigits_to_byte([upper, lower]: [u8; 2]) -> u8 {
     let i_upper = match (upper & 0xf0).cmp(&0x40) {
         Ordering::Less => upper - b'0',
         Ordering::Equal => upper - (b'A' - 10),
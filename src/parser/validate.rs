@@ -2,6 +2,7 @@
 
 mod authority;
 mod path;
+mod stream;
 
 use crate::parser::char;
 use crate::parser::str::{
@@ -10,13 +11,19 @@ use crate::parser::str::{
 use crate::spec::Spec;
 use crate::validate::Error;
 
-use self::authority::validate_authority;
-pub(crate) use self::authority::{validate_host, validate_userinfo};
-pub(crate) use self::path::validate_path;
+#[cfg(feature = "alloc")]
+pub(crate) use self::authority::validate_ipv6address;
+pub(crate) use self::authority::{
+    validate_authority, validate_host, validate_ipv4address, validate_userinfo,
+};
+pub(crate) use self::path::{validate_path, validate_path_chars};
 use self::path::{
     validate_path_abempty, validate_path_absolute_authority_absent,
     validate_path_relative_authority_absent,
 };
+#[cfg(feature = "alloc")]
+pub(crate) use self::path::{validate_path_in_context, validate_path_segment};
+pub(crate) use self::stream::{StreamState, Target as StreamTarget};
 
 /// Returns `Ok(_)` if the string matches `scheme`.
 pub(crate) fn validate_scheme(i: &str) -> Result<(), Error> {
@@ -196,18 +203,20 @@ pub(crate) fn validate_relative_ref<S: Spec>(i: &str) -> Result<(), Error> {
 fn validate_after_path<S: Spec>(first: u8, rest: &str, accept_fragment: bool) -> Result<(), Error> {
     let (maybe_query, maybe_fragment) = if first == b'?' {
         match find_split_hole(rest, b'#') {
-            Some(v) => v,
-            None => (rest, ""),
+            Some((query, fragment)) => (query, Some(fragment)),
+            None => (rest, None),
         }
     } else {
         debug_assert_eq!(first, b'#');
-        ("", rest)
+        ("", Some(rest))
     };
     validate_query::<S>(maybe_query)?;
-    if !accept_fragment && !maybe_fragment.is_empty() {
-        return Err(Error::new());
+    match maybe_fragment {
+        // Even an empty fragment is not allowed.
+        Some(_) if !accept_fragment => Err(Error::new()),
+        Some(fragment) => validate_fragment::<S>(fragment),
+        None => Ok(()),
     }
-    validate_fragment::<S>(maybe_fragment)
 }
 
 /// Returns `Ok(_)` if the string matches `fragment`/`ifragment` rules.
@@ -40,7 +40,7 @@ fn is_dec_octet(i: &str) -> bool {
 }
 
 /// Returns `Ok(_)` if the string matches `IPv4address`.
-fn validate_ipv4address(i: &str) -> Result<(), Error> {
+pub(crate) fn validate_ipv4address(i: &str) -> Result<(), Error> {
     let (first, rest) = find_split_hole(i, b'.').ok_or_else(Error::new)?;
     if !is_dec_octet(first) {
         return Err(Error::new());
@@ -114,7 +114,7 @@ fn split_v6_addr_part(i: &str) -> Result<(&str, V6AddrPart), Error> {
 }
 
 /// Returns `Ok(_)` if the string matches `IPv6address`.
-fn validate_ipv6address(mut i: &str) -> Result<(), Error> {
+pub(crate) fn validate_ipv6address(mut i: &str) -> Result<(), Error> {
     let mut h16_count = 0;
     let mut is_omitted = false;
     while !i.is_empty() {
@@ -168,7 +168,7 @@ fn validate_ipv6address(mut i: &str) -> Result<(), Error> {
 }
 
 /// Returns `Ok(_)` if the string matches `authority` or `iauthority`.
-pub(super) fn validate_authority<S: Spec>(i: &str) -> Result<(), Error> {
+pub(crate) fn validate_authority<S: Spec>(i: &str) -> Result<(), Error> {
     // Strip and validate `userinfo`.
     let (i, _userinfo) = match find_split_hole(i, b'@') {
         Some((maybe_userinfo, i)) => {
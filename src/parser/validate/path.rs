@@ -73,11 +73,23 @@ pub(super) fn validate_path_relative_authority_absent<S: Spec>(i: &str) -> Resul
     }
 }
 
-/// Returns `Ok(_)` if the string matches `path`/`ipath` rules.
-pub(crate) fn validate_path<S: Spec>(i: &str) -> Result<(), Error> {
-    if i.starts_with("//") {
-        return Err(Error::new());
+/// Returns `Ok(_)` if the string matches `isegment` or `segment`, i.e. a path segment without slashes.
+#[cfg(feature = "alloc")]
+pub(crate) fn validate_path_segment<S: Spec>(i: &str) -> Result<(), Error> {
+    let is_valid =
+        satisfy_chars_with_pct_encoded(i, char::is_ascii_pchar, S::is_nonascii_char_unreserved);
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::new())
     }
+}
+
+/// Returns `Ok(_)` if the string consists of path characters.
+///
+/// Unlike [`validate_path`], this accepts paths starting with `//`, which can
+/// appear as `path-abempty` after an authority.
+pub(crate) fn validate_path_chars<S: Spec>(i: &str) -> Result<(), Error> {
     let is_valid = satisfy_chars_with_pct_encoded(
         i,
         char::is_ascii_pchar_slash,
@@ -89,3 +101,37 @@ pub(crate) fn validate_path<S: Spec>(i: &str) -> Result<(), Error> {
         Err(Error::new())
     }
 }
+
+/// Returns `Ok(_)` if the string matches `path`/`ipath` rules.
+pub(crate) fn validate_path<S: Spec>(i: &str) -> Result<(), Error> {
+    if i.starts_with("//") {
+        return Err(Error::new());
+    }
+    validate_path_chars::<S>(i)
+}
+
+/// Returns `Ok(_)` if the string is valid as the path of an IRI reference
+/// with the given scheme and authority presence.
+#[cfg(feature = "alloc")]
+pub(crate) fn validate_path_in_context<S: Spec>(
+    i: &str,
+    has_scheme: bool,
+    has_authority: bool,
+) -> Result<(), Error> {
+    if has_authority {
+        // `path-abempty`.
+        if !i.is_empty() && !i.starts_with('/') {
+            return Err(Error::new());
+        }
+        return validate_path_chars::<S>(i);
+    }
+    validate_path::<S>(i)?;
+    if !has_scheme {
+        // `path-noscheme`: the first segment should not contain a colon.
+        let first_seg = i.split('/').next().unwrap_or_default();
+        if first_seg.contains(':') {
+            return Err(Error::new());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,372 @@
+//! Incremental validation of IRIs and IRI references, char by char.
+
+use core::marker::PhantomData;
+
+use crate::parser::char;
+use crate::parser::validate::authority::validate_ipv6address;
+use crate::spec::Spec;
+use crate::validate::Error;
+
+/// Maximum length of `IPv6address`.
+// "ffff:ffff:ffff:ffff:ffff:ffff:255.255.255.255".
+const IPV6_MAX_LEN: usize = 45;
+
+/// Grammar to validate against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Target {
+    /// `IRI`.
+    Iri,
+    /// `IRI-reference`.
+    IriReference,
+}
+
+/// Position in the grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Nothing consumed yet.
+    Start,
+    /// In the scheme, or the first path segment of a relative reference
+    /// which looks like a scheme so far.
+    Scheme,
+    /// Just after `scheme ":"`.
+    AfterScheme,
+    /// Just after `scheme ":/"`, or `/` at the beginning of a relative reference.
+    FirstSlash,
+    /// In the authority before `@`, where userinfo and host-and-port are indistinguishable.
+    AuthorityPrefix {
+        /// Number of colons seen.
+        colons: usize,
+        /// Whether all characters after the first colon are digits.
+        port_digits_only: bool,
+        /// Whether no characters are consumed in the authority yet.
+        is_empty: bool,
+    },
+    /// In the host after `@`, and whether no characters are consumed in the host yet.
+    Host(bool),
+    /// In the IP literal, after `[`.
+    IpLiteralStart,
+    /// In the IPv6 address.
+    Ipv6,
+    /// In the version of `IPvFuture`, and whether at least one digit is seen.
+    IpvFutureVersion(bool),
+    /// In the address of `IPvFuture`, and whether at least one character is seen.
+    IpvFutureAddr(bool),
+    /// Just after `]` of the IP literal.
+    AfterIpLiteral,
+    /// In the port.
+    Port,
+    /// In the path, and whether a colon is forbidden (i.e. in the first
+    /// segment of `ipath-noscheme`).
+    Path(bool),
+    /// In the query.
+    Query,
+    /// In the fragment.
+    Fragment,
+}
+
+/// Incremental validator state.
+#[derive(Debug, Clone)]
+pub(crate) struct StreamState<S> {
+    /// Grammar to validate against.
+    target: Target,
+    /// Current state.
+    state: State,
+    /// Number of hexadecimal digits remaining in the current `pct-encoded`.
+    pct_remaining: u8,
+    /// Whether an error has been detected.
+    failed: bool,
+    /// Buffer for the IPv6 address.
+    ///
+    /// Only ASCII characters are stored.
+    ipv6_buf: [u8; IPV6_MAX_LEN],
+    /// Length of the IPv6 address in the buffer.
+    ipv6_len: usize,
+    /// Spec.
+    _spec: PhantomData<fn() -> S>,
+}
+
+impl<S: Spec> StreamState<S> {
+    /// Creates a new state.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(target: Target) -> Self {
+        Self {
+            target,
+            state: State::Start,
+            pct_remaining: 0,
+            failed: false,
+            ipv6_buf: [0; IPV6_MAX_LEN],
+            ipv6_len: 0,
+            _spec: PhantomData,
+        }
+    }
+
+    /// Consumes the next character.
+    pub(crate) fn push(&mut self, c: char) -> Result<(), Error> {
+        if self.failed {
+            return Err(Error::new());
+        }
+        let result = self.push_impl(c);
+        if result.is_err() {
+            self.failed = true;
+        }
+        result
+    }
+
+    /// Checks that the consumed characters form a complete string.
+    pub(crate) fn finish(&self) -> Result<(), Error> {
+        if self.failed || self.pct_remaining != 0 {
+            return Err(Error::new());
+        }
+        let is_valid = match self.state {
+            State::Start | State::Scheme => self.target == Target::IriReference,
+            State::AuthorityPrefix {
+                colons,
+                port_digits_only,
+                ..
+            } => is_host_port_valid(colons, port_digits_only),
+            State::IpLiteralStart
+            | State::Ipv6
+            | State::IpvFutureVersion(_)
+            | State::IpvFutureAddr(_) => false,
+            _ => true,
+        };
+        if is_valid {
+            Ok(())
+        } else {
+            Err(Error::new())
+        }
+    }
+
+    /// Consumes the next character, without caring about the failure state.
+    fn push_impl(&mut self, c: char) -> Result<(), Error> {
+        if self.pct_remaining != 0 {
+            if !c.is_ascii_hexdigit() {
+                return Err(Error::new());
+            }
+            self.pct_remaining -= 1;
+            return Ok(());
+        }
+
+        let next = match self.state {
+            State::Start => match c {
+                'a'..='z' | 'A'..='Z' => State::Scheme,
+                _ if self.target == Target::Iri => return Err(Error::new()),
+                '/' => State::FirstSlash,
+                ':' => return Err(Error::new()),
+                _ => return self.transition_to_path(c, true),
+            },
+            State::Scheme => match c {
+                ':' => State::AfterScheme,
+                _ if c.is_ascii() && char::is_ascii_scheme_continue(c as u8) => State::Scheme,
+                _ if self.target == Target::Iri => return Err(Error::new()),
+                '/' => State::Path(false),
+                _ => return self.transition_to_path(c, true),
+            },
+            State::AfterScheme => match c {
+                '/' => State::FirstSlash,
+                _ => return self.transition_to_path(c, false),
+            },
+            State::FirstSlash => match c {
+                '/' => State::AuthorityPrefix {
+                    colons: 0,
+                    port_digits_only: true,
+                    is_empty: true,
+                },
+                _ => return self.transition_to_path(c, false),
+            },
+            State::AuthorityPrefix {
+                colons,
+                port_digits_only,
+                is_empty,
+            } => match c {
+                '[' if is_empty => State::IpLiteralStart,
+                '@' => State::Host(true),
+                ':' => State::AuthorityPrefix {
+                    colons: colons + 1,
+                    port_digits_only,
+                    is_empty: false,
+                },
+                '/' | '?' | '#' => {
+                    if !is_host_port_valid(colons, port_digits_only) {
+                        return Err(Error::new());
+                    }
+                    return self.transition_after_authority(c);
+                }
+                _ => {
+                    let is_valid = if c.is_ascii() {
+                        c == '%' || char::is_ascii_userinfo_ipvfutureaddr(c as u8)
+                    } else {
+                        char::is_nonascii_userinfo::<S>(c)
+                    };
+                    if !is_valid {
+                        return Err(Error::new());
+                    }
+                    self.start_pct_encoded_if(c);
+                    State::AuthorityPrefix {
+                        colons,
+                        port_digits_only: port_digits_only && (colons == 0 || c.is_ascii_digit()),
+                        is_empty: false,
+                    }
+                }
+            },
+            State::Host(is_empty) => match c {
+                '[' if is_empty => State::IpLiteralStart,
+                ':' => State::Port,
+                '/' | '?' | '#' => return self.transition_after_authority(c),
+                _ => {
+                    let is_valid = if c.is_ascii() {
+                        c == '%' || char::is_ascii_regname(c as u8)
+                    } else {
+                        char::is_nonascii_regname::<S>(c)
+                    };
+                    if !is_valid {
+                        return Err(Error::new());
+                    }
+                    self.start_pct_encoded_if(c);
+                    State::Host(false)
+                }
+            },
+            State::IpLiteralStart => match c {
+                'v' | 'V' => State::IpvFutureVersion(false),
+                _ => {
+                    self.ipv6_len = 0;
+                    return self.push_ipv6(c);
+                }
+            },
+            State::Ipv6 => return self.push_ipv6(c),
+            State::IpvFutureVersion(has_digit) => match c {
+                '.' if has_digit => State::IpvFutureAddr(false),
+                _ if c.is_ascii_hexdigit() => State::IpvFutureVersion(true),
+                _ => return Err(Error::new()),
+            },
+            State::IpvFutureAddr(non_empty) => match c {
+                ']' if non_empty => State::AfterIpLiteral,
+                _ if c.is_ascii() && char::is_ascii_userinfo_ipvfutureaddr(c as u8) => {
+                    State::IpvFutureAddr(true)
+                }
+                _ => return Err(Error::new()),
+            },
+            State::AfterIpLiteral => match c {
+                ':' => State::Port,
+                '/' | '?' | '#' => return self.transition_after_authority(c),
+                _ => return Err(Error::new()),
+            },
+            State::Port => match c {
+                '0'..='9' => State::Port,
+                '/' | '?' | '#' => return self.transition_after_authority(c),
+                _ => return Err(Error::new()),
+            },
+            State::Path(colon_forbidden) => match c {
+                '/' => State::Path(false),
+                ':' if colon_forbidden => return Err(Error::new()),
+                _ => return self.transition_to_path(c, colon_forbidden),
+            },
+            State::Query => match c {
+                '#' => State::Fragment,
+                _ => {
+                    let is_valid = if c.is_ascii() {
+                        c == '%' || char::is_ascii_frag_query(c as u8)
+                    } else {
+                        char::is_nonascii_query::<S>(c)
+                    };
+                    if !is_valid {
+                        return Err(Error::new());
+                    }
+                    self.start_pct_encoded_if(c);
+                    State::Query
+                }
+            },
+            State::Fragment => {
+                let is_valid = if c.is_ascii() {
+                    c == '%' || char::is_ascii_frag_query(c as u8)
+                } else {
+                    char::is_nonascii_fragment::<S>(c)
+                };
+                if !is_valid {
+                    return Err(Error::new());
+                }
+                self.start_pct_encoded_if(c);
+                State::Fragment
+            }
+        };
+        self.state = next;
+        Ok(())
+    }
+
+    /// Starts `pct-encoded` if the character is `%`.
+    #[inline]
+    fn start_pct_encoded_if(&mut self, c: char) {
+        if c == '%' {
+            self.pct_remaining = 2;
+        }
+    }
+
+    /// Consumes the character in the path, query, or fragment.
+    ///
+    /// `colon_forbidden` is whether the character is in the first segment of
+    /// `ipath-noscheme`.
+    fn transition_to_path(&mut self, c: char, colon_forbidden: bool) -> Result<(), Error> {
+        self.state = match c {
+            '?' => State::Query,
+            '#' => State::Fragment,
+            '/' => State::Path(false),
+            ':' if colon_forbidden => return Err(Error::new()),
+            _ => {
+                let is_valid = if c.is_ascii() {
+                    c == '%' || char::is_ascii_pchar(c as u8)
+                } else {
+                    S::is_nonascii_char_unreserved(c)
+                };
+                if !is_valid {
+                    return Err(Error::new());
+                }
+                self.start_pct_encoded_if(c);
+                State::Path(colon_forbidden)
+            }
+        };
+        Ok(())
+    }
+
+    /// Consumes the character just after the authority.
+    ///
+    /// # Precondition
+    ///
+    /// The character should be `/`, `?`, or `#`.
+    fn transition_after_authority(&mut self, c: char) -> Result<(), Error> {
+        debug_assert!(matches!(c, '/' | '?' | '#'));
+        self.transition_to_path(c, false)
+    }
+
+    /// Consumes the character in the IPv6 address.
+    fn push_ipv6(&mut self, c: char) -> Result<(), Error> {
+        if c == ']' {
+            let addr = core::str::from_utf8(&self.ipv6_buf[..self.ipv6_len])
+                .expect("[validity] only ASCII characters are stored");
+            validate_ipv6address(addr)?;
+            self.state = State::AfterIpLiteral;
+            return Ok(());
+        }
+        if !(c.is_ascii_hexdigit() || c == ':' || c == '.') || self.ipv6_len >= IPV6_MAX_LEN {
+            return Err(Error::new());
+        }
+        self.ipv6_buf[self.ipv6_len] = c as u8;
+        self.ipv6_len += 1;
+        self.state = State::Ipv6;
+        Ok(())
+    }
+}
+
+/// Returns true if the authority prefix without `@` is a valid `host [ ":" port ]`.
+///
+/// Characters before the first colon are already checked as userinfo
+/// characters, which are reg-name characters or colons.
+#[inline]
+#[must_use]
+fn is_host_port_valid(colons: usize, port_digits_only: bool) -> bool {
+    match colons {
+        0 => true,
+        1 => port_digits_only,
+        _ => false,
+    }
+}
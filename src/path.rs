@@ -0,0 +1,114 @@
+//! Comparison of paths with percent-encoding taken into account.
+//!
+//! Routers and access control checks often compare paths of requested IRIs
+//! with fixed paths. Comparing raw strings can be bypassed by percent-encoding
+//! (for example `/%61dmin` for `/admin`), and comparing fully decoded strings
+//! can be bypassed by encoded slashes (for example `/admin%2Fx` for
+//! `/admin/x`). The functions in this module treat percent-encoded triplets
+//! and the characters they represent as equal, except that `%2F` is never
+//! equal to `/`.
+//!
+//! # Examples
+//!
+//! ```
+//! use iri_string::path::{path_eq, segment_eq};
+//!
+//! assert!(segment_eq("%61dmin", "admin"));
+//! assert!(path_eq("/%61dmin/%7euser", "/admin/~user"));
+//!
+//! // Encoded slashes are not path separators.
+//! assert!(!path_eq("/admin%2Fx", "/admin/x"));
+//! // ASCII case of the decoded characters matters.
+//! assert!(!segment_eq("%41", "a"));
+//! ```
+
+/// A unit of a path for comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    /// A byte, possibly decoded from a percent-encoded triplet.
+    Byte(u8),
+    /// A literal slash, i.e. a path separator.
+    Slash,
+}
+
+/// Returns an iterator over the comparison units of the string.
+///
+/// `%` not followed by two hexadecimal digits is treated as a literal byte.
+fn units(s: &str) -> impl Iterator<Item = Unit> + '_ {
+    let bytes = s.as_bytes();
+    let mut pos = 0;
+    core::iter::from_fn(move || {
+        let b = *bytes.get(pos)?;
+        pos += 1;
+        if b == b'/' {
+            return Some(Unit::Slash);
+        }
+        if b == b'%' {
+            let hex = bytes.get(pos..(pos + 2)).and_then(|hex| {
+                let hi = char::from(hex[0]).to_digit(16)?;
+                let lo = char::from(hex[1]).to_digit(16)?;
+                Some((hi * 16 + lo) as u8)
+            });
+            if let Some(decoded) = hex {
+                pos += 2;
+                return Some(Unit::Byte(decoded));
+            }
+        }
+        Some(Unit::Byte(b))
+    })
+}
+
+/// Returns true if the two path segments are equal after percent-decoding.
+///
+/// Percent-encoded triplets (including `%2F`) are compared as the bytes they
+/// represent, and hexadecimal digits in them are case-insensitive. Literal
+/// slashes (if any) are not equal to `%2F`, so passing whole paths is also
+/// safe.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::path::segment_eq;
+///
+/// assert!(segment_eq("caf%C3%A9", "caf\u{E9}"));
+/// assert!(segment_eq("%2f", "%2F"));
+/// assert!(!segment_eq("a%2Fb", "a/b"));
+/// assert!(!segment_eq("a", "a%20"));
+/// ```
+#[must_use]
+pub fn segment_eq(a: &str, b: &str) -> bool {
+    units(a).eq(units(b))
+}
+
+/// Returns true if the two paths are equal segment-wise after percent-decoding.
+///
+/// Paths are split at literal slashes, and each segment is compared by
+/// [`segment_eq`]. Dot segments are not removed, and empty segments are not
+/// collapsed; normalize the IRIs beforehand if necessary.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::path::path_eq;
+/// use iri_string::types::IriStr;
+///
+/// let request = IriStr::new("https://example.com/%70rivate/%64ata")?;
+/// assert!(path_eq(request.path_str(), "/private/data"));
+///
+/// let bypass = IriStr::new("https://example.com/private%2Fdata")?;
+/// assert!(!path_eq(bypass.path_str(), "/private/data"));
+/// # Ok::<_, Error>(())
+/// ```
+#[must_use]
+pub fn path_eq(a: &str, b: &str) -> bool {
+    let mut a_segments = a.split('/');
+    let mut b_segments = b.split('/');
+    loop {
+        match (a_segments.next(), b_segments.next()) {
+            (Some(a_seg), Some(b_seg)) if segment_eq(a_seg, b_seg) => {}
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
@@ -0,0 +1,538 @@
+//! IRI patterns with wildcards.
+//!
+//! [`IriPattern`] is a simple glob-like pattern for IRIs, such as
+//! `https://*.example.com/api/*`. This is intended for allowlists and
+//! denylists of proxies, webhooks, and so on.
+//!
+//! # Syntax and semantics
+//!
+//! A pattern looks like an absolute IRI without the fragment, decomposed to
+//! the scheme, the host, the port, the path, and the query. Each component is
+//! matched independently, so a wildcard never matches across component
+//! boundaries: `https://*.example.com/*` does not match
+//! `https://evil.test/.example.com/`, since `*` in the host cannot consume
+//! `/`.
+//!
+//! * `*` matches any (possibly empty) sequence of characters in the
+//!   component. In the path, this includes `/`.
+//! * The scheme is either `*` or a literal scheme. It is compared ASCII
+//!   case-insensitively.
+//! * The host is compared ASCII case-insensitively.
+//! * The port is either `*` or a literal port. If the pattern has no port,
+//!   the target must not have a non-empty port.
+//! * If the pattern has an empty path, the target path must be empty or `/`.
+//! * If the pattern has no query, any query (or the absence of it) is
+//!   accepted. Otherwise the target must have a query matching the pattern.
+//! * Userinfo and fragment of the target are ignored, and patterns cannot
+//!   have them.
+//!
+//! Matching is done against the raw strings, i.e. no normalization or
+//! percent-decoding is applied. Normalize the target IRI before matching
+//! if necessary.
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::validate::Error;
+//! use iri_string::pattern::IriPattern;
+//! use iri_string::types::IriStr;
+//!
+//! let pattern = IriPattern::new("https://*.example.com/api/*")?;
+//!
+//! assert!(pattern.matches(IriStr::new("https://www.example.com/api/v1/users")?));
+//! assert!(pattern.matches(IriStr::new("HTTPS://a.b.Example.COM/api/?q=1#top")?));
+//!
+//! assert!(!pattern.matches(IriStr::new("https://example.com/api/v1")?));
+//! assert!(!pattern.matches(IriStr::new("https://evil-example.com/api/v1")?));
+//! assert!(!pattern.matches(IriStr::new("https://www.example.com:8443/api/v1")?));
+//! assert!(!pattern.matches(IriStr::new("https://www.example.com/other")?));
+//! # Ok::<_, Error>(())
+//! ```
+//!
+//! # robots.txt path rules
+//!
+//! [`RobotsPathRule`] matches request paths against path rules of
+//! [robots.txt (RFC 9309)][RFC 9309], i.e. prefix matching with `*` and `$`
+//! special characters, treating percent-encoded and decoded forms as
+//! equivalent.
+//!
+//! [RFC 9309]: https://www.rfc-editor.org/rfc/rfc9309.html
+
+use crate::components::AuthorityComponents;
+use crate::parser::trusted::hexdigits_to_byte;
+use crate::parser::validate::{validate_host, validate_path, validate_query, validate_scheme};
+use crate::spec::{IriSpec, Spec};
+use crate::types::RiReferenceStr;
+use crate::validate::Error;
+
+/// The wildcard character.
+const WILDCARD: &str = "*";
+
+/// Authority part of a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct AuthorityPattern<'a> {
+    /// Host pattern.
+    host: &'a str,
+    /// Port pattern.
+    port: Option<&'a str>,
+}
+
+/// IRI pattern with wildcards.
+///
+/// See [the module documentation][`crate::pattern`] for the syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IriPattern<'a> {
+    /// Whole pattern.
+    pattern: &'a str,
+    /// Scheme pattern.
+    scheme: &'a str,
+    /// Authority pattern.
+    authority: Option<AuthorityPattern<'a>>,
+    /// Path pattern.
+    path: &'a str,
+    /// Query pattern.
+    query: Option<&'a str>,
+}
+
+impl<'a> IriPattern<'a> {
+    /// Parses the pattern.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the pattern is not valid, for example when it lacks a scheme,
+    /// or has userinfo or a fragment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::pattern::IriPattern;
+    ///
+    /// assert!(IriPattern::new("*://*/*").is_ok());
+    /// assert!(IriPattern::new("urn:isbn:*").is_ok());
+    ///
+    /// assert!(IriPattern::new("/no/scheme").is_err());
+    /// assert!(IriPattern::new("https://user@example.com/").is_err());
+    /// assert!(IriPattern::new("https://example.com/#frag").is_err());
+    /// assert!(IriPattern::new("https://example.com:8*/").is_err());
+    /// ```
+    pub fn new(pattern: &'a str) -> Result<Self, Error> {
+        if pattern.contains('#') {
+            return Err(Error::new());
+        }
+        let (scheme, rest) = pattern.split_once(':').ok_or_else(Error::new)?;
+        if scheme.is_empty() {
+            return Err(Error::new());
+        }
+        if scheme != WILDCARD {
+            validate_scheme(scheme)?;
+        }
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let authority_end = rest.find('/').unwrap_or(rest.len());
+                let (authority, path) = rest.split_at(authority_end);
+                (Some(parse_authority_pattern(authority)?), path)
+            }
+            None => (None, rest),
+        };
+        if authority.is_none() && path.starts_with("//") {
+            return Err(Error::new());
+        }
+        validate_path::<IriSpec>(path)?;
+        if let Some(query) = query {
+            validate_query::<IriSpec>(query)?;
+        }
+
+        Ok(Self {
+            pattern,
+            scheme,
+            authority,
+            path,
+            query,
+        })
+    }
+
+    /// Returns the pattern string.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.pattern
+    }
+
+    /// Returns true if the given IRI matches the pattern.
+    ///
+    /// Relative IRI references never match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::pattern::IriPattern;
+    /// use iri_string::types::{IriReferenceStr, UriAbsoluteStr};
+    ///
+    /// let pattern = IriPattern::new("*://example.com:*/*.json?*")?;
+    ///
+    /// assert!(pattern.matches(UriAbsoluteStr::new("http://example.com/a/b.json?")?));
+    /// assert!(pattern.matches(UriAbsoluteStr::new("wss://example.com:8080/.json?x")?));
+    /// assert!(!pattern.matches(UriAbsoluteStr::new("http://example.com/a.json")?));
+    /// assert!(!pattern.matches(IriReferenceStr::new("//example.com/a.json?")?));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[must_use]
+    pub fn matches<S: Spec, T: ?Sized + AsRef<RiReferenceStr<S>>>(&self, iri: &T) -> bool {
+        let iri = iri.as_ref();
+        let scheme = match iri.scheme_str() {
+            Some(v) => v,
+            None => return false,
+        };
+        if (self.scheme != WILDCARD) && !self.scheme.eq_ignore_ascii_case(scheme) {
+            return false;
+        }
+
+        match (self.authority, AuthorityComponents::from_iri(iri)) {
+            (None, None) => {}
+            (Some(pattern), Some(target)) => {
+                if !glob_match(pattern.host, target.host(), true) {
+                    return false;
+                }
+                let port = target.port().filter(|port| !port.is_empty());
+                match (pattern.port, port) {
+                    (None, None) | (Some(WILDCARD), _) => {}
+                    (Some(pattern), Some(port)) if pattern == port => {}
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        }
+
+        let path = iri.path_str();
+        let path_matches = if self.path.is_empty() {
+            path.is_empty() || path == "/"
+        } else {
+            glob_match(self.path, path, false)
+        };
+        if !path_matches {
+            return false;
+        }
+
+        match (self.query, iri.query()) {
+            (None, _) => true,
+            (Some(pattern), Some(query)) => glob_match(pattern, query.as_str(), false),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Path rule of robots.txt.
+///
+/// The rule matches a path if the path starts with the rule, where `*` in the
+/// rule matches any (possibly empty) sequence of characters, and `$` at the
+/// end of the rule matches the end of the path. See [RFC 9309 section 2.2.2]
+/// and [section 2.2.3].
+///
+/// Percent-encoded octets are compared as the decoded octets, and non-ASCII
+/// characters are compared as the octets of their UTF-8 encoding. The only
+/// exception is `%2F`, which is distinct from `/` since it does not separate
+/// path segments. Hexadecimal digits are case-insensitive, but other
+/// characters are compared case-sensitively.
+///
+/// Note that an empty rule matches any path. robots.txt treats an empty
+/// `Disallow` rule as "allow everything", so handle it before matching.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::pattern::RobotsPathRule;
+///
+/// let rule = RobotsPathRule::new("/caf%C3%A9/*.php$");
+///
+/// assert!(rule.matches("/café/index.php"));
+/// assert!(rule.matches("/caf%c3%a9/dir/index.php"));
+/// assert!(!rule.matches("/café/index.php?q"));
+/// assert!(!rule.matches("/CAFé/index.php"));
+///
+/// let rule = RobotsPathRule::new("/a%2Fb");
+/// assert!(rule.matches("/a%2fb/c"));
+/// assert!(!rule.matches("/a/b/c"));
+/// ```
+///
+/// [RFC 9309 section 2.2.2]: https://www.rfc-editor.org/rfc/rfc9309.html#section-2.2.2
+/// [section 2.2.3]: https://www.rfc-editor.org/rfc/rfc9309.html#section-2.2.3
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RobotsPathRule<'a> {
+    /// Rule path.
+    rule: &'a str,
+}
+
+impl<'a> RobotsPathRule<'a> {
+    /// Creates a new rule.
+    #[inline]
+    #[must_use]
+    pub fn new(rule: &'a str) -> Self {
+        Self { rule }
+    }
+
+    /// Returns the rule string.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.rule
+    }
+
+    /// Returns true if the given path (optionally followed by `?` and the query) matches the rule.
+    #[must_use]
+    pub fn matches(&self, path: &str) -> bool {
+        self.matches_octets(Octets::new(path))
+    }
+
+    /// Returns true if the given octets matches the rule.
+    fn matches_octets(&self, mut target: Octets<'_>) -> bool {
+        let mut rule = RuleTokens::new(self.rule);
+        // The rule right after the last wildcard, and the target position to
+        // be retried next when the match fails after the wildcard.
+        let mut backtrack: Option<(RuleTokens<'_>, Octets<'_>)> = None;
+        loop {
+            let is_matched = match rule.next() {
+                None => return true,
+                Some(RuleToken::Wildcard) => {
+                    backtrack = Some((rule.clone(), target.clone()));
+                    continue;
+                }
+                Some(RuleToken::End) => target.clone().next().is_none(),
+                Some(RuleToken::Octet(expected)) => target.next() == Some(expected),
+            };
+            if is_matched {
+                continue;
+            }
+            match &mut backtrack {
+                Some((after_wildcard, retry)) => {
+                    if retry.next().is_none() {
+                        return false;
+                    }
+                    rule = after_wildcard.clone();
+                    target = retry.clone();
+                }
+                None => return false,
+            }
+        }
+    }
+
+    /// Returns true if the path and the query of the given IRI matches the rule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::pattern::RobotsPathRule;
+    /// use iri_string::types::IriStr;
+    ///
+    /// let rule = RobotsPathRule::new("/search?q=");
+    ///
+    /// assert!(rule.matches_iri(IriStr::new("https://example.com/search?q=rust#top")?));
+    /// assert!(!rule.matches_iri(IriStr::new("https://example.com/search")?));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[must_use]
+    pub fn matches_iri<S: Spec, T: ?Sized + AsRef<RiReferenceStr<S>>>(&self, iri: &T) -> bool {
+        let iri = iri.as_ref();
+        let s = iri.as_str();
+        // The path and the query are contiguous in the IRI.
+        let path_start = iri.scheme_str().map_or(0, |scheme| scheme.len() + 1)
+            + iri
+                .authority_str()
+                .map_or(0, |authority| authority.len() + 2);
+        let end = match iri.fragment() {
+            Some(fragment) => s.len() - fragment.as_str().len() - 1,
+            None => s.len(),
+        };
+        let mut target = Octets::new(&s[path_start..end]);
+        // robots.txt rules are matched against the path starting with `/`.
+        target.leading_slash = iri.path_str().is_empty();
+        self.matches_octets(target)
+    }
+}
+
+/// An octet in a path, compared in the robots.txt way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Octet {
+    /// Decoded octet (other than percent-encoded slash).
+    Byte(u8),
+    /// Percent-encoded slash.
+    EncodedSlash,
+}
+
+/// Iterator of octets of the path.
+#[derive(Debug, Clone)]
+struct Octets<'a> {
+    /// Whether to emit `/` before the rest.
+    leading_slash: bool,
+    /// Rest of the path.
+    rest: &'a [u8],
+}
+
+impl<'a> Octets<'a> {
+    /// Creates a new iterator.
+    #[inline]
+    #[must_use]
+    fn new(s: &'a str) -> Self {
+        Self {
+            leading_slash: false,
+            rest: s.as_bytes(),
+        }
+    }
+}
+
+impl Iterator for Octets<'_> {
+    type Item = Octet;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.leading_slash {
+            self.leading_slash = false;
+            return Some(Octet::Byte(b'/'));
+        }
+        let (&first, rest) = self.rest.split_first()?;
+        if first == b'%' {
+            if let [upper, lower, rest @ ..] = rest {
+                if upper.is_ascii_hexdigit() && lower.is_ascii_hexdigit() {
+                    self.rest = rest;
+                    return Some(match hexdigits_to_byte([*upper, *lower]) {
+                        b'/' => Octet::EncodedSlash,
+                        v => Octet::Byte(v),
+                    });
+                }
+            }
+        }
+        self.rest = rest;
+        Some(Octet::Byte(first))
+    }
+}
+
+/// A token of a robots.txt path rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleToken {
+    /// Wildcard `*`.
+    Wildcard,
+    /// End anchor `$`.
+    End,
+    /// An octet to be matched literally.
+    Octet(Octet),
+}
+
+/// Iterator of tokens of the robots.txt path rule.
+#[derive(Debug, Clone)]
+struct RuleTokens<'a> {
+    /// Octets of the rule.
+    octets: Octets<'a>,
+}
+
+impl<'a> RuleTokens<'a> {
+    /// Creates a new iterator.
+    #[inline]
+    #[must_use]
+    fn new(s: &'a str) -> Self {
+        Self {
+            octets: Octets::new(s),
+        }
+    }
+}
+
+impl Iterator for RuleTokens<'_> {
+    type Item = RuleToken;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Check before decoding, since percent-encoded `*` and `$` are literal.
+        let token = match self.octets.rest {
+            [b'*', ..] => RuleToken::Wildcard,
+            [b'$'] => RuleToken::End,
+            _ => return self.octets.next().map(RuleToken::Octet),
+        };
+        self.octets.rest = &self.octets.rest[1..];
+        Some(token)
+    }
+}
+
+/// Parses the authority part of the pattern.
+fn parse_authority_pattern(authority: &str) -> Result<AuthorityPattern<'_>, Error> {
+    if authority.contains('@') {
+        return Err(Error::new());
+    }
+    let host_end = if authority.starts_with('[') {
+        authority.find(']').ok_or_else(Error::new)? + 1
+    } else {
+        authority.find(':').unwrap_or(authority.len())
+    };
+    let (host, port) = authority.split_at(host_end);
+    let port = match port.strip_prefix(':') {
+        Some(port) => Some(port),
+        None if port.is_empty() => None,
+        None => return Err(Error::new()),
+    };
+    validate_host::<IriSpec>(host)?;
+    if let Some(port) = port {
+        if (port != WILDCARD) && !port.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::new());
+        }
+    }
+    Ok(AuthorityPattern { host, port })
+}
+
+/// Returns true if the target matches the glob pattern with `*` wildcards.
+fn glob_match(pattern: &str, target: &str, ignore_ascii_case: bool) -> bool {
+    let eq = |p: u8, t: u8| {
+        if ignore_ascii_case {
+            p.eq_ignore_ascii_case(&t)
+        } else {
+            p == t
+        }
+    };
+    let (pattern, target) = (pattern.as_bytes(), target.as_bytes());
+    let (mut p, mut t) = (0, 0);
+    // Position of the last wildcard in the pattern, and the position in the
+    // target to be retried next when the match fails after the wildcard.
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < target.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&pc) if eq(pc, target[t]) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    backtrack = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&b| b == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        assert!(glob_match("", "", false));
+        assert!(glob_match("*", "", false));
+        assert!(glob_match("*", "abc", false));
+        assert!(glob_match("a*c", "abbbc", false));
+        assert!(glob_match("a*c", "ac", false));
+        assert!(glob_match("*.example.com", "a.example.com", false));
+        assert!(glob_match("*a*b", "xaxxab", false));
+        assert!(glob_match("ABC", "abc", true));
+        assert!(!glob_match("ABC", "abc", false));
+        assert!(!glob_match("a*c", "abcd", false));
+        assert!(!glob_match("*.example.com", "example.com", false));
+        assert!(!glob_match("", "a", false));
+    }
+}
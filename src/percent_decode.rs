@@ -0,0 +1,180 @@
+//! Percent decoding.
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::parser::trusted::hexdigits_to_byte;
+#[cfg(feature = "alloc")]
+use crate::parser::trusted::take_xdigits2;
+
+/// Decodes percent-encoded triplets in the string.
+///
+/// If `plus_as_space` is true, `+` is decoded to a space.
+///
+/// # Precondition
+///
+/// Every `%` in the string should be followed by two hexadecimal digits.
+/// This is satisfied by (substrings of) valid IRI components.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub(crate) fn decode_bytes(s: &str, plus_as_space: bool) -> Cow<'_, [u8]> {
+    let needs_decode = |b: u8| b == b'%' || (plus_as_space && b == b'+');
+    let first = match s.bytes().position(needs_decode) {
+        Some(v) => v,
+        None => return Cow::Borrowed(s.as_bytes()),
+    };
+
+    let mut decoded = Vec::with_capacity(s.len());
+    decoded.extend_from_slice(&s.as_bytes()[..first]);
+    let mut rest = &s[first..];
+    while let Some(pos) = rest.bytes().position(needs_decode) {
+        decoded.extend_from_slice(&rest.as_bytes()[..pos]);
+        if rest.as_bytes()[pos] == b'+' {
+            decoded.push(b' ');
+            rest = &rest[(pos + 1)..];
+        } else {
+            let (byte, after_triplet) = take_xdigits2(&rest[(pos + 1)..]);
+            decoded.push(byte);
+            rest = after_triplet;
+        }
+    }
+    decoded.extend_from_slice(rest.as_bytes());
+    Cow::Owned(decoded)
+}
+
+/// Converts the decoded bytes into a string, replacing invalid UTF-8 sequences.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub(crate) fn bytes_to_str_lossy(bytes: Cow<'_, [u8]>) -> Cow<'_, str> {
+    match bytes {
+        Cow::Borrowed(v) => String::from_utf8_lossy(v),
+        Cow::Owned(v) => match String::from_utf8(v) {
+            Ok(s) => Cow::Owned(s),
+            Err(e) => Cow::Owned(String::from_utf8_lossy(e.as_bytes()).into_owned()),
+        },
+    }
+}
+
+/// Iterator of the percent-decoded bytes, without allocation.
+///
+/// # Precondition
+///
+/// Every `%` in the string should be followed by two hexadecimal digits.
+#[derive(Debug, Clone)]
+pub(crate) struct DecodedBytes<'a> {
+    /// Rest of the encoded bytes.
+    rest: &'a [u8],
+}
+
+impl<'a> DecodedBytes<'a> {
+    /// Creates a new iterator.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(s: &'a [u8]) -> Self {
+        Self { rest: s }
+    }
+}
+
+impl Iterator for DecodedBytes<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match *self.rest {
+            [b'%', upper, lower, ..] => {
+                self.rest = &self.rest[3..];
+                Some(hexdigits_to_byte([upper, lower]))
+            }
+            [b, ..] => {
+                self.rest = &self.rest[1..];
+                Some(b)
+            }
+            [] => None,
+        }
+    }
+}
+
+impl DoubleEndedIterator for DecodedBytes<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // `%` never appears as the second or the third byte of a triplet.
+        match *self.rest {
+            [.., b'%', upper, lower] => {
+                self.rest = &self.rest[..(self.rest.len() - 3)];
+                Some(hexdigits_to_byte([upper, lower]))
+            }
+            [.., b] => {
+                self.rest = &self.rest[..(self.rest.len() - 1)];
+                Some(b)
+            }
+            [] => None,
+        }
+    }
+}
+
+/// Returns true if the percent-decoded string starts with the needle.
+#[must_use]
+pub(crate) fn starts_with_decoded(s: &[u8], needle: &[u8]) -> bool {
+    let mut decoded = DecodedBytes::new(s);
+    needle.iter().all(|&b| decoded.next() == Some(b))
+}
+
+/// Returns true if the percent-decoded string ends with the needle.
+#[must_use]
+pub(crate) fn ends_with_decoded(s: &[u8], needle: &[u8]) -> bool {
+    let mut decoded = DecodedBytes::new(s);
+    needle.iter().rev().all(|&b| decoded.next_back() == Some(b))
+}
+
+/// Returns the position in the encoded string where the percent-decoded
+/// needle first appears.
+#[must_use]
+pub(crate) fn find_decoded(s: &[u8], needle: &[u8]) -> Option<usize> {
+    let mut pos = 0;
+    loop {
+        if starts_with_decoded(&s[pos..], needle) {
+            return Some(pos);
+        }
+        match s.get(pos) {
+            Some(b'%') => pos += 3,
+            Some(_) => pos += 1,
+            None => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decoded_search() {
+        assert!(DecodedBytes::new(b"a%41%2fb").eq(b"aA/b".iter().copied()));
+        assert!(DecodedBytes::new(b"a%41%2fb")
+            .rev()
+            .eq(b"b/Aa".iter().copied()));
+        assert_eq!(find_decoded(b"x%2Fy%2fz", b"/z"), Some(5));
+        assert_eq!(find_decoded(b"x%2Fy", b""), Some(0));
+        assert_eq!(find_decoded(b"x%2Fy", b"2F"), None);
+        assert!(starts_with_decoded(b"%E3%81%82b", "\u{3042}".as_bytes()));
+        assert!(ends_with_decoded(b"a%E3%81%82", "\u{3042}".as_bytes()));
+        assert!(!ends_with_decoded(b"%82", "\u{3042}".as_bytes()));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decode() {
+        assert!(matches!(decode_bytes("abc", false), Cow::Borrowed(b"abc")));
+        assert!(matches!(decode_bytes("a+c", false), Cow::Borrowed(b"a+c")));
+        assert_eq!(decode_bytes("a+c", true), &b"a c"[..]);
+        assert_eq!(decode_bytes("%41%2b%2B+", false), &b"A+++"[..]);
+        assert_eq!(decode_bytes("%41%2b%2B+", true), &b"A++ "[..]);
+        assert_eq!(decode_bytes("%ff%00", false), &b"\xff\x00"[..]);
+        assert_eq!(
+            bytes_to_str_lossy(decode_bytes("%CE%B1%ff", false)),
+            "\u{03B1}\u{FFFD}"
+        );
+    }
+}
@@ -30,6 +30,10 @@ enum Context {
     Path,
     /// Encode the string as a query string (without the `?` prefix).
     Query,
+    /// Encode the string as a key or a value of a query pair.
+    ///
+    /// `&`, `=`, `+`, and `#` will be encoded.
+    QueryKeyOrValue,
     /// Encode the string as a fragment string (without the `#` prefix).
     Fragment,
     /// Encode all characters except for `unreserved` characters.
@@ -213,6 +217,64 @@ impl<T: fmt::Display, S: Spec> PercentEncoded<T, S> {
         }
     }
 
+    /// Creates an encoded string from a raw key of a query pair.
+    ///
+    /// In addition to the characters not allowed in the query, `&`, `=`, and
+    /// `+` are encoded since they have special meanings in
+    /// `key1=value1&key2=value2` style queries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use iri_string::percent_encode::PercentEncoded;
+    /// use iri_string::spec::UriSpec;
+    ///
+    /// let raw = "a&b=c+d e/\u{03B1}?#";
+    /// let encoded = "a%26b%3Dc%2Bd%20e/%CE%B1?%23";
+    /// assert_eq!(
+    ///     PercentEncoded::<_, UriSpec>::from_query_key(raw).to_string(),
+    ///     encoded
+    /// );
+    /// # }
+    /// ```
+    pub fn from_query_key(raw: T) -> Self {
+        Self {
+            context: Context::QueryKeyOrValue,
+            raw,
+            _spec: PhantomData,
+        }
+    }
+
+    /// Creates an encoded string from a raw value of a query pair.
+    ///
+    /// In addition to the characters not allowed in the query, `&`, `=`, and
+    /// `+` are encoded since they have special meanings in
+    /// `key1=value1&key2=value2` style queries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use iri_string::percent_encode::PercentEncoded;
+    /// use iri_string::spec::UriSpec;
+    ///
+    /// let raw = "a&b=c+d e/\u{03B1}?#";
+    /// let encoded = "a%26b%3Dc%2Bd%20e/%CE%B1?%23";
+    /// assert_eq!(
+    ///     PercentEncoded::<_, UriSpec>::from_query_value(raw).to_string(),
+    ///     encoded
+    /// );
+    /// # }
+    /// ```
+    pub fn from_query_value(raw: T) -> Self {
+        Self {
+            context: Context::QueryKeyOrValue,
+            raw,
+            _spec: PhantomData,
+        }
+    }
+
     /// Creates an encoded string from a raw fragment.
     ///
     /// # Examples
@@ -344,6 +406,11 @@ impl<T: fmt::Display, S: Spec> fmt::Display for PercentEncoded<T, S> {
                     (Context::Path, false) => S::is_nonascii_char_unreserved(c),
                     (Context::Query, true) => c == '/' || char::is_ascii_frag_query(c as u8),
                     (Context::Query, false) => char::is_nonascii_query::<S>(c),
+                    (Context::QueryKeyOrValue, true) => {
+                        !matches!(c, '&' | '=' | '+')
+                            && (c == '/' || char::is_ascii_frag_query(c as u8))
+                    }
+                    (Context::QueryKeyOrValue, false) => char::is_nonascii_query::<S>(c),
                     (Context::Fragment, true) => c == '/' || char::is_ascii_frag_query(c as u8),
                     (Context::Fragment, false) => char::is_nonascii_fragment::<S>(c),
                     (Context::Unreserve, true) => char::is_ascii_unreserved(c as u8),
@@ -376,3 +443,53 @@ fn write_pct_encoded_char<W: fmt::Write>(writer: &mut W, c: char) -> fmt::Result
     let buf = c.encode_utf8(&mut buf);
     buf.bytes().try_for_each(|b| write!(writer, "%{:02X}", b))
 }
+
+/// A proxy to percent-encode arbitrary bytes as a key or a value of a query pair.
+///
+/// Valid UTF-8 sequences are encoded as [`PercentEncoded::from_query_key`]
+/// does, and bytes not consisting valid UTF-8 sequences are always
+/// percent-encoded.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct QueryBytesEncoded<'a, S> {
+    /// Raw bytes before being encoded.
+    raw: &'a [u8],
+    /// Spec.
+    _spec: PhantomData<fn() -> S>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, S: Spec> QueryBytesEncoded<'a, S> {
+    /// Creates a new proxy.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(raw: &'a [u8]) -> Self {
+        Self {
+            raw,
+            _spec: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Spec> fmt::Display for QueryBytesEncoded<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rest = self.raw;
+        while !rest.is_empty() {
+            let (valid, invalid_len) = match core::str::from_utf8(rest) {
+                Ok(s) => (s, 0),
+                Err(e) => {
+                    let (valid, after_valid) = rest.split_at(e.valid_up_to());
+                    let valid = core::str::from_utf8(valid)
+                        .expect("[validity] the prefix is checked to be valid UTF-8");
+                    (valid, e.error_len().unwrap_or(after_valid.len()))
+                }
+            };
+            PercentEncoded::<_, S>::from_query_key(valid).fmt(f)?;
+            let invalid = &rest[valid.len()..(valid.len() + invalid_len)];
+            invalid.iter().try_for_each(|b| write!(f, "%{:02X}", b))?;
+            rest = &rest[(valid.len() + invalid_len)..];
+        }
+        Ok(())
+    }
+}
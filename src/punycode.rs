@@ -0,0 +1,213 @@
+//! Punycode encoder and decoder.
+//!
+//! This module provides [Punycode] (RFC 3492) encoding and decoding of a
+//! single domain label, without IDNA mapping and validation.
+//!
+//! When `idna` feature is enabled, the Punycode implementation of `idna` crate
+//! (which is also used for IDNA processing) is used, so that only one
+//! implementation is built.
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::punycode::Error;
+//! use iri_string::punycode;
+//!
+//! assert_eq!(punycode::encode("b\u{FC}cher")?, "bcher-kva");
+//! assert_eq!(punycode::decode("bcher-kva")?, "b\u{FC}cher");
+//!
+//! assert_eq!(punycode::to_ascii_label("m\u{FC}nchen")?, "xn--mnchen-3ya");
+//! assert_eq!(punycode::to_unicode_label("xn--mnchen-3ya")?, "m\u{FC}nchen");
+//! // Labels without non-ASCII characters are returned as is.
+//! assert_eq!(punycode::to_ascii_label("example")?, "example");
+//! # Ok::<_, Error>(())
+//! ```
+//!
+//! [Punycode]: https://datatracker.ietf.org/doc/html/rfc3492
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+#[cfg(not(feature = "idna"))]
+mod rfc3492;
+
+/// ACE prefix for IDNA labels.
+const ACE_PREFIX: &str = "xn--";
+
+/// Punycode encoding or decoding error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(ErrorKind);
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    fn new(kind: ErrorKind) -> Self {
+        Self(kind)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self.0 {
+            ErrorKind::InvalidInput => "invalid Punycode input",
+            ErrorKind::Overflow => "Punycode arithmetic overflow",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+/// Error kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorKind {
+    /// Invalid input.
+    InvalidInput,
+    /// Arithmetic overflow.
+    Overflow,
+}
+
+/// Returns the overflow error.
+#[inline]
+#[must_use]
+fn overflow() -> Error {
+    Error::new(ErrorKind::Overflow)
+}
+
+/// Returns the invalid input error.
+#[inline]
+#[must_use]
+fn invalid() -> Error {
+    Error::new(ErrorKind::InvalidInput)
+}
+
+/// Encodes the label into Punycode.
+///
+/// The result does not have the ACE prefix (`xn--`). Use [`to_ascii_label`]
+/// to get an IDNA label.
+///
+/// # Failures
+///
+/// Fails if the label is too long and the encoding overflows.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::punycode::Error;
+/// use iri_string::punycode;
+///
+/// assert_eq!(punycode::encode("m\u{FC}nchen")?, "mnchen-3ya");
+/// assert_eq!(punycode::encode("abc")?, "abc-");
+/// # Ok::<_, Error>(())
+/// ```
+pub fn encode(label: &str) -> Result<String, Error> {
+    #[cfg(feature = "idna")]
+    {
+        ::idna::punycode::encode_str(label).ok_or_else(overflow)
+    }
+    #[cfg(not(feature = "idna"))]
+    {
+        rfc3492::encode(label)
+    }
+}
+
+/// Decodes the Punycode-encoded label.
+///
+/// The input should not have the ACE prefix (`xn--`). Use
+/// [`to_unicode_label`] to decode an IDNA label.
+///
+/// # Failures
+///
+/// Fails if the input is invalid as Punycode.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::punycode::Error;
+/// use iri_string::punycode;
+///
+/// assert_eq!(punycode::decode("mnchen-3ya")?, "m\u{FC}nchen");
+/// assert_eq!(punycode::decode("abc-")?, "abc");
+/// assert!(punycode::decode("\u{3042}").is_err());
+/// # Ok::<_, Error>(())
+/// ```
+pub fn decode(input: &str) -> Result<String, Error> {
+    #[cfg(feature = "idna")]
+    {
+        if !input.is_ascii() {
+            return Err(invalid());
+        }
+        ::idna::punycode::decode_to_string(input).ok_or_else(invalid)
+    }
+    #[cfg(not(feature = "idna"))]
+    {
+        rfc3492::decode(input)
+    }
+}
+
+/// Converts the label into the ASCII form, i.e. adds the ACE prefix to the
+/// Punycode-encoded label if the label contains non-ASCII characters.
+///
+/// ASCII labels are returned as is.
+///
+/// # Failures
+///
+/// Fails if the label is too long and the encoding overflows.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::punycode::Error;
+/// use iri_string::punycode;
+///
+/// assert_eq!(punycode::to_ascii_label("b\u{FC}cher")?, "xn--bcher-kva");
+/// assert_eq!(punycode::to_ascii_label("Example")?, "Example");
+/// # Ok::<_, Error>(())
+/// ```
+pub fn to_ascii_label(label: &str) -> Result<Cow<'_, str>, Error> {
+    if label.is_ascii() {
+        return Ok(Cow::Borrowed(label));
+    }
+    let encoded = encode(label)?;
+    let mut buf = String::with_capacity(ACE_PREFIX.len() + encoded.len());
+    buf.push_str(ACE_PREFIX);
+    buf.push_str(&encoded);
+    Ok(Cow::Owned(buf))
+}
+
+/// Converts the label into the Unicode form, i.e. decodes the label if it
+/// has the ACE prefix (`xn--`, ASCII case-insensitively).
+///
+/// Labels without the ACE prefix are returned as is.
+///
+/// # Failures
+///
+/// Fails if the label has the ACE prefix and the rest is invalid as Punycode.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::punycode::Error;
+/// use iri_string::punycode;
+///
+/// assert_eq!(punycode::to_unicode_label("XN--bcher-kva")?, "b\u{FC}cher");
+/// assert_eq!(punycode::to_unicode_label("example")?, "example");
+/// assert!(punycode::to_unicode_label("xn--a\u{FC}").is_err());
+/// # Ok::<_, Error>(())
+/// ```
+pub fn to_unicode_label(label: &str) -> Result<Cow<'_, str>, Error> {
+    match label.get(..ACE_PREFIX.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(ACE_PREFIX) => {
+            decode(&label[ACE_PREFIX.len()..]).map(Cow::Owned)
+        }
+        _ => Ok(Cow::Borrowed(label)),
+    }
+}
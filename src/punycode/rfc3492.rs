@@ -0,0 +1,174 @@
+//! Punycode implementation of this crate.
+//!
+//! This is used only when `idna` feature is disabled. Otherwise, the
+//! implementation in `idna` crate is used instead.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{invalid, overflow, Error};
+
+/// Base.
+const BASE: u32 = 36;
+/// Minimum threshold.
+const T_MIN: u32 = 1;
+/// Maximum threshold.
+const T_MAX: u32 = 26;
+/// Skew.
+const SKEW: u32 = 38;
+/// Damping factor.
+const DAMP: u32 = 700;
+/// Initial bias.
+const INITIAL_BIAS: u32 = 72;
+/// Initial code point.
+const INITIAL_N: u32 = 0x80;
+/// Delimiter between basic code points and encoded deltas.
+const DELIMITER: char = '-';
+
+/// Bias adaptation function (RFC 3492 section 6.1).
+fn adapt(delta: u32, num_points: u32, is_first: bool) -> u32 {
+    let mut delta = if is_first { delta / DAMP } else { delta / 2 };
+    delta += delta / num_points;
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+    k + (((BASE - T_MIN + 1) * delta) / (delta + SKEW))
+}
+
+/// Returns the threshold for the position `k`.
+#[inline]
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        T_MIN
+    } else if k >= bias + T_MAX {
+        T_MAX
+    } else {
+        k - bias
+    }
+}
+
+/// Returns the basic code point for the digit.
+#[inline]
+fn encode_digit(digit: u32) -> char {
+    debug_assert!(
+        digit < BASE,
+        "[precondition] digit must be less than the base"
+    );
+    let digit = digit as u8;
+    if digit < 26 {
+        char::from(b'a' + digit)
+    } else {
+        char::from(b'0' + (digit - 26))
+    }
+}
+
+/// Returns the digit for the basic code point.
+#[inline]
+fn decode_digit(b: u8) -> Option<u32> {
+    match b {
+        b'a'..=b'z' => Some(u32::from(b - b'a')),
+        b'A'..=b'Z' => Some(u32::from(b - b'A')),
+        b'0'..=b'9' => Some(u32::from(b - b'0') + 26),
+        _ => None,
+    }
+}
+
+/// Encodes the label into Punycode.
+pub(super) fn encode(label: &str) -> Result<String, Error> {
+    let mut output = String::with_capacity(label.len());
+    output.extend(label.chars().filter(char::is_ascii));
+    let num_basic = u32::try_from(output.len()).map_err(|_| overflow())?;
+    if num_basic > 0 {
+        output.push(DELIMITER);
+    }
+    let num_total = u32::try_from(label.chars().count()).map_err(|_| overflow())?;
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut num_handled = num_basic;
+    while num_handled < num_total {
+        let m = label
+            .chars()
+            .map(u32::from)
+            .filter(|&c| c >= n)
+            .min()
+            .expect("[consistency] unhandled code points must remain");
+        delta = (m - n)
+            .checked_mul(num_handled + 1)
+            .and_then(|v| delta.checked_add(v))
+            .ok_or_else(overflow)?;
+        n = m;
+        for c in label.chars().map(u32::from) {
+            if c < n {
+                delta = delta.checked_add(1).ok_or_else(overflow)?;
+            } else if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    output.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(encode_digit(q));
+                bias = adapt(delta, num_handled + 1, num_handled == num_basic);
+                delta = 0;
+                num_handled += 1;
+            }
+        }
+        delta = delta.checked_add(1).ok_or_else(overflow)?;
+        n = n.checked_add(1).ok_or_else(overflow)?;
+    }
+
+    Ok(output)
+}
+
+/// Decodes the Punycode-encoded label.
+pub(super) fn decode(input: &str) -> Result<String, Error> {
+    if !input.is_ascii() {
+        return Err(invalid());
+    }
+    let (basic, encoded) = match input.rfind(DELIMITER) {
+        Some(pos) => (&input[..pos], &input[(pos + 1)..]),
+        None => ("", input),
+    };
+
+    let mut output: Vec<char> = basic.chars().collect();
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut bytes = encoded.bytes();
+    while bytes.len() != 0 {
+        let old_i = i;
+        let mut weight: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let digit = bytes.next().and_then(decode_digit).ok_or_else(invalid)?;
+            i = digit
+                .checked_mul(weight)
+                .and_then(|v| i.checked_add(v))
+                .ok_or_else(overflow)?;
+            let t = threshold(k, bias);
+            if digit < t {
+                break;
+            }
+            weight = weight.checked_mul(BASE - t).ok_or_else(overflow)?;
+            k += BASE;
+        }
+        let len = u32::try_from(output.len() + 1).map_err(|_| overflow())?;
+        bias = adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len).ok_or_else(overflow)?;
+        i %= len;
+        let c = char::from_u32(n).ok_or_else(invalid)?;
+        output.insert(i as usize, c);
+        i += 1;
+    }
+
+    Ok(output.into_iter().collect())
+}
@@ -0,0 +1,315 @@
+//! Query utilities.
+//!
+//! This module provides utilities for `key1=value1&key2=value2` style
+//! queries (such as `application/x-www-form-urlencoded`).
+//!
+//! # Order of pairs
+//!
+//! All the APIs of this module (and the query APIs of the IRI types) keep the
+//! original textual order of the pairs, and never deduplicate pairs with the
+//! same key. This is important for applications such as signature
+//! verification of signed URLs, where reordering or deduplication silently
+//! changes the signed content. Use [`FormData::nth_occurrence`] (or
+//! [`RiQueryStr::nth_occurrence`]) to access a specific one of the values for
+//! the duplicate keys.
+//!
+//! Note that empty pairs (such as the one between `&&`) are skipped by the
+//! iterators since they have no key and no value. Use the raw query string
+//! (such as [`RiQueryStr::as_str`]) if the exact text is necessary.
+//!
+//! [`RiQueryStr::nth_occurrence`]: `crate::types::RiQueryStr::nth_occurrence`
+//! [`RiQueryStr::as_str`]: `crate::types::RiQueryStr::as_str`
+
+use core::str::FromStr;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::percent_decode::{bytes_to_str_lossy, decode_bytes};
+use crate::spec::Spec;
+use crate::types::RiQueryStr;
+
+/// Iterator of the raw (not decoded) `key=value` pairs.
+#[derive(Debug, Clone)]
+struct RawPairs<'a> {
+    /// Rest of the query.
+    rest: &'a str,
+}
+
+impl<'a> Iterator for RawPairs<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let pair = match self.rest.find('&') {
+                Some(pos) => {
+                    let pair = &self.rest[..pos];
+                    self.rest = &self.rest[(pos + 1)..];
+                    pair
+                }
+                None => core::mem::take(&mut self.rest),
+            };
+            if pair.is_empty() {
+                continue;
+            }
+            return Some(match pair.find('=') {
+                Some(pos) => (&pair[..pos], &pair[(pos + 1)..]),
+                None => (pair, ""),
+            });
+        }
+    }
+}
+
+impl core::iter::FusedIterator for RawPairs<'_> {}
+
+/// Iterator of the decoded `key=value` pairs of a query.
+///
+/// Pairs are yielded in the order they appear in the query, including the
+/// ones with duplicate keys.
+///
+/// This is a return type of [`RiQueryStr::query_pairs`].
+///
+/// [`RiQueryStr::query_pairs`]: `crate::types::RiQueryStr::query_pairs`
+#[derive(Debug, Clone)]
+pub struct QueryPairs<'a> {
+    /// Raw pairs.
+    inner: RawPairs<'a>,
+}
+
+impl<'a> QueryPairs<'a> {
+    /// Creates a new iterator.
+    ///
+    /// # Precondition
+    ///
+    /// The given string should be a valid query.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(query: &'a str) -> Self {
+        Self {
+            inner: RawPairs { rest: query },
+        }
+    }
+}
+
+impl<'a> Iterator for QueryPairs<'a> {
+    type Item = (Cow<'a, str>, Cow<'a, str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.inner.next()?;
+        Some((
+            bytes_to_str_lossy(decode_bytes(key, true)),
+            bytes_to_str_lossy(decode_bytes(value, true)),
+        ))
+    }
+}
+
+impl core::iter::FusedIterator for QueryPairs<'_> {}
+
+/// Iterator of the decoded `key=value` pairs of a query, as bytes.
+///
+/// Pairs are yielded in the order they appear in the query, including the
+/// ones with duplicate keys.
+///
+/// This is a return type of [`RiQueryStr::query_pairs_bytes`].
+///
+/// [`RiQueryStr::query_pairs_bytes`]: `crate::types::RiQueryStr::query_pairs_bytes`
+#[derive(Debug, Clone)]
+pub struct QueryPairsBytes<'a> {
+    /// Raw pairs.
+    inner: RawPairs<'a>,
+}
+
+impl<'a> QueryPairsBytes<'a> {
+    /// Creates a new iterator.
+    ///
+    /// # Precondition
+    ///
+    /// The given string should be a valid query.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(query: &'a str) -> Self {
+        Self {
+            inner: RawPairs { rest: query },
+        }
+    }
+}
+
+impl<'a> Iterator for QueryPairsBytes<'a> {
+    type Item = (Cow<'a, [u8]>, Cow<'a, [u8]>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.inner.next()?;
+        Some((decode_bytes(key, true), decode_bytes(value, true)))
+    }
+}
+
+impl core::iter::FusedIterator for QueryPairsBytes<'_> {}
+
+/// Parsed `key=value` pairs of a query, with the original order preserved.
+///
+/// Keys and values are decoded in the same way as
+/// [`RiQueryStr::query_pairs`]. Multiple values for the same key are all
+/// kept, and pairs are stored in the order they appear in the query.
+///
+/// This is a return type of [`RiQueryStr::to_form_data`].
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::types::{IriQueryStr, IriQueryString};
+///
+/// let query = IriQueryStr::new("page=2&tag=rust&tag=iri&q=a+b")?;
+/// let form = query.to_form_data();
+///
+/// assert_eq!(form.get::<u32>("page"), Some(Ok(2)));
+/// assert!(form.get::<u32>("q").expect("`q` exists").is_err());
+/// assert_eq!(form.get::<u32>("missing"), None);
+/// assert_eq!(form.get_str("q"), Some("a b"));
+/// assert_eq!(form.get_all("tag").collect::<Vec<_>>(), ["rust", "iri"]);
+///
+/// // Order is preserved on serialization.
+/// let query: IriQueryString = form.iter().collect();
+/// assert_eq!(query, "page=2&tag=rust&tag=iri&q=a%20b");
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// [`RiQueryStr::query_pairs`]: `crate::types::RiQueryStr::query_pairs`
+/// [`RiQueryStr::to_form_data`]: `crate::types::RiQueryStr::to_form_data`
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FormData {
+    /// Decoded pairs.
+    pairs: Vec<(String, String)>,
+}
+
+impl FormData {
+    /// Parses the query.
+    ///
+    /// # Precondition
+    ///
+    /// The given string should be a valid query.
+    #[must_use]
+    pub(crate) fn parse(query: &str) -> Self {
+        let pairs = QueryPairs::new(query)
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        Self { pairs }
+    }
+
+    /// Returns the first value for the key, parsed as `T`.
+    ///
+    /// Returns `None` if the key does not exist, and `Some(Err(_))` if the
+    /// value cannot be parsed.
+    #[inline]
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<Result<T, T::Err>> {
+        self.get_str(key).map(str::parse)
+    }
+
+    /// Returns the first value for the key.
+    #[must_use]
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the `n`-th (0-based) value for the key, in the original order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriQueryStr;
+    ///
+    /// let form = IriQueryStr::new("a=1&b=2&a=3")?.to_form_data();
+    /// assert_eq!(form.nth_occurrence("a", 0), Some("1"));
+    /// assert_eq!(form.nth_occurrence("a", 1), Some("3"));
+    /// assert_eq!(form.nth_occurrence("a", 2), None);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[must_use]
+    pub fn nth_occurrence(&self, key: &str, n: usize) -> Option<&str> {
+        self.pairs
+            .iter()
+            .filter(|(k, _)| k == key)
+            .nth(n)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns an iterator of all values for the key, in the original order.
+    #[inline]
+    pub fn get_all<'a>(&'a self, key: &'a str) -> FormValues<'a> {
+        FormValues {
+            pairs: self.pairs.iter(),
+            key,
+        }
+    }
+
+    /// Returns true if the key exists.
+    #[inline]
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.pairs.iter().any(|(k, _)| k == key)
+    }
+
+    /// Returns an iterator of all pairs, in the original order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns the number of pairs.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Returns true if there are no pairs.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Returns the pairs.
+    #[inline]
+    #[must_use]
+    pub fn into_pairs(self) -> Vec<(String, String)> {
+        self.pairs
+    }
+}
+
+impl<S: Spec> From<&RiQueryStr<S>> for FormData {
+    #[inline]
+    fn from(query: &RiQueryStr<S>) -> Self {
+        Self::parse(query.as_str())
+    }
+}
+
+/// Iterator of the values for a key in [`FormData`].
+///
+/// This is a return type of [`FormData::get_all`].
+#[derive(Debug, Clone)]
+pub struct FormValues<'a> {
+    /// Rest of the pairs.
+    pairs: core::slice::Iter<'a, (String, String)>,
+    /// Key.
+    key: &'a str,
+}
+
+impl<'a> Iterator for FormValues<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.key;
+        self.pairs.find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+impl core::iter::FusedIterator for FormValues<'_> {}
@@ -22,6 +22,27 @@ pub(crate) fn set_fragment(s: &mut String, fragment: Option<&str>) {
     }
 }
 
+/// Sets the query part to the given string.
+///
+/// Removes query part (and following `?` character) if `None` is given.
+#[cfg(feature = "alloc")]
+pub(crate) fn set_query(s: &mut String, query: Option<&str>) {
+    let prefix_len = trusted_parser::split_fragment(s).0.len();
+    // `?` does not appear before the query part.
+    let query_start = s[..prefix_len].find('?').unwrap_or(prefix_len);
+    match query {
+        Some(query) => {
+            let mut replacement = String::with_capacity(query.len() + 1);
+            replacement.push('?');
+            replacement.push_str(query);
+            s.replace_range(query_start..prefix_len, &replacement);
+        }
+        None => {
+            s.drain(query_start..prefix_len);
+        }
+    }
+}
+
 /// Removes the fragment part from the string.
 #[cfg(feature = "alloc")]
 #[inline]
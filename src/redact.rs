@@ -0,0 +1,257 @@
+//! Redaction of secrets in IRIs.
+//!
+//! IRIs often carry secrets in the userinfo (such as passwords and API
+//! tokens) and in query values (such as signatures and session IDs).
+//! [`Redacted`] hides them when displayed, and is returned by `redacted`
+//! method of IRI string types (such as [`RiStr::redacted`]).
+//!
+//! # `Debug` redaction
+//!
+//! `Debug` implementations of IRI string types print the whole string by
+//! default. When `redact-debug` feature is enabled, they mask the userinfo in
+//! the same way as [`Redacted`], so that derived `Debug` implementations of
+//! structs containing IRIs don't leak secrets into logs and error reports.
+//! When `redact-debug-query` feature is enabled, query values are also masked
+//! (including the `Debug` output of query string types).
+//!
+//! [`RiStr::redacted`]: `crate::types::RiStr::redacted`
+
+use core::fmt::{self, Write as _};
+
+use crate::components::AuthorityComponents;
+use crate::parser::trusted::split_fragment;
+use crate::spec::Spec;
+use crate::types::RiReferenceStr;
+#[cfg(feature = "redact-debug")]
+use crate::types::{
+    RiAbsoluteStr, RiAuthorityStr, RiFragmentStr, RiHostStr, RiPathStr, RiQueryStr, RiRelativeStr,
+    RiStr,
+};
+
+/// Placeholder for the redacted content.
+const PLACEHOLDER: &str = "***";
+
+/// A wrapper of an IRI string that masks the secrets when `Display`ed.
+///
+/// The whole userinfo (if any) is replaced with `***`. If
+/// [`mask_query_values`][`Self::mask_query_values`] is enabled, the value of
+/// each `key=value` pair in the query is also replaced with `***`. Query
+/// parameters without `=` are replaced entirely since they can also be
+/// secrets. Empty values are kept as is.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// # #[cfg(feature = "alloc")] {
+/// use iri_string::types::IriStr;
+///
+/// let iri = IriStr::new("https://user:pw@example.com/path?token=secret&page=2&flag#frag")?;
+/// assert_eq!(
+///     iri.redacted().to_string(),
+///     "https://***@example.com/path?token=secret&page=2&flag#frag"
+/// );
+/// assert_eq!(
+///     iri.redacted().mask_query_values(true).to_string(),
+///     "https://***@example.com/path?token=***&page=***&***#frag"
+/// );
+/// # }
+/// # Ok::<_, Error>(())
+/// ```
+#[derive(Clone, Copy)]
+pub struct Redacted<'a, S: Spec> {
+    /// IRI reference.
+    iri_ref: &'a RiReferenceStr<S>,
+    /// Whether to mask query values.
+    mask_query: bool,
+}
+
+impl<'a, S: Spec> Redacted<'a, S> {
+    /// Creates a new `Redacted` object.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(iri_ref: &'a RiReferenceStr<S>) -> Self {
+        Self {
+            iri_ref,
+            mask_query: false,
+        }
+    }
+
+    /// Sets whether to mask the query values.
+    #[inline]
+    #[must_use]
+    pub fn mask_query_values(self, value: bool) -> Self {
+        Self {
+            mask_query: value,
+            ..self
+        }
+    }
+}
+
+impl<S: Spec> fmt::Display for Redacted<'_, S> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_redacted(f, self.iri_ref, self.mask_query)
+    }
+}
+
+impl<S: Spec> fmt::Debug for Redacted<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('<')?;
+        fmt::Display::fmt(self, f)?;
+        f.write_char('>')
+    }
+}
+
+/// Writes the IRI reference with the secrets masked.
+fn write_redacted<W: fmt::Write, S: Spec>(
+    w: &mut W,
+    iri_ref: &RiReferenceStr<S>,
+    mask_query: bool,
+) -> fmt::Result {
+    let s = iri_ref.as_str();
+    let rest = match AuthorityComponents::from_iri(iri_ref) {
+        Some(authority) => match authority.userinfo() {
+            Some(userinfo) => {
+                // 2: `"//".len()`.
+                let userinfo_start = 2 + s
+                    .find("//")
+                    .expect("[validity] `authority` component must be prefixed with `//`");
+                w.write_str(&s[..userinfo_start])?;
+                w.write_str(PLACEHOLDER)?;
+                &s[(userinfo_start + userinfo.len())..]
+            }
+            None => s,
+        },
+        None => s,
+    };
+    if !mask_query {
+        return w.write_str(rest);
+    }
+    let (before_fragment, fragment) = split_fragment(rest);
+    match before_fragment.find('?') {
+        Some(query_start) => {
+            w.write_str(&before_fragment[..=query_start])?;
+            write_query_redacted(w, &before_fragment[(query_start + 1)..])?;
+        }
+        None => w.write_str(before_fragment)?,
+    }
+    if let Some(fragment) = fragment {
+        w.write_char('#')?;
+        w.write_str(fragment)?;
+    }
+    Ok(())
+}
+
+/// Writes the query with the values masked.
+fn write_query_redacted<W: fmt::Write>(w: &mut W, query: &str) -> fmt::Result {
+    for (i, pair) in query.split('&').enumerate() {
+        if i != 0 {
+            w.write_char('&')?;
+        }
+        match pair.find('=') {
+            Some(eq) if eq + 1 == pair.len() => w.write_str(pair)?,
+            Some(eq) => {
+                w.write_str(&pair[..=eq])?;
+                w.write_str(PLACEHOLDER)?;
+            }
+            None if pair.is_empty() => {}
+            None => w.write_str(PLACEHOLDER)?,
+        }
+    }
+    Ok(())
+}
+
+/// A type whose `Debug` output can be redacted.
+#[cfg(feature = "redact-debug")]
+pub(crate) trait RedactDebug {
+    /// Writes the content with the secrets masked.
+    fn write_redacted<W: fmt::Write>(&self, w: &mut W) -> fmt::Result;
+}
+
+/// Implements `RedactDebug` for IRI (reference) types.
+macro_rules! impl_redact_debug_for_iri {
+    ($($ty:ident),*) => {
+        $(
+            #[cfg(feature = "redact-debug")]
+            impl<S: Spec> RedactDebug for $ty<S> {
+                #[inline]
+                fn write_redacted<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+                    write_redacted(w, self.as_ref(), cfg!(feature = "redact-debug-query"))
+                }
+            }
+        )*
+    };
+}
+
+impl_redact_debug_for_iri!(RiReferenceStr, RiStr, RiAbsoluteStr, RiRelativeStr);
+
+#[cfg(feature = "redact-debug")]
+impl<S: Spec> RedactDebug for RiQueryStr<S> {
+    #[inline]
+    fn write_redacted<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        if cfg!(feature = "redact-debug-query") {
+            write_query_redacted(w, self.as_str())
+        } else {
+            w.write_str(self.as_str())
+        }
+    }
+}
+
+#[cfg(feature = "redact-debug")]
+impl<S: Spec> RedactDebug for RiAuthorityStr<S> {
+    fn write_redacted<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        match self.userinfo() {
+            Some(userinfo) => {
+                w.write_str(PLACEHOLDER)?;
+                w.write_str(&self.as_str()[userinfo.len()..])
+            }
+            None => w.write_str(self.as_str()),
+        }
+    }
+}
+
+/// Implements `RedactDebug` for types without secrets.
+macro_rules! impl_redact_debug_as_is {
+    ($($ty:ident),*) => {
+        $(
+            #[cfg(feature = "redact-debug")]
+            impl<S: Spec> RedactDebug for $ty<S> {
+                #[inline]
+                fn write_redacted<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+                    w.write_str(self.as_str())
+                }
+            }
+        )*
+    };
+}
+
+impl_redact_debug_as_is!(RiFragmentStr, RiHostStr, RiPathStr);
+
+/// A wrapper to print the redacted content as a quoted string by `Debug`.
+#[cfg(feature = "redact-debug")]
+pub(crate) struct DebugRedacted<'a, T: ?Sized>(pub(crate) &'a T);
+
+#[cfg(feature = "redact-debug")]
+impl<T: ?Sized + RedactDebug> fmt::Debug for DebugRedacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Writer to escape the content in the same way as `Debug for str`.
+        struct Escape<'a, 'b>(&'a mut fmt::Formatter<'b>);
+        impl fmt::Write for Escape<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for c in s.chars() {
+                    if c == '\'' {
+                        self.0.write_char(c)?;
+                    } else {
+                        write!(self.0, "{}", c.escape_debug())?;
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        f.write_char('"')?;
+        self.0.write_redacted(&mut Escape(f))?;
+        f.write_char('"')
+    }
+}
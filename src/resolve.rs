@@ -76,17 +76,138 @@
 //! # }
 //! # Ok::<_, iri_string::validate::Error>(())
 //! ```
+//!
+//! # Strict and legacy resolution
+//!
+//! [RFC 3986 section 5.2.2] allows "non-strict" parsers to ignore a scheme in
+//! the reference if it is identical to the base IRI's scheme, for backward
+//! compatibility. For example, the reference `http:g` is resolved against the
+//! base `http://a/b/c/d;p?q` to `http:g` in the strict mode, but to
+//! `http://a/b/c/g` in the legacy mode (see [RFC 3986 section 5.4.2]).
+//!
+//! The mode can be selected by [`FixedBaseResolver::with_mode`]. The strict
+//! mode is the default.
+//!
+//! Other abnormal examples in RFC 3986 section 5.4.2 (such as `../../../g`,
+//! `/./g`, `g.`, and `g;x=1/../y`) are resolved to the results listed in the
+//! RFC in both modes. The modes differ in how excess `..` segments (such as
+//! in `../../../g`) are handled by [`FixedBaseResolver::try_resolve`]: the
+//! strict mode reports them as [`ResolveError::PathAboveRoot`], and the
+//! legacy mode silently discards them as the RFC examples do.
+//! [`FixedBaseResolver::resolve`] discards them in both modes.
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use iri_string::format::ToDedicatedString;
+//! use iri_string::resolve::{FixedBaseResolver, ResolutionMode};
+//! use iri_string::types::{IriAbsoluteStr, IriReferenceStr};
+//!
+//! let base = IriAbsoluteStr::new("http://a/b/c/d;p?q")?;
+//! let reference = IriReferenceStr::new("http:g")?;
+//!
+//! let strict = FixedBaseResolver::new(base);
+//! assert_eq!(strict.resolve(reference).to_dedicated_string(), "http:g");
+//!
+//! let legacy = FixedBaseResolver::new(base).with_mode(ResolutionMode::Legacy);
+//! assert_eq!(legacy.resolve(reference).to_dedicated_string(), "http://a/b/c/g");
+//! # }
+//! # Ok::<_, iri_string::validate::Error>(())
+//! ```
+//!
+//! [RFC 3986 section 5.2.2]: https://datatracker.ietf.org/doc/html/rfc3986#section-5.2.2
+//! [RFC 3986 section 5.4.2]: https://datatracker.ietf.org/doc/html/rfc3986#section-5.4.2
+
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::num::NonZeroUsize;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 
 use crate::components::RiReferenceComponents;
+#[cfg(feature = "alloc")]
+use crate::format::ToDedicatedString;
+#[cfg(feature = "alloc")]
+use crate::lenient::InputCleanup;
 use crate::normalize::{NormalizationInput, Normalized};
 use crate::spec::Spec;
 use crate::types::{RiAbsoluteStr, RiReferenceStr, RiStr};
+#[cfg(feature = "alloc")]
+use crate::types::{RiAbsoluteString, RiString};
+
+/// Resolution mode.
+///
+/// See [the module documentation][crate::resolve#strict-and-legacy-resolution] for detail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ResolutionMode {
+    /// Strict resolution as defined in RFC 3986 section 5.2.2.
+    Strict,
+    /// Legacy non-strict resolution.
+    ///
+    /// The scheme of the reference is ignored if it is identical to the
+    /// scheme of the base (ASCII case-insensitively), and excess `..`
+    /// segments are silently discarded even by
+    /// [`FixedBaseResolver::try_resolve`], i.e. [`ResolveError::PathAboveRoot`]
+    /// is never reported. Other steps of the resolution are the same as
+    /// [`Strict`][`Self::Strict`].
+    Legacy,
+}
+
+impl Default for ResolutionMode {
+    #[inline]
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+/// Resolution error.
+///
+/// This is returned by the fallible resolution API, such as
+/// [`FixedBaseResolver::try_new`] and [`FixedBaseResolver::try_resolve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ResolveError {
+    /// The base is not an absolute IRI, i.e. it lacks a scheme or has a fragment.
+    BaseNotAbsolute,
+    /// The reference has more `..` segments than the base path can consume.
+    ///
+    /// This is reported only in [the strict mode][`ResolutionMode::Strict`].
+    /// In [the legacy mode][`ResolutionMode::Legacy`], the excess `..`
+    /// segments are silently discarded as RFC 3986 resolution does.
+    PathAboveRoot,
+    /// The resolution result cannot be represented by the RFC 3986 algorithm.
+    ///
+    /// See [the module documentation][`crate::resolve`] for detail.
+    Unrepresentable,
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::BaseNotAbsolute => "base IRI is not absolute",
+            Self::PathAboveRoot => "reference path goes above the root of the base",
+            Self::Unrepresentable => "resolution result is not representable by RFC 3986",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for ResolveError {}
 
 /// A resolver against the fixed base.
 #[derive(Debug, Clone, Copy)]
 pub struct FixedBaseResolver<'a, S: Spec> {
     /// Components of the base IRI.
     base_components: RiReferenceComponents<'a, S>,
+    /// Resolution mode.
+    mode: ResolutionMode,
 }
 
 impl<'a, S: Spec> FixedBaseResolver<'a, S> {
@@ -117,9 +238,80 @@ impl<'a, S: Spec> FixedBaseResolver<'a, S> {
     pub fn new(base: &'a RiAbsoluteStr<S>) -> Self {
         Self {
             base_components: RiReferenceComponents::from(base.as_ref()),
+            mode: ResolutionMode::Strict,
         }
     }
 
+    /// Creates a new resolver with the given base, checking it is absolute.
+    ///
+    /// This is useful when the base comes from an IRI reference, such as a
+    /// `base` attribute of a document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::resolve::{FixedBaseResolver, ResolveError};
+    /// use iri_string::types::IriReferenceStr;
+    ///
+    /// let base = IriReferenceStr::new("http://example.com/base/")?;
+    /// assert!(FixedBaseResolver::try_new(base).is_ok());
+    ///
+    /// let relative = IriReferenceStr::new("/base/")?;
+    /// assert_eq!(
+    ///     FixedBaseResolver::try_new(relative).err(),
+    ///     Some(ResolveError::BaseNotAbsolute)
+    /// );
+    ///
+    /// let with_fragment = IriReferenceStr::new("http://example.com/#frag")?;
+    /// assert_eq!(
+    ///     FixedBaseResolver::try_new(with_fragment).err(),
+    ///     Some(ResolveError::BaseNotAbsolute)
+    /// );
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub fn try_new(base: &'a RiReferenceStr<S>) -> Result<Self, ResolveError> {
+        match base.to_iri() {
+            Ok(iri) if iri.fragment().is_none() => Ok(Self::new(iri.to_absolute())),
+            _ => Err(ResolveError::BaseNotAbsolute),
+        }
+    }
+
+    /// Returns the resolver with the given resolution mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// # #[cfg(feature = "alloc")] {
+    /// use iri_string::format::ToDedicatedString;
+    /// use iri_string::resolve::{FixedBaseResolver, ResolutionMode};
+    /// use iri_string::types::{IriAbsoluteStr, IriReferenceStr};
+    ///
+    /// let base = IriAbsoluteStr::new("http://example.com/base/")?;
+    /// let resolver = FixedBaseResolver::new(base).with_mode(ResolutionMode::Legacy);
+    /// assert_eq!(resolver.mode(), ResolutionMode::Legacy);
+    ///
+    /// let reference = IriReferenceStr::new("HTTP:there")?;
+    /// let resolved = resolver.resolve(reference);
+    ///
+    /// assert_eq!(resolved.to_dedicated_string(), "http://example.com/base/there");
+    /// # }
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_mode(self, mode: ResolutionMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    /// Returns the resolution mode.
+    #[inline]
+    #[must_use]
+    pub fn mode(&self) -> ResolutionMode {
+        self.mode
+    }
+
     /// Returns the base.
     ///
     /// # Examples
@@ -208,7 +400,401 @@ impl<'a, S: Spec> FixedBaseResolver<'a, S> {
     #[inline]
     #[must_use]
     pub fn resolve(&self, reference: &'a RiReferenceStr<S>) -> Normalized<'a, RiStr<S>> {
-        let input = NormalizationInput::with_resolution_params(&self.base_components, reference);
+        let input =
+            NormalizationInput::with_resolution_params(&self.base_components, reference, self.mode);
         Normalized::from_input(input)
     }
+
+    /// Resolves the given reference against the fixed base, failing on abnormal cases.
+    ///
+    /// Unlike [`resolve`][`Self::resolve`], this does not silently fix up
+    /// the result.
+    ///
+    /// # Failures
+    ///
+    /// * [`ResolveError::PathAboveRoot`] if the reference has excess `..`
+    ///   segments, only in the strict mode. The legacy mode discards them.
+    /// * [`ResolveError::Unrepresentable`] if the result cannot be represented
+    ///   without the WHATWG URL Standard serialization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// # #[cfg(feature = "alloc")] {
+    /// use iri_string::format::ToDedicatedString;
+    /// use iri_string::resolve::{FixedBaseResolver, ResolutionMode, ResolveError};
+    /// use iri_string::types::{IriAbsoluteStr, IriReferenceStr};
+    ///
+    /// let base = IriAbsoluteStr::new("http://example.com/a/b")?;
+    /// let resolver = FixedBaseResolver::new(base);
+    ///
+    /// let reference = IriReferenceStr::new("../c")?;
+    /// let resolved = resolver.try_resolve(reference).expect("no excess `..`");
+    /// assert_eq!(resolved.to_dedicated_string(), "http://example.com/c");
+    ///
+    /// let too_deep = IriReferenceStr::new("../../c")?;
+    /// assert_eq!(
+    ///     resolver.try_resolve(too_deep).err(),
+    ///     Some(ResolveError::PathAboveRoot)
+    /// );
+    ///
+    /// // Excess `..` segments are discarded in the legacy mode.
+    /// let legacy = resolver.with_mode(ResolutionMode::Legacy);
+    /// let resolved = legacy.try_resolve(too_deep).expect("legacy mode");
+    /// assert_eq!(resolved.to_dedicated_string(), "http://example.com/c");
+    /// # }
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub fn try_resolve(
+        &self,
+        reference: &'a RiReferenceStr<S>,
+    ) -> Result<Normalized<'a, RiStr<S>>, ResolveError> {
+        let input =
+            NormalizationInput::with_resolution_params(&self.base_components, reference, self.mode);
+        if (self.mode == ResolutionMode::Strict) && input.path_climbs_above_root() {
+            return Err(ResolveError::PathAboveRoot);
+        }
+        input
+            .ensure_rfc3986_normalizable()
+            .map_err(|_| ResolveError::Unrepresentable)?;
+        Ok(Normalized::from_input(input))
+    }
+}
+
+/// Computes the effective base IRI of a document, as HTML does for `<base href>`.
+///
+/// `document` is the IRI of the document (i.e. the fallback base IRI), and
+/// `base_href` is the `href` attribute value of the first `<base>` element
+/// with the attribute, if any.
+///
+/// The `href` value is trimmed and cleaned by [`InputCleanup`], parsed as an
+/// IRI reference, and resolved against the document IRI. The document IRI
+/// is used instead if the value is absent or invalid, or the resolved IRI has
+/// `data` or `javascript` scheme, as [HTML Standard] specifies. The fragment
+/// is always removed since base IRIs cannot have fragments.
+///
+/// Discovering the `<base>` element is the caller's responsibility.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::resolve::document_base;
+/// use iri_string::types::IriStr;
+///
+/// let document = IriStr::new("https://example.com/dir/page.html#top")?;
+///
+/// assert_eq!(document_base(document, None), "https://example.com/dir/page.html");
+/// assert_eq!(
+///     document_base(document, Some(" ../static/\n")),
+///     "https://example.com/static/"
+/// );
+/// assert_eq!(
+///     document_base(document, Some("https://cdn.example.net/#x")),
+///     "https://cdn.example.net/"
+/// );
+///
+/// // Invalid or dangerous values are ignored.
+/// assert_eq!(
+///     document_base(document, Some("http://[invalid")),
+///     "https://example.com/dir/page.html"
+/// );
+/// assert_eq!(
+///     document_base(document, Some("javascript:alert(1)")),
+///     "https://example.com/dir/page.html"
+/// );
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// [HTML Standard]: https://html.spec.whatwg.org/multipage/semantics.html#set-the-frozen-base-url
+/// [`InputCleanup`]: `crate::lenient::InputCleanup`
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[must_use]
+pub fn document_base<S: Spec>(document: &RiStr<S>, base_href: Option<&str>) -> RiAbsoluteString<S> {
+    let fallback = document.to_absolute();
+    let href = match base_href {
+        Some(v) => InputCleanup::new().apply(v),
+        None => return fallback.to_owned(),
+    };
+    let reference = match RiReferenceStr::<S>::new(&href) {
+        Ok(v) => v,
+        Err(_) => return fallback.to_owned(),
+    };
+    let resolved = reference.resolve_against(fallback).to_dedicated_string();
+    let scheme = resolved.scheme_str();
+    if scheme.eq_ignore_ascii_case("data") || scheme.eq_ignore_ascii_case("javascript") {
+        return fallback.to_owned();
+    }
+    resolved.into_absolute()
+}
+
+/// A resolver owning the base, with an optional cache of the resolution results.
+///
+/// Unlike [`FixedBaseResolver`], this owns the base IRI, so it can be stored
+/// in long-lived document processors. The base is decomposed only once when
+/// the resolver is created.
+///
+/// If the cache is enabled by [`with_cache`][`Self::with_cache`],
+/// [`resolve_cached`][`Self::resolve_cached`] memoizes the resolution results
+/// and evicts the least recently used ones when the cache is full.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::resolve::Resolver;
+/// use iri_string::types::{IriAbsoluteString, IriReferenceStr};
+///
+/// let base = IriAbsoluteString::try_from("http://example.com/base/")?;
+/// let mut resolver = Resolver::new(base).with_cache(2);
+///
+/// let reference = IriReferenceStr::new("../there")?;
+/// assert_eq!(resolver.resolve_cached(reference), "http://example.com/there");
+/// assert_eq!(resolver.cache_len(), 1);
+///
+/// // The second resolution of the same reference hits the cache.
+/// assert_eq!(resolver.resolve_cached(reference), "http://example.com/there");
+/// assert_eq!(resolver.cache_len(), 1);
+/// # Ok::<_, Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone)]
+pub struct Resolver<S: Spec> {
+    /// Base IRI.
+    base: RiAbsoluteString<S>,
+    /// Scheme end of the base.
+    scheme_end: Option<NonZeroUsize>,
+    /// Authority end of the base.
+    authority_end: Option<NonZeroUsize>,
+    /// Query start of the base.
+    query_start: Option<NonZeroUsize>,
+    /// Resolution mode.
+    mode: ResolutionMode,
+    /// Cache of the resolution results.
+    cache: Option<LruCache<S>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Spec> Resolver<S> {
+    /// Creates a new resolver with the given base, without the cache.
+    #[must_use]
+    pub fn new(base: RiAbsoluteString<S>) -> Self {
+        let components = RiReferenceComponents::from(base.as_ref());
+        debug_assert!(
+            components.fragment_start.is_none(),
+            "[validity] absolute IRI has no fragment"
+        );
+        let (scheme_end, authority_end, query_start) = (
+            components.scheme_end,
+            components.authority_end,
+            components.query_start,
+        );
+        Self {
+            base,
+            scheme_end,
+            authority_end,
+            query_start,
+            mode: ResolutionMode::Strict,
+            cache: None,
+        }
+    }
+
+    /// Returns the resolver with the given resolution mode.
+    ///
+    /// The cache is cleared since the results depend on the mode.
+    #[must_use]
+    pub fn with_mode(mut self, mode: ResolutionMode) -> Self {
+        if self.mode != mode {
+            self.mode = mode;
+            self.clear_cache();
+        }
+        self
+    }
+
+    /// Returns the resolver with the cache of the given capacity.
+    ///
+    /// The capacity is the maximum number of the results to be cached.
+    /// If the capacity is zero, the cache is disabled.
+    #[must_use]
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = if capacity == 0 {
+            None
+        } else {
+            Some(LruCache::new(capacity))
+        };
+        self
+    }
+
+    /// Returns the resolution mode.
+    #[inline]
+    #[must_use]
+    pub fn mode(&self) -> ResolutionMode {
+        self.mode
+    }
+
+    /// Returns the base.
+    #[inline]
+    #[must_use]
+    pub fn base(&self) -> &RiAbsoluteStr<S> {
+        &self.base
+    }
+
+    /// Returns the [`FixedBaseResolver`] borrowing the base.
+    #[must_use]
+    pub fn as_fixed_base_resolver(&self) -> FixedBaseResolver<'_, S> {
+        FixedBaseResolver {
+            base_components: RiReferenceComponents {
+                iri: self.base.as_ref(),
+                scheme_end: self.scheme_end,
+                authority_end: self.authority_end,
+                query_start: self.query_start,
+                fragment_start: None,
+            },
+            mode: self.mode,
+        }
+    }
+
+    /// Resolves the given reference against the base, without the cache.
+    ///
+    /// See [`FixedBaseResolver::resolve`].
+    #[inline]
+    #[must_use]
+    pub fn resolve<'a>(&'a self, reference: &'a RiReferenceStr<S>) -> Normalized<'a, RiStr<S>> {
+        self.as_fixed_base_resolver().resolve(reference)
+    }
+
+    /// Resolves the given reference against the base, failing on abnormal cases.
+    ///
+    /// See [`FixedBaseResolver::try_resolve`].
+    #[inline]
+    pub fn try_resolve<'a>(
+        &'a self,
+        reference: &'a RiReferenceStr<S>,
+    ) -> Result<Normalized<'a, RiStr<S>>, ResolveError> {
+        self.as_fixed_base_resolver().try_resolve(reference)
+    }
+
+    /// Resolves the given reference against the base into a string, using the cache.
+    ///
+    /// The result is the same as `resolve(reference).to_dedicated_string()`.
+    /// If the cache is disabled, this always resolves the reference.
+    #[must_use]
+    pub fn resolve_cached(&mut self, reference: &RiReferenceStr<S>) -> RiString<S> {
+        if let Some(resolved) = self
+            .cache
+            .as_mut()
+            .and_then(|cache| cache.get(reference.as_str()))
+        {
+            return resolved.clone();
+        }
+        let resolved = self.resolve(reference).to_dedicated_string();
+        if let Some(cache) = &mut self.cache {
+            cache.insert(reference.as_str().into(), resolved.clone());
+        }
+        resolved
+    }
+
+    /// Returns the number of the cached results.
+    #[inline]
+    #[must_use]
+    pub fn cache_len(&self) -> usize {
+        self.cache.as_ref().map_or(0, |cache| cache.entries.len())
+    }
+
+    /// Removes all the cached results.
+    #[inline]
+    pub fn clear_cache(&mut self) {
+        if let Some(cache) = &mut self.cache {
+            cache.clear();
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Spec> From<RiAbsoluteString<S>> for Resolver<S> {
+    #[inline]
+    fn from(base: RiAbsoluteString<S>) -> Self {
+        Self::new(base)
+    }
+}
+
+/// Least recently used cache of the resolution results.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+struct LruCache<S: Spec> {
+    /// Maximum number of the entries.
+    capacity: usize,
+    /// Cached results and their last use time, keyed by the references.
+    entries: BTreeMap<String, (RiString<S>, u64)>,
+    /// References keyed by their last use time.
+    recency: BTreeMap<u64, String>,
+    /// Counter for the last use time.
+    tick: u64,
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Spec> LruCache<S> {
+    /// Creates a new empty cache.
+    #[inline]
+    #[must_use]
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: BTreeMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Returns the next time tick.
+    #[inline]
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Returns the cached result and marks it as recently used.
+    fn get(&mut self, reference: &str) -> Option<&RiString<S>> {
+        let tick = self.next_tick();
+        let (resolved, last_used) = self.entries.get_mut(reference)?;
+        let key = self
+            .recency
+            .remove(last_used)
+            .expect("[consistency] every entry must have its recency");
+        *last_used = tick;
+        self.recency.insert(tick, key);
+        Some(resolved)
+    }
+
+    /// Inserts the result, evicting the least recently used one if necessary.
+    fn insert(&mut self, reference: String, resolved: RiString<S>) {
+        let tick = self.next_tick();
+        if let Some((_, last_used)) = self.entries.get(&reference) {
+            self.recency.remove(last_used);
+        } else if self.entries.len() >= self.capacity {
+            let oldest = self
+                .recency
+                .keys()
+                .next()
+                .copied()
+                .expect("[consistency] full cache must have entries");
+            let evicted = self
+                .recency
+                .remove(&oldest)
+                .expect("[consistency] the oldest key must exist");
+            self.entries.remove(&evicted);
+        }
+        self.recency.insert(tick, reference.clone());
+        self.entries.insert(reference, (resolved, tick));
+    }
+
+    /// Removes all the entries.
+    #[inline]
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
 }
@@ -0,0 +1,459 @@
+//! Registry of scheme-specific parsers and normalization rules.
+//!
+//! Applications supporting many schemes (such as `data`, `mailto`, `file`,
+//! `urn`, and `magnet`) can register a parser for each scheme to a
+//! [`SchemeRegistry`], and parse IRIs generically by looking up the parser by
+//! the scheme of the IRI.
+//!
+//! Parsers for `data`, `mailto`, `file`, `urn`, and `magnet` are provided by
+//! this module, and can be registered by [`SchemeRegistry::with_builtin_parsers`].
+//! The parsed values are [`DataIri`], [`MailtoIri`], [`FileIri`], [`Urn`], and
+//! [`MagnetLink`], respectively.
+//!
+//! Scheme-specific normalization rules (such as default ports) can also be
+//! registered as [`SchemeNormalization`]; see [`SchemeRegistry::normalize`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::validate::Error;
+//! use iri_string::scheme::{self, SchemeRegistry, SchemeSpecific};
+//! use iri_string::spec::IriSpec;
+//! use iri_string::types::IriStr;
+//!
+//! /// Parsed `urn:` IRI.
+//! #[derive(Debug)]
+//! struct Urn {
+//!     nid: String,
+//!     nss: String,
+//! }
+//!
+//! let mut registry = SchemeRegistry::<IriSpec>::new();
+//! registry.register("urn", |iri: &IriStr| {
+//!     let (nid, nss) = iri.path_str().split_once(':').ok_or_else(scheme::Error::new)?;
+//!     let parsed: Box<dyn SchemeSpecific> = Box::new(Urn {
+//!         nid: nid.to_owned(),
+//!         nss: nss.to_owned(),
+//!     });
+//!     Ok(parsed)
+//! });
+//!
+//! let iri = IriStr::new("URN:isbn:0451450523")?;
+//! let parsed = iri
+//!     .parse_scheme_specific(&registry)
+//!     .expect("`urn` scheme is registered")
+//!     .expect("valid URN");
+//! let urn = parsed.downcast_ref::<Urn>().expect("parsed by the `urn` parser");
+//! assert_eq!(urn.nid, "isbn");
+//! assert_eq!(urn.nss, "0451450523");
+//!
+//! let unknown = IriStr::new("unknown:foo")?;
+//! assert!(unknown.parse_scheme_specific(&registry).is_none());
+//! # Ok::<_, Error>(())
+//! ```
+//!
+//! Using the built-in parsers:
+//!
+//! ```
+//! # use iri_string::validate::Error;
+//! use iri_string::scheme::{DataIri, SchemeRegistry};
+//! use iri_string::spec::IriSpec;
+//! use iri_string::types::IriStr;
+//!
+//! let registry = SchemeRegistry::<IriSpec>::new().with_builtin_parsers();
+//!
+//! let iri = IriStr::new("data:text/plain;base64,SGVsbG8=")?;
+//! let parsed = registry
+//!     .parse(iri)
+//!     .expect("`data` scheme is registered")
+//!     .expect("valid `data` IRI");
+//! let data = parsed.downcast_ref::<DataIri>().expect("parsed by the `data` parser");
+//! assert_eq!(data.media_type(), "text/plain");
+//! assert!(data.is_base64());
+//! assert_eq!(data.data_str(), "SGVsbG8=");
+//! # Ok::<_, Error>(())
+//! ```
+
+mod builtin;
+
+use core::any::Any;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use crate::format::ToDedicatedString;
+use crate::spec::Spec;
+use crate::types::{RiStr, RiString};
+use crate::validate;
+
+pub use self::builtin::{DataIri, FileIri, MagnetLink, MailtoIri, Urn};
+
+/// Scheme-specific parsing error.
+///
+/// Unlike other error types of this crate, this is intended to be created by
+/// users, i.e. by [`SchemeHandler`] implementations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Error(())
+    }
+}
+
+impl Default for Error {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Invalid scheme-specific syntax")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+impl From<validate::Error> for Error {
+    #[inline]
+    fn from(_: validate::Error) -> Self {
+        Self::new()
+    }
+}
+
+/// Result of scheme-specific parsing.
+///
+/// This is implemented for all `'static` types implementing `Debug`, and the
+/// concrete value can be retrieved by [`downcast_ref`][`dyn SchemeSpecific::downcast_ref`].
+pub trait SchemeSpecific: Any + fmt::Debug {
+    /// Returns `self` as `&dyn Any`.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + fmt::Debug> SchemeSpecific for T {
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl dyn SchemeSpecific {
+    /// Returns the reference to the value if it is of type `T`.
+    #[inline]
+    #[must_use]
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Returns true if the value is of type `T`.
+    #[inline]
+    #[must_use]
+    pub fn is<T: Any>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+}
+
+/// Scheme-specific parser.
+///
+/// This is implemented for closures and functions with a compatible
+/// signature.
+pub trait SchemeHandler<S: Spec>: Send + Sync {
+    /// Parses the IRI.
+    ///
+    /// The scheme of the given IRI is the one the handler is registered for
+    /// (ASCII case-insensitively).
+    fn parse(&self, iri: &RiStr<S>) -> Result<Box<dyn SchemeSpecific>, Error>;
+}
+
+impl<S, F> SchemeHandler<S> for F
+where
+    S: Spec,
+    F: Fn(&RiStr<S>) -> Result<Box<dyn SchemeSpecific>, Error> + Send + Sync,
+{
+    #[inline]
+    fn parse(&self, iri: &RiStr<S>) -> Result<Box<dyn SchemeSpecific>, Error> {
+        self(iri)
+    }
+}
+
+/// Registry of scheme-specific parsers.
+///
+/// Schemes are compared ASCII case-insensitively.
+///
+/// See [the module documentation][`crate::scheme`] for usage.
+pub struct SchemeRegistry<S: Spec> {
+    /// Handlers, keyed by the ASCII-lowercased scheme.
+    handlers: BTreeMap<String, Box<dyn SchemeHandler<S>>>,
+    /// Normalization rules, keyed by the ASCII-lowercased scheme.
+    normalizations: BTreeMap<String, SchemeNormalization>,
+}
+
+impl<S: Spec> SchemeRegistry<S> {
+    /// Creates an empty registry.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+            normalizations: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the handler for the scheme.
+    ///
+    /// Returns the previously registered handler for the scheme, if exists.
+    pub fn register<H>(&mut self, scheme: &str, handler: H) -> Option<Box<dyn SchemeHandler<S>>>
+    where
+        H: SchemeHandler<S> + 'static,
+    {
+        self.handlers
+            .insert(scheme.to_ascii_lowercase(), Box::new(handler))
+    }
+
+    /// Unregisters the handler for the scheme.
+    pub fn unregister(&mut self, scheme: &str) -> Option<Box<dyn SchemeHandler<S>>> {
+        self.handlers.remove(&scheme.to_ascii_lowercase())
+    }
+
+    /// Returns the handler for the scheme.
+    #[must_use]
+    pub fn get(&self, scheme: &str) -> Option<&dyn SchemeHandler<S>> {
+        get_by_scheme(&self.handlers, scheme).map(AsRef::as_ref)
+    }
+
+    /// Returns true if a handler is registered for the scheme.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, scheme: &str) -> bool {
+        self.get(scheme).is_some()
+    }
+
+    /// Returns an iterator of the registered schemes, in ASCII lowercase.
+    pub fn schemes(&self) -> impl Iterator<Item = &str> + '_ {
+        self.handlers.keys().map(String::as_str)
+    }
+
+    /// Parses the IRI with the handler for its scheme.
+    ///
+    /// Returns `None` if no handlers are registered for the scheme.
+    #[must_use]
+    pub fn parse(&self, iri: &RiStr<S>) -> Option<Result<Box<dyn SchemeSpecific>, Error>> {
+        self.get(iri.scheme_str()).map(|handler| handler.parse(iri))
+    }
+
+    /// Returns the registry with the built-in parsers.
+    ///
+    /// The parsers for `data` ([`DataIri`]), `mailto` ([`MailtoIri`]), `file`
+    /// ([`FileIri`]), `urn` ([`Urn`]), and `magnet` ([`MagnetLink`]) are
+    /// registered.
+    #[must_use]
+    pub fn with_builtin_parsers(mut self) -> Self
+    where
+        S: 'static,
+    {
+        self.register("data", builtin::parse_data::<S>);
+        self.register("mailto", builtin::parse_mailto::<S>);
+        self.register("file", builtin::parse_file::<S>);
+        self.register("urn", builtin::parse_urn::<S>);
+        self.register("magnet", builtin::parse_magnet::<S>);
+        self
+    }
+
+    /// Returns the registry with the normalization rules for well-known schemes.
+    ///
+    /// The rules for `http`, `https`, `ws`, `wss`, `ftp`, and `gopher` are
+    /// registered. All of them have default ports, and treat an empty path
+    /// as `/`.
+    #[must_use]
+    pub fn with_well_known_normalizations(mut self) -> Self {
+        for &(scheme, port) in &[
+            ("http", 80),
+            ("https", 443),
+            ("ws", 80),
+            ("wss", 443),
+            ("ftp", 21),
+            ("gopher", 70),
+        ] {
+            self.register_normalization(
+                scheme,
+                SchemeNormalization::new()
+                    .default_port(Some(port))
+                    .empty_path_as_slash(true),
+            );
+        }
+        self
+    }
+
+    /// Registers the normalization rule for the scheme.
+    ///
+    /// Returns the previously registered rule for the scheme, if exists.
+    pub fn register_normalization(
+        &mut self,
+        scheme: &str,
+        normalization: SchemeNormalization,
+    ) -> Option<SchemeNormalization> {
+        self.normalizations
+            .insert(scheme.to_ascii_lowercase(), normalization)
+    }
+
+    /// Unregisters the normalization rule for the scheme.
+    pub fn unregister_normalization(&mut self, scheme: &str) -> Option<SchemeNormalization> {
+        self.normalizations.remove(&scheme.to_ascii_lowercase())
+    }
+
+    /// Returns the normalization rule for the scheme.
+    #[must_use]
+    pub fn normalization(&self, scheme: &str) -> Option<SchemeNormalization> {
+        get_by_scheme(&self.normalizations, scheme).copied()
+    }
+
+    /// Normalizes the IRI, applying the normalization rule for its scheme.
+    ///
+    /// The IRI is first normalized by [`RiStr::normalize`], and then the
+    /// normalization rule for the scheme (if registered) is applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::scheme::{SchemeNormalization, SchemeRegistry};
+    /// use iri_string::spec::IriSpec;
+    /// use iri_string::types::IriStr;
+    ///
+    /// let mut registry = SchemeRegistry::<IriSpec>::new().with_well_known_normalizations();
+    /// registry.register_normalization("rtsp", SchemeNormalization::new().default_port(Some(554)));
+    ///
+    /// let ws = IriStr::new("WS://Example.COM:80")?;
+    /// assert_eq!(registry.normalize(ws), "ws://example.com/");
+    /// let ftp = IriStr::new("ftp://example.com:2121/a/../b")?;
+    /// assert_eq!(registry.normalize(ftp), "ftp://example.com:2121/b");
+    /// let rtsp = IriStr::new("rtsp://example.com:554")?;
+    /// assert_eq!(registry.normalize(rtsp), "rtsp://example.com");
+    ///
+    /// // Schemes without rules are normalized by RFC 3986 rules only.
+    /// let other = IriStr::new("foo://example.com:80")?;
+    /// assert_eq!(registry.normalize(other), "foo://example.com:80");
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[must_use]
+    pub fn normalize(&self, iri: &RiStr<S>) -> RiString<S> {
+        let normalized = iri.normalize().to_dedicated_string();
+        match self.normalization(normalized.scheme_str()) {
+            Some(normalization) => normalization.apply(normalized),
+            None => normalized,
+        }
+    }
+}
+
+/// Returns the value for the scheme, comparing schemes ASCII case-insensitively.
+fn get_by_scheme<'a, T>(map: &'a BTreeMap<String, T>, scheme: &str) -> Option<&'a T> {
+    if scheme.bytes().any(|b| b.is_ascii_uppercase()) {
+        map.get(&scheme.to_ascii_lowercase())
+    } else {
+        map.get(scheme)
+    }
+}
+
+/// Scheme-specific normalization rule.
+///
+/// Rules are applied to IRIs with authorities, and the following rules are
+/// available:
+///
+/// * Default port: the port is removed if it is the default port of the
+///   scheme. An empty port (such as `http://example.com:/`) is also removed.
+/// * Empty path as slash: an empty path is replaced with `/`.
+///
+/// By default, no rules are enabled.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct SchemeNormalization {
+    /// Default port.
+    default_port: Option<u16>,
+    /// Whether to treat an empty path as `/`.
+    empty_path_as_slash: bool,
+}
+
+impl SchemeNormalization {
+    /// Creates a new rule with nothing enabled.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the default port of the scheme.
+    #[inline]
+    pub fn default_port(mut self, port: Option<u16>) -> Self {
+        self.default_port = port;
+        self
+    }
+
+    /// Sets whether to replace an empty path with `/`.
+    #[inline]
+    pub fn empty_path_as_slash(mut self, value: bool) -> Self {
+        self.empty_path_as_slash = value;
+        self
+    }
+
+    /// Applies the rule to the (already normalized) IRI.
+    fn apply<S: Spec>(&self, iri: RiString<S>) -> RiString<S> {
+        let authority = match iri.authority_components() {
+            Some(v) => v,
+            None => return iri,
+        };
+        let remove_port = match authority.port() {
+            None => false,
+            Some("") => true,
+            Some(port) => self.default_port.map_or(false, |default| {
+                // Leading zeros are removed by neither RFC 3986 normalization
+                // nor this rule, since they can be significant.
+                port.parse::<u16>().ok() == Some(default) && !port.starts_with('0')
+            }),
+        };
+        let add_slash = self.empty_path_as_slash && iri.path_str().is_empty();
+        if !remove_port && !add_slash {
+            return iri;
+        }
+
+        let mut components = iri.into_components();
+        if remove_port {
+            components
+                .set_port(None)
+                .expect("[validity] removing the port never fails");
+        }
+        if add_slash {
+            components
+                .set_path("/")
+                .expect("[validity] `/` is a valid path");
+        }
+        RiString::from_components(&components)
+            .expect("[validity] only the port and the empty path are modified")
+    }
+}
+
+impl<S: Spec> Default for SchemeRegistry<S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Spec> fmt::Debug for SchemeRegistry<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchemeRegistry")
+            .field("schemes", &self.handlers.keys())
+            .field("normalizations", &self.normalizations)
+            .finish()
+    }
+}
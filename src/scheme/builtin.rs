@@ -0,0 +1,293 @@
+//! Built-in scheme-specific parsers.
+
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::mailto::{mailto_to_addresses, AddressEncoding};
+use crate::spec::Spec;
+use crate::types::RiStr;
+
+use super::{Error, SchemeSpecific};
+
+/// Default media type of `data:` IRIs, as defined in RFC 2397.
+const DEFAULT_DATA_MEDIA_TYPE: &str = "text/plain;charset=US-ASCII";
+
+/// Parsed `data:` IRI ([RFC 2397]).
+///
+/// The media type and the data are kept as written in the IRI, i.e. they are
+/// not percent-decoded nor base64-decoded.
+///
+/// [RFC 2397]: https://datatracker.ietf.org/doc/html/rfc2397
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DataIri {
+    /// Media type (possibly with parameters), without `;base64`.
+    media_type: String,
+    /// Whether the data is base64-encoded.
+    is_base64: bool,
+    /// Data.
+    data: String,
+}
+
+impl DataIri {
+    /// Returns the media type with parameters.
+    ///
+    /// If the media type is omitted, `text/plain;charset=US-ASCII` is returned
+    /// as RFC 2397 specifies.
+    #[inline]
+    #[must_use]
+    pub fn media_type(&self) -> &str {
+        if self.media_type.is_empty() {
+            DEFAULT_DATA_MEDIA_TYPE
+        } else {
+            &self.media_type
+        }
+    }
+
+    /// Returns true if the data is base64-encoded.
+    #[inline]
+    #[must_use]
+    pub fn is_base64(&self) -> bool {
+        self.is_base64
+    }
+
+    /// Returns the data as written in the IRI.
+    #[inline]
+    #[must_use]
+    pub fn data_str(&self) -> &str {
+        &self.data
+    }
+}
+
+/// Parsed `mailto:` IRI ([RFC 6068]).
+///
+/// [RFC 6068]: https://datatracker.ietf.org/doc/html/rfc6068
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MailtoIri {
+    /// Addresses in the path.
+    addresses: Vec<String>,
+}
+
+impl MailtoIri {
+    /// Returns the email addresses in the path.
+    ///
+    /// Addresses are percent-decoded, and the domains are kept in Unicode.
+    /// See [`mailto::mailto_to_addresses`][`crate::mailto::mailto_to_addresses`].
+    #[inline]
+    #[must_use]
+    pub fn addresses(&self) -> &[String] {
+        &self.addresses
+    }
+}
+
+/// Parsed `file:` IRI ([RFC 8089]).
+///
+/// The host and the path are kept as written in the IRI.
+///
+/// [RFC 8089]: https://datatracker.ietf.org/doc/html/rfc8089
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileIri {
+    /// Host, if not empty.
+    host: Option<String>,
+    /// Absolute path.
+    path: String,
+}
+
+impl FileIri {
+    /// Returns the host.
+    ///
+    /// Returns `None` if the IRI has no authority or the host is empty.
+    #[inline]
+    #[must_use]
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Returns the absolute path.
+    #[inline]
+    #[must_use]
+    pub fn path_str(&self) -> &str {
+        &self.path
+    }
+}
+
+/// Parsed `urn:` IRI ([RFC 8141]).
+///
+/// [RFC 8141]: https://datatracker.ietf.org/doc/html/rfc8141
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Urn {
+    /// Namespace identifier.
+    nid: String,
+    /// Namespace-specific string.
+    nss: String,
+}
+
+impl Urn {
+    /// Returns the namespace identifier as written in the IRI.
+    #[inline]
+    #[must_use]
+    pub fn nid(&self) -> &str {
+        &self.nid
+    }
+
+    /// Returns the namespace-specific string as written in the IRI.
+    #[inline]
+    #[must_use]
+    pub fn nss(&self) -> &str {
+        &self.nss
+    }
+}
+
+/// Parsed `magnet:` link.
+///
+/// Only the exact topics (`xt`), the display name (`dn`), and the trackers
+/// (`tr`) are extracted. Values are kept as written in the IRI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MagnetLink {
+    /// Exact topics.
+    exact_topics: Vec<String>,
+    /// Display name.
+    display_name: Option<String>,
+    /// Trackers.
+    trackers: Vec<String>,
+}
+
+impl MagnetLink {
+    /// Returns the exact topics (`xt` parameters).
+    ///
+    /// This always has at least one element.
+    #[inline]
+    #[must_use]
+    pub fn exact_topics(&self) -> &[String] {
+        &self.exact_topics
+    }
+
+    /// Returns the display name (the first `dn` parameter).
+    #[inline]
+    #[must_use]
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    /// Returns the trackers (`tr` parameters).
+    #[inline]
+    #[must_use]
+    pub fn trackers(&self) -> &[String] {
+        &self.trackers
+    }
+}
+
+/// Parses the `data:` IRI.
+pub(super) fn parse_data<S: Spec>(iri: &RiStr<S>) -> Result<Box<dyn SchemeSpecific>, Error> {
+    // The query part is a part of the data.
+    let iri = iri.as_str();
+    let rest = iri.split_once(':').ok_or_else(Error::new)?.1;
+    let rest = rest.split_once('#').map_or(rest, |(rest, _)| rest);
+    let (header, data) = rest.split_once(',').ok_or_else(Error::new)?;
+    let base64_start = header.len().checked_sub(";base64".len());
+    let (media_type, is_base64) = match base64_start {
+        Some(pos)
+            if header.is_char_boundary(pos) && header[pos..].eq_ignore_ascii_case(";base64") =>
+        {
+            (&header[..pos], true)
+        }
+        _ => (header, false),
+    };
+    // The media type is `type/subtype` with optional parameters, or omitted
+    // (possibly with parameters).
+    let essence = media_type.split(';').next().unwrap_or_default();
+    if !essence.is_empty() {
+        match essence.split_once('/') {
+            Some((ty, subty)) if !ty.is_empty() && !subty.is_empty() => {}
+            _ => return Err(Error::new()),
+        }
+    }
+    Ok(Box::new(DataIri {
+        media_type: media_type.to_owned(),
+        is_base64,
+        data: data.to_owned(),
+    }))
+}
+
+/// Parses the `mailto:` IRI.
+pub(super) fn parse_mailto<S: Spec>(iri: &RiStr<S>) -> Result<Box<dyn SchemeSpecific>, Error> {
+    let addresses =
+        mailto_to_addresses(iri, AddressEncoding::Smtputf8).map_err(|_| Error::new())?;
+    Ok(Box::new(MailtoIri { addresses }))
+}
+
+/// Parses the `file:` IRI.
+pub(super) fn parse_file<S: Spec>(iri: &RiStr<S>) -> Result<Box<dyn SchemeSpecific>, Error> {
+    let path = iri.path_str();
+    if !path.starts_with('/') {
+        return Err(Error::new());
+    }
+    let host = match iri.authority_components() {
+        Some(authority) => {
+            if authority.userinfo().is_some() || authority.port().is_some() {
+                return Err(Error::new());
+            }
+            Some(authority.host()).filter(|host| !host.is_empty())
+        }
+        None => None,
+    };
+    Ok(Box::new(FileIri {
+        host: host.map(ToOwned::to_owned),
+        path: path.to_owned(),
+    }))
+}
+
+/// Parses the `urn:` IRI.
+pub(super) fn parse_urn<S: Spec>(iri: &RiStr<S>) -> Result<Box<dyn SchemeSpecific>, Error> {
+    if iri.authority_str().is_some() {
+        return Err(Error::new());
+    }
+    let (nid, nss) = iri.path_str().split_once(':').ok_or_else(Error::new)?;
+    // NID = (alphanum) 0*30(ldh) (alphanum)
+    let nid_bytes = nid.as_bytes();
+    let is_valid_nid = (2..=32).contains(&nid_bytes.len())
+        && nid_bytes[0].is_ascii_alphanumeric()
+        && nid_bytes[nid_bytes.len() - 1].is_ascii_alphanumeric()
+        && nid_bytes
+            .iter()
+            .all(|&b| b.is_ascii_alphanumeric() || (b == b'-'));
+    if !is_valid_nid || nss.is_empty() {
+        return Err(Error::new());
+    }
+    Ok(Box::new(Urn {
+        nid: nid.to_owned(),
+        nss: nss.to_owned(),
+    }))
+}
+
+/// Parses the `magnet:` link.
+pub(super) fn parse_magnet<S: Spec>(iri: &RiStr<S>) -> Result<Box<dyn SchemeSpecific>, Error> {
+    if iri.authority_str().is_some() || !iri.path_str().is_empty() {
+        return Err(Error::new());
+    }
+    let query = iri.query_str().ok_or_else(Error::new)?;
+    let mut exact_topics = Vec::new();
+    let mut display_name = None;
+    let mut trackers = Vec::new();
+    for pair in query.split('&') {
+        let (key, value) = match pair.split_once('=') {
+            Some(v) => v,
+            None => continue,
+        };
+        match key {
+            "xt" => exact_topics.push(value.to_owned()),
+            "dn" if display_name.is_none() => display_name = Some(value.to_owned()),
+            "tr" => trackers.push(value.to_owned()),
+            _ => {}
+        }
+    }
+    if exact_topics.is_empty() {
+        return Err(Error::new());
+    }
+    Ok(Box::new(MagnetLink {
+        exact_topics,
+        display_name,
+        trackers,
+    }))
+}
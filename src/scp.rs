@@ -0,0 +1,285 @@
+//! Recognition of SCP-like Git remote strings.
+//!
+//! Git and SSH accept remote locations in the form `[user@]host:path`, such as
+//! `git@example.com:owner/repo.git`. They are not URIs: `git@example.com` is
+//! not a valid scheme, and strict parsing either rejects them or misreads
+//! them. Utilities in this module recognize such strings leniently and convert
+//! them into valid `ssh://` URIs, without changing what strings are accepted
+//! by the strict types.
+//!
+//! The recognition follows Git's rules: a string is SCP-like if it contains a
+//! colon before any slash, and is not a URL with `://`. The host can be
+//! enclosed in brackets (e.g. `[::1]:repo.git`) to use an IPv6 address.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use iri_string::scp::{RelativePath, ScpLike};
+//! use iri_string::types::UriString;
+//!
+//! let scp = ScpLike::parse("git@example.com:owner/repo.git").expect("SCP-like string");
+//! assert_eq!(scp.user(), Some("git"));
+//! assert_eq!(scp.host(), "example.com");
+//! assert_eq!(scp.path(), "owner/repo.git");
+//!
+//! let uri: UriString = scp.to_ssh(RelativePath::FromRoot);
+//! assert_eq!(uri, "ssh://git@example.com/owner/repo.git");
+//! let uri: UriString = scp.to_ssh(RelativePath::FromHome);
+//! assert_eq!(uri, "ssh://git@example.com/~/owner/repo.git");
+//!
+//! // URLs and local paths are not SCP-like.
+//! assert!(ScpLike::parse("ssh://git@example.com/owner/repo.git").is_none());
+//! assert!(ScpLike::parse("./foo:bar").is_none());
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use core::fmt::Write as _;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "alloc")]
+use crate::parser::char::{
+    is_ascii_pchar_slash, is_ascii_regname, is_ascii_userinfo_ipvfutureaddr,
+};
+#[cfg(feature = "alloc")]
+use crate::spec::Spec;
+use crate::spec::UriSpec;
+#[cfg(feature = "alloc")]
+use crate::types::RiString;
+use crate::validate;
+
+/// Interpretation of relative paths in SCP-like strings.
+///
+/// In SCP-like strings, a path without the leading slash (such as
+/// `owner/repo.git`) is relative to the home directory of the user on the
+/// server. `ssh://` URIs always have absolute paths, so the relative path
+/// should be converted in either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelativePath {
+    /// Converts `host:path` into `ssh://host/path`.
+    ///
+    /// This is how Git hosting services (which ignore the home directory)
+    /// usually write their `ssh://` URIs.
+    FromRoot,
+    /// Converts `host:path` into `ssh://host/~/path`.
+    ///
+    /// This preserves the meaning of the relative path for Git:
+    /// `ssh://host/~/path` is resolved from the home directory.
+    FromHome,
+}
+
+/// SCP-like remote string, such as `git@example.com:owner/repo.git`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScpLike<'a> {
+    /// User.
+    user: Option<&'a str>,
+    /// Host, including the brackets for IPv6 addresses.
+    host: &'a str,
+    /// Path.
+    path: &'a str,
+}
+
+impl<'a> ScpLike<'a> {
+    /// Recognizes the string as an SCP-like remote.
+    ///
+    /// Returns `None` if the string is not SCP-like, i.e. if any of the
+    /// following is true:
+    ///
+    /// * the string has no colon, or has a slash before the first colon,
+    /// * the string is a URL (the colon is followed by `//`),
+    /// * the user, the host, or the path is empty,
+    /// * the host is a single ASCII letter (to avoid misreading DOS drive
+    ///   letters such as `C:foo`), or
+    /// * the host is enclosed in brackets but is not an IPv6 address (or a
+    ///   valid `IPvFuture`).
+    ///
+    /// Other characters (including spaces and non-ASCII characters) are
+    /// accepted, and they are percent-encoded on conversion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::scp::ScpLike;
+    ///
+    /// let scp = ScpLike::parse("[::1]:/srv/repo.git").expect("SCP-like string");
+    /// assert_eq!(scp.user(), None);
+    /// assert_eq!(scp.host(), "[::1]");
+    /// assert_eq!(scp.path(), "/srv/repo.git");
+    ///
+    /// assert!(ScpLike::parse("example.com").is_none());
+    /// assert!(ScpLike::parse("https://example.com/repo.git").is_none());
+    /// assert!(ScpLike::parse("C:repo.git").is_none());
+    /// assert!(ScpLike::parse("@example.com:repo.git").is_none());
+    /// assert!(ScpLike::parse("example.com:").is_none());
+    /// ```
+    #[must_use]
+    pub fn parse(s: &'a str) -> Option<Self> {
+        // The user is separated by the last `@` before the host.
+        let prefix_end = s.find([':', '/', '[']).unwrap_or(s.len());
+        let (user, rest) = match s[..prefix_end].rfind('@') {
+            Some(at) => (Some(&s[..at]), &s[(at + 1)..]),
+            None => (None, s),
+        };
+        if user == Some("") {
+            return None;
+        }
+        let (host, path) = if rest.starts_with('[') {
+            let close = rest.find(']')?;
+            let (host, after_host) = rest.split_at(close + 1);
+            validate::host::<UriSpec>(host).ok()?;
+            (host, after_host.strip_prefix(':')?)
+        } else {
+            let colon = rest.find([':', '/'])?;
+            if rest.as_bytes()[colon] == b'/' {
+                return None;
+            }
+            (&rest[..colon], &rest[(colon + 1)..])
+        };
+        let is_drive_letter = host.len() == 1 && host.as_bytes()[0].is_ascii_alphabetic();
+        if host.is_empty() || is_drive_letter || path.is_empty() || path.starts_with("//") {
+            return None;
+        }
+        Some(Self { user, host, path })
+    }
+
+    /// Returns the user.
+    #[inline]
+    #[must_use]
+    pub fn user(&self) -> Option<&'a str> {
+        self.user
+    }
+
+    /// Returns the host.
+    ///
+    /// For IPv6 addresses, the brackets are included.
+    #[inline]
+    #[must_use]
+    pub fn host(&self) -> &'a str {
+        self.host
+    }
+
+    /// Returns the path.
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+
+    /// Returns true if the path is relative (i.e. relative to the home directory).
+    ///
+    /// Paths starting with `~` are also regarded as relative here, since they
+    /// are not absolute in `ssh://` URIs either.
+    #[inline]
+    #[must_use]
+    pub fn is_path_relative(&self) -> bool {
+        !self.path.starts_with('/')
+    }
+
+    /// Converts the SCP-like string into an `ssh://` IRI.
+    ///
+    /// Characters not allowed in each component (including `%`) are
+    /// percent-encoded, so the conversion never fails and is lossless.
+    ///
+    /// A path starting with `~` (such as `~user/repo.git`) is converted into
+    /// `/~user/repo.git` regardless of `relative`. Other relative paths are
+    /// converted according to `relative`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::scp::{RelativePath, ScpLike};
+    /// use iri_string::types::{IriString, UriString};
+    ///
+    /// let scp = ScpLike::parse("me@example.com:~/my repo.git").expect("SCP-like string");
+    /// let uri: UriString = scp.to_ssh(RelativePath::FromRoot);
+    /// assert_eq!(uri, "ssh://me@example.com/~/my%20repo.git");
+    ///
+    /// let scp = ScpLike::parse("r\u{E9}sum\u{E9}.example:/srv/\u{E9}t\u{E9}.git")
+    ///     .expect("SCP-like string");
+    /// let iri: IriString = scp.to_ssh(RelativePath::FromRoot);
+    /// assert_eq!(iri, "ssh://r\u{E9}sum\u{E9}.example/srv/\u{E9}t\u{E9}.git");
+    /// let uri: UriString = scp.to_ssh(RelativePath::FromRoot);
+    /// assert_eq!(uri, "ssh://r%C3%A9sum%C3%A9.example/srv/%C3%A9t%C3%A9.git");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[must_use]
+    pub fn to_ssh<S: Spec>(&self, relative: RelativePath) -> RiString<S> {
+        let mut buf = String::with_capacity("ssh://@/~/".len() + self.host.len() + self.path.len());
+        buf.push_str("ssh://");
+        if let Some(user) = self.user {
+            push_encoded::<S>(&mut buf, user, |c| {
+                c != b':' && is_ascii_userinfo_ipvfutureaddr(c)
+            });
+            buf.push('@');
+        }
+        if self.host.starts_with('[') {
+            buf.push_str(self.host);
+        } else {
+            push_encoded::<S>(&mut buf, self.host, is_ascii_regname);
+        }
+        if self.path.starts_with('~') {
+            buf.push('/');
+        } else if !self.path.starts_with('/') {
+            buf.push_str(match relative {
+                RelativePath::FromRoot => "/",
+                RelativePath::FromHome => "/~/",
+            });
+        }
+        push_encoded::<S>(&mut buf, self.path, is_ascii_pchar_slash);
+
+        debug_assert!(
+            validate::iri::<S>(&buf).is_ok(),
+            "[consistency] the components are encoded properly: {:?}",
+            buf
+        );
+        RiString::try_from(buf).expect("[consistency] the components are encoded properly")
+    }
+}
+
+/// Converts the SCP-like string into an `ssh://` IRI.
+///
+/// Returns `None` if the string is not SCP-like. See [`ScpLike::parse`] for
+/// the recognition rules and [`ScpLike::to_ssh`] for the conversion.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::scp::{scp_to_ssh, RelativePath};
+/// use iri_string::types::UriString;
+///
+/// let uri: Option<UriString> = scp_to_ssh("git@example.com:repo.git", RelativePath::FromRoot);
+/// assert_eq!(uri.expect("SCP-like string"), "ssh://git@example.com/repo.git");
+///
+/// let uri: Option<UriString> = scp_to_ssh("https://example.com/repo.git", RelativePath::FromRoot);
+/// assert_eq!(uri, None);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[must_use]
+pub fn scp_to_ssh<S: Spec>(s: &str, relative: RelativePath) -> Option<RiString<S>> {
+    ScpLike::parse(s).map(|scp| scp.to_ssh(relative))
+}
+
+/// Writes the string with the characters not allowed percent-encoded.
+#[cfg(feature = "alloc")]
+fn push_encoded<S: Spec>(buf: &mut String, s: &str, is_ascii_allowed: impl Fn(u8) -> bool) {
+    for c in s.chars() {
+        let is_raw = if c.is_ascii() {
+            is_ascii_allowed(c as u8)
+        } else {
+            S::is_nonascii_char_unreserved(c)
+        };
+        if is_raw {
+            buf.push(c);
+        } else {
+            let mut utf8 = [0_u8; 4];
+            for b in c.encode_utf8(&mut utf8).bytes() {
+                write!(buf, "%{:02X}", b).expect("[consistency] writing to a string never fails");
+            }
+        }
+    }
+}
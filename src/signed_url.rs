@@ -0,0 +1,344 @@
+//! Signed IRIs with expiration.
+//!
+//! [`UrlSigner`] appends an expiration time and an HMAC-SHA256 signature to
+//! IRIs as query parameters, and verifies them, for presigned links such as
+//! temporary download URLs.
+//!
+//! # Canonical form
+//!
+//! The signature is computed over the normalized IRI (see
+//! [`RiStr::normalize`]) without the fragment, up to and including the
+//! expiration parameter. The signed IRI is returned in that normalized form
+//! with the signature parameter appended, so it is verifiable even if a
+//! proxy changes the case of the scheme and the host or percent-encoding of
+//! unreserved characters. Any other change (including reordering the query
+//! parameters) invalidates the signature.
+//!
+//! The fragment is kept as is, and is not signed since it is not sent to
+//! servers.
+//!
+//! # Examples
+//!
+//! ```
+//! use iri_string::signed_url::{UrlSigner, VerifyError};
+//! use iri_string::types::UriStr;
+//!
+//! let signer = UrlSigner::new(b"secret key");
+//! let uri = UriStr::new("HTTPS://Example.com/files/report.pdf?download=1#page=2")?;
+//!
+//! // Valid until 1700000000 (seconds since the Unix epoch).
+//! let signed = signer.sign(uri, 1_700_000_000).expect("no conflicting parameters");
+//! assert!(signed
+//!     .as_str()
+//!     .starts_with("https://example.com/files/report.pdf?download=1&expires=1700000000&signature="));
+//! assert_eq!(signed.fragment().map(|f| f.as_str()), Some("page=2"));
+//!
+//! assert_eq!(signer.verify(&signed, 1_699_999_999), Ok(()));
+//! assert_eq!(signer.verify(&signed, 1_700_000_001), Err(VerifyError::Expired));
+//! assert_eq!(
+//!     UrlSigner::new(b"other key").verify(&signed, 1_699_999_999),
+//!     Err(VerifyError::InvalidSignature)
+//! );
+//! # Ok::<_, iri_string::validate::Error>(())
+//! ```
+//!
+//! [`RiStr::normalize`]: `crate::types::RiStr::normalize`
+
+use core::fmt::{self, Write as _};
+
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::string::String;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::format::ToDedicatedString;
+use crate::parser::char::is_ascii_unreserved;
+use crate::spec::Spec;
+use crate::types::{RiAbsoluteStr, RiStr, RiString};
+
+/// HMAC-SHA256.
+type HmacSha256 = Hmac<Sha256>;
+
+/// Length of the MAC in bytes.
+const MAC_LEN: usize = 32;
+
+/// Error on signing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to sign the IRI")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+/// Error on verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum VerifyError {
+    /// The IRI does not have the expiration and the signature parameters at
+    /// the end of the query, or they are malformed.
+    Malformed,
+    /// The signature does not match.
+    InvalidSignature,
+    /// The signature is valid but expired.
+    Expired,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Malformed => "the signature parameters are missing or malformed",
+            Self::InvalidSignature => "the signature does not match",
+            Self::Expired => "the signature is expired",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for VerifyError {}
+
+/// Signer and verifier of IRIs.
+///
+/// The expiration is written as a query parameter `expires` (in seconds
+/// since the Unix epoch), followed by `signature` (HMAC-SHA256 in lowercase
+/// hexadecimal digits). The parameter names can be changed by
+/// [`expires_param`][`Self::expires_param`] and
+/// [`signature_param`][`Self::signature_param`].
+///
+/// See [the module documentation][self] for the signed content.
+#[derive(Clone)]
+#[must_use]
+pub struct UrlSigner<'a> {
+    /// Secret key.
+    key: &'a [u8],
+    /// Name of the expiration parameter.
+    expires_param: &'a str,
+    /// Name of the signature parameter.
+    signature_param: &'a str,
+}
+
+impl fmt::Debug for UrlSigner<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UrlSigner")
+            .field("key", &"***")
+            .field("expires_param", &self.expires_param)
+            .field("signature_param", &self.signature_param)
+            .finish()
+    }
+}
+
+impl<'a> UrlSigner<'a> {
+    /// Creates a new signer with the secret key.
+    #[inline]
+    pub fn new(key: &'a [u8]) -> Self {
+        Self {
+            key,
+            expires_param: "expires",
+            signature_param: "signature",
+        }
+    }
+
+    /// Sets the name of the expiration parameter.
+    ///
+    /// The name should be a non-empty string of `unreserved` characters.
+    /// Otherwise signing and verification always fail.
+    #[inline]
+    pub fn expires_param(mut self, name: &'a str) -> Self {
+        self.expires_param = name;
+        self
+    }
+
+    /// Sets the name of the signature parameter.
+    ///
+    /// The name should be a non-empty string of `unreserved` characters.
+    /// Otherwise signing and verification always fail.
+    #[inline]
+    pub fn signature_param(mut self, name: &'a str) -> Self {
+        self.signature_param = name;
+        self
+    }
+
+    /// Returns true if the parameter names are usable.
+    #[must_use]
+    fn has_valid_params(&self) -> bool {
+        let is_valid = |name: &str| !name.is_empty() && name.bytes().all(is_ascii_unreserved);
+        is_valid(self.expires_param)
+            && is_valid(self.signature_param)
+            && self.expires_param != self.signature_param
+    }
+
+    /// Returns the MAC initialized with the key.
+    #[must_use]
+    fn new_mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(self.key).expect("[consistency] HMAC accepts keys of any length")
+    }
+
+    /// Returns the MAC of the message.
+    #[must_use]
+    fn mac(&self, message: &str) -> [u8; MAC_LEN] {
+        let mut mac = self.new_mac();
+        mac.update(message.as_bytes());
+        mac.finalize().into_bytes().into()
+    }
+
+    /// Returns the signed IRI valid until `expires` (in seconds since the
+    /// Unix epoch).
+    ///
+    /// # Failures
+    ///
+    /// Fails if the parameter names are invalid, or the query already has
+    /// the expiration or signature parameter.
+    pub fn sign<S: Spec>(&self, iri: &RiStr<S>, expires: u64) -> Result<RiString<S>, Error> {
+        if !self.has_valid_params() {
+            return Err(Error::new());
+        }
+        let (absolute, fragment) = iri.to_absolute_and_fragment();
+        if let Some(query) = absolute.query_str() {
+            let conflicts = query.split('&').any(|pair| {
+                let key = pair.split_once('=').map_or(pair, |(key, _)| key);
+                key == self.expires_param || key == self.signature_param
+            });
+            if conflicts {
+                return Err(Error::new());
+            }
+        }
+
+        let mut buf: String = absolute.normalize().to_dedicated_string().into();
+        buf.push(if absolute.query_str().is_some() {
+            '&'
+        } else {
+            '?'
+        });
+        write!(buf, "{}={}", self.expires_param, expires)
+            .expect("[consistency] writing to a string never fails");
+        let mac = self.mac(&buf);
+        write!(buf, "&{}=", self.signature_param)
+            .expect("[consistency] writing to a string never fails");
+        for b in mac {
+            write!(buf, "{:02x}", b).expect("[consistency] writing to a string never fails");
+        }
+        if let Some(fragment) = fragment {
+            buf.push('#');
+            buf.push_str(fragment.as_str());
+        }
+        Ok(RiString::try_from(buf).expect("[validity] only valid components are appended"))
+    }
+
+    /// Verifies the signed IRI at the time `now` (in seconds since the Unix
+    /// epoch).
+    ///
+    /// The IRI is expired if `now` is later than the expiration. The
+    /// signature is checked before the expiration, so [`VerifyError::Expired`]
+    /// is returned only for authentic IRIs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")] {
+    /// use std::time::{SystemTime, UNIX_EPOCH};
+    /// use iri_string::signed_url::UrlSigner;
+    /// use iri_string::types::IriStr;
+    ///
+    /// let now = SystemTime::now()
+    ///     .duration_since(UNIX_EPOCH)
+    ///     .expect("the clock is after the epoch")
+    ///     .as_secs();
+    /// let signer = UrlSigner::new(b"secret").expires_param("e").signature_param("s");
+    /// let iri = IriStr::new("https://example.com/").expect("valid IRI");
+    /// let signed = signer.sign(iri, now + 3600).expect("no conflicting parameters");
+    /// assert!(signed.as_str().starts_with("https://example.com/?e="));
+    /// assert!(signer.verify(&signed, now).is_ok());
+    /// # }
+    /// ```
+    pub fn verify<S: Spec>(&self, iri: &RiStr<S>, now: u64) -> Result<(), VerifyError> {
+        if !self.has_valid_params() {
+            return Err(VerifyError::Malformed);
+        }
+        let absolute = iri.to_absolute().as_str();
+        let query_start = iri
+            .to_absolute()
+            .query_str()
+            .map(|query| absolute.len() - query.len())
+            .ok_or(VerifyError::Malformed)?;
+
+        // Take the trailing `&signature=...`.
+        let (signed, signature) = absolute
+            .rsplit_once('&')
+            .filter(|(signed, _)| signed.len() >= query_start)
+            .ok_or(VerifyError::Malformed)?;
+        let signature = signature
+            .strip_prefix(self.signature_param)
+            .and_then(|rest| rest.strip_prefix('='))
+            .and_then(decode_hex_mac)
+            .ok_or(VerifyError::Malformed)?;
+
+        // Take the trailing `expires=...` of the signed part.
+        let expires_pair_start = signed
+            .rfind('&')
+            .filter(|&pos| pos >= query_start)
+            .map_or(query_start, |pos| pos + 1);
+        let expires = signed[expires_pair_start..]
+            .strip_prefix(self.expires_param)
+            .and_then(|rest| rest.strip_prefix('='))
+            .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|digits| digits.parse::<u64>().ok())
+            .ok_or(VerifyError::Malformed)?;
+
+        let signed = RiAbsoluteStr::<S>::new(signed).map_err(|_| VerifyError::Malformed)?;
+        let mut mac = self.new_mac();
+        write!(MacWriter(&mut mac), "{}", signed.normalize())
+            .expect("[consistency] the MAC never fails");
+        // This compares the MACs in constant time.
+        mac.verify_slice(&signature)
+            .map_err(|_| VerifyError::InvalidSignature)?;
+        if now > expires {
+            return Err(VerifyError::Expired);
+        }
+        Ok(())
+    }
+}
+
+/// Writer to feed the formatted string to the MAC.
+struct MacWriter<'a>(&'a mut HmacSha256);
+
+impl fmt::Write for MacWriter<'_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Decodes the MAC in hexadecimal digits.
+#[must_use]
+fn decode_hex_mac(s: &str) -> Option<[u8; MAC_LEN]> {
+    if s.len() != MAC_LEN * 2 {
+        return None;
+    }
+    let mut mac = [0_u8; MAC_LEN];
+    for (b, pair) in mac.iter_mut().zip(s.as_bytes().chunks_exact(2)) {
+        let pair = core::str::from_utf8(pair).ok()?;
+        *b = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(mac)
+}
@@ -0,0 +1,223 @@
+//! Version-stable hashing.
+//!
+//! The algorithm is SipHash-2-4 (64-bit and 128-bit variants) with the fixed
+//! key `00 01 02 ... 0f` (i.e. `k0 = 0x0706050403020100` and
+//! `k1 = 0x0f0e0d0c0b0a0908`), as in the reference test vectors. The output
+//! for the same input bytes must not change across versions of this crate,
+//! so do not modify this file without a major version bump.
+//!
+//! Note that this only freezes the hash of the given bytes. The public
+//! methods hash the normalization result, which is not frozen.
+
+use core::fmt;
+
+/// First half of the key.
+const K0: u64 = 0x0706_0504_0302_0100;
+/// Second half of the key.
+const K1: u64 = 0x0f0e_0d0c_0b0a_0908;
+
+/// SipHash-2-4 hasher with the fixed key.
+#[derive(Debug, Clone)]
+pub(crate) struct StableHasher {
+    /// Internal states.
+    v: [u64; 4],
+    /// Pending bytes, not yet forming a complete 8-byte word.
+    tail: u64,
+    /// Number of pending bytes.
+    ntail: usize,
+    /// Total length of the input.
+    len: usize,
+}
+
+impl StableHasher {
+    /// Creates a new hasher for the 64-bit output.
+    #[must_use]
+    pub(crate) fn new64() -> Self {
+        Self {
+            v: [
+                K0 ^ 0x736f_6d65_7073_6575,
+                K1 ^ 0x646f_7261_6e64_6f6d,
+                K0 ^ 0x6c79_6765_6e65_7261,
+                K1 ^ 0x7465_6462_7974_6573,
+            ],
+            tail: 0,
+            ntail: 0,
+            len: 0,
+        }
+    }
+
+    /// Creates a new hasher for the 128-bit output.
+    #[must_use]
+    pub(crate) fn new128() -> Self {
+        let mut hasher = Self::new64();
+        hasher.v[1] ^= 0xee;
+        hasher
+    }
+
+    /// Runs SipRound.
+    #[inline]
+    fn round(&mut self) {
+        let [v0, v1, v2, v3] = &mut self.v;
+        *v0 = v0.wrapping_add(*v1);
+        *v1 = v1.rotate_left(13);
+        *v1 ^= *v0;
+        *v0 = v0.rotate_left(32);
+        *v2 = v2.wrapping_add(*v3);
+        *v3 = v3.rotate_left(16);
+        *v3 ^= *v2;
+        *v0 = v0.wrapping_add(*v3);
+        *v3 = v3.rotate_left(21);
+        *v3 ^= *v0;
+        *v2 = v2.wrapping_add(*v1);
+        *v1 = v1.rotate_left(17);
+        *v1 ^= *v2;
+        *v2 = v2.rotate_left(32);
+    }
+
+    /// Compresses the 8-byte word.
+    #[inline]
+    fn compress(&mut self, m: u64) {
+        self.v[3] ^= m;
+        self.round();
+        self.round();
+        self.v[0] ^= m;
+    }
+
+    /// Feeds the bytes.
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        self.len = self.len.wrapping_add(bytes.len());
+        for &b in bytes {
+            self.tail |= u64::from(b) << (8 * self.ntail);
+            self.ntail += 1;
+            if self.ntail == 8 {
+                let m = self.tail;
+                self.compress(m);
+                self.tail = 0;
+                self.ntail = 0;
+            }
+        }
+    }
+
+    /// Processes the last block.
+    fn finish_input(&mut self) {
+        let b = ((self.len as u64) << 56) | self.tail;
+        self.compress(b);
+    }
+
+    /// Returns the 64-bit hash.
+    ///
+    /// The hasher should be created by [`new64`][`Self::new64`].
+    #[must_use]
+    pub(crate) fn finish64(mut self) -> u64 {
+        self.finish_input();
+        self.v[2] ^= 0xff;
+        (0..4).for_each(|_| self.round());
+        self.v.iter().fold(0, |acc, v| acc ^ v)
+    }
+
+    /// Returns the 128-bit hash.
+    ///
+    /// The hasher should be created by [`new128`][`Self::new128`]. The result
+    /// is the little-endian interpretation of the reference output bytes.
+    #[must_use]
+    pub(crate) fn finish128(mut self) -> u128 {
+        self.finish_input();
+        self.v[2] ^= 0xee;
+        (0..4).for_each(|_| self.round());
+        let lo = self.v.iter().fold(0, |acc, v| acc ^ v);
+        self.v[1] ^= 0xdd;
+        (0..4).for_each(|_| self.round());
+        let hi = self.v.iter().fold(0, |acc, v| acc ^ v);
+        (u128::from(hi) << 64) | u128::from(lo)
+    }
+}
+
+impl fmt::Write for StableHasher {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Returns the 64-bit stable hash of the displayed value.
+#[must_use]
+pub(crate) fn hash64<T: fmt::Display + ?Sized>(v: &T) -> u64 {
+    use core::fmt::Write as _;
+
+    let mut hasher = StableHasher::new64();
+    write!(hasher, "{}", v).expect("[consistency] the hasher never fails");
+    hasher.finish64()
+}
+
+/// Returns the 128-bit stable hash of the displayed value.
+#[must_use]
+pub(crate) fn hash128<T: fmt::Display + ?Sized>(v: &T) -> u128 {
+    use core::fmt::Write as _;
+
+    let mut hasher = StableHasher::new128();
+    write!(hasher, "{}", v).expect("[consistency] the hasher never fails");
+    hasher.finish128()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the bytes `0, step, 2 * step, ...` (wrapping).
+    fn test_input(step: u8) -> [u8; 64] {
+        let mut input = [0_u8; 64];
+        for (i, b) in input.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(step);
+        }
+        input
+    }
+
+    /// Returns the 64-bit hash of the bytes.
+    fn sip64(bytes: &[u8]) -> u64 {
+        let mut hasher = StableHasher::new64();
+        hasher.write(bytes);
+        hasher.finish64()
+    }
+
+    /// Returns the 128-bit hash of the bytes.
+    fn sip128(bytes: &[u8]) -> u128 {
+        let mut hasher = StableHasher::new128();
+        hasher.write(bytes);
+        hasher.finish128()
+    }
+
+    #[test]
+    fn reference_vectors() {
+        let input = test_input(1);
+        // Entries of `vectors_sip64` and `vectors_sip128` of the reference
+        // implementation.
+        assert_eq!(sip64(&input[..0]), 0x726f_db47_dd0e_0e31);
+        assert_eq!(sip64(&input[..15]), 0xa129_ca61_49be_45e5);
+        assert_eq!(
+            sip128(&input[..0]),
+            0x9302_55c7_1472_f66d_e6a8_25ba_047f_81a3
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn compatible_with_core_siphasher() {
+        use core::hash::{Hasher, SipHasher};
+
+        let input = test_input(1);
+        for len in 0..=input.len() {
+            let mut expected = SipHasher::new_with_keys(K0, K1);
+            expected.write(&input[..len]);
+            assert_eq!(sip64(&input[..len]), expected.finish(), "len={}", len);
+        }
+    }
+
+    #[test]
+    fn chunked_write() {
+        let input = test_input(7);
+        let mut hasher = StableHasher::new128();
+        input.chunks(5).for_each(|chunk| hasher.write(chunk));
+        assert_eq!(hasher.finish128(), sip128(&input));
+    }
+}
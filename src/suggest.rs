@@ -0,0 +1,270 @@
+//! Suggestions for common typos in IRIs.
+//!
+//! [`suggest`] detects frequent mistakes in user input, and returns machine
+//! readable edits to fix them. This is intended for form validation, for
+//! example to offer a one-click fix such as "did you mean
+//! `https://example.com/a%20b`?".
+//!
+//! The detected mistakes are:
+//!
+//! * missing `//` after well-known hierarchical schemes (such as
+//!   `https:example.com`),
+//! * spaces instead of `%20`,
+//! * IPv6 addresses used as a host without brackets (such as
+//!   `http://::1/`), and
+//! * stray `#` characters in the query (such as `?color=#fff&size=2`).
+//!
+//! Note that some of the inputs are syntactically valid but likely not what
+//! the user intended, so suggestions can be returned even for valid IRIs.
+//!
+//! # Examples
+//!
+//! ```
+//! use iri_string::spec::UriSpec;
+//! use iri_string::suggest::{fix, suggest, SuggestionKind};
+//!
+//! let input = "https:example.com/a b";
+//! let suggestions = suggest::<UriSpec>(input);
+//! assert_eq!(suggestions.len(), 2);
+//! assert_eq!(suggestions[0].kind(), SuggestionKind::MissingSlashes);
+//! assert_eq!(suggestions[0].range(), 6..6);
+//! assert_eq!(suggestions[0].replacement(), "//");
+//! assert_eq!(suggestions[1].kind(), SuggestionKind::Space);
+//!
+//! let fixed = fix::<UriSpec>(input).expect("fixable");
+//! assert_eq!(fixed, "https://example.com/a%20b");
+//! ```
+
+use core::ops::Range;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::parser::validate::validate_ipv6address;
+use crate::spec::Spec;
+use crate::types::RiString;
+
+/// Schemes known to always have the authority.
+const HIERARCHICAL_SCHEMES: &[&str] = &["ftp", "http", "https", "ws", "wss"];
+
+/// Kind of the detected mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SuggestionKind {
+    /// `//` is missing after the scheme which always has the authority.
+    MissingSlashes,
+    /// A space is used instead of `%20`.
+    Space,
+    /// An IPv6 address is used as a host without brackets.
+    UnbracketedIpv6,
+    /// `#` is used in the query, which starts the fragment.
+    StrayHash,
+}
+
+/// A suggested edit to fix the mistake.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Suggestion {
+    /// Kind of the mistake.
+    kind: SuggestionKind,
+    /// Byte range of the input to be replaced.
+    range: Range<usize>,
+    /// Replacement.
+    replacement: Cow<'static, str>,
+}
+
+impl Suggestion {
+    /// Creates a new suggestion.
+    #[inline]
+    #[must_use]
+    fn new(
+        kind: SuggestionKind,
+        range: Range<usize>,
+        replacement: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            kind,
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Returns the kind of the mistake.
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> SuggestionKind {
+        self.kind
+    }
+
+    /// Returns the byte range of the input to be replaced.
+    ///
+    /// The range is empty if the replacement should be inserted.
+    #[inline]
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the replacement.
+    #[inline]
+    #[must_use]
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
+/// Returns the suggestions to fix common mistakes in the input.
+///
+/// The returned suggestions are sorted by their positions, and do not
+/// overlap each other. They can be applied all at once by [`apply`].
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::spec::UriSpec;
+/// use iri_string::suggest::{suggest, SuggestionKind};
+///
+/// let suggestions = suggest::<UriSpec>("http://2001:db8::1/");
+/// assert_eq!(suggestions.len(), 1);
+/// assert_eq!(suggestions[0].kind(), SuggestionKind::UnbracketedIpv6);
+/// assert_eq!(suggestions[0].range(), 7..18);
+/// assert_eq!(suggestions[0].replacement(), "[2001:db8::1]");
+///
+/// let suggestions = suggest::<UriSpec>("https://example.com/?color=#fff&size=2");
+/// assert_eq!(suggestions[0].kind(), SuggestionKind::StrayHash);
+/// assert_eq!(suggestions[0].replacement(), "%23");
+///
+/// assert!(suggest::<UriSpec>("https://example.com/").is_empty());
+/// ```
+#[must_use]
+pub fn suggest<S: Spec>(s: &str) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+
+    // Missing slashes.
+    let after_scheme = match s.find(':') {
+        Some(colon) if is_hierarchical_scheme(&s[..colon]) => {
+            let rest = &s[(colon + 1)..];
+            if !rest.starts_with("//") {
+                let missing = if rest.starts_with('/') { "/" } else { "//" };
+                suggestions.push(Suggestion::new(
+                    SuggestionKind::MissingSlashes,
+                    (colon + 1)..(colon + 1),
+                    missing,
+                ));
+                None
+            } else {
+                Some(colon + 1)
+            }
+        }
+        Some(colon) => Some(colon + 1),
+        None => Some(0),
+    };
+
+    // Unbracketed IPv6 host.
+    if let Some(authority_start) = after_scheme
+        .filter(|&pos| s[pos..].starts_with("//"))
+        .map(|pos| pos + 2)
+    {
+        let authority_len = s[authority_start..]
+            .find(['/', '?', '#'])
+            .unwrap_or(s.len() - authority_start);
+        let authority = &s[authority_start..(authority_start + authority_len)];
+        let host_start = authority.rfind('@').map_or(0, |pos| pos + 1);
+        let host = &authority[host_start..];
+        if host.bytes().filter(|&b| b == b':').count() >= 2 && validate_ipv6address(host).is_ok() {
+            let start = authority_start + host_start;
+            let mut replacement = String::with_capacity(host.len() + 2);
+            replacement.push('[');
+            replacement.push_str(host);
+            replacement.push(']');
+            suggestions.push(Suggestion::new(
+                SuggestionKind::UnbracketedIpv6,
+                start..(start + host.len()),
+                replacement,
+            ));
+        }
+    }
+
+    // Spaces.
+    suggestions.extend(
+        s.match_indices(' ')
+            .map(|(pos, _)| Suggestion::new(SuggestionKind::Space, pos..(pos + 1), "%20")),
+    );
+
+    // Stray hashes.
+    if let Some(first_hash) = s.find('#') {
+        let has_query = s[..first_hash].contains('?');
+        let fragment = &s[(first_hash + 1)..];
+        // The fragment looks like a continuation of the query.
+        if has_query && fragment.contains(['=', '&']) {
+            suggestions.push(Suggestion::new(
+                SuggestionKind::StrayHash,
+                first_hash..(first_hash + 1),
+                "%23",
+            ));
+        }
+        // The fragment cannot have `#`.
+        suggestions.extend(fragment.match_indices('#').map(|(pos, _)| {
+            let pos = first_hash + 1 + pos;
+            Suggestion::new(SuggestionKind::StrayHash, pos..(pos + 1), "%23")
+        }));
+    }
+
+    suggestions.sort_by_key(|suggestion| suggestion.range.start);
+    suggestions
+}
+
+/// Returns true if the scheme is known to always have the authority.
+fn is_hierarchical_scheme(scheme: &str) -> bool {
+    HIERARCHICAL_SCHEMES
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(scheme))
+}
+
+/// Applies the suggestions to the input.
+///
+/// The suggestions should be ones returned by [`suggest`] for the same
+/// input (or their subset).
+///
+/// # Panics
+///
+/// Panics if the suggestions overlap, are not sorted, or are out of range.
+#[must_use]
+pub fn apply(s: &str, suggestions: &[Suggestion]) -> String {
+    let mut buf = String::with_capacity(s.len() + suggestions.len() * 2);
+    let mut last = 0;
+    for suggestion in suggestions {
+        let range = suggestion.range();
+        assert!(
+            last <= range.start,
+            "suggestions must be sorted and must not overlap"
+        );
+        buf.push_str(&s[last..range.start]);
+        buf.push_str(suggestion.replacement());
+        last = range.end;
+    }
+    buf.push_str(&s[last..]);
+    buf
+}
+
+/// Fixes the common mistakes in the input, and returns the IRI.
+///
+/// Returns `None` if the input is still not a valid IRI after all the
+/// suggestions are applied. Returns the input as is if it is valid and no
+/// mistakes are detected.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::spec::UriSpec;
+/// use iri_string::suggest::fix;
+///
+/// let fixed = fix::<UriSpec>("http:/example.com/?q=a b#c#d").expect("fixable");
+/// assert_eq!(fixed, "http://example.com/?q=a%20b#c%23d");
+/// assert!(fix::<UriSpec>("http://example.com/<>").is_none());
+/// ```
+#[must_use]
+pub fn fix<S: Spec>(s: &str) -> Option<RiString<S>> {
+    let fixed = apply(s, &suggest::<S>(s));
+    RiString::try_from(fixed).ok()
+}
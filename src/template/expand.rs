@@ -253,7 +253,7 @@ fn expand<S: Spec, C: Context>(
         let visitor = ValueVisitor::<S>::new(f, varspec, op, &mut is_first_varspec);
         let token = context.visit(visitor)?;
         let formatter_ptr = token.formatter_ptr();
-        if formatter_ptr != f as *mut _ {
+        if !core::ptr::eq(formatter_ptr, f as *mut _) {
             // Invalid `VisitDoneToken` was returned. This cannot usually happen
             // without intentional unnatural usage.
             panic!("invalid `VisitDoneToken` was returned");
@@ -539,7 +539,6 @@ impl<'a, 'b, S: Spec> Visitor for ValueVisitor<'a, 'b, S> {
 
     /// Returns the name of the variable to visit.
     #[inline]
-    #[must_use]
     fn var_name(&self) -> VarName<'a> {
         self.varspec.name()
     }
@@ -580,7 +579,6 @@ impl<'a, 'b, S: Spec> Visitor for ValueVisitor<'a, 'b, S> {
 
     /// Visits a list variable.
     #[inline]
-    #[must_use]
     fn visit_list(self) -> Self::ListVisitor {
         let oppr = OpProps::from_op(self.op);
         ListValueVisitor {
@@ -592,7 +590,6 @@ impl<'a, 'b, S: Spec> Visitor for ValueVisitor<'a, 'b, S> {
 
     /// Visits an associative array variable.
     #[inline]
-    #[must_use]
     fn visit_assoc(self) -> Self::AssocVisitor {
         let oppr = OpProps::from_op(self.op);
         AssocValueVisitor {
@@ -851,7 +848,8 @@ impl<'a> Visitor for TypeVisitor<'a> {
 }
 
 /// Visitor to retrieve effective type of a list variable.
-struct ListTypeVisitor<'a>(TypeVisitor<'a>);
+// The field is kept to tie the lifetime to the originating visitor.
+struct ListTypeVisitor<'a>(#[allow(dead_code)] TypeVisitor<'a>);
 
 impl VisitorSealed for ListTypeVisitor<'_> {}
 
@@ -872,7 +870,8 @@ impl<'a> ListVisitor for ListTypeVisitor<'a> {
 }
 
 /// Visitor to retrieve effective type of an associative array variable.
-struct AssocTypeVisitor<'a>(TypeVisitor<'a>);
+// The field is kept to tie the lifetime to the originating visitor.
+struct AssocTypeVisitor<'a>(#[allow(dead_code)] TypeVisitor<'a>);
 
 impl VisitorSealed for AssocTypeVisitor<'_> {}
 
@@ -0,0 +1,512 @@
+//! Composable transformations of IRIs.
+//!
+//! [`Transform`] is a step rewriting an IRI reference, and [`Pipeline`]
+//! applies an ordered list of them. Ingestion services can declare their
+//! IRI policy once as a pipeline, and reuse it everywhere.
+//!
+//! This module provides the following transformations:
+//!
+//! * [`Normalize`]: normalizes the IRI (see [`RiStr::normalize`]).
+//! * [`StripQueryParams`]: removes query parameters by keys (by default,
+//!   well-known tracking parameters such as `utm_source`).
+//! * [`EnforceHttps`]: upgrades `http` to `https`, and rejects other schemes.
+//! * [`Relativize`]: makes the IRI relative to the base IRI if possible.
+//!
+//! Closures of type `Fn(RiReferenceString<S>) -> Result<RiReferenceString<S>, Error>`
+//! can also be used as transformations.
+//!
+//! # Examples
+//!
+//! ```
+//! use iri_string::spec::UriSpec;
+//! use iri_string::transform::{EnforceHttps, Normalize, Pipeline, Relativize, StripQueryParams};
+//! use iri_string::types::{UriAbsoluteStr, UriStr};
+//!
+//! let base = UriAbsoluteStr::new("https://example.com/docs/index.html")?;
+//! let pipeline = Pipeline::<UriSpec>::new()
+//!     .then(Normalize)
+//!     .then(StripQueryParams::tracking())
+//!     .then(EnforceHttps)
+//!     .then(Relativize::new(base));
+//!
+//! let uri = UriStr::new("HTTP://Example.COM/docs/%7Euser/a/../guide?utm_source=x&page=2#intro")?;
+//! assert_eq!(pipeline.apply(uri).expect("allowed"), "~user/guide?page=2#intro");
+//!
+//! let uri = UriStr::new("http://example.org/?fbclid=123")?;
+//! assert_eq!(pipeline.apply(uri).expect("allowed"), "//example.org/");
+//!
+//! let uri = UriStr::new("ftp://example.com/")?;
+//! assert!(pipeline.apply(uri).is_err());
+//! # Ok::<_, iri_string::validate::Error>(())
+//! ```
+//!
+//! [`RiStr::normalize`]: `crate::types::RiStr::normalize`
+
+use core::fmt;
+use core::marker::PhantomData;
+
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::format::ToDedicatedString;
+use crate::spec::Spec;
+use crate::types::{RiAbsoluteStr, RiQueryStr, RiReferenceStr, RiReferenceString};
+
+/// Well-known query parameters used for tracking.
+///
+/// Keys ending with `*` match any keys with the prefix.
+pub const TRACKING_PARAMS: &[&str] = &[
+    "utm_*", "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "mc_cid",
+    "mc_eid", "_ga", "_gl", "igshid", "twclid",
+];
+
+/// Error on transformation.
+///
+/// Transformations return this error to reject the IRI.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    ///
+    /// This is intended to be used by custom transformations.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the IRI is rejected by the transformation")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+/// A transformation of IRI references.
+pub trait Transform<S: Spec> {
+    /// Transforms the IRI reference.
+    ///
+    /// # Failures
+    ///
+    /// Fails if the IRI is rejected.
+    fn transform(&self, iri: RiReferenceString<S>) -> Result<RiReferenceString<S>, Error>;
+}
+
+impl<S, F> Transform<S> for F
+where
+    S: Spec,
+    F: Fn(RiReferenceString<S>) -> Result<RiReferenceString<S>, Error>,
+{
+    #[inline]
+    fn transform(&self, iri: RiReferenceString<S>) -> Result<RiReferenceString<S>, Error> {
+        self(iri)
+    }
+}
+
+/// Boxed transformation.
+type BoxedTransform<'a, S> = Box<dyn Transform<S> + Send + Sync + 'a>;
+
+/// An ordered list of transformations.
+///
+/// A pipeline is itself a [`Transform`], so pipelines can be nested.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::spec::IriSpec;
+/// use iri_string::transform::{Error, Normalize, Pipeline};
+/// use iri_string::types::{IriReferenceString, IriStr};
+///
+/// let reject_userinfo = |iri: IriReferenceString| match iri.authority_components() {
+///     Some(authority) if authority.userinfo().is_some() => Err(Error::new()),
+///     _ => Ok(iri),
+/// };
+/// let pipeline = Pipeline::<IriSpec>::new().then(Normalize).then(reject_userinfo);
+///
+/// let iri = IriStr::new("HTTPS://example.com/./a")?;
+/// assert_eq!(pipeline.apply(iri).expect("allowed"), "https://example.com/a");
+///
+/// let iri = IriStr::new("https://user@example.com/")?;
+/// assert!(pipeline.apply(iri).is_err());
+/// # Ok::<_, iri_string::validate::Error>(())
+/// ```
+pub struct Pipeline<'a, S: Spec> {
+    /// Transformations.
+    steps: Vec<BoxedTransform<'a, S>>,
+}
+
+impl<'a, S: Spec> Pipeline<'a, S> {
+    /// Creates a new empty pipeline.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Appends the transformation to the end of the pipeline.
+    #[inline]
+    pub fn push<T>(&mut self, step: T)
+    where
+        T: Transform<S> + Send + Sync + 'a,
+    {
+        self.steps.push(Box::new(step));
+    }
+
+    /// Returns the pipeline with the transformation appended to the end.
+    #[inline]
+    #[must_use]
+    pub fn then<T>(mut self, step: T) -> Self
+    where
+        T: Transform<S> + Send + Sync + 'a,
+    {
+        self.push(step);
+        self
+    }
+
+    /// Returns the number of the transformations.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns true if the pipeline has no transformations.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Applies the transformations in order to the IRI reference.
+    ///
+    /// # Failures
+    ///
+    /// Fails if any of the transformations rejects the IRI. The rest of the
+    /// transformations are not applied.
+    pub fn apply<T>(&self, iri: &T) -> Result<RiReferenceString<S>, Error>
+    where
+        T: ?Sized + AsRef<RiReferenceStr<S>>,
+    {
+        self.transform(iri.as_ref().to_owned())
+    }
+}
+
+impl<S: Spec> Default for Pipeline<'_, S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Spec> fmt::Debug for Pipeline<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("steps", &self.steps.len())
+            .finish()
+    }
+}
+
+impl<S: Spec> Transform<S> for Pipeline<'_, S> {
+    fn transform(&self, iri: RiReferenceString<S>) -> Result<RiReferenceString<S>, Error> {
+        self.steps
+            .iter()
+            .try_fold(iri, |iri, step| step.transform(iri))
+    }
+}
+
+/// Normalizes the IRI.
+///
+/// Relative references are not changed.
+///
+/// See [`RiStr::normalize`] for details.
+///
+/// [`RiStr::normalize`]: `crate::types::RiStr::normalize`
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Normalize;
+
+impl<S: Spec> Transform<S> for Normalize {
+    fn transform(&self, iri: RiReferenceString<S>) -> Result<RiReferenceString<S>, Error> {
+        let normalized = match iri.to_iri() {
+            Ok(iri) => iri.normalize().to_dedicated_string(),
+            Err(_) => return Ok(iri),
+        };
+        Ok(normalized.into())
+    }
+}
+
+/// Removes the query parameters with the given keys.
+///
+/// Keys ending with `*` match any keys with the prefix. Keys are compared
+/// case-sensitively without percent-decoding. The order and the text of the
+/// other parameters are kept as is. If all the parameters are removed, the
+/// query is removed entirely (including the leading `?`).
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::spec::IriSpec;
+/// use iri_string::transform::{Pipeline, StripQueryParams};
+/// use iri_string::types::IriStr;
+///
+/// let pipeline = Pipeline::<IriSpec>::new().then(StripQueryParams::new(&["session", "x-*"]));
+///
+/// let iri = IriStr::new("https://example.com/?x-a=1&q=rust&session&x-b=2&x=3")?;
+/// assert_eq!(pipeline.apply(iri).expect("allowed"), "https://example.com/?q=rust&x=3");
+///
+/// let iri = IriStr::new("https://example.com/?session=1#frag")?;
+/// assert_eq!(pipeline.apply(iri).expect("allowed"), "https://example.com/#frag");
+/// # Ok::<_, iri_string::validate::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StripQueryParams<'a> {
+    /// Keys to remove.
+    keys: &'a [&'a str],
+}
+
+impl<'a> StripQueryParams<'a> {
+    /// Creates a new transformation removing the given keys.
+    #[inline]
+    #[must_use]
+    pub fn new(keys: &'a [&'a str]) -> Self {
+        Self { keys }
+    }
+
+    /// Returns true if the parameter with the key should be removed.
+    #[must_use]
+    fn matches(&self, key: &str) -> bool {
+        self.keys
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => key.starts_with(prefix),
+                None => key == *pattern,
+            })
+    }
+}
+
+impl StripQueryParams<'static> {
+    /// Creates a new transformation removing [`TRACKING_PARAMS`].
+    #[inline]
+    #[must_use]
+    pub fn tracking() -> Self {
+        Self::new(TRACKING_PARAMS)
+    }
+}
+
+impl Default for StripQueryParams<'static> {
+    #[inline]
+    fn default() -> Self {
+        Self::tracking()
+    }
+}
+
+impl<S: Spec> Transform<S> for StripQueryParams<'_> {
+    fn transform(&self, mut iri: RiReferenceString<S>) -> Result<RiReferenceString<S>, Error> {
+        let query = match iri.query_str() {
+            Some(query) => query,
+            None => return Ok(iri),
+        };
+        let is_removed = |pair: &str| {
+            let key = pair.split_once('=').map_or(pair, |(key, _)| key);
+            self.matches(key)
+        };
+        if !query.split('&').any(is_removed) {
+            return Ok(iri);
+        }
+
+        let mut stripped = String::with_capacity(query.len());
+        let mut num_kept = 0_usize;
+        for pair in query.split('&').filter(|pair| !is_removed(pair)) {
+            if num_kept != 0 {
+                stripped.push('&');
+            }
+            stripped.push_str(pair);
+            num_kept += 1;
+        }
+        if num_kept == 0 {
+            iri.set_query(None);
+        } else {
+            let stripped = RiQueryStr::<S>::new(&stripped)
+                .expect("[validity] the parameters are taken from the valid query");
+            iri.set_query(Some(stripped));
+        }
+        Ok(iri)
+    }
+}
+
+/// Upgrades `http` IRIs to `https`, and rejects other schemes.
+///
+/// The explicit port `80` is removed on upgrade. Relative references are not
+/// changed.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::spec::IriSpec;
+/// use iri_string::transform::{EnforceHttps, Pipeline};
+/// use iri_string::types::IriReferenceStr;
+///
+/// let pipeline = Pipeline::<IriSpec>::new().then(EnforceHttps);
+/// let apply = |s| pipeline.apply(IriReferenceStr::new(s).expect("valid IRI"));
+///
+/// assert_eq!(apply("http://example.com:80/").expect("allowed"), "https://example.com/");
+/// assert_eq!(apply("http://example.com:8080/").expect("allowed"), "https://example.com:8080/");
+/// assert_eq!(apply("HTTPS://example.com/").expect("allowed"), "HTTPS://example.com/");
+/// assert_eq!(apply("/relative").expect("allowed"), "/relative");
+/// assert!(apply("mailto:user@example.com").is_err());
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnforceHttps;
+
+impl<S: Spec> Transform<S> for EnforceHttps {
+    fn transform(&self, iri: RiReferenceString<S>) -> Result<RiReferenceString<S>, Error> {
+        match iri.scheme_str() {
+            None => return Ok(iri),
+            Some(scheme) if scheme.eq_ignore_ascii_case("https") => return Ok(iri),
+            Some(scheme) if scheme.eq_ignore_ascii_case("http") => {}
+            Some(_) => return Err(Error::new()),
+        }
+        let has_default_port = matches!(
+            iri.authority_components()
+                .and_then(|authority| authority.port()),
+            Some("" | "80")
+        );
+
+        let mut components = iri.into_components();
+        components
+            .set_scheme(Some("https"))
+            .expect("[validity] `https` is a valid scheme");
+        if has_default_port {
+            components
+                .set_port(None)
+                .expect("[validity] removing the port always succeeds");
+        }
+        Ok(RiReferenceString::from_components(&components)
+            .expect("[validity] only the scheme and the port are changed"))
+    }
+}
+
+/// Makes the IRI relative to the base IRI if possible.
+///
+/// Resolving the result against the base IRI gives the original IRI, as long
+/// as the original IRI has no dot segments (this is the case for the
+/// normalized IRIs). IRIs with a different scheme from the base are not
+/// changed, and IRIs with a different authority become network-path
+/// references (such as `//example.com/path`). Relative references are not
+/// changed.
+///
+/// The scheme is compared case-insensitively, and the authority and the path
+/// are compared as is. Apply [`Normalize`] beforehand to get equivalent IRIs
+/// relativized.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::spec::IriSpec;
+/// use iri_string::transform::{Pipeline, Relativize};
+/// use iri_string::types::{IriAbsoluteStr, IriReferenceStr};
+///
+/// let base = IriAbsoluteStr::new("https://example.com/a/b?q")?;
+/// let pipeline = Pipeline::<IriSpec>::new().then(Relativize::new(base));
+/// let apply = |s| pipeline.apply(IriReferenceStr::new(s).expect("valid IRI"));
+///
+/// assert_eq!(apply("https://example.com/a/c#frag").expect("allowed"), "c#frag");
+/// assert_eq!(apply("https://example.com/a/").expect("allowed"), "./");
+/// assert_eq!(apply("https://example.com/x/y").expect("allowed"), "/x/y");
+/// assert_eq!(apply("https://example.org/a/c").expect("allowed"), "//example.org/a/c");
+/// assert_eq!(apply("http://example.com/a/c").expect("allowed"), "http://example.com/a/c");
+/// # Ok::<_, iri_string::validate::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Relativize<'a, S: Spec> {
+    /// Base IRI.
+    base: &'a RiAbsoluteStr<S>,
+    /// Spec.
+    _spec: PhantomData<fn() -> S>,
+}
+
+impl<'a, S: Spec> Relativize<'a, S> {
+    /// Creates a new transformation with the base IRI.
+    #[inline]
+    #[must_use]
+    pub fn new(base: &'a RiAbsoluteStr<S>) -> Self {
+        Self {
+            base,
+            _spec: PhantomData,
+        }
+    }
+
+    /// Returns the relative path, if the path relative to the base can be
+    /// used with the same authority.
+    #[must_use]
+    fn relative_path<'b>(&self, path: &'b str) -> Option<Cow<'b, str>> {
+        let base_path = self.base.path_str();
+        let base_dir = match base_path.rfind('/') {
+            Some(pos) => &base_path[..=pos],
+            None if base_path.is_empty() && self.base.authority_str().is_some() => "/",
+            None => "",
+        };
+        if let Some(rest) = path.strip_prefix(base_dir).filter(|_| !base_dir.is_empty()) {
+            let first_segment = rest.split('/').next().unwrap_or_default();
+            if rest.is_empty() || rest.starts_with('/') || first_segment.contains(':') {
+                let mut prefixed = String::with_capacity(rest.len() + 2);
+                prefixed.push_str("./");
+                prefixed.push_str(rest);
+                return Some(Cow::Owned(prefixed));
+            }
+            return Some(Cow::Borrowed(rest));
+        }
+        if path.starts_with('/') && !path.starts_with("//") {
+            return Some(Cow::Borrowed(path));
+        }
+        None
+    }
+}
+
+impl<S: Spec> Transform<S> for Relativize<'_, S> {
+    fn transform(&self, iri: RiReferenceString<S>) -> Result<RiReferenceString<S>, Error> {
+        match iri.scheme_str() {
+            Some(scheme) if scheme.eq_ignore_ascii_case(self.base.scheme_str()) => {}
+            _ => return Ok(iri),
+        }
+        let has_authority = iri.authority_str().is_some();
+        let relative_path = if iri.authority_str() == self.base.authority_str() {
+            self.relative_path(iri.path_str()).map(Cow::into_owned)
+        } else {
+            None
+        };
+
+        let mut components = iri.clone().into_components();
+        components
+            .set_scheme(None)
+            .expect("[validity] removing the scheme always succeeds");
+        if let Some(path) = relative_path {
+            let mut path_relative = components.clone();
+            path_relative
+                .set_host(None)
+                .expect("[validity] removing the authority always succeeds");
+            path_relative
+                .set_path(&path)
+                .expect("[validity] the path consists of the valid path characters");
+            if let Ok(relative) = RiReferenceString::from_components(&path_relative) {
+                return Ok(relative);
+            }
+        }
+        if has_authority {
+            if let Ok(relative) = RiReferenceString::from_components(&components) {
+                return Ok(relative);
+            }
+        }
+        Ok(iri)
+    }
+}
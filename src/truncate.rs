@@ -0,0 +1,179 @@
+//! Truncating display of IRIs.
+
+use core::fmt::{self, Write as _};
+
+use crate::components::{AuthorityComponents, RiReferenceComponents};
+use crate::spec::Spec;
+use crate::types::RiReferenceStr;
+
+/// Ellipsis to be used in place of elided characters.
+const ELLIPSIS: char = '\u{2026}';
+
+/// A wrapper of an IRI string that truncates the IRI to the width budget when `Display`ed.
+///
+/// This is a return type of `display_truncated` method of IRI string types
+/// (such as [`RiStr::display_truncated`]).
+///
+/// The width is measured as the number of characters (i.e. Unicode scalar
+/// values), not the width on the terminal.
+///
+/// If the IRI is longer than the budget, the result is shortened as below
+/// (`…` is used as an ellipsis):
+///
+/// 1. The userinfo is removed, i.e. the scheme and the host (and the port) are kept.
+/// 2. The middle of the path is elided, i.e. the last path segment (and the
+///    following query and fragment) is kept.
+/// 3. If it is still too long, the end of the last path segment, the query,
+///    and the fragment are elided.
+/// 4. If it is still too long, the end of the scheme and the host is elided.
+///
+/// The result is only for display, and it is not an IRI in general.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::types::IriStr;
+///
+/// let iri = IriStr::new("https://user@example.com/very/long/path/to/file.html?q=1")?;
+/// assert_eq!(
+///     iri.display_truncated(40).to_string(),
+///     "https://example.com/very/\u{2026}/file.html?q=1"
+/// );
+/// assert_eq!(
+///     iri.display_truncated(24).to_string(),
+///     "https://example.com\u{2026}/fi\u{2026}"
+/// );
+/// assert_eq!(iri.display_truncated(10).to_string(), "https://e\u{2026}");
+///
+/// // Short IRIs are displayed as is.
+/// assert_eq!(iri.display_truncated(100).to_string(), iri.as_str());
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// [`RiStr::display_truncated`]: `crate::types::RiStr::display_truncated`
+#[derive(Clone, Copy)]
+pub struct Truncated<'a, S: Spec> {
+    /// IRI reference.
+    iri_ref: &'a RiReferenceStr<S>,
+    /// Maximum number of characters.
+    max_chars: usize,
+}
+
+impl<'a, S: Spec> Truncated<'a, S> {
+    /// Creates a new `Truncated` object.
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(iri_ref: &'a RiReferenceStr<S>, max_chars: usize) -> Self {
+        Self { iri_ref, max_chars }
+    }
+}
+
+impl<S: Spec> fmt::Display for Truncated<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.iri_ref.as_str();
+        let max = self.max_chars;
+        if char_len(s) <= max {
+            return f.write_str(s);
+        }
+        if max == 0 {
+            return Ok(());
+        }
+
+        let (scheme, authority, path, _query, _fragment) =
+            RiReferenceComponents::from(self.iri_ref).to_major();
+        let host_port = AuthorityComponents::from_iri(self.iri_ref)
+            .map(|authority| &authority.authority[authority.host_start..]);
+        let head_len = scheme.map_or(0, |scheme| char_len(scheme) + 1)
+            + host_port.map_or(0, |host_port| char_len(host_port) + 2);
+        let path_start = scheme.map_or(0, |scheme| scheme.len() + 1)
+            + authority.map_or(0, |authority| authority.len() + 2);
+        // The last path segment, the query, and the fragment.
+        let (middle, tail) = s[path_start..].split_at(path.rfind('/').unwrap_or(0));
+        let middle_len = char_len(middle);
+        let tail_len = char_len(tail);
+
+        let write_head = |f: &mut fmt::Formatter<'_>| -> fmt::Result {
+            if let Some(scheme) = scheme {
+                f.write_str(scheme)?;
+                f.write_char(':')?;
+            }
+            if let Some(host_port) = host_port {
+                f.write_str("//")?;
+                f.write_str(host_port)?;
+            }
+            Ok(())
+        };
+
+        if head_len + middle_len + tail_len <= max {
+            // Fits without the userinfo.
+            write_head(f)?;
+            f.write_str(middle)?;
+            return f.write_str(tail);
+        }
+        if head_len + 1 + tail_len <= max {
+            // Elide the middle of the path.
+            write_head(f)?;
+            f.write_str(char_prefix(middle, max - head_len - 1 - tail_len))?;
+            f.write_char(ELLIPSIS)?;
+            return f.write_str(tail);
+        }
+        let lead_len = usize::from(!middle.is_empty());
+        if head_len + lead_len + 1 < max {
+            // Elide the middle of the path and the end of the tail.
+            write_head(f)?;
+            if lead_len != 0 {
+                f.write_char(ELLIPSIS)?;
+            }
+            f.write_str(char_prefix(tail, max - head_len - lead_len - 1))?;
+            return f.write_char(ELLIPSIS);
+        }
+
+        if head_len == 0 {
+            // Elide the end of the relative path.
+            f.write_str(char_prefix(s, max - 1))?;
+            return f.write_char(ELLIPSIS);
+        }
+
+        // Elide the end of the head.
+        let mut rest = max - 1;
+        let mut write_part = |f: &mut fmt::Formatter<'_>, part: &str| -> fmt::Result {
+            let part = char_prefix(part, rest);
+            rest -= char_len(part);
+            f.write_str(part)
+        };
+        if let Some(scheme) = scheme {
+            write_part(f, scheme)?;
+            write_part(f, ":")?;
+        }
+        if let Some(host_port) = host_port {
+            write_part(f, "//")?;
+            write_part(f, host_port)?;
+        }
+        f.write_char(ELLIPSIS)
+    }
+}
+
+impl<S: Spec> fmt::Debug for Truncated<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('<')?;
+        fmt::Display::fmt(self, f)?;
+        f.write_char('>')
+    }
+}
+
+/// Returns the number of characters in the string.
+#[inline]
+#[must_use]
+fn char_len(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// Returns the prefix of the string with at most the given number of characters.
+#[must_use]
+fn char_prefix(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((pos, _)) => &s[..pos],
+        None => s,
+    }
+}
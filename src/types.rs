@@ -44,6 +44,13 @@
 //!     + String types for `ifragment` and `fragment` rules.
 //!     + Note that these types represents a substring of an IRI / URI references.
 //!       They are not intended to used directly as an IRI / URI references.
+//! * [`RiAuthorityStr`] and [`RiAuthorityString`]
+//!     + String types for `iauthority` and `authority` rules.
+//! * [`RiHostStr`] and [`RiHostString`]
+//!     + String types for `ihost` and `host` rules.
+//!     + Useful to store hosts alone, such as in configuration files.
+//! * [`RiPathStr`] and [`RiPathString`]
+//!     + String types for `ipath` and `path` rules.
 //!
 //! "Ri" stands for "Resource Identifier".
 //!
@@ -69,6 +76,9 @@
 //! `Deref` is implemented, `RiFooStr::len()` is available, `&RiFooString` can be coerced to
 //! `&RiFooStr`, `Cow<'_, RiFooStr>` and `Box<RiFooStr>` is available, and so on.
 //!
+//! Additionally, [`RiCow`] is provided as a dedicated borrowed-or-owned type
+//! for `RiStr` and `RiString`.
+//!
 //! # Hierarchy and safe conversion
 //!
 //! IRI syntaxes have the hierarchy below.
@@ -188,8 +198,15 @@
 //! [`RiString`]: struct.RiString.html
 //! [`RiAbsoluteStr`]: struct.RiAbsoluteStr.html
 //! [`RiAbsoluteString`]: struct.RiAbsoluteString.html
+//! [`RiCow`]: enum.RiCow.html
 //! [`RiFragmentStr`]: struct.RiFragmentStr.html
 //! [`RiFragmentString`]: struct.RiFragmentString.html
+//! [`RiAuthorityStr`]: struct.RiAuthorityStr.html
+//! [`RiAuthorityString`]: struct.RiAuthorityString.html
+//! [`RiHostStr`]: struct.RiHostStr.html
+//! [`RiHostString`]: struct.RiHostString.html
+//! [`RiPathStr`]: struct.RiPathStr.html
+//! [`RiPathString`]: struct.RiPathString.html
 //! [`RiReferenceStr`]: struct.RiReferenceStr.html
 //! [`RiReferenceString`]: struct.RiReferenceString.html
 //! [`RiReferenceString::into_iri()`]: struct.RiReferenceString.html#method.into_iri
@@ -198,25 +215,40 @@
 //! [`IriSpec`]: ../spec/enum.IriSpec.html
 //! [`UriSpec`]: ../spec/enum.UriSpec.html
 
+#[cfg(feature = "rkyv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+pub use self::generic::{
+    ArchivedRiAbsoluteString, ArchivedRiFragmentString, ArchivedRiQueryString,
+    ArchivedRiReferenceString, ArchivedRiRelativeString, ArchivedRiString, CheckArchivedError,
+};
 #[cfg(feature = "alloc")]
 pub use self::{
     generic::{
-        CreationError, RiAbsoluteString, RiFragmentString, RiQueryString, RiReferenceString,
-        RiRelativeString, RiString,
+        CreationError, RiAbsoluteString, RiAuthorityString, RiCow, RiFragmentString, RiHostString,
+        RiPathString, RiQueryString, RiReferenceString, RiRelativeString, RiString,
     },
     iri::{
-        IriAbsoluteString, IriFragmentString, IriQueryString, IriReferenceString,
-        IriRelativeString, IriString,
+        IriAbsoluteString, IriAuthorityString, IriCow, IriFragmentString, IriHostString,
+        IriPathString, IriQueryString, IriReferenceString, IriRelativeString, IriString,
     },
     uri::{
-        UriAbsoluteString, UriFragmentString, UriQueryString, UriReferenceString,
-        UriRelativeString, UriString,
+        UriAbsoluteString, UriAuthorityString, UriCow, UriFragmentString, UriHostString,
+        UriPathString, UriQueryString, UriReferenceString, UriRelativeString, UriString,
     },
 };
 pub use self::{
-    generic::{RiAbsoluteStr, RiFragmentStr, RiQueryStr, RiReferenceStr, RiRelativeStr, RiStr},
-    iri::{IriAbsoluteStr, IriFragmentStr, IriQueryStr, IriReferenceStr, IriRelativeStr, IriStr},
-    uri::{UriAbsoluteStr, UriFragmentStr, UriQueryStr, UriReferenceStr, UriRelativeStr, UriStr},
+    generic::{
+        RiAbsoluteStr, RiAuthorityStr, RiFragmentStr, RiHostStr, RiPathStr, RiQueryStr,
+        RiReferenceStr, RiRelativeStr, RiStr,
+    },
+    iri::{
+        IriAbsoluteStr, IriAuthorityStr, IriFragmentStr, IriHostStr, IriPathStr, IriQueryStr,
+        IriReferenceStr, IriRelativeStr, IriStr,
+    },
+    uri::{
+        UriAbsoluteStr, UriAuthorityStr, UriFragmentStr, UriHostStr, UriPathStr, UriQueryStr,
+        UriReferenceStr, UriRelativeStr, UriStr,
+    },
 };
 
 pub(crate) mod generic;
@@ -34,13 +34,20 @@
 //!     + `PartialEq` and `ParitalOrd`.
 //!     + Slice, owned, `Cow`, reference, etc...
 
+#[cfg(feature = "rkyv")]
+pub use self::archive::{
+    ArchivedRiAbsoluteString, ArchivedRiFragmentString, ArchivedRiQueryString,
+    ArchivedRiReferenceString, ArchivedRiRelativeString, ArchivedRiString, CheckArchivedError,
+};
 pub use self::{
-    absolute::RiAbsoluteStr, fragment::RiFragmentStr, normal::RiStr, query::RiQueryStr,
-    reference::RiReferenceStr, relative::RiRelativeStr,
+    absolute::RiAbsoluteStr, authority::RiAuthorityStr, fragment::RiFragmentStr, host::RiHostStr,
+    normal::RiStr, path::RiPathStr, query::RiQueryStr, reference::RiReferenceStr,
+    relative::RiRelativeStr,
 };
 #[cfg(feature = "alloc")]
 pub use self::{
-    absolute::RiAbsoluteString, error::CreationError, fragment::RiFragmentString, normal::RiString,
+    absolute::RiAbsoluteString, authority::RiAuthorityString, cow::RiCow, error::CreationError,
+    fragment::RiFragmentString, host::RiHostString, normal::RiString, path::RiPathString,
     query::RiQueryString, reference::RiReferenceString, relative::RiRelativeString,
 };
 
@@ -48,10 +55,27 @@ pub use self::{
 mod macros;
 
 mod absolute;
+#[cfg(feature = "rkyv")]
+mod archive;
+mod authority;
+#[cfg(feature = "alloc")]
+mod cow;
 #[cfg(feature = "alloc")]
 mod error;
 mod fragment;
+mod host;
+#[cfg(feature = "schemars")]
+mod json_schema;
 mod normal;
-mod query;
+#[cfg(feature = "std")]
+mod os_str;
+mod path;
+#[cfg(feature = "alloc")]
+mod path_edit;
+pub(crate) mod query;
+#[cfg(feature = "oxiri")]
+mod rdf;
 mod reference;
 mod relative;
+#[cfg(feature = "wasm")]
+mod wasm;
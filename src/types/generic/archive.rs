@@ -0,0 +1,203 @@
+//! Zero-copy archive support using `rkyv` crate.
+//!
+//! Owned IRI types are archived as strings. Archived strings are validated as
+//! IRIs on access with validation (i.e. via `CheckBytes`), so that archived
+//! values can be used as borrowed IRI types without parsing or allocation.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use rkyv::bytecheck::CheckBytes;
+use rkyv::ser::Serializer;
+use rkyv::string::{ArchivedString, StringResolver};
+use rkyv::validation::ArchiveContext;
+use rkyv::{Archive, Deserialize, Fallible, Serialize};
+
+use crate::spec::Spec;
+use crate::types::{
+    RiAbsoluteStr, RiAbsoluteString, RiFragmentStr, RiFragmentString, RiQueryStr, RiQueryString,
+    RiReferenceStr, RiReferenceString, RiRelativeStr, RiRelativeString, RiStr, RiString,
+};
+use crate::validate::Error;
+
+/// Error on checking an archived IRI string.
+#[derive(Debug)]
+pub enum CheckArchivedError<E> {
+    /// The archived string is broken.
+    String(E),
+    /// The archived string is not valid as the IRI type.
+    Validation(Error),
+}
+
+impl<E: fmt::Display> fmt::Display for CheckArchivedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(e) => write!(f, "invalid archived string: {}", e),
+            Self::Validation(e) => write!(f, "invalid archived IRI string: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<E: std::error::Error + 'static> std::error::Error for CheckArchivedError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::String(e) => Some(e),
+            Self::Validation(e) => Some(e),
+        }
+    }
+}
+
+/// Defines an archived type for the owned IRI type, and implements `rkyv` traits.
+macro_rules! impl_rkyv {
+    ($archived:ident, $owned:ident, $slice:ident) => {
+        #[doc = concat!(
+            "An archived [`", stringify!($owned), "`](struct.", stringify!($owned), ".html)."
+        )]
+        ///
+        /// This is dereferenced to the borrowed IRI type.
+        #[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+        #[repr(transparent)]
+        pub struct $archived<S: Spec> {
+            /// Archived string.
+            inner: ArchivedString,
+            /// Spec.
+            _spec: PhantomData<fn() -> S>,
+        }
+
+        impl<S: Spec> $archived<S> {
+            /// Returns the borrowed IRI.
+            #[inline]
+            #[must_use]
+            pub fn as_slice(&self) -> &$slice<S> {
+                // SAFETY: the archived value is created from the valid IRI
+                // string, or is validated by `CheckBytes`. Accessing archives
+                // without validation is `unsafe` and the caller guarantees
+                // that the archive is created from the valid value.
+                unsafe { $slice::new_maybe_unchecked(self.inner.as_str()) }
+            }
+
+            /// Returns `&str`.
+            #[inline]
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                self.inner.as_str()
+            }
+        }
+
+        impl<S: Spec> Deref for $archived<S> {
+            type Target = $slice<S>;
+
+            #[inline]
+            fn deref(&self) -> &$slice<S> {
+                self.as_slice()
+            }
+        }
+
+        impl<S: Spec> AsRef<str> for $archived<S> {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl<S: Spec> AsRef<$slice<S>> for $archived<S> {
+            #[inline]
+            fn as_ref(&self) -> &$slice<S> {
+                self.as_slice()
+            }
+        }
+
+        impl<S: Spec> fmt::Debug for $archived<S> {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(self.as_slice(), f)
+            }
+        }
+
+        impl<S: Spec> fmt::Display for $archived<S> {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(self.as_slice(), f)
+            }
+        }
+
+        impl<S: Spec> PartialEq<$owned<S>> for $archived<S> {
+            #[inline]
+            fn eq(&self, other: &$owned<S>) -> bool {
+                self.as_str() == other.as_str()
+            }
+        }
+
+        impl<S: Spec> PartialEq<$archived<S>> for $owned<S> {
+            #[inline]
+            fn eq(&self, other: &$archived<S>) -> bool {
+                self.as_str() == other.as_str()
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+        impl<S: Spec> Archive for $owned<S> {
+            type Archived = $archived<S>;
+            type Resolver = StringResolver;
+
+            #[inline]
+            unsafe fn resolve(&self, pos: usize, resolver: StringResolver, out: *mut $archived<S>) {
+                // SAFETY: `$archived<S>` is `repr(transparent)` wrapper of
+                // `ArchivedString`, and the caller guarantees the safety
+                // requirements of `Archive::resolve`.
+                unsafe {
+                    ArchivedString::resolve_from_str(self.as_str(), pos, resolver, out.cast());
+                }
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+        impl<S: Spec, Ser: Serializer + ?Sized> Serialize<Ser> for $owned<S> {
+            #[inline]
+            fn serialize(&self, serializer: &mut Ser) -> Result<StringResolver, Ser::Error> {
+                ArchivedString::serialize_from_str(self.as_str(), serializer)
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+        impl<S: Spec, D: Fallible + ?Sized> Deserialize<$owned<S>, D> for $archived<S> {
+            #[inline]
+            fn deserialize(&self, _: &mut D) -> Result<$owned<S>, D::Error> {
+                Ok(self.as_slice().into())
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+        impl<S: Spec, C: ArchiveContext + ?Sized> CheckBytes<C> for $archived<S>
+        where
+            ArchivedString: CheckBytes<C>,
+        {
+            type Error = CheckArchivedError<<ArchivedString as CheckBytes<C>>::Error>;
+
+            unsafe fn check_bytes<'a>(
+                value: *const Self,
+                context: &mut C,
+            ) -> Result<&'a Self, Self::Error> {
+                // SAFETY: `$archived<S>` is `repr(transparent)` wrapper of
+                // `ArchivedString`, and the caller guarantees the safety
+                // requirements of `CheckBytes::check_bytes`.
+                let inner = unsafe { ArchivedString::check_bytes(value.cast(), context) }
+                    .map_err(CheckArchivedError::String)?;
+                $slice::<S>::new(inner.as_str()).map_err(CheckArchivedError::Validation)?;
+                // SAFETY: the pointer is checked to point to the valid
+                // `ArchivedString` above, and the content is valid IRI.
+                Ok(unsafe { &*value })
+            }
+        }
+    };
+}
+
+impl_rkyv!(ArchivedRiString, RiString, RiStr);
+impl_rkyv!(ArchivedRiAbsoluteString, RiAbsoluteString, RiAbsoluteStr);
+impl_rkyv!(ArchivedRiReferenceString, RiReferenceString, RiReferenceStr);
+impl_rkyv!(ArchivedRiRelativeString, RiRelativeString, RiRelativeStr);
+impl_rkyv!(ArchivedRiQueryString, RiQueryString, RiQueryStr);
+impl_rkyv!(ArchivedRiFragmentString, RiFragmentString, RiFragmentStr);
@@ -0,0 +1,165 @@
+//! Authority string.
+
+use crate::components::AuthorityComponents;
+use crate::parser::trusted::authority::decompose_authority;
+use crate::{spec::Spec, types::RiHostStr, validate::authority};
+
+define_custom_string_slice! {
+    /// A borrowed slice of an IRI authority (i.e. after `//` and before the path).
+    ///
+    /// This corresponds to [`iauthority` rule] in [RFC 3987] (and [`authority` rule] in [RFC 3986]).
+    /// The rule for `iauthority` is `[ iuserinfo "@" ] ihost [ ":" port ]`.
+    ///
+    /// # Valid values
+    ///
+    /// This type can have an IRI authority.
+    /// Note that the IRI `foo://bar/baz` has the authority `bar`, **not** `//bar`.
+    ///
+    /// ```
+    /// # use iri_string::types::IriAuthorityStr;
+    /// assert!(IriAuthorityStr::new("").is_ok());
+    /// assert!(IriAuthorityStr::new("example.com").is_ok());
+    /// assert!(IriAuthorityStr::new("example.com:8080").is_ok());
+    /// assert!(IriAuthorityStr::new("user:pass@example.com:").is_ok());
+    /// assert!(IriAuthorityStr::new("[2001:db8::1]:443").is_ok());
+    /// ```
+    ///
+    /// Some characters and sequences cannot used in an authority.
+    ///
+    /// ```
+    /// # use iri_string::types::IriAuthorityStr;
+    /// // Leading `//` is not a part of an authority.
+    /// assert!(IriAuthorityStr::new("//example.com").is_err());
+    /// // Path cannot appear in an authority.
+    /// assert!(IriAuthorityStr::new("example.com/").is_err());
+    /// // Port should be digits.
+    /// assert!(IriAuthorityStr::new("example.com:http").is_err());
+    /// ```
+    ///
+    /// [RFC 3986]: https://tools.ietf.org/html/rfc3986
+    /// [RFC 3987]: https://tools.ietf.org/html/rfc3987
+    /// [`authority` rule]: https://tools.ietf.org/html/rfc3986#section-3.2
+    /// [`iauthority` rule]: https://tools.ietf.org/html/rfc3987#section-2.2
+    struct RiAuthorityStr {
+        validator = authority,
+        expecting_msg = "IRI authority string",
+    }
+}
+
+#[cfg(feature = "alloc")]
+define_custom_string_owned! {
+    /// An owned string of an IRI authority (i.e. after `//` and before the path).
+    ///
+    /// This corresponds to [`iauthority` rule] in [RFC 3987] (and [`authority` rule] in [RFC 3986]).
+    /// The rule for `iauthority` is `[ iuserinfo "@" ] ihost [ ":" port ]`.
+    ///
+    /// For details, see the documentation for [`RiAuthorityStr`].
+    ///
+    /// Enabled by `alloc` or `std` feature.
+    ///
+    /// [RFC 3986]: https://tools.ietf.org/html/rfc3986
+    /// [RFC 3987]: https://tools.ietf.org/html/rfc3987
+    /// [`authority` rule]: https://tools.ietf.org/html/rfc3986#section-3.2
+    /// [`iauthority` rule]: https://tools.ietf.org/html/rfc3987#section-2.2
+    /// [`RiAuthorityStr`]: struct.RiAuthorityStr.html
+    struct RiAuthorityString {
+        validator = authority,
+        slice = RiAuthorityStr,
+        expecting_msg = "IRI authority string",
+    }
+}
+
+impl<S: Spec> RiAuthorityStr<S> {
+    /// Returns the subcomponents of the authority.
+    #[inline]
+    #[must_use]
+    fn components(&self) -> AuthorityComponents<'_> {
+        decompose_authority(self.as_str())
+    }
+
+    /// Returns the userinfo, excluding the following `@`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriAuthorityStr;
+    ///
+    /// let authority = IriAuthorityStr::new("user:pass@example.com")?;
+    /// assert_eq!(authority.userinfo(), Some("user:pass"));
+    ///
+    /// let no_userinfo = IriAuthorityStr::new("example.com")?;
+    /// assert_eq!(no_userinfo.userinfo(), None);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn userinfo(&self) -> Option<&str> {
+        self.components().userinfo()
+    }
+
+    /// Returns the host.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriAuthorityStr;
+    ///
+    /// let authority = IriAuthorityStr::new("user@[2001:db8::1]:443")?;
+    /// assert_eq!(authority.host(), "[2001:db8::1]");
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn host(&self) -> &RiHostStr<S> {
+        let host = self.components().host();
+        // SAFETY: the host part of a valid authority is a valid host.
+        unsafe { RiHostStr::new_maybe_unchecked(host) }
+    }
+
+    /// Returns the port, excluding the leading `:`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriAuthorityStr;
+    ///
+    /// assert_eq!(IriAuthorityStr::new("example.com:8080")?.port(), Some("8080"));
+    /// // Empty port is distinguished from the absent port.
+    /// assert_eq!(IriAuthorityStr::new("example.com:")?.port(), Some(""));
+    /// assert_eq!(IriAuthorityStr::new("example.com")?.port(), None);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn port(&self) -> Option<&str> {
+        self.components().port()
+    }
+
+    /// Compares the authorities ignoring ASCII case of the hosts.
+    ///
+    /// Userinfo and port are compared as is, since userinfo is case
+    /// sensitive. For details of the host comparison, see
+    /// [`RiHostStr::eq_ignore_case`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriAuthorityStr;
+    ///
+    /// let authority = IriAuthorityStr::new("user@Example.COM:8080")?;
+    /// assert!(authority.eq_ignore_case(IriAuthorityStr::new("user@example.com:8080")?));
+    /// // Userinfo is case sensitive.
+    /// assert!(!authority.eq_ignore_case(IriAuthorityStr::new("USER@example.com:8080")?));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[must_use]
+    pub fn eq_ignore_case(&self, other: &Self) -> bool {
+        self.userinfo() == other.userinfo()
+            && self.host().eq_ignore_case(other.host())
+            && self.port() == other.port()
+    }
+}
@@ -0,0 +1,328 @@
+//! Borrowed or owned IRI.
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String;
+
+use crate::spec::Spec;
+use crate::types::{CreationError, RiReferenceStr, RiStr, RiString};
+use crate::validate::Error;
+
+/// A borrowed or owned IRI string.
+///
+/// This is similar to `Cow<'a, RiStr<S>>`, but dedicated to IRIs: it can be
+/// dereferenced to [`RiStr`] so that all methods of the borrowed IRI type are
+/// available, and the IRI is guaranteed to be valid in both variants.
+///
+/// APIs can accept `impl Into<RiCow<'a, S>>` to take either a borrowed or an
+/// owned IRI without forcing an allocation.
+///
+/// # Examples
+///
+/// ```
+/// # use iri_string::validate::Error;
+/// use iri_string::types::{IriCow, IriStr, IriString};
+///
+/// fn host_of<'a>(iri: impl Into<IriCow<'a>>) -> Option<String> {
+///     let iri = iri.into();
+///     iri.authority_components().map(|a| a.host().to_owned())
+/// }
+///
+/// let borrowed = IriStr::new("https://example.com/")?;
+/// assert_eq!(host_of(borrowed).as_deref(), Some("example.com"));
+///
+/// let owned: IriString = IriStr::new("https://example.org/")?.to_owned();
+/// assert_eq!(host_of(owned).as_deref(), Some("example.org"));
+/// # Ok::<_, Error>(())
+/// ```
+///
+/// [`RiStr`]: struct.RiStr.html
+pub enum RiCow<'a, S: Spec> {
+    /// Borrowed IRI.
+    Borrowed(&'a RiStr<S>),
+    /// Owned IRI.
+    Owned(RiString<S>),
+}
+
+impl<'a, S: Spec> RiCow<'a, S> {
+    /// Creates a borrowed `RiCow` from the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriCow;
+    ///
+    /// let iri = IriCow::new("https://example.com/")?;
+    /// assert!(iri.is_borrowed());
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub fn new(s: &'a str) -> Result<Self, Error> {
+        RiStr::new(s).map(Self::Borrowed)
+    }
+
+    /// Returns the borrowed IRI.
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &RiStr<S> {
+        match self {
+            Self::Borrowed(v) => v,
+            Self::Owned(v) => v.as_slice(),
+        }
+    }
+
+    /// Returns `&str`.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.as_slice().as_str()
+    }
+
+    /// Returns true if the IRI is borrowed.
+    #[inline]
+    #[must_use]
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, Self::Borrowed(_))
+    }
+
+    /// Returns true if the IRI is owned.
+    #[inline]
+    #[must_use]
+    pub fn is_owned(&self) -> bool {
+        matches!(self, Self::Owned(_))
+    }
+
+    /// Returns the mutable reference to the owned IRI, cloning the borrowed IRI if necessary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::{IriCow, IriFragmentStr};
+    ///
+    /// let mut iri = IriCow::new("https://example.com/")?;
+    /// iri.to_mut().set_fragment(Some(IriFragmentStr::new("top")?));
+    /// assert!(iri.is_owned());
+    /// assert_eq!(iri, "https://example.com/#top");
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub fn to_mut(&mut self) -> &mut RiString<S> {
+        if let Self::Borrowed(v) = *self {
+            *self = Self::Owned(v.to_owned());
+        }
+        match self {
+            Self::Owned(v) => v,
+            Self::Borrowed(_) => unreachable!("[consistency] should have been converted to owned"),
+        }
+    }
+
+    /// Returns the owned IRI, cloning the borrowed IRI if necessary.
+    #[inline]
+    #[must_use]
+    pub fn into_owned(self) -> RiString<S> {
+        match self {
+            Self::Borrowed(v) => v.to_owned(),
+            Self::Owned(v) => v,
+        }
+    }
+}
+
+impl<S: Spec> Clone for RiCow<'_, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self {
+            Self::Borrowed(v) => Self::Borrowed(v),
+            Self::Owned(v) => Self::Owned(v.clone()),
+        }
+    }
+}
+
+impl<S: Spec> fmt::Debug for RiCow<'_, S> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<S: Spec> fmt::Display for RiCow<'_, S> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_slice(), f)
+    }
+}
+
+impl<S: Spec> Deref for RiCow<'_, S> {
+    type Target = RiStr<S>;
+
+    #[inline]
+    fn deref(&self) -> &RiStr<S> {
+        self.as_slice()
+    }
+}
+
+impl<S: Spec> AsRef<str> for RiCow<'_, S> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<S: Spec> AsRef<RiStr<S>> for RiCow<'_, S> {
+    #[inline]
+    fn as_ref(&self) -> &RiStr<S> {
+        self.as_slice()
+    }
+}
+
+impl<S: Spec> AsRef<RiReferenceStr<S>> for RiCow<'_, S> {
+    #[inline]
+    fn as_ref(&self) -> &RiReferenceStr<S> {
+        self.as_slice().as_ref()
+    }
+}
+
+impl<S: Spec> Borrow<RiStr<S>> for RiCow<'_, S> {
+    #[inline]
+    fn borrow(&self) -> &RiStr<S> {
+        self.as_slice()
+    }
+}
+
+impl<S: Spec> PartialEq for RiCow<'_, S> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<S: Spec> Eq for RiCow<'_, S> {}
+
+impl<S: Spec> PartialOrd for RiCow<'_, S> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: Spec> Ord for RiCow<'_, S> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<S: Spec> Hash for RiCow<'_, S> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Consistent with `Borrow<RiStr<S>>`.
+        self.as_slice().hash(state);
+    }
+}
+
+impl<S: Spec> PartialEq<str> for RiCow<'_, S> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<S: Spec> PartialEq<&str> for RiCow<'_, S> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<S: Spec> PartialEq<RiStr<S>> for RiCow<'_, S> {
+    #[inline]
+    fn eq(&self, other: &RiStr<S>) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<S: Spec> PartialEq<&RiStr<S>> for RiCow<'_, S> {
+    #[inline]
+    fn eq(&self, other: &&RiStr<S>) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<S: Spec> PartialEq<RiString<S>> for RiCow<'_, S> {
+    #[inline]
+    fn eq(&self, other: &RiString<S>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<'a, S: Spec> From<&'a RiStr<S>> for RiCow<'a, S> {
+    #[inline]
+    fn from(s: &'a RiStr<S>) -> Self {
+        Self::Borrowed(s)
+    }
+}
+
+impl<'a, S: Spec> From<&'a RiString<S>> for RiCow<'a, S> {
+    #[inline]
+    fn from(s: &'a RiString<S>) -> Self {
+        Self::Borrowed(s.as_slice())
+    }
+}
+
+impl<S: Spec> From<RiString<S>> for RiCow<'_, S> {
+    #[inline]
+    fn from(s: RiString<S>) -> Self {
+        Self::Owned(s)
+    }
+}
+
+impl<'a, S: Spec> From<Cow<'a, RiStr<S>>> for RiCow<'a, S> {
+    #[inline]
+    fn from(s: Cow<'a, RiStr<S>>) -> Self {
+        match s {
+            Cow::Borrowed(v) => Self::Borrowed(v),
+            Cow::Owned(v) => Self::Owned(v),
+        }
+    }
+}
+
+impl<'a, S: Spec> From<RiCow<'a, S>> for Cow<'a, RiStr<S>> {
+    #[inline]
+    fn from(s: RiCow<'a, S>) -> Self {
+        match s {
+            RiCow::Borrowed(v) => Cow::Borrowed(v),
+            RiCow::Owned(v) => Cow::Owned(v),
+        }
+    }
+}
+
+impl<S: Spec> From<RiCow<'_, S>> for RiString<S> {
+    #[inline]
+    fn from(s: RiCow<'_, S>) -> Self {
+        s.into_owned()
+    }
+}
+
+impl<'a, S: Spec> TryFrom<&'a str> for RiCow<'a, S> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl<S: Spec> TryFrom<String> for RiCow<'_, S> {
+    type Error = CreationError<String>;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        RiString::try_from(s).map(Self::Owned)
+    }
+}
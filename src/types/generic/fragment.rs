@@ -3,6 +3,7 @@
 use core::convert::TryFrom;
 
 use crate::{
+    percent_decode,
     spec::Spec,
     validate::{fragment, Error},
 };
@@ -107,4 +108,46 @@ impl<S: Spec> RiFragmentStr<S> {
         }
         TryFrom::try_from(&s[1..])
     }
+
+    /// Returns true if the percent-decoded fragment contains the given string.
+    ///
+    /// The fragment is decoded on the fly, so this does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriFragmentStr;
+    ///
+    /// let fragment = IriFragmentStr::new("section%203.2")?;
+    /// assert!(fragment.contains_decoded("section 3"));
+    /// assert!(!fragment.contains_decoded("%20"));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_decoded(&self, needle: &str) -> bool {
+        self.find_decoded(needle).is_some()
+    }
+
+    /// Returns the byte position in the (encoded) fragment where the
+    /// percent-decoded fragment first contains the given string.
+    #[must_use]
+    pub fn find_decoded(&self, needle: &str) -> Option<usize> {
+        percent_decode::find_decoded(self.as_str().as_bytes(), needle.as_bytes())
+    }
+
+    /// Returns true if the percent-decoded fragment starts with the given string.
+    #[inline]
+    #[must_use]
+    pub fn starts_with_decoded(&self, prefix: &str) -> bool {
+        percent_decode::starts_with_decoded(self.as_str().as_bytes(), prefix.as_bytes())
+    }
+
+    /// Returns true if the percent-decoded fragment ends with the given string.
+    #[inline]
+    #[must_use]
+    pub fn ends_with_decoded(&self, suffix: &str) -> bool {
+        percent_decode::ends_with_decoded(self.as_str().as_bytes(), suffix.as_bytes())
+    }
 }
@@ -0,0 +1,200 @@
+//! Host string.
+
+use crate::{spec::Spec, validate::host};
+
+define_custom_string_slice! {
+    /// A borrowed slice of an IRI host (i.e. reg-name or IP address).
+    ///
+    /// This corresponds to [`ihost` rule] in [RFC 3987] (and [`host` rule] in [RFC 3986]).
+    /// The rule for `ihost` is `IP-literal / IPv4address / ireg-name`.
+    ///
+    /// This type is intended to store hosts alone, for example in
+    /// configuration files and allowlists, without constructing whole IRIs.
+    ///
+    /// # Valid values
+    ///
+    /// This type can have an IRI host, without userinfo and port.
+    /// Note that an empty string is a valid (empty) reg-name.
+    ///
+    /// ```
+    /// # use iri_string::types::IriHostStr;
+    /// assert!(IriHostStr::new("").is_ok());
+    /// assert!(IriHostStr::new("example.com").is_ok());
+    /// assert!(IriHostStr::new("192.0.2.1").is_ok());
+    /// assert!(IriHostStr::new("[2001:db8::1]").is_ok());
+    /// assert!(IriHostStr::new("\u{03B1}.example").is_ok());
+    /// ```
+    ///
+    /// Some characters and sequences cannot used in a host.
+    ///
+    /// ```
+    /// # use iri_string::types::IriHostStr;
+    /// // Port cannot appear in a host.
+    /// assert!(IriHostStr::new("example.com:8080").is_err());
+    /// // Userinfo cannot appear in a host.
+    /// assert!(IriHostStr::new("user@example.com").is_err());
+    /// // IPv6 address should be enclosed by brackets.
+    /// assert!(IriHostStr::new("2001:db8::1").is_err());
+    /// // Broken percent encoding cannot appear in a host.
+    /// assert!(IriHostStr::new("%GG").is_err());
+    /// ```
+    ///
+    /// [RFC 3986]: https://tools.ietf.org/html/rfc3986
+    /// [RFC 3987]: https://tools.ietf.org/html/rfc3987
+    /// [`host` rule]: https://tools.ietf.org/html/rfc3986#section-3.2.2
+    /// [`ihost` rule]: https://tools.ietf.org/html/rfc3987#section-2.2
+    struct RiHostStr {
+        validator = host,
+        expecting_msg = "IRI host string",
+    }
+}
+
+#[cfg(feature = "alloc")]
+define_custom_string_owned! {
+    /// An owned string of an IRI host (i.e. reg-name or IP address).
+    ///
+    /// This corresponds to [`ihost` rule] in [RFC 3987] (and [`host` rule] in [RFC 3986]).
+    /// The rule for `ihost` is `IP-literal / IPv4address / ireg-name`.
+    ///
+    /// For details, see the documentation for [`RiHostStr`].
+    ///
+    /// Enabled by `alloc` or `std` feature.
+    ///
+    /// [RFC 3986]: https://tools.ietf.org/html/rfc3986
+    /// [RFC 3987]: https://tools.ietf.org/html/rfc3987
+    /// [`host` rule]: https://tools.ietf.org/html/rfc3986#section-3.2.2
+    /// [`ihost` rule]: https://tools.ietf.org/html/rfc3987#section-2.2
+    /// [`RiHostStr`]: struct.RiHostStr.html
+    struct RiHostString {
+        validator = host,
+        slice = RiHostStr,
+        expecting_msg = "IRI host string",
+    }
+}
+
+impl<S: Spec> RiHostStr<S> {
+    /// Compares the hosts ignoring ASCII case.
+    ///
+    /// Hosts are case insensitive (see [RFC 3986 section 3.2.2]), and so are
+    /// hexadecimal digits in percent-encoded triplets and IP addresses.
+    /// Note that non-ASCII characters are compared as is, and percent-encoded
+    /// triplets are not decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriHostStr;
+    ///
+    /// let host = IriHostStr::new("Example.COM")?;
+    /// assert_ne!(host, "example.com");
+    /// assert!(host.eq_ignore_case(IriHostStr::new("example.com")?));
+    /// assert!(!host.eq_ignore_case(IriHostStr::new("example.org")?));
+    ///
+    /// let ipv6 = IriHostStr::new("[2001:DB8::1]")?;
+    /// assert!(ipv6.eq_ignore_case(IriHostStr::new("[2001:db8::1]")?));
+    /// # Ok::<_, Error>(())
+    /// ```
+    ///
+    /// [RFC 3986 section 3.2.2]: https://tools.ietf.org/html/rfc3986#section-3.2.2
+    #[inline]
+    #[must_use]
+    pub fn eq_ignore_case(&self, other: &Self) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_str())
+    }
+
+    /// Returns whether the host is an IP literal (i.e. enclosed by brackets).
+    ///
+    /// This returns `false` for IPv4 addresses, since they are not
+    /// syntactically distinguished from reg-names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriHostStr;
+    ///
+    /// assert!(IriHostStr::new("[2001:db8::1]")?.is_ip_literal());
+    /// assert!(IriHostStr::new("[v1.fe]")?.is_ip_literal());
+    /// assert!(!IriHostStr::new("192.0.2.1")?.is_ip_literal());
+    /// assert!(!IriHostStr::new("example.com")?.is_ip_literal());
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_ip_literal(&self) -> bool {
+        self.as_str().starts_with('[')
+    }
+
+    /// Returns the IP address if the host is an IPv4 address or an IPv6 address literal.
+    ///
+    /// Returns `None` for reg-names and IPvFuture literals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use iri_string::types::IriHostStr;
+    ///
+    /// assert_eq!(
+    ///     IriHostStr::new("192.0.2.1")?.to_ip_addr(),
+    ///     Some(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)))
+    /// );
+    /// assert_eq!(
+    ///     IriHostStr::new("[2001:DB8::1]")?.to_ip_addr(),
+    ///     Some(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)))
+    /// );
+    /// assert_eq!(IriHostStr::new("[v1.fe]")?.to_ip_addr(), None);
+    /// assert_eq!(IriHostStr::new("example.com")?.to_ip_addr(), None);
+    /// // Leading zeros are not allowed in IPv4 addresses, so this is a reg-name.
+    /// assert_eq!(IriHostStr::new("192.0.2.01")?.to_ip_addr(), None);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    pub fn to_ip_addr(&self) -> Option<std::net::IpAddr> {
+        let s = self.as_str();
+        match s.strip_prefix('[') {
+            Some(literal) => literal
+                .strip_suffix(']')?
+                .parse::<std::net::Ipv6Addr>()
+                .ok()
+                .map(Into::into),
+            None => s.parse::<std::net::Ipv4Addr>().ok().map(Into::into),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<S: Spec> RiHostString<S> {
+    /// Creates a host from the IP address.
+    ///
+    /// IPv6 addresses are enclosed by brackets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    /// use iri_string::types::IriHostString;
+    ///
+    /// let v4 = IriHostString::from_ip(Ipv4Addr::new(192, 0, 2, 1).into());
+    /// assert_eq!(v4, "192.0.2.1");
+    ///
+    /// let addr = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+    /// let v6 = IriHostString::from_ip(addr);
+    /// assert_eq!(v6, "[2001:db8::1]");
+    /// assert_eq!(v6.to_ip_addr(), Some(addr));
+    /// ```
+    #[must_use]
+    pub fn from_ip(addr: std::net::IpAddr) -> Self {
+        let s = match addr {
+            std::net::IpAddr::V4(v4) => v4.to_string(),
+            std::net::IpAddr::V6(v6) => format!("[{}]", v6),
+        };
+        // SAFETY: IPv4 addresses and IPv6 address literals are valid hosts.
+        unsafe { Self::new_maybe_unchecked(s) }
+    }
+}
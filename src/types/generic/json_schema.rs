@@ -0,0 +1,92 @@
+//! JSON Schema support using `schemars` crate.
+//!
+//! IRI types are described as strings with the corresponding `format`
+//! defined in [JSON Schema Validation], so that OpenAPI generators and
+//! validators can treat them properly.
+//!
+//! [JSON Schema Validation]: https://json-schema.org/draft/2020-12/json-schema-validation.html#name-resource-identifiers
+
+use std::borrow::Cow;
+use std::borrow::ToOwned;
+use std::string::String;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
+
+use crate::types::{
+    IriAbsoluteStr, IriAbsoluteString, IriFragmentStr, IriFragmentString, IriQueryStr,
+    IriQueryString, IriReferenceStr, IriReferenceString, IriRelativeStr, IriRelativeString, IriStr,
+    IriString, UriAbsoluteStr, UriAbsoluteString, UriFragmentStr, UriFragmentString, UriQueryStr,
+    UriQueryString, UriReferenceStr, UriReferenceString, UriRelativeStr, UriRelativeString, UriStr,
+    UriString,
+};
+
+/// Returns a string schema with the given format.
+fn string_schema(format: Option<&str>) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        format: format.map(ToOwned::to_owned),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// Implements `JsonSchema` for the given types.
+macro_rules! impl_json_schema {
+    ($($ty:ident => $format:expr,)*) => {
+        $(
+            #[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+            impl JsonSchema for $ty {
+                #[inline]
+                fn is_referenceable() -> bool {
+                    false
+                }
+
+                #[inline]
+                fn schema_name() -> String {
+                    stringify!($ty).to_owned()
+                }
+
+                #[inline]
+                fn schema_id() -> Cow<'static, str> {
+                    Cow::Borrowed(concat!("iri_string::types::", stringify!($ty)))
+                }
+
+                #[inline]
+                fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                    string_schema($format)
+                }
+            }
+        )*
+    };
+}
+
+// An absolute IRI is also an IRI, and a relative reference is also an IRI
+// reference. Query and fragment have no dedicated formats.
+impl_json_schema! {
+    IriStr => Some("iri"),
+    IriString => Some("iri"),
+    IriAbsoluteStr => Some("iri"),
+    IriAbsoluteString => Some("iri"),
+    IriReferenceStr => Some("iri-reference"),
+    IriReferenceString => Some("iri-reference"),
+    IriRelativeStr => Some("iri-reference"),
+    IriRelativeString => Some("iri-reference"),
+    IriQueryStr => None,
+    IriQueryString => None,
+    IriFragmentStr => None,
+    IriFragmentString => None,
+    UriStr => Some("uri"),
+    UriString => Some("uri"),
+    UriAbsoluteStr => Some("uri"),
+    UriAbsoluteString => Some("uri"),
+    UriReferenceStr => Some("uri-reference"),
+    UriReferenceString => Some("uri-reference"),
+    UriRelativeStr => Some("uri-reference"),
+    UriRelativeString => Some("uri-reference"),
+    UriQueryStr => None,
+    UriQueryString => None,
+    UriFragmentStr => None,
+    UriFragmentString => None,
+}
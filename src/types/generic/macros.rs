@@ -1,5 +1,283 @@
 //! Macros to define resource identifier types.
 
+/// Asserts that the string is still valid after an in-place mutation.
+///
+/// Mutation APIs validate only the changed component and trust the others, so
+/// the whole string is not validated again. This re-validates the whole string
+/// in debug build, or always if `validate-mutations` feature is enabled.
+#[cfg(feature = "alloc")]
+macro_rules! debug_assert_valid_mutation {
+    ($($arg:tt)*) => {
+        if cfg!(any(debug_assertions, feature = "validate-mutations")) {
+            assert!($($arg)*);
+        }
+    };
+}
+
+/// Implements `tracing_value()` method for a slice type.
+///
+/// This should be invoked inside the inherent `impl` block of the slice type
+/// which has `mask_password()` method. `doc_slice` is the IRI type alias used
+/// in the example, and `example` is a pair of the input and the expected
+/// recorded value.
+macro_rules! impl_tracing_value {
+    (
+        doc_slice = $doc_slice:ident,
+        example = ($src:literal, $dst:literal),
+    ) => {
+        /// Returns the value to be recorded by [`tracing`], with the password masked.
+        ///
+        /// The IRI is recorded as its [`mask_password`][`Self::mask_password`]
+        /// form, so that credentials won't be leaked into logs. To record the raw
+        /// IRI, use [`tracing::field::display`] explicitly.
+        ///
+        /// IRI types do not implement [`tracing::Value`] trait directly,
+        /// since the trait is sealed and cannot be implemented outside of
+        /// `tracing-core` crate. Use this method to pass IRIs as field values.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use iri_string::validate::Error;
+        #[doc = concat!("use iri_string::types::", stringify!($doc_slice), ";")]
+        ///
+        #[doc = concat!("let iri = ", stringify!($doc_slice), "::new(", stringify!($src), ")?;")]
+        /// let value = iri.tracing_value();
+        /// // `tracing::info!(iri = iri.tracing_value())` records the masked IRI.
+        #[doc = concat!("assert_eq!(format!(\"{:?}\", value), ", stringify!($dst), ");")]
+        /// # Ok::<_, Error>(())
+        /// ```
+        ///
+        /// [`tracing`]: https://docs.rs/tracing
+        /// [`tracing::Value`]: https://docs.rs/tracing/0.1/tracing/trait.Value.html
+        /// [`tracing::field::display`]: https://docs.rs/tracing/0.1/tracing/field/fn.display.html
+        #[cfg(feature = "tracing")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+        #[inline]
+        #[must_use]
+        pub fn tracing_value(
+            &self,
+        ) -> tracing_core::field::DisplayValue<crate::mask_password::PasswordMasked<'_, Self>> {
+            tracing_core::field::display(self.mask_password())
+        }
+    };
+}
+
+/// Implements path editing methods for an owned string type.
+///
+/// This should be invoked inside the inherent `impl` block of the owned type.
+/// `doc_owned` and `doc_slice` are the IRI type aliases used in the examples,
+/// and each method takes a pair of the input and the expected output as the
+/// example.
+#[cfg(feature = "alloc")]
+macro_rules! impl_path_edit_methods {
+    (
+        slice = $slice:ident,
+        doc_owned = $doc_owned:ident,
+        doc_slice = $doc_slice:ident,
+        ensure_trailing_slash = ($ensure_src:literal, $ensure_dst:literal),
+        strip_trailing_slash = ($strip_src:literal, $strip_dst:literal),
+        strip_index_segment = ($index_src:literal, $index_dst:literal),
+        set_path = ($set_path_src:literal, $set_path_path:literal, $set_path_dst:literal),
+        collapse_empty_segments = ($collapse_src:literal, $collapse_dst:literal),
+    ) => {
+        /// Replaces the path.
+        ///
+        /// Only the given path is validated, in the context of the other
+        /// components: a path after an authority must be empty or start with a
+        /// slash, a path without an authority must not start with `//`, and the
+        /// first segment of a path without a scheme and an authority must not
+        /// contain a colon. The other components are not validated again.
+        ///
+        /// # Failures
+        ///
+        /// Fails if the path is invalid in the context. `self` is not modified
+        /// in that case.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use iri_string::validate::Error;
+        /// # #[cfg(feature = "alloc")] {
+        #[doc = concat!("use iri_string::types::{", stringify!($doc_owned), ", ", stringify!($doc_slice), "};")]
+        ///
+        #[doc = concat!("let mut iri: ", stringify!($doc_owned), " = ", stringify!($doc_slice), "::new(", stringify!($set_path_src), ")?.into();")]
+        #[doc = concat!("iri.set_path(", stringify!($set_path_path), ")?;")]
+        #[doc = concat!("assert_eq!(iri, ", stringify!($set_path_dst), ");")]
+        /// # }
+        /// # Ok::<_, Error>(())
+        /// ```
+        pub fn set_path(&mut self, path: &str) -> Result<(), crate::validate::Error> {
+            let edit = crate::types::generic::path_edit::set_path(self.as_slice().into(), path)?;
+            // SAFETY: the path is valid in the context of the other components.
+            unsafe {
+                let buf = self.as_inner_mut();
+                edit.apply(buf);
+                debug_assert_valid_mutation!(
+                    <$slice<S>>::new(buf).is_ok(),
+                    "[validity] the IRI must be valid after the path is replaced"
+                );
+            }
+            Ok(())
+        }
+
+        /// Appends a slash to the path if the path does not end with a slash.
+        ///
+        /// This does nothing if the IRI is opaque (such as `mailto:` IRIs).
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use iri_string::validate::Error;
+        /// # #[cfg(feature = "alloc")] {
+        #[doc = concat!("use iri_string::types::{", stringify!($doc_owned), ", ", stringify!($doc_slice), "};")]
+        ///
+        #[doc = concat!("let mut iri: ", stringify!($doc_owned), " = ", stringify!($doc_slice), "::new(", stringify!($ensure_src), ")?.into();")]
+        /// iri.ensure_trailing_slash();
+        #[doc = concat!("assert_eq!(iri, ", stringify!($ensure_dst), ");")]
+        /// # }
+        /// # Ok::<_, Error>(())
+        /// ```
+        pub fn ensure_trailing_slash(&mut self) {
+            let edit = match crate::types::generic::path_edit::ensure_trailing_slash(
+                self.as_slice().into(),
+            ) {
+                Some(v) => v,
+                None => return,
+            };
+            // SAFETY: appending a slash to the non-opaque path keeps the IRI valid.
+            unsafe {
+                let buf = self.as_inner_mut();
+                edit.apply(buf);
+                debug_assert_valid_mutation!(
+                    <$slice<S>>::new(buf).is_ok(),
+                    "[validity] the IRI must be valid after a slash is appended to the path"
+                );
+            }
+        }
+
+        /// Removes a trailing slash of the path.
+        ///
+        /// Only one slash is removed, and the root path `/` is kept as is.
+        /// This does nothing if the IRI is opaque (such as `mailto:` IRIs).
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use iri_string::validate::Error;
+        /// # #[cfg(feature = "alloc")] {
+        #[doc = concat!("use iri_string::types::{", stringify!($doc_owned), ", ", stringify!($doc_slice), "};")]
+        ///
+        #[doc = concat!("let mut iri: ", stringify!($doc_owned), " = ", stringify!($doc_slice), "::new(", stringify!($strip_src), ")?.into();")]
+        /// iri.strip_trailing_slash();
+        #[doc = concat!("assert_eq!(iri, ", stringify!($strip_dst), ");")]
+        /// # }
+        /// # Ok::<_, Error>(())
+        /// ```
+        pub fn strip_trailing_slash(&mut self) {
+            let edit = match crate::types::generic::path_edit::strip_trailing_slash(
+                self.as_slice().into(),
+            ) {
+                Some(v) => v,
+                None => return,
+            };
+            // SAFETY: removing the trailing slash other than the root path keeps
+            // the IRI valid.
+            unsafe {
+                let buf = self.as_inner_mut();
+                edit.apply(buf);
+                debug_assert_valid_mutation!(
+                    <$slice<S>>::new(buf).is_ok(),
+                    "[validity] the IRI must be valid after the trailing slash is removed"
+                );
+            }
+        }
+
+        /// Removes the last path segment if it is one of the given index document names.
+        ///
+        /// The slash before the removed segment is kept, so `/docs/index.html`
+        /// becomes `/docs/`. Names are compared case-sensitively and without
+        /// percent-decoding. This does nothing if the IRI is opaque (such as
+        /// `mailto:` IRIs).
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use iri_string::validate::Error;
+        /// # #[cfg(feature = "alloc")] {
+        #[doc = concat!("use iri_string::types::{", stringify!($doc_owned), ", ", stringify!($doc_slice), "};")]
+        ///
+        #[doc = concat!("let mut iri: ", stringify!($doc_owned), " = ", stringify!($doc_slice), "::new(", stringify!($index_src), ")?.into();")]
+        /// iri.strip_index_segment(&["index.html", "index.htm"]);
+        #[doc = concat!("assert_eq!(iri, ", stringify!($index_dst), ");")]
+        /// # }
+        /// # Ok::<_, Error>(())
+        /// ```
+        pub fn strip_index_segment(&mut self, names: &[&str]) {
+            let edit = match crate::types::generic::path_edit::strip_index_segment(
+                self.as_slice().into(),
+                names,
+            ) {
+                Some(v) => v,
+                None => return,
+            };
+            // SAFETY: removing the last non-opaque path segment keeps the IRI valid.
+            unsafe {
+                let buf = self.as_inner_mut();
+                edit.apply(buf);
+                debug_assert_valid_mutation!(
+                    <$slice<S>>::new(buf).is_ok(),
+                    "[validity] the IRI must be valid after the last path segment is removed"
+                );
+            }
+        }
+
+        /// Collapses runs of slashes in the path into single slashes.
+        ///
+        /// Empty path segments are valid and significant in RFC 3986, so this is
+        /// not a part of normalization. However, many servers treat them as
+        /// noise, and collapsing them is useful to deduplicate IRIs for such
+        /// servers. This does nothing if the IRI is opaque (such as `mailto:`
+        /// IRIs).
+        ///
+        /// Returns true if the path is changed.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # use iri_string::validate::Error;
+        /// # #[cfg(feature = "alloc")] {
+        #[doc = concat!("use iri_string::types::{", stringify!($doc_owned), ", ", stringify!($doc_slice), "};")]
+        ///
+        #[doc = concat!("let mut iri: ", stringify!($doc_owned), " = ", stringify!($doc_slice), "::new(", stringify!($collapse_src), ")?.into();")]
+        /// assert!(iri.collapse_empty_segments());
+        #[doc = concat!("assert_eq!(iri, ", stringify!($collapse_dst), ");")]
+        /// assert!(!iri.collapse_empty_segments());
+        /// # }
+        /// # Ok::<_, Error>(())
+        /// ```
+        pub fn collapse_empty_segments(&mut self) -> bool {
+            let edit = match crate::types::generic::path_edit::collapse_empty_segments(
+                self.as_slice().into(),
+            ) {
+                Some(v) => v,
+                None => return false,
+            };
+            // SAFETY: collapsing the slashes in the non-opaque path keeps the IRI
+            // valid, since the first path segment is kept unless it is empty.
+            unsafe {
+                let buf = self.as_inner_mut();
+                edit.apply(buf);
+                debug_assert_valid_mutation!(
+                    <$slice<S>>::new(buf).is_ok(),
+                    "[validity] the IRI must be valid after the slashes are collapsed"
+                );
+            }
+            true
+        }
+    };
+}
+
 /// Implements type conversion from slice into smart pointer.
 macro_rules! impl_from_slice_into_smartptr {
     (
@@ -196,8 +474,13 @@ macro_rules! define_custom_string_slice {
 
             /// Creates a new string without validation.
             ///
-            /// This does not validate the given string, so it is caller's
-            /// responsibility to ensure the given string is valid.
+            /// This does not validate the given string in release build, so it
+            /// is caller's responsibility to ensure the given string is valid.
+            /// This is intended for strings which are already known to be
+            /// valid, for example strings just built by trusted generators.
+            ///
+            /// In debug build, this validates the string and panics if it is
+            /// invalid, to detect the violation of the precondition early.
             ///
             /// # Safety
             ///
@@ -207,9 +490,9 @@ macro_rules! define_custom_string_slice {
             #[inline]
             #[must_use]
             pub unsafe fn new_unchecked(s: &str) -> &Self {
-                // SAFETY: `new_always_unchecked` requires the same precondition
-                // as `new_always_unchecked`.
-                unsafe { Self::new_always_unchecked(s) }
+                // SAFETY: `new_maybe_unchecked` requires the same precondition
+                // as `new_unchecked`.
+                unsafe { Self::new_maybe_unchecked(s) }
             }
 
             /// Creates a new string maybe without validation.
@@ -270,7 +553,11 @@ macro_rules! define_custom_string_slice {
 
         impl<S: crate::spec::Spec> core::fmt::Debug for $ty<S> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                f.debug_tuple(stringify!($ty)).field(&&self.inner).finish()
+                #[cfg(feature = "redact-debug")]
+                let inner = crate::redact::DebugRedacted(self);
+                #[cfg(not(feature = "redact-debug"))]
+                let inner = &self.inner;
+                f.debug_tuple(stringify!($ty)).field(&inner).finish()
             }
         }
 
@@ -286,7 +573,7 @@ macro_rules! define_custom_string_slice {
         impl<S: crate::spec::Spec> PartialOrd for $ty<S> {
             #[inline]
             fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-                self.inner.partial_cmp(&other.inner)
+                Some(self.cmp(other))
             }
         }
 
@@ -538,8 +825,13 @@ macro_rules! define_custom_string_owned {
         impl<S: crate::spec::Spec> $ty<S> {
             /// Creates a new string without validation.
             ///
-            /// This does not validate the given string, so it is caller's
-            /// responsibility to ensure the given string is valid.
+            /// This does not validate the given string in release build, so it
+            /// is caller's responsibility to ensure the given string is valid.
+            /// This is intended for strings which are already known to be
+            /// valid, for example strings just built by trusted generators.
+            ///
+            /// In debug build, this validates the string and panics if it is
+            /// invalid, to detect the violation of the precondition early.
             ///
             /// # Safety
             ///
@@ -549,9 +841,9 @@ macro_rules! define_custom_string_owned {
             #[inline]
             #[must_use]
             pub unsafe fn new_unchecked(s: alloc::string::String) -> Self {
-                // SAFETY: `new_always_unchecked` requires the same precondition
-                // as `new_always_unchecked`.
-                unsafe { Self::new_always_unchecked(s) }
+                // SAFETY: `new_maybe_unchecked` requires the same precondition
+                // as `new_unchecked`.
+                unsafe { Self::new_maybe_unchecked(s) }
             }
 
             /// Creates a new string maybe without validation.
@@ -613,6 +905,7 @@ macro_rules! define_custom_string_owned {
             #[inline]
             #[must_use]
             // TODO: Use wrapper type to enforce validation on finish?
+            #[allow(dead_code)]
             pub(crate) unsafe fn as_inner_mut(&mut self) -> &mut alloc::string::String {
                 &mut self.inner
             }
@@ -623,6 +916,30 @@ macro_rules! define_custom_string_owned {
                 self.inner.shrink_to_fit()
             }
 
+            /// Reserves capacity for at least `additional` more bytes.
+            ///
+            /// This is useful when the IRI is going to be extended
+            /// (for example by `set_fragment`).
+            ///
+            /// # Panics
+            ///
+            /// Panics if the new capacity overflows `usize`.
+            #[inline]
+            pub fn reserve(&mut self, additional: usize) {
+                self.inner.reserve(additional)
+            }
+
+            /// Tries to reserve capacity for at least `additional` more bytes.
+            ///
+            /// This is the fallible version of [`reserve`][`Self::reserve`].
+            #[inline]
+            pub fn try_reserve(
+                &mut self,
+                additional: usize,
+            ) -> Result<(), alloc::collections::TryReserveError> {
+                self.inner.try_reserve(additional)
+            }
+
             /// Returns the internal buffer capacity in bytes.
             #[inline]
             #[must_use]
@@ -630,6 +947,54 @@ macro_rules! define_custom_string_owned {
                 self.inner.capacity()
             }
 
+            /// Updates the content to the given string by the minimal in-place edit.
+            ///
+            /// The common prefix and suffix of the current and the new values
+            /// are kept, and only the part between them is replaced. The
+            /// internal buffer is reused and reallocated only when the
+            /// capacity is not enough. This is useful for long IRIs which are
+            /// slightly and frequently modified.
+            ///
+            /// Returns `true` if the content has been changed.
+            pub fn update_in_place(&mut self, new: &$slice<S>) -> bool {
+                let old = self.inner.as_bytes();
+                let new = new.as_str();
+                let new_bytes = new.as_bytes();
+                let mut prefix = old
+                    .iter()
+                    .zip(new_bytes)
+                    .take_while(|(o, n)| o == n)
+                    .count();
+                if prefix == old.len() && prefix == new.len() {
+                    return false;
+                }
+                while !(self.inner.is_char_boundary(prefix) && new.is_char_boundary(prefix)) {
+                    prefix -= 1;
+                }
+                let max_suffix = core::cmp::min(old.len(), new.len()) - prefix;
+                let mut suffix = old
+                    .iter()
+                    .rev()
+                    .zip(new_bytes.iter().rev())
+                    .take(max_suffix)
+                    .take_while(|(o, n)| o == n)
+                    .count();
+                while !(self.inner.is_char_boundary(old.len() - suffix)
+                    && new.is_char_boundary(new.len() - suffix))
+                {
+                    suffix -= 1;
+                }
+                let old_end = old.len() - suffix;
+                // SAFETY: the buffer becomes the same as `new` after the edit,
+                // and `new` is valid as `Self`.
+                unsafe {
+                    self.as_inner_mut()
+                        .replace_range(prefix..old_end, &new[prefix..(new.len() - suffix)]);
+                }
+                debug_assert_eq!(self.inner, new, "[consistency] the edit must be exact");
+                true
+            }
+
             /// Returns the borrowed IRI string slice.
             ///
             /// This is equivalent to `&*self`.
@@ -642,7 +1007,11 @@ macro_rules! define_custom_string_owned {
 
         impl<S: crate::spec::Spec> core::fmt::Debug for $ty<S> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                f.debug_tuple(stringify!($ty)).field(&&self.inner).finish()
+                #[cfg(feature = "redact-debug")]
+                let inner = crate::redact::DebugRedacted(self.as_slice());
+                #[cfg(not(feature = "redact-debug"))]
+                let inner = &self.inner;
+                f.debug_tuple(stringify!($ty)).field(&inner).finish()
             }
         }
 
@@ -824,6 +1193,11 @@ macro_rules! define_custom_string_owned {
             }
         }
 
+        // SAFETY: The dereferenced slice is backed by the heap buffer of the
+        // inner `String`, so its address does not change when `$ty` is moved.
+        #[cfg(feature = "yoke")]
+        unsafe impl<S: crate::spec::Spec> stable_deref_trait::StableDeref for $ty<S> {}
+
         impl_cmp!(str, $slice<S>, alloc::borrow::Cow<'_, str>);
         impl_cmp!(str, &$slice<S>, alloc::borrow::Cow<'_, str>);
         impl_cmp2_as_str!(&$slice<S>, alloc::borrow::Cow<'_, $slice<T>>);
@@ -0,0 +1,80 @@
+//! Conversion from platform strings.
+//!
+//! Bytes not forming valid UTF-8 sequences (including unpaired surrogates on
+//! Windows, in the form of WTF-8) are percent-encoded before the validation,
+//! so the conversion never panics nor replaces them with U+FFFD. See
+//! [`crate::file`] module for the details of platform-specific handling.
+//!
+//! Note that the OS string is treated as an IRI text, so a literal `%` is not
+//! escaped and is regarded as the start of a percent-encoded triplet. This
+//! means the conversion is not injective: for example, the text `%FF` and the
+//! raw byte 0xFF are both converted to `%FF`. To convert arbitrary file paths
+//! losslessly, use [`crate::file::path_to_file_iri`], which escapes `%`.
+
+use core::convert::TryFrom;
+
+use std::ffi::{OsStr, OsString};
+use std::string::String;
+
+use crate::file::{os_str_to_bytes, push_bytes_encoded};
+use crate::spec::Spec;
+use crate::types::{RiAbsoluteString, RiReferenceString, RiRelativeString, RiString};
+use crate::validate::Error;
+
+/// Converts the OS string into a string, percent-encoding invalid bytes.
+fn os_str_to_string(s: &OsStr) -> Result<String, Error> {
+    if let Some(s) = s.to_str() {
+        return Ok(s.into());
+    }
+    let bytes = os_str_to_bytes(s).ok_or_else(Error::new)?;
+    let mut buf = String::with_capacity(bytes.len());
+    push_bytes_encoded(&mut buf, &bytes, |_| true);
+    Ok(buf)
+}
+
+/// Implements conversions from OS strings.
+macro_rules! impl_from_os_str {
+    ($($owned:ident),*) => {
+        $(
+            #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+            impl<S: Spec> TryFrom<&OsStr> for $owned<S> {
+                type Error = Error;
+
+                /// Creates the IRI string from the OS string.
+                ///
+                /// Bytes not forming valid UTF-8 sequences are percent-encoded.
+                /// A literal `%` is kept as is, so the text `%FF` and the raw
+                /// byte 0xFF result in the same IRI.
+                #[inline]
+                fn try_from(s: &OsStr) -> Result<Self, Self::Error> {
+                    Self::try_from(os_str_to_string(s)?).map_err(|e| e.validation_error())
+                }
+            }
+
+            #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+            impl<S: Spec> TryFrom<OsString> for $owned<S> {
+                type Error = Error;
+
+                /// Creates the IRI string from the OS string.
+                ///
+                /// Bytes not forming valid UTF-8 sequences are percent-encoded.
+                /// A literal `%` is kept as is, so the text `%FF` and the raw
+                /// byte 0xFF result in the same IRI.
+                #[inline]
+                fn try_from(s: OsString) -> Result<Self, Self::Error> {
+                    match s.into_string() {
+                        Ok(s) => Self::try_from(s).map_err(|e| e.validation_error()),
+                        Err(s) => Self::try_from(s.as_os_str()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_os_str!(
+    RiReferenceString,
+    RiString,
+    RiAbsoluteString,
+    RiRelativeString
+);
@@ -0,0 +1,195 @@
+//! Path string.
+
+use crate::parser::validate::validate_path_chars as path;
+use crate::percent_decode;
+use crate::spec::Spec;
+use crate::types::RiRelativeStr;
+
+define_custom_string_slice! {
+    /// A borrowed slice of an IRI path.
+    ///
+    /// This corresponds to [`ipath` rule] in [RFC 3987] (and [`path` rule] in [RFC 3986]),
+    /// and can have any path component of an IRI reference, including ones
+    /// starting with `//` (which can appear only after an authority, such as
+    /// `//bar` in `foo://host//bar`).
+    ///
+    /// Values of this type are usually taken from IRIs by `slice_path` method
+    /// (such as [`RiStr::slice_path`]), so that the validity of the path can
+    /// be passed around in the type.
+    ///
+    /// Note that not all values of this type can be used as a path of any
+    /// IRI. For example, a path starting with `//` is valid only when the IRI
+    /// has an authority.
+    ///
+    /// # Valid values
+    ///
+    /// ```
+    /// # use iri_string::types::IriPathStr;
+    /// assert!(IriPathStr::new("").is_ok());
+    /// assert!(IriPathStr::new("/foo/bar").is_ok());
+    /// assert!(IriPathStr::new("foo:bar").is_ok());
+    /// assert!(IriPathStr::new("//foo").is_ok());
+    /// assert!(IriPathStr::new("%E3%81%82/\u{03B1}").is_ok());
+    /// ```
+    ///
+    /// Some characters and sequences cannot used in a path.
+    ///
+    /// ```
+    /// # use iri_string::types::IriPathStr;
+    /// // Query and fragment cannot appear in a path.
+    /// assert!(IriPathStr::new("/foo?bar").is_err());
+    /// assert!(IriPathStr::new("/foo#bar").is_err());
+    /// // Broken percent encoding cannot appear in a path.
+    /// assert!(IriPathStr::new("%GG").is_err());
+    /// ```
+    ///
+    /// [RFC 3986]: https://tools.ietf.org/html/rfc3986
+    /// [RFC 3987]: https://tools.ietf.org/html/rfc3987
+    /// [`path` rule]: https://tools.ietf.org/html/rfc3986#section-3.3
+    /// [`ipath` rule]: https://tools.ietf.org/html/rfc3987#section-2.2
+    /// [`RiStr::slice_path`]: struct.RiStr.html#method.slice_path
+    struct RiPathStr {
+        validator = path,
+        expecting_msg = "IRI path string",
+    }
+}
+
+#[cfg(feature = "alloc")]
+define_custom_string_owned! {
+    /// An owned string of an IRI path.
+    ///
+    /// This corresponds to [`ipath` rule] in [RFC 3987] (and [`path` rule] in [RFC 3986]).
+    ///
+    /// For details, see the documentation for [`RiPathStr`].
+    ///
+    /// Enabled by `alloc` or `std` feature.
+    ///
+    /// [RFC 3986]: https://tools.ietf.org/html/rfc3986
+    /// [RFC 3987]: https://tools.ietf.org/html/rfc3987
+    /// [`path` rule]: https://tools.ietf.org/html/rfc3986#section-3.3
+    /// [`ipath` rule]: https://tools.ietf.org/html/rfc3987#section-2.2
+    /// [`RiPathStr`]: struct.RiPathStr.html
+    struct RiPathString {
+        validator = path,
+        slice = RiPathStr,
+        expecting_msg = "IRI path string",
+    }
+}
+
+impl<S: Spec> RiPathStr<S> {
+    /// Returns true if the path starts with `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriPathStr;
+    ///
+    /// assert!(IriPathStr::new("/foo")?.is_absolute());
+    /// assert!(!IriPathStr::new("foo/bar")?.is_absolute());
+    /// assert!(!IriPathStr::new("")?.is_absolute());
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_absolute(&self) -> bool {
+        self.as_str().starts_with('/')
+    }
+
+    /// Returns true if the percent-decoded path contains the given string.
+    ///
+    /// The path is decoded on the fly, so this does not allocate. Note that
+    /// `%2F` is decoded to `/` and is not distinguished from the segment
+    /// separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriPathStr;
+    ///
+    /// let path = IriPathStr::new("/docs/annual%20report/%E6%97%A5")?;
+    /// assert!(path.contains_decoded("annual report"));
+    /// assert!(path.contains_decoded("/\u{65E5}"));
+    /// assert!(!path.contains_decoded("%20"));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_decoded(&self, needle: &str) -> bool {
+        self.find_decoded(needle).is_some()
+    }
+
+    /// Returns the byte position in the (encoded) path where the percent-decoded
+    /// path first contains the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriPathStr;
+    ///
+    /// let path = IriPathStr::new("/a%20b/c")?;
+    /// assert_eq!(path.find_decoded("a b"), Some(1));
+    /// assert_eq!(path.find_decoded("b/"), Some(5));
+    /// assert_eq!(path.find_decoded("d"), None);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[must_use]
+    pub fn find_decoded(&self, needle: &str) -> Option<usize> {
+        percent_decode::find_decoded(self.as_str().as_bytes(), needle.as_bytes())
+    }
+
+    /// Returns true if the percent-decoded path starts with the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriPathStr;
+    ///
+    /// let path = IriPathStr::new("/%7Ealice/index.html")?;
+    /// assert!(path.starts_with_decoded("/~alice/"));
+    /// assert!(path.ends_with_decoded(".html"));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn starts_with_decoded(&self, prefix: &str) -> bool {
+        percent_decode::starts_with_decoded(self.as_str().as_bytes(), prefix.as_bytes())
+    }
+
+    /// Returns true if the percent-decoded path ends with the given string.
+    ///
+    /// See [`starts_with_decoded`][`Self::starts_with_decoded`] for an example.
+    #[inline]
+    #[must_use]
+    pub fn ends_with_decoded(&self, suffix: &str) -> bool {
+        percent_decode::ends_with_decoded(self.as_str().as_bytes(), suffix.as_bytes())
+    }
+}
+
+/// Returns the part after the authority as a relative IRI reference, if possible.
+///
+/// Returns `None` if the part cannot be a relative reference with the same
+/// path, i.e. if the path starts with `//` (which would be parsed as an
+/// authority), or if the path is relative and its first segment contains a
+/// colon (which would be parsed as a scheme).
+///
+/// # Precondition
+///
+/// The given string must be the part after the authority of a valid IRI
+/// reference (i.e. path, query, and fragment).
+#[must_use]
+pub(crate) fn relative_after_authority<S: Spec>(rest: &str) -> Option<&RiRelativeStr<S>> {
+    if rest.starts_with("//") {
+        return None;
+    }
+    let first_segment_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    if rest[..first_segment_end].contains(':') {
+        return None;
+    }
+    // SAFETY: the path does not look like an authority nor a scheme, and the
+    // rest consists of characters allowed in relative references.
+    Some(unsafe { RiRelativeStr::new_maybe_unchecked(rest) })
+}
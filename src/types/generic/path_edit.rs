@@ -0,0 +1,114 @@
+//! Path editing operations.
+
+use core::ops::Range;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use crate::components::RiReferenceComponents;
+use crate::parser::validate::validate_path_in_context;
+use crate::spec::Spec;
+use crate::types::RiReferenceStr;
+use crate::validate::Error;
+
+/// An edit of the path.
+#[derive(Debug, Clone)]
+pub(crate) struct PathEdit {
+    /// Range of the IRI string to be replaced.
+    range: Range<usize>,
+    /// Replacement.
+    replacement: Cow<'static, str>,
+}
+
+impl PathEdit {
+    /// Applies the edit to the string.
+    pub(crate) fn apply(self, buf: &mut String) {
+        buf.replace_range(self.range, &self.replacement);
+    }
+}
+
+/// Returns the range of the path, or `None` if the path is opaque.
+fn hierarchical_path_range<S: Spec>(iri: &RiReferenceStr<S>) -> Option<Range<usize>> {
+    if iri.is_opaque() {
+        return None;
+    }
+    Some(RiReferenceComponents::from(iri).path_range())
+}
+
+/// Returns the edit to replace the path.
+///
+/// Only the new path is validated, in the context of the presence of the
+/// scheme and the authority. Other components are not validated again.
+pub(crate) fn set_path<S: Spec>(iri: &RiReferenceStr<S>, path: &str) -> Result<PathEdit, Error> {
+    validate_path_in_context::<S>(
+        path,
+        iri.scheme_str().is_some(),
+        iri.authority_str().is_some(),
+    )?;
+    Ok(PathEdit {
+        range: RiReferenceComponents::from(iri).path_range(),
+        replacement: Cow::Owned(path.into()),
+    })
+}
+
+/// Returns the edit to append a slash to the path if it does not end with a slash.
+pub(crate) fn ensure_trailing_slash<S: Spec>(iri: &RiReferenceStr<S>) -> Option<PathEdit> {
+    let range = hierarchical_path_range(iri)?;
+    if iri.as_str()[range.clone()].ends_with('/') {
+        return None;
+    }
+    Some(PathEdit {
+        range: range.end..range.end,
+        replacement: Cow::Borrowed("/"),
+    })
+}
+
+/// Returns the edit to remove a trailing slash of the path, except for the root path `/`.
+pub(crate) fn strip_trailing_slash<S: Spec>(iri: &RiReferenceStr<S>) -> Option<PathEdit> {
+    let range = hierarchical_path_range(iri)?;
+    let path = &iri.as_str()[range.clone()];
+    if path == "/" || !path.ends_with('/') {
+        return None;
+    }
+    Some(PathEdit {
+        range: (range.end - 1)..range.end,
+        replacement: Cow::Borrowed(""),
+    })
+}
+
+/// Returns the edit to remove the last path segment if it is one of the given names.
+pub(crate) fn strip_index_segment<S: Spec>(
+    iri: &RiReferenceStr<S>,
+    names: &[&str],
+) -> Option<PathEdit> {
+    let range = hierarchical_path_range(iri)?;
+    let path = &iri.as_str()[range.clone()];
+    let last_seg_start = path.rfind('/').map_or(0, |pos| pos + 1);
+    let last_seg = &path[last_seg_start..];
+    if last_seg.is_empty() || !names.contains(&last_seg) {
+        return None;
+    }
+    Some(PathEdit {
+        range: (range.start + last_seg_start)..range.end,
+        replacement: Cow::Borrowed(""),
+    })
+}
+
+/// Returns the edit to collapse runs of slashes in the path into single slashes.
+pub(crate) fn collapse_empty_segments<S: Spec>(iri: &RiReferenceStr<S>) -> Option<PathEdit> {
+    let range = hierarchical_path_range(iri)?;
+    let path = &iri.as_str()[range.clone()];
+    if !path.contains("//") {
+        return None;
+    }
+    let mut collapsed = String::with_capacity(path.len());
+    for c in path.chars() {
+        if !(c == '/' && collapsed.ends_with('/')) {
+            collapsed.push(c);
+        }
+    }
+    Some(PathEdit {
+        range,
+        replacement: Cow::Owned(collapsed),
+    })
+}
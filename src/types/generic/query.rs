@@ -1,8 +1,20 @@
 //! Query string.
 
 use core::convert::TryFrom;
+#[cfg(feature = "alloc")]
+use core::fmt::{self, Write as _};
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 
+#[cfg(feature = "alloc")]
+use crate::percent_encode::{PercentEncoded, QueryBytesEncoded};
+#[cfg(feature = "alloc")]
+use crate::query::{FormData, QueryPairs, QueryPairsBytes};
 use crate::{
+    percent_decode,
     spec::Spec,
     validate::{query, Error},
 };
@@ -134,4 +146,371 @@ impl<S: Spec> RiQueryStr<S> {
         }
         TryFrom::try_from(&s[1..])
     }
+
+    /// Returns true if the percent-decoded query contains the given string.
+    ///
+    /// The query is decoded on the fly, so this does not allocate. Note that `+` is not decoded to a space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriQueryStr;
+    ///
+    /// let query = IriQueryStr::new("name=J%C3%BCrgen+M")?;
+    /// assert!(query.contains_decoded("J\u{FC}rgen"));
+    /// assert!(!query.contains_decoded("J\u{FC}rgen M"));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_decoded(&self, needle: &str) -> bool {
+        self.find_decoded(needle).is_some()
+    }
+
+    /// Returns the byte position in the (encoded) query where the
+    /// percent-decoded query first contains the given string.
+    #[must_use]
+    pub fn find_decoded(&self, needle: &str) -> Option<usize> {
+        percent_decode::find_decoded(self.as_str().as_bytes(), needle.as_bytes())
+    }
+
+    /// Returns true if the percent-decoded query starts with the given string.
+    #[inline]
+    #[must_use]
+    pub fn starts_with_decoded(&self, prefix: &str) -> bool {
+        percent_decode::starts_with_decoded(self.as_str().as_bytes(), prefix.as_bytes())
+    }
+
+    /// Returns true if the percent-decoded query ends with the given string.
+    #[inline]
+    #[must_use]
+    pub fn ends_with_decoded(&self, suffix: &str) -> bool {
+        percent_decode::ends_with_decoded(self.as_str().as_bytes(), suffix.as_bytes())
+    }
+
+    /// Returns an iterator of the decoded `key=value` pairs.
+    ///
+    /// The query is split by `&`, and each pair is split at the first `=`.
+    /// Empty pairs are skipped, and a pair without `=` has an empty value.
+    /// Percent-encoded triplets are decoded and `+` is decoded to a space,
+    /// as `application/x-www-form-urlencoded` does. Invalid UTF-8 sequences
+    /// are replaced with `U+FFFD REPLACEMENT CHARACTER`; use
+    /// [`query_pairs_bytes`][`Self::query_pairs_bytes`] to get raw bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriQueryStr;
+    ///
+    /// let query = IriQueryStr::new("q=rust+iri&empty&&lang=%E6%97%A5")?;
+    /// let pairs: Vec<_> = query.query_pairs().collect();
+    /// assert_eq!(pairs, [("q".into(), "rust iri".into()), ("empty".into(), "".into()), ("lang".into(), "\u{65E5}".into())]);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    #[must_use]
+    pub fn query_pairs(&self) -> QueryPairs<'_> {
+        QueryPairs::new(self.as_str())
+    }
+
+    /// Parses the query into the decoded `key=value` pairs with typed value access.
+    ///
+    /// See [`FormData`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriQueryStr;
+    ///
+    /// let form = IriQueryStr::new("limit=10&sort=asc")?.to_form_data();
+    /// assert_eq!(form.get::<usize>("limit"), Some(Ok(10)));
+    /// assert_eq!(form.get_str("sort"), Some("asc"));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    #[must_use]
+    pub fn to_form_data(&self) -> FormData {
+        FormData::parse(self.as_str())
+    }
+
+    /// Returns an iterator of the decoded `key=value` pairs as bytes.
+    ///
+    /// This is same as [`query_pairs`][`Self::query_pairs`], except that
+    /// keys and values are not required to be valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriQueryStr;
+    ///
+    /// let query = IriQueryStr::new("bin=%FF%00")?;
+    /// let pairs: Vec<_> = query.query_pairs_bytes().collect();
+    /// assert_eq!(pairs, [(b"bin"[..].into(), b"\xff\x00"[..].into())]);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    #[must_use]
+    pub fn query_pairs_bytes(&self) -> QueryPairsBytes<'_> {
+        QueryPairsBytes::new(self.as_str())
+    }
+
+    /// Returns the `n`-th (0-based) decoded value for the decoded key, in the
+    /// original order.
+    ///
+    /// Keys and values are decoded as [`query_pairs`][`Self::query_pairs`]
+    /// does. This is useful when the same key appears multiple times and the
+    /// position matters, such as verification of signed URLs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriQueryStr;
+    ///
+    /// let query = IriQueryStr::new("sig=a&x=1&sig=b+c&%73ig=d")?;
+    /// assert_eq!(query.nth_occurrence("sig", 0).as_deref(), Some("a"));
+    /// assert_eq!(query.nth_occurrence("sig", 1).as_deref(), Some("b c"));
+    /// assert_eq!(query.nth_occurrence("sig", 2).as_deref(), Some("d"));
+    /// assert_eq!(query.nth_occurrence("sig", 3), None);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[must_use]
+    pub fn nth_occurrence(&self, key: &str, n: usize) -> Option<Cow<'_, str>> {
+        self.query_pairs()
+            .filter(|(k, _)| k == key)
+            .nth(n)
+            .map(|(_, v)| v)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Spec> RiQueryString<S> {
+    /// Appends a `key=value` pair to the query, percent-encoding the key and the value.
+    ///
+    /// A separator `&` is inserted if the query is not empty. The key and the
+    /// value are encoded as [`PercentEncoded::from_query_key`] and
+    /// [`PercentEncoded::from_query_value`] do.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriQueryString;
+    ///
+    /// let mut query = IriQueryString::try_from("foo=bar".to_owned())
+    ///     .map_err(|e| e.validation_error())?;
+    /// query.push_pair("key", "a&b=c");
+    /// assert_eq!(query, "foo=bar&key=a%26b%3Dc");
+    /// # Ok::<_, Error>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `Display` implementation of the key or the value returned
+    /// an error. In this case, the pair is not appended.
+    ///
+    /// [`PercentEncoded::from_query_key`]: `crate::percent_encode::PercentEncoded::from_query_key`
+    /// [`PercentEncoded::from_query_value`]: `crate::percent_encode::PercentEncoded::from_query_value`
+    pub fn push_pair<K, V>(&mut self, key: K, value: V)
+    where
+        K: fmt::Display,
+        V: fmt::Display,
+    {
+        // SAFETY: only `&`, `=`, and percent-encoded strings valid as a part
+        // of a query are appended.
+        unsafe {
+            let buf = self.as_inner_mut();
+            let result = append_query_pairs::<S, _, _, _>(buf, 0, core::iter::once((key, value)));
+            debug_assert_valid_mutation!(
+                query::<S>(buf).is_ok(),
+                "[validity] the query must be valid after the pair is appended"
+            );
+            result.expect("a Display implementation returned an error unexpectedly");
+        }
+    }
+
+    /// Appends a `key=value` pair of arbitrary bytes to the query.
+    ///
+    /// Valid UTF-8 sequences are encoded as [`push_pair`][`Self::push_pair`]
+    /// does, and other bytes are always percent-encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::types::IriQueryString;
+    ///
+    /// let mut query: IriQueryString = [("a", "b")].iter().collect();
+    /// query.push_pair_bytes(b"bin", b"\xff\x00=\xce\xb1");
+    /// assert_eq!(query, "a=b&bin=%FF%00%3D\u{03B1}");
+    /// ```
+    pub fn push_pair_bytes(&mut self, key: &[u8], value: &[u8]) {
+        self.extend_bytes(core::iter::once((key, value)));
+    }
+
+    /// Appends `key=value` pairs of arbitrary bytes to the query.
+    ///
+    /// See [`push_pair_bytes`][`Self::push_pair_bytes`] for detail.
+    pub fn extend_bytes<I, K, V>(&mut self, pairs: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        // SAFETY: only `&`, `=`, and percent-encoded strings valid as a part
+        // of a query are appended.
+        unsafe {
+            let buf = self.as_inner_mut();
+            append_query_byte_pairs::<S, _, _, _>(buf, 0, pairs)
+                .expect("[consistency] formatting bytes into a string never fails");
+            debug_assert_valid_mutation!(
+                query::<S>(buf).is_ok(),
+                "[validity] the query must be valid after the pairs are appended"
+            );
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Spec, K: fmt::Display, V: fmt::Display> Extend<(K, V)> for RiQueryString<S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|(key, value)| self.push_pair(key, value));
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, S: Spec, K: fmt::Display, V: fmt::Display> Extend<&'a (K, V)> for RiQueryString<S> {
+    fn extend<I: IntoIterator<Item = &'a (K, V)>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|(key, value)| self.push_pair(key, value));
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Spec, K: fmt::Display, V: fmt::Display> FromIterator<(K, V)> for RiQueryString<S> {
+    /// Creates a `key1=value1&key2=value2` style query from the iterator of pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iri_string::types::IriQueryString;
+    ///
+    /// let pairs = [("q", "rust iri"), ("lang", "en")];
+    /// let query: IriQueryString = pairs.iter().collect();
+    /// assert_eq!(query, "q=rust%20iri&lang=en");
+    /// ```
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        // SAFETY: an empty string is a valid query.
+        let mut query = unsafe { Self::new_maybe_unchecked(String::new()) };
+        query.extend(iter);
+        query
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, S: Spec, K: fmt::Display, V: fmt::Display> FromIterator<&'a (K, V)> for RiQueryString<S> {
+    fn from_iter<I: IntoIterator<Item = &'a (K, V)>>(iter: I) -> Self {
+        // SAFETY: an empty string is a valid query.
+        let mut query = unsafe { Self::new_maybe_unchecked(String::new()) };
+        query.extend(iter);
+        query
+    }
+}
+
+/// Appends the `key=value` pairs to the query in the buffer.
+///
+/// Keys and values should be already percent-encoded as a part of a
+/// query pair.
+///
+/// `query_start` is the position of the query (after the leading `?`) in the
+/// buffer, and the query should end at the end of the buffer.
+///
+/// # Failures
+///
+/// Returns an error if the `Display` implementation of keys or values
+/// returned an error. In this case, the pair and the later pairs are not
+/// appended, while the preceding pairs are left appended.
+#[cfg(feature = "alloc")]
+pub(crate) fn append_encoded_query_pairs<I, K, V>(
+    buf: &mut String,
+    query_start: usize,
+    pairs: I,
+) -> fmt::Result
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: fmt::Display,
+    V: fmt::Display,
+{
+    for (key, value) in pairs {
+        let orig_len = buf.len();
+        if orig_len > query_start {
+            buf.push('&');
+        }
+        if let Err(e) = write!(buf, "{}={}", key, value) {
+            buf.truncate(orig_len);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// Appends the percent-encoded `key=value` pairs to the query in the buffer.
+///
+/// See [`append_encoded_query_pairs`] for detail.
+#[cfg(feature = "alloc")]
+pub(crate) fn append_query_pairs<S, I, K, V>(
+    buf: &mut String,
+    query_start: usize,
+    pairs: I,
+) -> fmt::Result
+where
+    S: Spec,
+    I: IntoIterator<Item = (K, V)>,
+    K: fmt::Display,
+    V: fmt::Display,
+{
+    let pairs = pairs.into_iter().map(|(key, value)| {
+        (
+            PercentEncoded::<_, S>::from_query_key(key),
+            PercentEncoded::<_, S>::from_query_value(value),
+        )
+    });
+    append_encoded_query_pairs(buf, query_start, pairs)
+}
+
+/// Appends the percent-encoded `key=value` pairs of arbitrary bytes to the query in the buffer.
+///
+/// See [`append_encoded_query_pairs`] for detail.
+#[cfg(feature = "alloc")]
+pub(crate) fn append_query_byte_pairs<S, I, K, V>(
+    buf: &mut String,
+    query_start: usize,
+    pairs: I,
+) -> fmt::Result
+where
+    S: Spec,
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+{
+    for (key, value) in pairs {
+        let pair = (
+            QueryBytesEncoded::<S>::new(key.as_ref()),
+            QueryBytesEncoded::<S>::new(value.as_ref()),
+        );
+        append_encoded_query_pairs(buf, query_start, core::iter::once(pair))?;
+    }
+    Ok(())
 }
@@ -0,0 +1,97 @@
+//! Interoperability with `oxiri` crate.
+//!
+//! IRIs parsed by RDF toolkits using [`oxiri`] can be converted into the IRI
+//! types of this crate, and vice versa.
+//!
+//! Conversion from `oxiri` types validates the string, since the syntax
+//! accepted by `oxiri` is not guaranteed to be the same as this crate. If the
+//! input is already known to be valid, `new_unchecked` (for example
+//! [`RiStr::new_unchecked`]) can be used to skip the validation.
+//!
+//! Conversion into `oxiri` types does not validate the string again.
+
+use core::convert::TryFrom;
+use core::ops::Deref;
+
+use std::string::String;
+
+use oxiri::{Iri, IriRef};
+
+use crate::spec::Spec;
+use crate::types::{
+    CreationError, RiAbsoluteStr, RiAbsoluteString, RiReferenceStr, RiReferenceString,
+    RiRelativeStr, RiRelativeString, RiStr, RiString,
+};
+use crate::validate::Error;
+
+/// Implements conversions into `oxiri` types.
+macro_rules! impl_into_oxiri {
+    ($($slice:ident, $owned:ident => $oxiri:ident,)*) => {
+        $(
+            #[cfg_attr(docsrs, doc(cfg(feature = "oxiri")))]
+            impl<'a, S: Spec> From<&'a $slice<S>> for $oxiri<&'a str> {
+                #[inline]
+                fn from(iri: &'a $slice<S>) -> Self {
+                    debug_assert!(
+                        $oxiri::parse(iri.as_str()).is_ok(),
+                        "[consistency] valid IRI should be accepted by `oxiri`"
+                    );
+                    $oxiri::parse_unchecked(iri.as_str())
+                }
+            }
+
+            #[cfg_attr(docsrs, doc(cfg(feature = "oxiri")))]
+            impl<S: Spec> From<$owned<S>> for $oxiri<String> {
+                #[inline]
+                fn from(iri: $owned<S>) -> Self {
+                    debug_assert!(
+                        $oxiri::parse(iri.as_str()).is_ok(),
+                        "[consistency] valid IRI should be accepted by `oxiri`"
+                    );
+                    $oxiri::parse_unchecked(iri.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_into_oxiri! {
+    RiStr, RiString => Iri,
+    RiAbsoluteStr, RiAbsoluteString => Iri,
+    RiReferenceStr, RiReferenceString => IriRef,
+    RiRelativeStr, RiRelativeString => IriRef,
+}
+
+/// Implements conversions from `oxiri` types.
+macro_rules! impl_from_oxiri {
+    ($($oxiri:ident => $slice:ident, $owned:ident,)*) => {
+        $(
+            #[cfg_attr(docsrs, doc(cfg(feature = "oxiri")))]
+            impl<'a, S: Spec, T: Deref<Target = str>> TryFrom<&'a $oxiri<T>> for &'a $slice<S> {
+                type Error = Error;
+
+                #[inline]
+                fn try_from(iri: &'a $oxiri<T>) -> Result<Self, Self::Error> {
+                    $slice::new(iri.as_str())
+                }
+            }
+
+            #[cfg_attr(docsrs, doc(cfg(feature = "oxiri")))]
+            impl<S: Spec> TryFrom<$oxiri<String>> for $owned<S> {
+                type Error = CreationError<String>;
+
+                #[inline]
+                fn try_from(iri: $oxiri<String>) -> Result<Self, Self::Error> {
+                    Self::try_from(iri.into_inner())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_oxiri! {
+    Iri => RiStr, RiString,
+    Iri => RiAbsoluteStr, RiAbsoluteString,
+    IriRef => RiReferenceStr, RiReferenceString,
+    IriRef => RiRelativeStr, RiRelativeString,
+}
@@ -1,20 +1,27 @@
 //! Relative IRI reference.
 
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+
 use crate::components::AuthorityComponents;
 #[cfg(feature = "alloc")]
+use crate::components::OwnedComponents;
+#[cfg(feature = "alloc")]
 use crate::mask_password::password_range_to_hide;
 use crate::mask_password::PasswordMasked;
+#[cfg(feature = "unicode-normalization")]
+use crate::normalize::to_nfc_string;
 use crate::normalize::Normalized;
 use crate::parser::trusted as trusted_parser;
 #[cfg(feature = "alloc")]
 use crate::raw;
+use crate::redact::Redacted;
 use crate::resolve::FixedBaseResolver;
 use crate::spec::Spec;
+use crate::truncate::Truncated;
 #[cfg(feature = "alloc")]
 use crate::types::RiReferenceString;
-use crate::types::{RiAbsoluteStr, RiFragmentStr, RiQueryStr, RiReferenceStr, RiStr};
-#[cfg(feature = "alloc")]
-use crate::validate::iri;
+use crate::types::{RiAbsoluteStr, RiFragmentStr, RiPathStr, RiQueryStr, RiReferenceStr, RiStr};
 use crate::validate::relative_ref;
 
 define_custom_string_slice! {
@@ -167,6 +174,98 @@ impl<S: Spec> RiRelativeStr<S> {
     pub fn mask_password(&self) -> PasswordMasked<'_, Self> {
         PasswordMasked::new(self)
     }
+
+    /// Returns the proxy to the IRI truncated to the given number of characters when displayed.
+    ///
+    /// See [`Truncated`] for the truncation rule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriRelativeStr;
+    ///
+    /// let iri = IriRelativeStr::new("a/b/c/d/e/f#frag")?;
+    /// assert_eq!(iri.display_truncated(10).to_string(), "a/\u{2026}/f#frag");
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn display_truncated(&self, max_chars: usize) -> Truncated<'_, S> {
+        Truncated::new(self.as_ref(), max_chars)
+    }
+
+    /// Returns the proxy to the IRI with the secrets masked when displayed.
+    ///
+    /// The userinfo is masked by default, and the query values can also be
+    /// masked. See [`Redacted`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriRelativeStr;
+    ///
+    /// let iri = IriRelativeStr::new("//user:pw@example.com/?sig=abc#top")?;
+    /// assert_eq!(iri.redacted().to_string(), "//***@example.com/?sig=abc#top");
+    /// assert_eq!(
+    ///     iri.redacted().mask_query_values(true).to_string(),
+    ///     "//***@example.com/?sig=***#top"
+    /// );
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn redacted(&self) -> Redacted<'_, S> {
+        Redacted::new(self.as_ref())
+    }
+
+    impl_tracing_value! {
+        doc_slice = IriRelativeStr,
+        example = ("//user:password@example.com/path?query", "//user:@example.com/path?query"),
+    }
+
+    /// Returns the IRI with Unicode normalization (NFC) applied to the non-ASCII components.
+    ///
+    /// [RFC 3987 section 5.3.2.2] recommends IRIs to be in NFC before
+    /// comparison or storage. Each component (such as the host, the path and
+    /// the query) is normalized separately, and the components that would
+    /// become invalid by normalization are left as is. Percent-encoded
+    /// characters are not decoded nor normalized.
+    ///
+    /// If the IRI is already in NFC, the borrowed IRI is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use std::borrow::Cow;
+    /// use iri_string::types::IriRelativeStr;
+    ///
+    /// // `e` followed by U+0301 COMBINING ACUTE ACCENT.
+    /// let iri = IriRelativeStr::new("//caf\u{65}\u{301}.example/")?;
+    /// let nfc = iri.to_nfc();
+    /// // U+00E9 LATIN SMALL LETTER E WITH ACUTE.
+    /// assert_eq!(nfc.as_str(), "//caf\u{e9}.example/");
+    ///
+    /// let already_nfc = IriRelativeStr::new("//caf\u{e9}.example/")?;
+    /// assert!(matches!(already_nfc.to_nfc(), Cow::Borrowed(_)));
+    /// # Ok::<_, Error>(())
+    /// ```
+    ///
+    /// [RFC 3987 section 5.3.2.2]: https://www.rfc-editor.org/rfc/rfc3987.html#section-5.3.2.2
+    #[cfg(feature = "unicode-normalization")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+    #[must_use]
+    pub fn to_nfc(&self) -> Cow<'_, Self> {
+        match to_nfc_string(self.as_ref()) {
+            Some(s) => match RiRelativeString::try_from(s) {
+                Ok(iri) => Cow::Owned(iri),
+                Err(_) => Cow::Borrowed(self),
+            },
+            None => Cow::Borrowed(self),
+        }
+    }
 }
 
 /// Components getters.
@@ -227,6 +326,29 @@ impl<S: Spec> RiRelativeStr<S> {
         trusted_parser::extract_path_relative(self.as_str())
     }
 
+    /// Returns the path as a typed slice.
+    ///
+    /// This is same as [`path_str`][`Self::path_str`], but the returned value
+    /// carries its validity in the type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::{IriRelativeStr, IriPathStr};
+    ///
+    /// let iri = IriRelativeStr::new("//user@example.com/a/b?q#f")?;
+    /// let path: &IriPathStr = iri.slice_path();
+    /// assert_eq!(path, "/a/b");
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn slice_path(&self) -> &RiPathStr<S> {
+        // SAFETY: the path of a valid IRI is a valid path.
+        unsafe { RiPathStr::new_maybe_unchecked(self.path_str()) }
+    }
+
     /// Returns the query.
     ///
     /// The leading question mark (`?`) is truncated.
@@ -369,16 +491,198 @@ impl<S: Spec> RiRelativeStr<S> {
     pub fn authority_components(&self) -> Option<AuthorityComponents<'_>> {
         AuthorityComponents::from_iri(self.as_ref())
     }
+
+    /// Returns true if the host equals to the given string, ignoring ASCII case.
+    ///
+    /// Hosts are case insensitive (see [RFC 3986 section 3.2.2]), and so are
+    /// hexadecimal digits in percent-encoded triplets. This compares the host
+    /// without modifying or allocating. Note that percent-encoded triplets are
+    /// not decoded, and IDNs are not converted.
+    ///
+    /// Returns `false` if the IRI has no authority.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriRelativeStr;
+    ///
+    /// let iri = IriRelativeStr::new("//user@Example.COM:8080/")?;
+    /// assert!(iri.host_eq("example.com"));
+    /// assert!(!iri.host_eq("example.com:8080"));
+    /// assert!(!iri.host_eq("example.org"));
+    /// # Ok::<_, Error>(())
+    /// ```
+    ///
+    /// [RFC 3986 section 3.2.2]: https://tools.ietf.org/html/rfc3986#section-3.2.2
+    #[must_use]
+    pub fn host_eq(&self, host: &str) -> bool {
+        self.authority_components().map_or(false, |authority| {
+            authority.host().eq_ignore_ascii_case(host)
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Spec> RiRelativeStr<S> {
+    /// Returns the percent-decoded path.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    /// Use [`decoded_path_bytes`][`Self::decoded_path_bytes`] to get the raw
+    /// bytes.
+    ///
+    /// Note that percent-encoded slashes (`%2F`) are also decoded, so the
+    /// result cannot be split into the path segments reliably.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriRelativeStr;
+    ///
+    /// let iri = IriRelativeStr::new("caf%C3%A9/a%2Fb")?;
+    /// assert_eq!(iri.decoded_path(), "café/a/b");
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn decoded_path(&self) -> Cow<'_, str> {
+        AsRef::<RiReferenceStr<S>>::as_ref(self).decoded_path()
+    }
+
+    /// Returns the percent-decoded path as bytes.
+    #[inline]
+    #[must_use]
+    pub fn decoded_path_bytes(&self) -> Cow<'_, [u8]> {
+        AsRef::<RiReferenceStr<S>>::as_ref(self).decoded_path_bytes()
+    }
+
+    /// Returns the percent-decoded fragment.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    /// Use [`decoded_fragment_bytes`][`Self::decoded_fragment_bytes`] to get
+    /// the raw bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriRelativeStr;
+    ///
+    /// let iri = IriRelativeStr::new("#sec%201")?;
+    /// assert_eq!(iri.decoded_fragment().as_deref(), Some("sec 1"));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn decoded_fragment(&self) -> Option<Cow<'_, str>> {
+        AsRef::<RiReferenceStr<S>>::as_ref(self).decoded_fragment()
+    }
+
+    /// Returns the percent-decoded fragment as bytes.
+    #[inline]
+    #[must_use]
+    pub fn decoded_fragment_bytes(&self) -> Option<Cow<'_, [u8]>> {
+        AsRef::<RiReferenceStr<S>>::as_ref(self).decoded_fragment_bytes()
+    }
+
+    /// Returns the percent-decoded userinfo.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    /// Use [`decoded_userinfo_bytes`][`Self::decoded_userinfo_bytes`] to get
+    /// the raw bytes.
+    ///
+    /// Note that the userinfo may contain a password.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriRelativeStr;
+    ///
+    /// let iri = IriRelativeStr::new("//user%40example.com@example.com/")?;
+    /// assert_eq!(iri.decoded_userinfo().as_deref(), Some("user@example.com"));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn decoded_userinfo(&self) -> Option<Cow<'_, str>> {
+        AsRef::<RiReferenceStr<S>>::as_ref(self).decoded_userinfo()
+    }
+
+    /// Returns the percent-decoded userinfo as bytes.
+    #[inline]
+    #[must_use]
+    pub fn decoded_userinfo_bytes(&self) -> Option<Cow<'_, [u8]>> {
+        AsRef::<RiReferenceStr<S>>::as_ref(self).decoded_userinfo_bytes()
+    }
 }
 
 #[cfg(feature = "alloc")]
 impl<S: Spec> RiRelativeString<S> {
+    /// Decomposes the IRI into owned components.
+    ///
+    /// The IRI is parsed only once, and the components are not validated
+    /// again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::IriRelativeString;
+    ///
+    /// let iri = "//user@example.com/path?query#frag".parse::<IriRelativeString>()?;
+    /// let components = iri.into_components();
+    ///
+    /// assert_eq!(components.scheme_str(), None);
+    /// assert_eq!(components.authority().map(|a| a.host()), Some("example.com"));
+    /// assert_eq!(components.path_str(), "/path");
+    /// assert_eq!(components.query().map(|q| q.as_str()), Some("query"));
+    /// assert_eq!(components.fragment().map(|f| f.as_str()), Some("frag"));
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[must_use]
+    pub fn into_components(self) -> OwnedComponents<S> {
+        OwnedComponents::from_reference(self.as_slice().as_ref())
+    }
+
     /// Sets the fragment part to the given string.
     ///
     /// Removes fragment part (and following `#` character) if `None` is given.
     pub fn set_fragment(&mut self, fragment: Option<&RiFragmentStr<S>>) {
         raw::set_fragment(&mut self.inner, fragment.map(AsRef::as_ref));
-        debug_assert!(iri::<S>(&self.inner).is_ok());
+        debug_assert_valid_mutation!(
+            relative_ref::<S>(&self.inner).is_ok(),
+            "[validity] the IRI must be valid after the fragment is replaced"
+        );
+    }
+
+    /// Sets the query part to the given string.
+    ///
+    /// Removes query part (and following `?` character) if `None` is given.
+    ///
+    /// Only the given query is required to be valid, and other components are
+    /// not validated again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use iri_string::validate::Error;
+    /// use iri_string::types::{IriQueryStr, IriRelativeString};
+    ///
+    /// let mut iri = IriRelativeString::try_from("../path?old#frag")?;
+    /// iri.set_query(Some(IriQueryStr::new("new=1")?));
+    /// assert_eq!(iri, "../path?new=1#frag");
+    /// iri.set_query(None);
+    /// assert_eq!(iri, "../path#frag");
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub fn set_query(&mut self, query: Option<&RiQueryStr<S>>) {
+        raw::set_query(&mut self.inner, query.map(AsRef::as_ref));
+        debug_assert_valid_mutation!(
+            relative_ref::<S>(&self.inner).is_ok(),
+            "[validity] the IRI must be valid after the query is replaced"
+        );
     }
 
     /// Removes the password completely (including separator colon) from `self` even if it is empty.
@@ -421,7 +725,7 @@ impl<S: Spec> RiRelativeString<S> {
         unsafe {
             let buf = self.as_inner_mut();
             buf.drain(separator_colon..pw_range.end);
-            debug_assert!(
+            debug_assert_valid_mutation!(
                 RiRelativeStr::<S>::new(buf).is_ok(),
                 "[validity] the IRI must be valid after the password component is removed"
             );
@@ -474,13 +778,24 @@ impl<S: Spec> RiRelativeString<S> {
         unsafe {
             let buf = self.as_inner_mut();
             buf.drain(pw_range);
-            debug_assert!(
+            debug_assert_valid_mutation!(
                 RiRelativeStr::<S>::new(buf).is_ok(),
                 "[validity] the IRI must be valid after the password component \
                  is replaced with the empty password"
             );
         }
     }
+
+    impl_path_edit_methods! {
+        slice = RiRelativeStr,
+        doc_owned = IriRelativeString,
+        doc_slice = IriRelativeStr,
+        ensure_trailing_slash = ("docs#top", "docs/#top"),
+        strip_trailing_slash = ("//example.com/docs/", "//example.com/docs"),
+        strip_index_segment = ("index.html", ""),
+        set_path = ("//example.com/old#top", "", "//example.com#top"),
+        collapse_empty_segments = ("//example.com//docs///a//?q#f", "//example.com/docs/a/?q#f"),
+    }
 }
 
 impl_trivial_conv_between_iri! {
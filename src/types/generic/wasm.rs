@@ -0,0 +1,89 @@
+//! Interoperability with `js-sys` crate.
+//!
+//! IRI string types can be converted into [`JsString`], and vice versa, so
+//! that validators running in browsers can share the logic with backends.
+//!
+//! Conversion from [`JsString`] validates the string. A [`JsString`] is a
+//! sequence of UTF-16 code units and can contain lone surrogates, which
+//! cannot be represented in Rust strings; such strings are rejected as
+//! invalid.
+//!
+//! Conversion into [`JsString`] does not validate the string again.
+//!
+//! [`JsString`]: `js_sys::JsString`
+
+use core::convert::TryFrom;
+
+use js_sys::JsString;
+
+use crate::spec::Spec;
+use crate::types::{
+    CreationError, RiAbsoluteStr, RiAbsoluteString, RiAuthorityStr, RiAuthorityString,
+    RiFragmentStr, RiFragmentString, RiHostStr, RiHostString, RiPathStr, RiPathString, RiQueryStr,
+    RiQueryString, RiReferenceStr, RiReferenceString, RiRelativeStr, RiRelativeString, RiStr,
+    RiString,
+};
+use crate::validate::Error;
+
+/// Implements conversions between string types and `JsString`.
+macro_rules! impl_js_string_conversions {
+    ($($slice:ident, $owned:ident;)*) => {
+        $(
+            #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+            impl<S: Spec> From<&$slice<S>> for JsString {
+                #[inline]
+                fn from(s: &$slice<S>) -> Self {
+                    JsString::from(s.as_str())
+                }
+            }
+
+            #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+            impl<S: Spec> From<$owned<S>> for JsString {
+                #[inline]
+                fn from(s: $owned<S>) -> Self {
+                    JsString::from(s.as_str())
+                }
+            }
+
+            #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+            impl<S: Spec> From<&$owned<S>> for JsString {
+                #[inline]
+                fn from(s: &$owned<S>) -> Self {
+                    JsString::from(s.as_str())
+                }
+            }
+
+            #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+            impl<S: Spec> TryFrom<&JsString> for $owned<S> {
+                type Error = Error;
+
+                fn try_from(s: &JsString) -> Result<Self, Self::Error> {
+                    let s = s.as_string().ok_or_else(Error::new)?;
+                    <$owned<S>>::try_from(s).map_err(|e| e.validation_error())
+                }
+            }
+
+            #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+            impl<S: Spec> TryFrom<JsString> for $owned<S> {
+                type Error = CreationError<JsString>;
+
+                #[inline]
+                fn try_from(s: JsString) -> Result<Self, Self::Error> {
+                    <$owned<S>>::try_from(&s).map_err(|e| CreationError::new(e, s))
+                }
+            }
+        )*
+    };
+}
+
+impl_js_string_conversions! {
+    RiReferenceStr, RiReferenceString;
+    RiStr, RiString;
+    RiAbsoluteStr, RiAbsoluteString;
+    RiRelativeStr, RiRelativeString;
+    RiQueryStr, RiQueryString;
+    RiFragmentStr, RiFragmentString;
+    RiAuthorityStr, RiAuthorityString;
+    RiHostStr, RiHostString;
+    RiPathStr, RiPathString;
+}
@@ -10,12 +10,13 @@ use crate::convert::try_percent_encode_iri_inline;
 use crate::convert::MappedToUri;
 use crate::spec::IriSpec;
 use crate::types::{
-    RiAbsoluteStr, RiFragmentStr, RiQueryStr, RiReferenceStr, RiRelativeStr, RiStr,
+    RiAbsoluteStr, RiAuthorityStr, RiFragmentStr, RiHostStr, RiPathStr, RiQueryStr, RiReferenceStr,
+    RiRelativeStr, RiStr,
 };
 #[cfg(feature = "alloc")]
 use crate::types::{
-    RiAbsoluteString, RiFragmentString, RiQueryString, RiReferenceString, RiRelativeString,
-    RiString,
+    RiAbsoluteString, RiAuthorityString, RiCow, RiFragmentString, RiHostString, RiPathString,
+    RiQueryString, RiReferenceString, RiRelativeString, RiString,
 };
 use crate::types::{
     UriAbsoluteStr, UriFragmentStr, UriQueryStr, UriReferenceStr, UriRelativeStr, UriStr,
@@ -34,6 +35,22 @@ pub type IriAbsoluteStr = RiAbsoluteStr<IriSpec>;
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type IriAbsoluteString = RiAbsoluteString<IriSpec>;
 
+/// A type alias for [`RiAuthorityStr`]`<`[`IriSpec`]`>`.
+pub type IriAuthorityStr = RiAuthorityStr<IriSpec>;
+
+/// A type alias for [`RiAuthorityString`]`<`[`IriSpec`]`>`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type IriAuthorityString = RiAuthorityString<IriSpec>;
+
+/// A type alias for [`RiHostStr`]`<`[`IriSpec`]`>`.
+pub type IriHostStr = RiHostStr<IriSpec>;
+
+/// A type alias for [`RiHostString`]`<`[`IriSpec`]`>`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type IriHostString = RiHostString<IriSpec>;
+
 /// A type alias for [`RiFragmentStr`]`<`[`IriSpec`]`>`.
 pub type IriFragmentStr = RiFragmentStr<IriSpec>;
 
@@ -50,6 +67,11 @@ pub type IriStr = RiStr<IriSpec>;
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type IriString = RiString<IriSpec>;
 
+/// A type alias for [`RiCow`]`<'a, `[`IriSpec`]`>`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type IriCow<'a> = RiCow<'a, IriSpec>;
+
 /// A type alias for [`RiReferenceStr`]`<`[`IriSpec`]`>`.
 pub type IriReferenceStr = RiReferenceStr<IriSpec>;
 
@@ -66,6 +88,14 @@ pub type IriRelativeStr = RiRelativeStr<IriSpec>;
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type IriRelativeString = RiRelativeString<IriSpec>;
 
+/// A type alias for [`RiPathStr`]`<`[`IriSpec`]`>`.
+pub type IriPathStr = RiPathStr<IriSpec>;
+
+/// A type alias for [`RiPathString`]`<`[`IriSpec`]`>`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type IriPathString = RiPathString<IriSpec>;
+
 /// A type alias for [`RiQueryStr`]`<`[`IriSpec`]`>`.
 pub type IriQueryStr = RiQueryStr<IriSpec>;
 
@@ -1,15 +1,17 @@
 //! URI-specific implementations.
 
+use crate::convert::MappedToIri;
 use crate::spec::UriSpec;
 use crate::types::{
     IriAbsoluteStr, IriFragmentStr, IriQueryStr, IriReferenceStr, IriRelativeStr, IriStr,
-    RiAbsoluteStr, RiFragmentStr, RiQueryStr, RiReferenceStr, RiRelativeStr, RiStr,
+    RiAbsoluteStr, RiAuthorityStr, RiFragmentStr, RiHostStr, RiPathStr, RiQueryStr, RiReferenceStr,
+    RiRelativeStr, RiStr,
 };
 #[cfg(feature = "alloc")]
 use crate::types::{
     IriAbsoluteString, IriFragmentString, IriQueryString, IriReferenceString, IriRelativeString,
-    IriString, RiAbsoluteString, RiFragmentString, RiQueryString, RiReferenceString,
-    RiRelativeString, RiString,
+    IriString, RiAbsoluteString, RiAuthorityString, RiCow, RiFragmentString, RiHostString,
+    RiPathString, RiQueryString, RiReferenceString, RiRelativeString, RiString,
 };
 
 /// A type alias for [`RiAbsoluteStr`]`<`[`UriSpec`]`>`.
@@ -20,6 +22,22 @@ pub type UriAbsoluteStr = RiAbsoluteStr<UriSpec>;
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type UriAbsoluteString = RiAbsoluteString<UriSpec>;
 
+/// A type alias for [`RiAuthorityStr`]`<`[`UriSpec`]`>`.
+pub type UriAuthorityStr = RiAuthorityStr<UriSpec>;
+
+/// A type alias for [`RiAuthorityString`]`<`[`UriSpec`]`>`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type UriAuthorityString = RiAuthorityString<UriSpec>;
+
+/// A type alias for [`RiHostStr`]`<`[`UriSpec`]`>`.
+pub type UriHostStr = RiHostStr<UriSpec>;
+
+/// A type alias for [`RiHostString`]`<`[`UriSpec`]`>`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type UriHostString = RiHostString<UriSpec>;
+
 /// A type alias for [`RiFragmentStr`]`<`[`UriSpec`]`>`.
 pub type UriFragmentStr = RiFragmentStr<UriSpec>;
 
@@ -36,6 +54,11 @@ pub type UriStr = RiStr<UriSpec>;
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type UriString = RiString<UriSpec>;
 
+/// A type alias for [`RiCow`]`<'a, `[`UriSpec`]`>`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type UriCow<'a> = RiCow<'a, UriSpec>;
+
 /// A type alias for [`RiReferenceStr`]`<`[`UriSpec`]`>`.
 pub type UriReferenceStr = RiReferenceStr<UriSpec>;
 
@@ -52,6 +75,14 @@ pub type UriRelativeStr = RiRelativeStr<UriSpec>;
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type UriRelativeString = RiRelativeString<UriSpec>;
 
+/// A type alias for [`RiPathStr`]`<`[`UriSpec`]`>`.
+pub type UriPathStr = RiPathStr<UriSpec>;
+
+/// A type alias for [`RiPathString`]`<`[`UriSpec`]`>`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type UriPathString = RiPathString<UriSpec>;
+
 /// A type alias for [`RiQueryStr`]`<`[`UriSpec`]`>`.
 pub type UriQueryStr = RiQueryStr<UriSpec>;
 
@@ -68,6 +99,20 @@ macro_rules! impl_conversions_between_iri {
         $borrowed_iri:ident,
         $owned_iri:ident,
     ) => {
+        /// Conversion from a URI into an IRI.
+        impl $borrowed_uri {
+            /// Decodes percent-encoded non-ASCII characters in the URI, and returns the equivalent IRI.
+            ///
+            /// Characters to be decoded can be specified by
+            /// [`MappedToIri::mode`][`crate::convert::MappedToIri::mode`].
+            /// See [`MappedToIri`] for detail.
+            #[inline]
+            #[must_use]
+            pub fn decode_to_iri(&self) -> MappedToIri<'_, Self> {
+                MappedToIri::from(self)
+            }
+        }
+
         impl AsRef<$borrowed_iri> for $borrowed_uri {
             fn as_ref(&self) -> &$borrowed_iri {
                 // SAFETY: A valid URI is also a valid IRI.
@@ -1,6 +1,7 @@
 //! Validators.
 
 use core::fmt;
+use core::marker::PhantomData;
 
 #[cfg(feature = "std")]
 use std::error;
@@ -186,6 +187,8 @@ pub fn iri_reference<S: Spec>(s: &str) -> Result<(), Error> {
 /// use iri_string::{spec::UriSpec, validate::absolute_iri};
 /// // Fragment part is not allowed.
 /// assert!(absolute_iri::<UriSpec>("https://example.com/foo?bar=baz#qux").is_err());
+/// // Even an empty fragment is not allowed.
+/// assert!(absolute_iri::<UriSpec>("https://example.com/foo#").is_err());
 /// ```
 ///
 /// Some characters and sequences cannot used in an absolute IRI.
@@ -270,6 +273,54 @@ pub fn path<S: Spec>(s: &str) -> Result<(), Error> {
     parser::validate_path::<S>(s)
 }
 
+/// Validates [IRI authority][authority].
+///
+/// This validator corresponds to [`RiAuthorityStr`] and [`RiAuthorityString`] types.
+///
+/// Note that the leading `//` in an IRI is not a part of an authority.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::authority};
+/// assert!(authority::<UriSpec>("").is_ok());
+/// assert!(authority::<UriSpec>("user:pass@example.com:8080").is_ok());
+/// assert!(authority::<UriSpec>("[::1]").is_ok());
+///
+/// assert!(authority::<UriSpec>("//example.com").is_err());
+/// assert!(authority::<UriSpec>("example.com/").is_err());
+/// ```
+///
+/// [authority]: https://tools.ietf.org/html/rfc3986#section-3.2
+/// [`RiAuthorityStr`]: ../types/struct.RiAuthorityStr.html
+/// [`RiAuthorityString`]: ../types/struct.RiAuthorityString.html
+pub fn authority<S: Spec>(s: &str) -> Result<(), Error> {
+    parser::validate_authority::<S>(s)
+}
+
+/// Validates [IRI host][host].
+///
+/// This validator corresponds to [`RiHostStr`] and [`RiHostString`] types.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::host};
+/// assert!(host::<UriSpec>("").is_ok());
+/// assert!(host::<UriSpec>("example.com").is_ok());
+/// assert!(host::<UriSpec>("[2001:db8::1]").is_ok());
+///
+/// assert!(host::<UriSpec>("example.com:8080").is_err());
+/// assert!(host::<UriSpec>("2001:db8::1").is_err());
+/// ```
+///
+/// [host]: https://tools.ietf.org/html/rfc3986#section-3.2.2
+/// [`RiHostStr`]: ../types/struct.RiHostStr.html
+/// [`RiHostString`]: ../types/struct.RiHostString.html
+pub fn host<S: Spec>(s: &str) -> Result<(), Error> {
+    parser::validate_host::<S>(s)
+}
+
 /// Validates [IRI query][query].
 ///
 /// This validator corresponds to [`RiQueryStr`] and [`RiQueryString`] types.
@@ -357,3 +408,746 @@ pub fn query<S: Spec>(s: &str) -> Result<(), Error> {
 pub fn fragment<S: Spec>(s: &str) -> Result<(), Error> {
     parser::validate_fragment::<S>(s)
 }
+
+/// Validates many [IRIs][uri] at once, and returns the indices of invalid entries.
+///
+/// This is a batch version of [`iri`] function, intended for jobs such as
+/// dataset cleaning. The returned iterator yields the indices of the invalid
+/// entries in ascending order, and it is lazy: nothing is validated until
+/// the iterator is consumed.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::validate_all};
+///
+/// let iris = ["https://example.com/", "not an IRI", "foo:bar", "%GG"];
+/// let mut invalid = validate_all::<UriSpec, _>(&iris);
+/// assert_eq!(invalid.next(), Some(1));
+/// assert_eq!(invalid.next(), Some(3));
+/// assert_eq!(invalid.next(), None);
+/// ```
+///
+/// [uri]: https://tools.ietf.org/html/rfc3986#section-3
+pub fn validate_all<S, I>(iris: I) -> InvalidEntries<S, I::IntoIter>
+where
+    S: Spec,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    InvalidEntries {
+        iter: iris.into_iter().enumerate(),
+        _spec: PhantomData,
+    }
+}
+
+/// Iterator of the indices of invalid entries.
+///
+/// This is a return type of [`validate_all`].
+#[derive(Debug, Clone)]
+pub struct InvalidEntries<S, I> {
+    /// Iterator of the entries to validate.
+    iter: core::iter::Enumerate<I>,
+    /// Spec.
+    _spec: PhantomData<fn() -> S>,
+}
+
+impl<S, I> Iterator for InvalidEntries<S, I>
+where
+    S: Spec,
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .by_ref()
+            .find(|(_, s)| iri::<S>(s.as_ref()).is_err())
+            .map(|(i, _)| i)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<S, I> core::iter::FusedIterator for InvalidEntries<S, I>
+where
+    S: Spec,
+    I: core::iter::FusedIterator,
+    I::Item: AsRef<str>,
+{
+}
+
+/// Validates many [IRIs][uri] in parallel, and returns the indices of invalid entries.
+///
+/// This is a parallel version of [`validate_all`] using [`rayon`]. The
+/// returned indices are sorted in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "rayon")] {
+/// use iri_string::{spec::UriSpec, validate::par_validate_all};
+///
+/// let iris = ["https://example.com/", "not an IRI", "foo:bar", "%GG"];
+/// assert_eq!(par_validate_all::<UriSpec, _>(&iris), [1, 3]);
+/// # }
+/// ```
+///
+/// [uri]: https://tools.ietf.org/html/rfc3986#section-3
+/// [`rayon`]: https://docs.rs/rayon
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+#[must_use]
+pub fn par_validate_all<S, T>(iris: &[T]) -> Vec<usize>
+where
+    S: Spec,
+    T: AsRef<str> + Sync,
+{
+    use rayon::prelude::*;
+
+    iris.par_iter()
+        .enumerate()
+        .filter(|(_, s)| iri::<S>(s.as_ref()).is_err())
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Incremental validator of [IRIs][uri] and IRI references.
+///
+/// This validator consumes the string char by char, so that long IRIs read
+/// from files or networks can be validated without collecting them into one
+/// contiguous string. No allocation is required.
+///
+/// Once an invalid character is pushed, the validator stays in the error
+/// state, and [`push`][`Self::push`] and [`finish`][`Self::finish`] always
+/// return errors.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::StreamValidator};
+///
+/// let mut validator = StreamValidator::<UriSpec>::iri();
+/// validator.push_str("https://example.com")?;
+/// validator.push_str("/foo?bar=baz")?;
+/// validator.finish()?;
+///
+/// let mut validator = StreamValidator::<UriSpec>::iri();
+/// validator.push_str("https://example.com/")?;
+/// assert!(validator.push_str("foo bar").is_err());
+/// assert!(validator.finish().is_err());
+/// # Ok::<_, iri_string::validate::Error>(())
+/// ```
+///
+/// Errors that can only be detected at the end of the input are reported by
+/// [`finish`][`Self::finish`].
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::StreamValidator};
+///
+/// let mut validator = StreamValidator::<UriSpec>::iri();
+/// validator.push_str("https")?;
+/// // An IRI should have a scheme, followed by a colon.
+/// assert!(validator.finish().is_err());
+///
+/// let mut validator = StreamValidator::<UriSpec>::iri_reference();
+/// validator.push_str("https")?;
+/// // ...but this is a valid relative reference.
+/// assert!(validator.finish().is_ok());
+/// # Ok::<_, iri_string::validate::Error>(())
+/// ```
+///
+/// [uri]: https://tools.ietf.org/html/rfc3986#section-3
+#[derive(Debug, Clone)]
+pub struct StreamValidator<S> {
+    /// Inner state.
+    inner: parser::StreamState<S>,
+}
+
+impl<S: Spec> StreamValidator<S> {
+    /// Creates a new validator for IRIs.
+    ///
+    /// The result corresponds to [`iri`] function.
+    #[inline]
+    #[must_use]
+    pub fn iri() -> Self {
+        Self {
+            inner: parser::StreamState::new(parser::StreamTarget::Iri),
+        }
+    }
+
+    /// Creates a new validator for IRI references.
+    ///
+    /// The result corresponds to [`iri_reference`] function.
+    #[inline]
+    #[must_use]
+    pub fn iri_reference() -> Self {
+        Self {
+            inner: parser::StreamState::new(parser::StreamTarget::IriReference),
+        }
+    }
+
+    /// Consumes the next character.
+    ///
+    /// # Failures
+    ///
+    /// Fails if no valid string starts with the characters consumed so far.
+    #[inline]
+    pub fn push(&mut self, c: char) -> Result<(), Error> {
+        self.inner.push(c)
+    }
+
+    /// Consumes the characters in the string.
+    ///
+    /// # Failures
+    ///
+    /// Fails if no valid string starts with the characters consumed so far.
+    pub fn push_str(&mut self, s: &str) -> Result<(), Error> {
+        s.chars().try_for_each(|c| self.inner.push(c))
+    }
+
+    /// Checks that the characters consumed so far form a valid string.
+    ///
+    /// The validator is not consumed, so more characters can be pushed after
+    /// a successful check.
+    #[inline]
+    pub fn finish(&self) -> Result<(), Error> {
+        self.inner.finish()
+    }
+}
+
+/// Validates [IRI][uri] given as an iterator of characters.
+///
+/// This is a streaming version of [`iri`] function. See [`StreamValidator`]
+/// for details.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::iri_chars};
+///
+/// let chunks = ["https://", "example.com", "/foo"];
+/// assert!(iri_chars::<UriSpec, _>(chunks.iter().flat_map(|s| s.chars())).is_ok());
+/// assert!(iri_chars::<UriSpec, _>("/foo".chars()).is_err());
+/// ```
+///
+/// [uri]: https://tools.ietf.org/html/rfc3986#section-3
+pub fn iri_chars<S, I>(chars: I) -> Result<(), Error>
+where
+    S: Spec,
+    I: IntoIterator<Item = char>,
+{
+    validate_chars(StreamValidator::<S>::iri(), chars)
+}
+
+/// Validates [IRI reference][uri] given as an iterator of characters.
+///
+/// This is a streaming version of [`iri_reference`] function. See
+/// [`StreamValidator`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::iri_reference_chars};
+///
+/// assert!(iri_reference_chars::<UriSpec, _>("/foo?bar".chars()).is_ok());
+/// assert!(iri_reference_chars::<UriSpec, _>("foo:bar".chars()).is_ok());
+/// assert!(iri_reference_chars::<UriSpec, _>("%zz".chars()).is_err());
+/// ```
+///
+/// [uri]: https://tools.ietf.org/html/rfc3986#section-4.1
+pub fn iri_reference_chars<S, I>(chars: I) -> Result<(), Error>
+where
+    S: Spec,
+    I: IntoIterator<Item = char>,
+{
+    validate_chars(StreamValidator::<S>::iri_reference(), chars)
+}
+
+/// Validates the characters with the given validator.
+fn validate_chars<S, I>(mut validator: StreamValidator<S>, chars: I) -> Result<(), Error>
+where
+    S: Spec,
+    I: IntoIterator<Item = char>,
+{
+    chars.into_iter().try_for_each(|c| validator.push(c))?;
+    validator.finish()
+}
+
+/// Validates [IRI][uri] read from the reader.
+///
+/// The input is decoded as UTF-8 incrementally, and is not collected into a
+/// string.
+///
+/// # Failures
+///
+/// Returns an error of kind [`std::io::ErrorKind::InvalidData`] if the input
+/// is not a valid UTF-8 string or not a valid IRI. In the latter case, the
+/// inner error is [`Error`]. I/O errors from the reader are returned as is.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::iri_reader};
+///
+/// let input: &[u8] = b"https://example.com/foo";
+/// assert!(iri_reader::<UriSpec, _>(input).is_ok());
+///
+/// let input: &[u8] = b"https://example.com/\xFF";
+/// let err = iri_reader::<UriSpec, _>(input).expect_err("invalid UTF-8");
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+/// ```
+///
+/// [uri]: https://tools.ietf.org/html/rfc3986#section-3
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn iri_reader<S, R>(reader: R) -> std::io::Result<()>
+where
+    S: Spec,
+    R: std::io::Read,
+{
+    validate_reader(StreamValidator::<S>::iri(), reader)
+}
+
+/// Validates [IRI reference][uri] read from the reader.
+///
+/// See [`iri_reader`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::IriSpec, validate::iri_reference_reader};
+///
+/// let input = "/r\u{E9}sum\u{E9}?q#frag";
+/// assert!(iri_reference_reader::<IriSpec, _>(input.as_bytes()).is_ok());
+/// ```
+///
+/// [uri]: https://tools.ietf.org/html/rfc3986#section-4.1
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn iri_reference_reader<S, R>(reader: R) -> std::io::Result<()>
+where
+    S: Spec,
+    R: std::io::Read,
+{
+    validate_reader(StreamValidator::<S>::iri_reference(), reader)
+}
+
+/// Validates the input from the reader with the given validator.
+#[cfg(feature = "std")]
+fn validate_reader<S, R>(mut validator: StreamValidator<S>, mut reader: R) -> std::io::Result<()>
+where
+    S: Spec,
+    R: std::io::Read,
+{
+    use std::io;
+
+    /// Creates an error for invalid input.
+    fn invalid_data<E>(e: E) -> io::Error
+    where
+        E: Into<Box<dyn error::Error + Send + Sync>>,
+    {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+
+    let mut buf = [0_u8; 4096];
+    // Length of the incomplete UTF-8 sequence at the beginning of `buf`.
+    let mut pending = 0;
+    loop {
+        let len = match reader.read(&mut buf[pending..]) {
+            Ok(0) if pending != 0 => return Err(invalid_data("incomplete UTF-8 sequence")),
+            Ok(0) => break,
+            Ok(len) => pending + len,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let (valid, rest) = match core::str::from_utf8(&buf[..len]) {
+            Ok(s) => (s, 0),
+            Err(e) if e.error_len().is_some() => return Err(invalid_data(e)),
+            Err(e) => {
+                let valid_len = e.valid_up_to();
+                let s = core::str::from_utf8(&buf[..valid_len])
+                    .expect("[validity] the prefix is valid UTF-8");
+                (s, len - valid_len)
+            }
+        };
+        validator.push_str(valid).map_err(invalid_data)?;
+        buf.copy_within((len - rest)..len, 0);
+        pending = rest;
+    }
+    validator.finish().map_err(invalid_data)
+}
+
+/// Result of the quick pre-check.
+///
+/// See [`quick_check_iri`] and [`quick_check_iri_reference`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuickCheck {
+    /// The string is definitely invalid.
+    Invalid,
+    /// The string is definitely valid.
+    Valid,
+    /// The string should be validated by the full validator.
+    Indeterminate,
+}
+
+impl QuickCheck {
+    /// Returns the result of the full validation, running the validator only if necessary.
+    #[inline]
+    fn or_else<F: FnOnce() -> Result<(), Error>>(self, validate: F) -> Result<(), Error> {
+        match self {
+            Self::Invalid => Err(Error::new()),
+            Self::Valid => Ok(()),
+            Self::Indeterminate => validate(),
+        }
+    }
+}
+
+/// Class bit for characters never allowed in IRIs.
+const CLASS_FORBIDDEN: u8 = 1;
+/// Class bit for characters which need the full parser to be checked.
+const CLASS_NONTRIVIAL: u8 = 1 << 1;
+
+/// Returns the class bits of the byte.
+#[inline]
+fn byte_class(b: u8) -> u8 {
+    if !b.is_ascii() {
+        // Non-ASCII characters are allowed only in some contexts.
+        CLASS_NONTRIVIAL
+    } else if b == b'%'
+        || b == b':'
+        || b == b'?'
+        || b == b'#'
+        || b == b'@'
+        || b == b'['
+        || b == b']'
+    {
+        CLASS_NONTRIVIAL
+    } else if crate::parser::char::is_ascii_unreserved_or_reserved(b) {
+        0
+    } else {
+        CLASS_FORBIDDEN
+    }
+}
+
+/// Returns the union of the class bits of the bytes.
+#[inline]
+fn classify(bytes: &[u8]) -> u8 {
+    // Branchless fold, so that the compiler can vectorize the loop.
+    bytes.iter().fold(0, |acc, &b| acc | byte_class(b))
+}
+
+/// Returns true if the string matches `scheme` rule.
+fn is_scheme(s: &[u8]) -> bool {
+    match s.split_first() {
+        Some((first, rest)) => {
+            first.is_ascii_alphabetic()
+                && rest
+                    .iter()
+                    .all(|&b| b.is_ascii() && crate::parser::char::is_ascii_scheme_continue(b))
+        }
+        None => false,
+    }
+}
+
+/// Quickly checks the string as [IRI][uri] without fully parsing it.
+///
+/// This scans the string only once, and returns:
+///
+/// * [`QuickCheck::Invalid`] if the string has ASCII characters never allowed
+///   in IRIs (such as spaces, control characters, and `<`), or does not
+///   start with a valid scheme followed by a colon,
+/// * [`QuickCheck::Valid`] if the string is a valid scheme followed by a
+///   colon and then characters with no special meanings (i.e. `unreserved`,
+///   `sub-delims`, and `/`), or
+/// * [`QuickCheck::Indeterminate`] otherwise.
+///
+/// Use this to skip the full validation for clearly bad or clearly trivial
+/// inputs on hot paths. [`quick_check_iri_then_validate`] combines this with
+/// [`iri`].
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::{quick_check_iri, QuickCheck}};
+///
+/// assert_eq!(quick_check_iri::<UriSpec>("https://example.com/a/b"), QuickCheck::Valid);
+/// assert_eq!(quick_check_iri::<UriSpec>("https://example.com/?q"), QuickCheck::Indeterminate);
+/// assert_eq!(quick_check_iri::<UriSpec>("https://example.com/a b"), QuickCheck::Invalid);
+/// assert_eq!(quick_check_iri::<UriSpec>("/no/scheme"), QuickCheck::Invalid);
+/// ```
+///
+/// [uri]: https://tools.ietf.org/html/rfc3986#section-3
+#[must_use]
+pub fn quick_check_iri<S: Spec>(s: &str) -> QuickCheck {
+    let bytes = s.as_bytes();
+    let class = classify(bytes);
+    if class & CLASS_FORBIDDEN != 0 {
+        return QuickCheck::Invalid;
+    }
+    // The first colon always terminates the scheme.
+    let colon = match bytes.iter().position(|&b| b == b':') {
+        Some(pos) => pos,
+        None => return QuickCheck::Invalid,
+    };
+    if !is_scheme(&bytes[..colon]) {
+        return QuickCheck::Invalid;
+    }
+    if classify(&bytes[(colon + 1)..]) == 0 {
+        QuickCheck::Valid
+    } else {
+        QuickCheck::Indeterminate
+    }
+}
+
+/// Quickly checks the string as [IRI reference][uri-reference] without fully parsing it.
+///
+/// This is similar to [`quick_check_iri`], but a string without a scheme is
+/// also considered as [`QuickCheck::Valid`] if it has no characters with
+/// special meanings (i.e. it consists of `unreserved`, `sub-delims`, and `/`).
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::{quick_check_iri_reference, QuickCheck}};
+///
+/// assert_eq!(quick_check_iri_reference::<UriSpec>("../a/b"), QuickCheck::Valid);
+/// assert_eq!(quick_check_iri_reference::<UriSpec>("foo:bar"), QuickCheck::Valid);
+/// assert_eq!(quick_check_iri_reference::<UriSpec>("a/b:c"), QuickCheck::Indeterminate);
+/// assert_eq!(quick_check_iri_reference::<UriSpec>("a\\b"), QuickCheck::Invalid);
+/// ```
+///
+/// [uri-reference]: https://tools.ietf.org/html/rfc3986#section-4.1
+#[must_use]
+pub fn quick_check_iri_reference<S: Spec>(s: &str) -> QuickCheck {
+    let bytes = s.as_bytes();
+    let class = classify(bytes);
+    if class & CLASS_FORBIDDEN != 0 {
+        return QuickCheck::Invalid;
+    }
+    if class == 0 {
+        // A relative reference without colons.
+        return QuickCheck::Valid;
+    }
+    match quick_check_iri::<S>(s) {
+        QuickCheck::Valid => QuickCheck::Valid,
+        // Can be a relative reference.
+        _ => QuickCheck::Indeterminate,
+    }
+}
+
+/// Validates [IRI][uri], skipping the full validation if the quick check is conclusive.
+///
+/// The result is the same as [`iri`].
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::{spec::UriSpec, validate::quick_check_iri_then_validate};
+///
+/// assert!(quick_check_iri_then_validate::<UriSpec>("https://example.com/").is_ok());
+/// assert!(quick_check_iri_then_validate::<UriSpec>("https://example.com/?q=%20").is_ok());
+/// assert!(quick_check_iri_then_validate::<UriSpec>("https://example.com/?q=%").is_err());
+/// ```
+///
+/// [uri]: https://tools.ietf.org/html/rfc3986#section-3
+pub fn quick_check_iri_then_validate<S: Spec>(s: &str) -> Result<(), Error> {
+    quick_check_iri::<S>(s).or_else(|| iri::<S>(s))
+}
+
+/// Validates [IRI reference][uri-reference], skipping the full validation if the quick check is conclusive.
+///
+/// The result is the same as [`iri_reference`].
+///
+/// [uri-reference]: https://tools.ietf.org/html/rfc3986#section-4.1
+pub fn quick_check_iri_reference_then_validate<S: Spec>(s: &str) -> Result<(), Error> {
+    quick_check_iri_reference::<S>(s).or_else(|| iri_reference::<S>(s))
+}
+
+/// Upper bounds of the work to validate an untrusted input.
+///
+/// The validators of this crate run in time linear to the input length, and
+/// do not recurse. So limiting the length of the input gives a hard bound on
+/// the parse work, independent of the shape of the input. In addition, the
+/// number of components to be processed later (such as the path segments
+/// and the query parameters) can be limited.
+///
+/// The limits are checked before full validation where possible: inputs
+/// exceeding the length limit are rejected without being scanned at all.
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::spec::UriSpec;
+/// use iri_string::validate::{BudgetedError, ParserBudget};
+///
+/// let budget = ParserBudget::new().max_len(32).max_components(4);
+/// assert!(budget.iri::<UriSpec>("https://example.com/a/b?c").is_ok());
+///
+/// let long = "https://example.com/0123456789abcdef";
+/// assert_eq!(budget.iri::<UriSpec>(long), Err(BudgetedError::BudgetExceeded));
+///
+/// let deep = "https://example.com/a/b/c/d/e";
+/// assert_eq!(budget.iri::<UriSpec>(deep), Err(BudgetedError::BudgetExceeded));
+///
+/// assert!(matches!(
+///     budget.iri::<UriSpec>("not an IRI"),
+///     Err(BudgetedError::Invalid(_))
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct ParserBudget {
+    /// Maximum length in bytes.
+    max_len: usize,
+    /// Maximum number of the path segments and the query parameters in total.
+    max_components: usize,
+}
+
+impl Default for ParserBudget {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_len: usize::MAX,
+            max_components: usize::MAX,
+        }
+    }
+}
+
+impl ParserBudget {
+    /// Creates a new budget without limits.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length of the input in bytes.
+    #[inline]
+    pub fn max_len(mut self, len: usize) -> Self {
+        self.max_len = len;
+        self
+    }
+
+    /// Sets the maximum number of the components.
+    ///
+    /// The components counted are the path segments (i.e. the non-empty
+    /// path without the leading `/`, split by `/`) and the query parameters
+    /// (i.e. the query split by `&`, if present).
+    #[inline]
+    pub fn max_components(mut self, components: usize) -> Self {
+        self.max_components = components;
+        self
+    }
+
+    /// Validates the input with the validator, within the budget.
+    fn check<S: Spec>(
+        &self,
+        s: &str,
+        validate: fn(&str) -> Result<(), Error>,
+    ) -> Result<(), BudgetedError> {
+        if s.len() > self.max_len {
+            return Err(BudgetedError::BudgetExceeded);
+        }
+        validate(s)?;
+        if self.max_components == usize::MAX {
+            return Ok(());
+        }
+        // SAFETY: all the validators used with this function accept only
+        // IRI references.
+        let iri = unsafe { crate::types::RiReferenceStr::<S>::new_maybe_unchecked(s) };
+        let path = iri.path_str();
+        let segments = if path.is_empty() {
+            0
+        } else {
+            path.strip_prefix('/').unwrap_or(path).split('/').count()
+        };
+        let params = iri.query_str().map_or(0, |query| query.split('&').count());
+        if segments.saturating_add(params) > self.max_components {
+            return Err(BudgetedError::BudgetExceeded);
+        }
+        Ok(())
+    }
+
+    /// Validates [IRI][uri] within the budget.
+    ///
+    /// The validation result is the same as [`iri`] if the budget is not
+    /// exceeded.
+    ///
+    /// [uri]: https://tools.ietf.org/html/rfc3986#section-3
+    pub fn iri<S: Spec>(&self, s: &str) -> Result<(), BudgetedError> {
+        self.check::<S>(s, iri::<S>)
+    }
+
+    /// Validates [IRI reference][uri-reference] within the budget.
+    ///
+    /// The validation result is the same as [`iri_reference`] if the budget
+    /// is not exceeded.
+    ///
+    /// [uri-reference]: https://tools.ietf.org/html/rfc3986#section-4.1
+    pub fn iri_reference<S: Spec>(&self, s: &str) -> Result<(), BudgetedError> {
+        self.check::<S>(s, iri_reference::<S>)
+    }
+
+    /// Validates [absolute IRI][absolute-uri] within the budget.
+    ///
+    /// The validation result is the same as [`absolute_iri`] if the budget
+    /// is not exceeded.
+    ///
+    /// [absolute-uri]: https://tools.ietf.org/html/rfc3986#section-4.3
+    pub fn absolute_iri<S: Spec>(&self, s: &str) -> Result<(), BudgetedError> {
+        self.check::<S>(s, absolute_iri::<S>)
+    }
+
+    /// Validates [relative reference][relative-ref] within the budget.
+    ///
+    /// The validation result is the same as [`relative_ref`] if the budget
+    /// is not exceeded.
+    ///
+    /// [relative-ref]: https://tools.ietf.org/html/rfc3986#section-4.2
+    pub fn relative_ref<S: Spec>(&self, s: &str) -> Result<(), BudgetedError> {
+        self.check::<S>(s, relative_ref::<S>)
+    }
+}
+
+/// Error on validation within [`ParserBudget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BudgetedError {
+    /// The input exceeded the budget.
+    ///
+    /// The input may or may not be valid.
+    BudgetExceeded,
+    /// The input is invalid.
+    Invalid(Error),
+}
+
+impl From<Error> for BudgetedError {
+    #[inline]
+    fn from(e: Error) -> Self {
+        Self::Invalid(e)
+    }
+}
+
+impl fmt::Display for BudgetedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BudgetExceeded => f.write_str("parser budget exceeded"),
+            Self::Invalid(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for BudgetedError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::BudgetExceeded => None,
+            Self::Invalid(e) => Some(e),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Shorthand XPointer fragment identifiers.
+//!
+//! See [XPointer Framework] for XPointer. The shorthand pointer is a bare
+//! `NCName` that identifies the element with the same ID, such as `#intro`
+//! of `doc.xml#intro`. Other pointer schemes (such as `element()` and
+//! `xpointer()`) are not supported by this module.
+//!
+//! In the fragment, non-ASCII characters of the name are percent-encoded as
+//! UTF-8 for URIs.
+//!
+//! # Examples
+//!
+//! ```
+//! # use iri_string::xpointer::Error;
+//! use iri_string::types::UriReferenceStr;
+//! use iri_string::xpointer::shorthand;
+//!
+//! let uri = UriReferenceStr::new("doc.xml#sec-%C3%A9t%C3%A9").expect("valid URI");
+//! assert_eq!(shorthand(uri.fragment().expect("has fragment"))?, "sec-\u{E9}t\u{E9}");
+//!
+//! let uri = UriReferenceStr::new("doc.xml#element(/1/2)").expect("valid URI");
+//! assert!(shorthand(uri.fragment().expect("has fragment")).is_err());
+//! # Ok::<_, Error>(())
+//! ```
+//!
+//! [XPointer Framework]: https://www.w3.org/TR/xptr-framework/
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use crate::percent_decode::decode_bytes;
+use crate::spec::Spec;
+use crate::types::RiFragmentStr;
+
+/// Error on parsing XPointers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error(());
+
+impl Error {
+    /// Creates a new `Error`.
+    #[inline]
+    #[must_use]
+    fn new() -> Self {
+        Self(())
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid shorthand XPointer")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl error::Error for Error {}
+
+/// Returns the name of the shorthand pointer in the fragment.
+///
+/// Percent-encoded triplets in the fragment are decoded.
+///
+/// # Failures
+///
+/// Fails if the decoded fragment is not valid UTF-8, or not an `NCName`
+/// (including the case when the fragment uses pointer schemes).
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::types::IriFragmentStr;
+/// use iri_string::xpointer::shorthand;
+///
+/// let fragment = IriFragmentStr::new("\u{E9}t\u{E9}").expect("valid fragment");
+/// assert_eq!(shorthand(fragment).as_deref(), Ok("\u{E9}t\u{E9}"));
+///
+/// for s in ["", "1st", "a:b", "xpointer(id('a'))", "%FF"] {
+///     let fragment = IriFragmentStr::new(s).expect("valid fragment");
+///     assert!(shorthand(fragment).is_err(), "{s:?}");
+/// }
+/// ```
+pub fn shorthand<S: Spec>(fragment: &RiFragmentStr<S>) -> Result<Cow<'_, str>, Error> {
+    let s = fragment.as_str();
+    let name = match decode_bytes(s, false) {
+        Cow::Borrowed(_) => Cow::Borrowed(s),
+        Cow::Owned(bytes) => Cow::Owned(String::from_utf8(bytes).map_err(|_| Error::new())?),
+    };
+    if is_ncname(&name) {
+        Ok(name)
+    } else {
+        Err(Error::new())
+    }
+}
+
+/// Returns true if the string is an `NCName` of [Namespaces in XML].
+///
+/// # Examples
+///
+/// ```
+/// use iri_string::xpointer::is_ncname;
+///
+/// assert!(is_ncname("_a-b.c\u{B7}1"));
+/// assert!(!is_ncname(""));
+/// assert!(!is_ncname("-a"));
+/// assert!(!is_ncname("a:b"));
+/// ```
+///
+/// [Namespaces in XML]: https://www.w3.org/TR/xml-names/#NT-NCName
+#[must_use]
+pub fn is_ncname(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if is_name_start_char(c) => chars.all(is_name_char),
+        _ => false,
+    }
+}
+
+/// Returns true if the character matches `NameStartChar` except for `:`.
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        'A'..='Z' | '_' | 'a'..='z'
+        | '\u{C0}'..='\u{D6}'
+        | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}'
+        | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// Returns true if the character matches `NameChar` except for `:`.
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c,
+            '-' | '.' | '0'..='9' | '\u{B7}'
+            | '\u{300}'..='\u{36F}'
+            | '\u{203F}'..='\u{2040}'
+        )
+}
@@ -0,0 +1,117 @@
+//! Tests for `acct:` IRIs.
+#![cfg(feature = "alloc")]
+
+use iri_string::acct::{account_to_acct, acct_to_account};
+use iri_string::types::{IriStr, IriString, UriStr, UriString};
+
+// [(user, host, IRI, URI)]
+const ROUNDTRIP_CASES: &[(&str, &str, &str, &str)] = &[
+    (
+        "alice",
+        "example.com",
+        "acct:alice@example.com",
+        "acct:alice@example.com",
+    ),
+    (
+        "juliet@capulet.example",
+        "shoppingsite.example",
+        "acct:juliet%40capulet.example@shoppingsite.example",
+        "acct:juliet%40capulet.example@shoppingsite.example",
+    ),
+    (
+        "a!$&'()*+,;=-._~b",
+        "192.0.2.1",
+        "acct:a!$&'()*+,;=-._~b@192.0.2.1",
+        "acct:a!$&'()*+,;=-._~b@192.0.2.1",
+    ),
+    (
+        "a:b/c?d#e%f g",
+        "example.com",
+        "acct:a%3Ab%2Fc%3Fd%23e%25f%20g@example.com",
+        "acct:a%3Ab%2Fc%3Fd%23e%25f%20g@example.com",
+    ),
+    (
+        "j\u{FC}rgen",
+        "example.com",
+        "acct:j\u{FC}rgen@example.com",
+        "acct:j%C3%BCrgen@example.com",
+    ),
+];
+
+#[test]
+fn roundtrip() {
+    for &(user, host, iri_expected, uri_expected) in ROUNDTRIP_CASES {
+        let iri: IriString = account_to_acct(user, host).expect(user);
+        assert_eq!(iri, iri_expected);
+        let uri: UriString = account_to_acct(user, host).expect(user);
+        assert_eq!(uri, uri_expected);
+
+        let account = acct_to_account(&iri).expect(iri_expected);
+        assert_eq!(account.user().expect(iri_expected), user);
+        assert_eq!(account.host_str(), host);
+        let account = acct_to_account(&uri).expect(uri_expected);
+        assert_eq!(account.user().expect(uri_expected), user);
+        assert_eq!(account.host_str(), host);
+    }
+}
+
+#[test]
+fn non_ascii_first_char() {
+    // `userpart` cannot start with a percent-encoded triplet.
+    let iri: IriString = account_to_acct("\u{3042}", "example.com").expect("valid for IRI");
+    assert_eq!(iri, "acct:\u{3042}@example.com");
+    assert!(account_to_acct::<iri_string::spec::UriSpec>("\u{3042}", "example.com").is_err());
+}
+
+#[test]
+fn non_ascii_host() {
+    let iri: IriString = account_to_acct("alice", "b\u{FC}cher.example").expect("valid host");
+    assert_eq!(iri, "acct:alice@b\u{FC}cher.example");
+    assert!(account_to_acct::<iri_string::spec::UriSpec>("alice", "b\u{FC}cher.example").is_err());
+
+    let uri = UriStr::new("acct:alice@b%C3%BCcher.example").expect("valid URI");
+    let account = acct_to_account(uri).expect("valid acct URI");
+    assert_eq!(account.host_str(), "b%C3%BCcher.example");
+}
+
+#[test]
+fn invalid_construction() {
+    for (user, host) in [
+        ("", "example.com"),
+        ("@alice", "example.com"),
+        ("%alice", "example.com"),
+        ("alice", ""),
+        ("alice", "a@b"),
+        ("alice", "a/b"),
+        ("alice", "[::1]"),
+        ("alice", "%GG"),
+    ] {
+        assert!(
+            account_to_acct::<iri_string::spec::IriSpec>(user, host).is_err(),
+            "user={user:?}, host={host:?}"
+        );
+    }
+}
+
+#[test]
+fn invalid_parse() {
+    for s in [
+        "mailto:alice@example.com",
+        "acct://alice@example.com",
+        "acct:alice@example.com?q",
+        "acct:alice@example.com#f",
+        "acct:alice",
+        "acct:@example.com",
+        "acct:%61lice@example.com",
+        "acct:alice@",
+        "acct:a:b@example.com",
+        "acct:a/b@example.com",
+        "acct:alice@example.com/",
+        "acct:a@b@example.com",
+        "acct:a%FFb@example.com",
+    ] {
+        let iri = IriStr::new(s).expect("valid IRI");
+        let result = acct_to_account(iri).and_then(|account| account.user().map(|_| ()));
+        assert!(result.is_err(), "{s:?}");
+    }
+}
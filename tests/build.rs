@@ -574,3 +574,222 @@ fn no_authority_and_relative_first_segment_colon() {
     builder.normalize();
     assert!(builder.build::<IriReferenceStr>().is_err());
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn capacity_hint() {
+    use iri_string::format::ToDedicatedString;
+
+    let mut builder = Builder::with_capacity(32);
+    builder.reserve(32);
+    builder.scheme("http");
+    builder.host("example.com");
+    builder.path("/");
+
+    let built = builder.build::<IriStr>().expect("valid IRI");
+    let owned: IriString = (&built).into();
+    assert_eq!(owned, "http://example.com/");
+    assert!(owned.capacity() >= 64);
+
+    let mut owned = built.to_dedicated_string();
+    assert!(owned.capacity() >= 64);
+
+    owned.shrink_to_fit();
+    owned.reserve(100);
+    assert!(owned.capacity() >= owned.len() + 100);
+    assert!(owned.try_reserve(10).is_ok());
+    assert_eq!(owned, "http://example.com/");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn write_to_writer() {
+    use core::fmt::Write as _;
+
+    /// Writer that accepts at most the given number of bytes.
+    struct Limited {
+        buf: String,
+        limit: usize,
+    }
+    impl core::fmt::Write for Limited {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            if self.buf.len() + s.len() > self.limit {
+                return Err(core::fmt::Error);
+            }
+            self.buf.push_str(s);
+            Ok(())
+        }
+    }
+
+    let mut builder = Builder::new();
+    builder.scheme("HTTP");
+    builder.host("example.com");
+    builder.path("/a/../b");
+    builder.query("q");
+    builder.normalize();
+
+    let mut w = String::from("Location: ");
+    builder
+        .write_to::<IriStr, _>(&mut w)
+        .expect("valid IRI and infallible writer");
+    w.write_str("\r\n").expect("infallible writer");
+    assert_eq!(w, "Location: http://example.com/b?q\r\n");
+
+    let built = builder.clone().build::<UriStr>().expect("valid URI");
+    let mut w = String::new();
+    built.write_to(&mut w).expect("infallible writer");
+    assert_eq!(w, built.to_string());
+
+    let mut limited = Limited {
+        buf: String::new(),
+        limit: 8,
+    };
+    assert_eq!(
+        builder.write_to::<IriStr, _>(&mut limited),
+        Err(iri_string::build::WriteError::Fmt(core::fmt::Error))
+    );
+    assert!(matches!(
+        builder.write_to::<IriRelativeStr, _>(&mut String::new()),
+        Err(iri_string::build::WriteError::Validation(_))
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn build_partial_forms() {
+    use iri_string::build::Form;
+
+    // (form, expected).
+    const CASES: &[(Form, &str)] = &[
+        (Form::Full, "http://user@example.com/a/b?q=1#f"),
+        (Form::WithoutScheme, "//user@example.com/a/b?q=1#f"),
+        (Form::WithoutOrigin, "/a/b?q=1#f"),
+        (Form::PathAndQuery, "/a/b?q=1"),
+    ];
+
+    let mut builder = Builder::new();
+    builder.scheme("http");
+    builder.userinfo("user");
+    builder.host("example.com");
+    builder.path("/a/b");
+    builder.query("q=1");
+    builder.fragment("f");
+    for &(form, expected) in CASES {
+        builder.form(form);
+        let built = builder
+            .clone()
+            .build::<IriReferenceStr>()
+            .expect("valid IRI reference");
+        assert_eq!(built.to_string(), expected, "form={form:?}");
+        // Rendering is deterministic.
+        assert_eq!(built.to_string(), built.clone().to_string());
+    }
+
+    // Relative references cannot be built as IRIs.
+    builder.form(Form::WithoutOrigin);
+    assert!(builder.clone().build::<IriStr>().is_err());
+    assert!(builder.build::<IriRelativeStr>().is_ok());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn build_partial_forms_with_ambiguous_paths() {
+    use iri_string::build::Form;
+
+    // (scheme, has_authority, path, normalize, form, expected).
+    type Case<'a> = (&'a str, bool, &'a str, bool, Form, Option<&'a str>);
+    const CASES: &[Case<'_>] = &[
+        ("foo", false, "//bar", false, Form::Full, None),
+        (
+            "foo",
+            false,
+            "//bar",
+            false,
+            Form::WithoutScheme,
+            Some("/.//bar"),
+        ),
+        (
+            "foo",
+            true,
+            "//bar",
+            false,
+            Form::WithoutOrigin,
+            Some("/.//bar"),
+        ),
+        ("foo", false, "a:b/c", false, Form::Full, Some("foo:a:b/c")),
+        (
+            "foo",
+            false,
+            "a:b/c",
+            false,
+            Form::WithoutScheme,
+            Some("./a:b/c"),
+        ),
+        (
+            "foo",
+            false,
+            "a:b/c",
+            true,
+            Form::WithoutScheme,
+            Some("./a:b/c"),
+        ),
+        (
+            "foo",
+            false,
+            "/..//bar",
+            true,
+            Form::WithoutScheme,
+            Some("/.//bar"),
+        ),
+        ("foo", true, "", false, Form::WithoutOrigin, Some("")),
+        ("foo", true, "", true, Form::PathAndQuery, Some("/")),
+        ("foo", true, "/a/../b", true, Form::PathAndQuery, Some("/b")),
+        (
+            "foo",
+            false,
+            "//bar",
+            false,
+            Form::PathAndQuery,
+            Some("/.//bar"),
+        ),
+        // The origin-form requires an absolute path.
+        ("foo", true, "a", false, Form::PathAndQuery, None),
+        ("foo", false, "a", false, Form::PathAndQuery, None),
+        ("foo", false, "a", true, Form::PathAndQuery, None),
+        ("foo", false, "a:b/c", false, Form::PathAndQuery, None),
+    ];
+    for &(scheme, has_authority, path, normalize, form, expected) in CASES {
+        let mut builder = Builder::new();
+        builder.scheme(scheme);
+        if has_authority {
+            builder.host("example.com");
+        }
+        builder.path(path);
+        if normalize {
+            builder.normalize();
+        }
+        builder.form(form);
+        let built = builder.build::<IriReferenceStr>().map(|b| b.to_string());
+        let case = (scheme, has_authority, path, normalize, form);
+        assert_eq!(built.ok().as_deref(), expected, "case={case:?}");
+        if let Some(expected) = expected {
+            let iri = IriReferenceStr::new(expected).expect("valid IRI reference");
+            assert!(
+                iri.authority_str().is_none() || has_authority,
+                "case={case:?}"
+            );
+        }
+    }
+}
+
+/// Hosts can be given as references to owned strings.
+#[cfg(feature = "alloc")]
+#[test]
+fn host_from_string_ref() {
+    let host = String::from("example.com");
+    let mut builder = Builder::new();
+    builder.scheme("http");
+    builder.host(&host);
+    let built = builder.build::<IriStr>().expect("valid IRI");
+    assert_eq_display!(built, "http://example.com");
+}
@@ -0,0 +1,54 @@
+//! Tests for bulk validation.
+#![cfg(feature = "alloc")]
+
+use iri_string::bulk::{validate_slice, validate_slice_into, ValidityBits};
+use iri_string::spec::UriSpec;
+use iri_string::validate::iri;
+
+fn rows(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| {
+            if i % 3 == 0 || i % 7 == 0 {
+                format!("%GG{i}")
+            } else {
+                format!("http://example.com/{i}")
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn matches_individual_validation() {
+    for count in [0, 1, 63, 64, 65, 128, 200] {
+        let rows = rows(count);
+        let expected = rows
+            .iter()
+            .map(|s| iri::<UriSpec>(s).is_ok())
+            .collect::<Vec<_>>();
+        let bits = validate_slice::<UriSpec, _>(&rows);
+        assert_eq!(bits.len(), count);
+        assert_eq!(bits.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(bits.count_valid(), expected.iter().filter(|&&v| v).count());
+        assert_eq!(
+            bits.invalid_indices().collect::<Vec<_>>(),
+            (0..count).filter(|&i| !expected[i]).collect::<Vec<_>>()
+        );
+        assert_eq!(bits.get(count), None);
+    }
+}
+
+#[test]
+fn incremental_validation() {
+    let rows = rows(300);
+    let whole = validate_slice::<UriSpec, _>(&rows);
+
+    let mut bits = ValidityBits::default();
+    for chunk in [&rows[..10], &rows[10..70], &rows[70..71], &rows[71..]] {
+        validate_slice_into::<UriSpec, _>(chunk, &mut bits);
+    }
+    assert_eq!(bits, whole);
+
+    bits.clear();
+    assert!(bits.is_empty());
+    assert!(bits.all_valid());
+}
@@ -0,0 +1,90 @@
+//! Tests for character class predicates.
+
+use iri_string::chars::{
+    is_gen_delim, is_ifragment_char, is_ipchar, is_iprivate, is_iquery_char, is_ireg_name_char,
+    is_iunreserved, is_iuserinfo_char, is_reserved, is_scheme_continue, is_sub_delim, is_ucschar,
+};
+use iri_string::spec::{IriSpec, UriSpec};
+
+const GEN_DELIMS: &str = ":/?#[]@";
+const SUB_DELIMS: &str = "!$&'()*+,;=";
+
+fn is_ascii_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~".contains(c)
+}
+
+#[test]
+fn ascii_classes() {
+    for b in 0_u8..=0x7F {
+        let c = char::from(b);
+        let unreserved = is_ascii_unreserved(c);
+        let sub_delim = SUB_DELIMS.contains(c);
+        let pchar = unreserved || sub_delim || c == ':' || c == '@';
+
+        assert_eq!(is_gen_delim(c), GEN_DELIMS.contains(c), "{c:?}");
+        assert_eq!(is_sub_delim(c), sub_delim, "{c:?}");
+        assert_eq!(is_reserved(c), GEN_DELIMS.contains(c) || sub_delim, "{c:?}");
+        assert_eq!(
+            is_scheme_continue(c),
+            c.is_ascii_alphanumeric() || "+-.".contains(c),
+            "{c:?}"
+        );
+        assert_eq!(is_iunreserved::<UriSpec>(c), unreserved, "{c:?}");
+        assert_eq!(is_iunreserved::<IriSpec>(c), unreserved, "{c:?}");
+        assert_eq!(is_ipchar::<UriSpec>(c), pchar, "{c:?}");
+        assert_eq!(
+            is_iuserinfo_char::<UriSpec>(c),
+            unreserved || sub_delim || c == ':',
+            "{c:?}"
+        );
+        assert_eq!(
+            is_ireg_name_char::<UriSpec>(c),
+            unreserved || sub_delim,
+            "{c:?}"
+        );
+        assert_eq!(
+            is_iquery_char::<UriSpec>(c),
+            pchar || "/?".contains(c),
+            "{c:?}"
+        );
+        assert_eq!(
+            is_ifragment_char::<UriSpec>(c),
+            pchar || "/?".contains(c),
+            "{c:?}"
+        );
+        assert!(!is_ucschar(c), "{c:?}");
+        assert!(!is_iprivate(c), "{c:?}");
+    }
+}
+
+#[test]
+fn nonascii_classes() {
+    for c in ['\u{A0}', '\u{03B1}', '\u{3042}', '\u{1F363}'] {
+        assert!(is_ucschar(c), "{c:?}");
+        assert!(!is_iprivate(c), "{c:?}");
+        assert!(is_iunreserved::<IriSpec>(c), "{c:?}");
+        assert!(is_ipchar::<IriSpec>(c), "{c:?}");
+        assert!(is_iuserinfo_char::<IriSpec>(c), "{c:?}");
+        assert!(is_ireg_name_char::<IriSpec>(c), "{c:?}");
+        assert!(is_iquery_char::<IriSpec>(c), "{c:?}");
+        assert!(is_ifragment_char::<IriSpec>(c), "{c:?}");
+        assert!(!is_iunreserved::<UriSpec>(c), "{c:?}");
+        assert!(!is_ipchar::<UriSpec>(c), "{c:?}");
+        assert!(!is_iquery_char::<UriSpec>(c), "{c:?}");
+    }
+
+    for c in ['\u{E000}', '\u{F8FF}', '\u{F0000}', '\u{10FFFD}'] {
+        assert!(!is_ucschar(c), "{c:?}");
+        assert!(is_iprivate(c), "{c:?}");
+        assert!(is_iquery_char::<IriSpec>(c), "{c:?}");
+        assert!(!is_ifragment_char::<IriSpec>(c), "{c:?}");
+        assert!(!is_ipchar::<IriSpec>(c), "{c:?}");
+        assert!(!is_iquery_char::<UriSpec>(c), "{c:?}");
+    }
+
+    for c in ['\u{80}', '\u{9F}', '\u{FFFE}', '\u{FDD0}', '\u{E0001}'] {
+        assert!(!is_ucschar(c), "{c:?}");
+        assert!(!is_iprivate(c), "{c:?}");
+        assert!(!is_iquery_char::<IriSpec>(c), "{c:?}");
+    }
+}
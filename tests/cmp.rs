@@ -0,0 +1,51 @@
+//! Tests for comparison of IRIs.
+
+use core::cmp::Ordering;
+
+use iri_string::types::{IriReferenceStr, IriStr};
+
+#[test]
+fn component_cmp_sort() {
+    let mut iris = [
+        "https://example.com/a-b",
+        "https://example.com/a/b?q",
+        "http://example.com/z",
+        "https://example.com/a/b",
+        "https://example.com/a",
+        "https://a.example.com/z",
+        "https:/no-authority",
+        "https://example.com/a/b#f",
+        "https://example.com/a/b?q#f",
+    ]
+    .map(|s| IriStr::new(s).expect("should be valid"));
+    iris.sort_by(|a, b| a.component_cmp(b));
+    assert_eq!(
+        iris.map(IriStr::as_str),
+        [
+            "http://example.com/z",
+            "https:/no-authority",
+            "https://a.example.com/z",
+            "https://example.com/a",
+            "https://example.com/a/b",
+            "https://example.com/a/b#f",
+            "https://example.com/a/b?q",
+            "https://example.com/a/b?q#f",
+            "https://example.com/a-b",
+        ]
+    );
+}
+
+#[test]
+fn component_cmp_relative() {
+    let cmp = |a: &str, b: &str| {
+        IriReferenceStr::new(a)
+            .expect("should be valid")
+            .component_cmp(IriReferenceStr::new(b).expect("should be valid"))
+    };
+    assert_eq!(cmp("a/b", "a/b"), Ordering::Equal);
+    assert_eq!(cmp("a/b", "a-b"), Ordering::Less);
+    assert_eq!(cmp("/a", "a"), Ordering::Less);
+    assert_eq!(cmp("a", "//host"), Ordering::Less);
+    assert_eq!(cmp("//host", "scheme:"), Ordering::Less);
+    assert_eq!(cmp("", "?"), Ordering::Less);
+}
@@ -0,0 +1,42 @@
+//! Tests for borrowed-or-owned IRI type.
+#![cfg(feature = "alloc")]
+
+use std::collections::HashSet;
+
+use iri_string::types::{IriCow, IriStr, IriString};
+
+#[test]
+fn borrowed_and_owned_are_equivalent() {
+    let s = "https://user@example.com/path?query#fragment";
+    let borrowed = IriCow::new(s).expect("valid IRI");
+    let owned = IriCow::from(IriStr::new(s).expect("valid IRI").to_owned());
+    assert!(borrowed.is_borrowed());
+    assert!(owned.is_owned());
+
+    assert_eq!(borrowed, owned);
+    assert_eq!(borrowed.as_str(), s);
+    assert_eq!(borrowed.authority_str(), owned.authority_str());
+    assert_eq!(borrowed.to_string(), owned.to_string());
+
+    let mut set = HashSet::new();
+    set.insert(borrowed);
+    assert!(set.contains(IriStr::new(s).expect("valid IRI")));
+    assert!(!set.insert(owned));
+}
+
+#[test]
+fn conversions() {
+    let s = "https://example.com/";
+    assert!(IriCow::try_from(s).is_ok());
+    assert!(IriCow::try_from("not an IRI").is_err());
+    assert!(IriCow::try_from(String::from(s))
+        .expect("valid IRI")
+        .is_owned());
+    assert!(IriCow::try_from(String::from("not an IRI")).is_err());
+
+    let cow: std::borrow::Cow<'_, IriStr> = IriCow::new(s).expect("valid IRI").into();
+    assert!(matches!(cow, std::borrow::Cow::Borrowed(_)));
+
+    let owned: IriString = IriCow::new(s).expect("valid IRI").into_owned();
+    assert_eq!(owned, s);
+}
@@ -0,0 +1,89 @@
+//! Tests for the C-compatible API.
+#![cfg(feature = "ffi")]
+
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use iri_string::ffi::{
+    iri_string_parse_iri_reference, iri_string_parse_uri_reference, iri_string_validate_iri,
+    iri_string_validate_iri_reference, iri_string_validate_uri, iri_string_validate_uri_reference,
+    FfiComponents,
+};
+
+/// Calls the validator with the bytes.
+fn validate(f: unsafe extern "C" fn(*const u8, usize) -> bool, input: &[u8]) -> bool {
+    // SAFETY: the slice is valid.
+    unsafe { f(input.as_ptr(), input.len()) }
+}
+
+#[test]
+fn validate_bytes() {
+    // [(input, iri, iri_reference, uri, uri_reference)]
+    const CASES: &[(&[u8], bool, bool, bool, bool)] = &[
+        (b"https://example.com/", true, true, true, true),
+        (b"/relative", false, true, false, true),
+        (
+            "http://\u{E9}.example/".as_bytes(),
+            true,
+            true,
+            false,
+            false,
+        ),
+        (b"http://example.com/\xFF", false, false, false, false),
+        (b"<invalid>", false, false, false, false),
+        (b"", false, true, false, true),
+    ];
+    for (input, iri, iri_ref, uri, uri_ref) in CASES.iter().copied() {
+        assert_eq!(validate(iri_string_validate_iri, input), iri, "{:?}", input);
+        assert_eq!(
+            validate(iri_string_validate_iri_reference, input),
+            iri_ref,
+            "{:?}",
+            input
+        );
+        assert_eq!(validate(iri_string_validate_uri, input), uri, "{:?}", input);
+        assert_eq!(
+            validate(iri_string_validate_uri_reference, input),
+            uri_ref,
+            "{:?}",
+            input
+        );
+    }
+
+    // SAFETY: zero length with the null pointer is allowed.
+    assert!(unsafe { iri_string_validate_iri_reference(ptr::null(), 0) });
+}
+
+#[test]
+fn parse_borrowed() {
+    let input = "https://user@example.com:8080/a/b?q#f";
+    let mut out = MaybeUninit::<FfiComponents<'static>>::uninit();
+    // SAFETY: the input and the output pointers are valid.
+    let ok =
+        unsafe { iri_string_parse_iri_reference(input.as_ptr(), input.len(), out.as_mut_ptr()) };
+    assert!(ok);
+    // SAFETY: `out` is initialized on success.
+    let components = unsafe { out.assume_init() };
+    assert_eq!(components.scheme().as_str(), Some("https"));
+    assert_eq!(
+        components.authority().as_str(),
+        Some("user@example.com:8080")
+    );
+    assert_eq!(components.path().as_str(), Some("/a/b"));
+    assert_eq!(components.query().as_str(), Some("q"));
+    assert_eq!(components.fragment().as_str(), Some("f"));
+    // No copies are made.
+    let path = components.path().as_str().expect("path is always present");
+    assert_eq!(path.as_ptr(), input[29..].as_ptr());
+
+    let input = "//example.com";
+    // SAFETY: the input pointer is valid, and the null output is allowed.
+    assert!(unsafe {
+        iri_string_parse_uri_reference(input.as_ptr(), input.len(), ptr::null_mut())
+    });
+    let input = "/\u{E9}";
+    // SAFETY: the input pointer is valid, and the null output is allowed.
+    assert!(!unsafe {
+        iri_string_parse_uri_reference(input.as_ptr(), input.len(), ptr::null_mut())
+    });
+}
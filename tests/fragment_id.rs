@@ -0,0 +1,150 @@
+//! Tests for structured fragment identifiers.
+
+#[cfg(feature = "json-pointer")]
+#[test]
+fn json_pointer_rfc6901_examples() {
+    use iri_string::json_pointer::JsonPointer;
+    use iri_string::types::{UriFragmentStr, UriFragmentString};
+
+    // [(fragment, tokens)], from RFC 6901 section 6.
+    const CASES: &[(&str, &[&str])] = &[
+        ("", &[]),
+        ("/foo", &["foo"]),
+        ("/foo/0", &["foo", "0"]),
+        ("/", &[""]),
+        ("/a~1b", &["a/b"]),
+        ("/c%25d", &["c%d"]),
+        ("/e%5Ef", &["e^f"]),
+        ("/g%7Ch", &["g|h"]),
+        ("/i%5Cj", &["i\\j"]),
+        ("/k%22l", &["k\"l"]),
+        ("/%20", &[" "]),
+        ("/m~0n", &["m~n"]),
+    ];
+    for &(fragment, tokens) in CASES {
+        let fragment = UriFragmentStr::new(fragment).expect("valid fragment");
+        let pointer = JsonPointer::from_fragment(fragment).expect("valid pointer");
+        assert!(
+            pointer.tokens().eq(tokens.iter().copied()),
+            "{:?}",
+            fragment
+        );
+        assert_eq!(pointer.is_root(), tokens.is_empty());
+
+        let encoded: UriFragmentString = pointer.to_fragment();
+        let roundtrip = JsonPointer::from_fragment(&encoded).expect("valid pointer");
+        assert_eq!(roundtrip, pointer);
+    }
+}
+
+#[cfg(feature = "json-pointer")]
+#[test]
+fn json_pointer_invalid() {
+    use iri_string::json_pointer::JsonPointer;
+    use iri_string::types::IriFragmentStr;
+
+    for s in ["foo", "%2Ffoo~", "/~2", "/%7E", "/%C3"] {
+        let fragment = IriFragmentStr::new(s).expect("valid fragment");
+        assert!(JsonPointer::from_fragment(fragment).is_err(), "{:?}", s);
+    }
+    // `%2F` is decoded before the pointer is parsed.
+    let fragment = IriFragmentStr::new("%2Fa%2Fb").expect("valid fragment");
+    let pointer = JsonPointer::from_fragment(fragment).expect("valid pointer");
+    assert!(pointer.tokens().eq(["a", "b"]));
+}
+
+#[cfg(feature = "xpointer")]
+#[test]
+fn xpointer_shorthand() {
+    use iri_string::types::{IriFragmentStr, UriFragmentStr};
+    use iri_string::xpointer::shorthand;
+
+    let fragment = UriFragmentStr::new("section-1.2_%C3%A9").expect("valid fragment");
+    assert_eq!(shorthand(fragment).as_deref(), Ok("section-1.2_\u{E9}"));
+    let fragment = IriFragmentStr::new("\u{4E2D}\u{6587}").expect("valid fragment");
+    assert_eq!(shorthand(fragment).as_deref(), Ok("\u{4E2D}\u{6587}"));
+
+    for s in [
+        "",
+        ".a",
+        "a%20b",
+        "ns:a",
+        "element(/1)",
+        "a%2Fb",
+        "%E2%80%8B",
+    ] {
+        let fragment = UriFragmentStr::new(s).expect("valid fragment");
+        assert!(shorthand(fragment).is_err(), "{:?}", s);
+    }
+}
+
+#[cfg(feature = "json-pointer")]
+#[test]
+fn json_schema_ref() {
+    use iri_string::json_pointer::resolve_ref;
+    use iri_string::types::{IriReferenceStr, IriStr};
+
+    /// (base, reference, document, pointer, anchor).
+    type Case = (
+        &'static str,
+        &'static str,
+        &'static str,
+        Option<&'static str>,
+        Option<&'static str>,
+    );
+    const CASES: &[Case] = &[
+        (
+            "https://example.com/a/b.json",
+            "#/$defs/x",
+            "https://example.com/a/b.json",
+            Some("/$defs/x"),
+            None,
+        ),
+        (
+            "https://example.com/a/b.json#",
+            "",
+            "https://example.com/a/b.json",
+            Some(""),
+            None,
+        ),
+        (
+            "https://example.com/a/b.json#/ignored",
+            "../c.json#",
+            "https://example.com/c.json",
+            Some(""),
+            None,
+        ),
+        (
+            "https://example.com/a/b.json",
+            "urn:uuid:deadbeef-1234-ffff-ffff-4321feebdaed#foo",
+            "urn:uuid:deadbeef-1234-ffff-ffff-4321feebdaed",
+            None,
+            Some("foo"),
+        ),
+        (
+            "https://example.com/a/b.json",
+            "/s.json#/%E2%82%AC",
+            "https://example.com/s.json",
+            Some("/\u{20AC}"),
+            None,
+        ),
+    ];
+    for &(base, reference, document, pointer, anchor) in CASES {
+        let base = IriStr::new(base).expect("valid IRI");
+        let reference = IriReferenceStr::new(reference).expect("valid IRI reference");
+        let resolved = resolve_ref(base, reference).expect("resolvable");
+        assert_eq!(resolved.document(), document, "{:?}", reference);
+        assert_eq!(
+            resolved.pointer().ok().as_ref().map(|p| p.as_str()),
+            pointer,
+            "{:?}",
+            reference
+        );
+        assert_eq!(
+            resolved.anchor().map(|a| a.as_str()),
+            anchor,
+            "{:?}",
+            reference
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Tests for host and authority types.
+
+use iri_string::types::{
+    IriAuthorityStr, IriHostStr, IriReferenceStr, UriAuthorityStr, UriHostStr,
+};
+
+#[test]
+fn host_validation() {
+    for s in [
+        "",
+        "example.com",
+        "192.0.2.1",
+        "[::1]",
+        "[v1.fe]",
+        "%E3%81%82",
+    ] {
+        assert!(UriHostStr::new(s).is_ok(), "{s:?}");
+    }
+    for s in ["a/b", "a?b", "a#b", "a@b", "a:1", "[::1", "::1", "\u{03B1}"] {
+        assert!(UriHostStr::new(s).is_err(), "{s:?}");
+    }
+    assert!(IriHostStr::new("\u{03B1}.example").is_ok());
+}
+
+#[test]
+fn host_eq_ignore_case() {
+    let host = IriHostStr::new("%e3%81%82.Example.COM").expect("valid host");
+    assert!(host.eq_ignore_case(IriHostStr::new("%E3%81%82.example.com").expect("valid host")));
+    assert!(!host.eq_ignore_case(IriHostStr::new("example.com").expect("valid host")));
+}
+
+#[test]
+fn authority_subcomponents() {
+    let authority = UriAuthorityStr::new("user:pass@[::1]:8080").expect("valid authority");
+    assert_eq!(authority.userinfo(), Some("user:pass"));
+    assert_eq!(authority.host(), "[::1]");
+    assert_eq!(authority.port(), Some("8080"));
+
+    let authority = UriAuthorityStr::new("[::1]").expect("valid authority");
+    assert_eq!(authority.userinfo(), None);
+    assert_eq!(authority.host(), "[::1]");
+    assert_eq!(authority.port(), None);
+
+    assert!(UriAuthorityStr::new("a@b@c").is_err());
+    assert!(UriAuthorityStr::new("example.com:80a").is_err());
+}
+
+#[test]
+fn authority_eq_ignore_case() {
+    let authority = IriAuthorityStr::new("User@EXAMPLE.com:80").expect("valid authority");
+    assert!(authority.eq_ignore_case(IriAuthorityStr::new("User@example.com:80").expect("valid")));
+    assert!(!authority.eq_ignore_case(IriAuthorityStr::new("user@example.com:80").expect("valid")));
+    assert!(!authority.eq_ignore_case(IriAuthorityStr::new("User@example.com").expect("valid")));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn builder_with_authority() {
+    use iri_string::build::Builder;
+
+    let authority = IriAuthorityStr::new("user@example.com:").expect("valid authority");
+    let mut builder = Builder::new();
+    builder.scheme("http");
+    builder.port(8080_u16);
+    builder.authority(authority);
+    builder.path("/");
+    let built = builder
+        .clone()
+        .build::<IriReferenceStr>()
+        .expect("valid IRI");
+    assert_eq!(built.to_string(), "http://user@example.com:/");
+
+    let host = IriHostStr::new("example.org").expect("valid host");
+    builder.validated_host(host);
+    builder.unset_userinfo();
+    let built = builder.build::<IriReferenceStr>().expect("valid IRI");
+    assert_eq!(built.to_string(), "http://example.org:/");
+}
+
+#[test]
+fn scheme_and_host_eq_helpers() {
+    let iri = IriReferenceStr::new("HtTp://User@%e3%81%82.Example.COM:80/").expect("valid IRI");
+    assert!(iri.scheme_eq_ignore_ascii_case("http"));
+    assert!(iri.host_eq("%E3%81%82.example.com"));
+    assert!(!iri.host_eq("User@%E3%81%82.example.com"));
+    // The original case is preserved.
+    assert_eq!(iri.scheme_str(), Some("HtTp"));
+
+    let urn = IriReferenceStr::new("urn:example:foo").expect("valid IRI");
+    assert!(!urn.host_eq(""));
+    let empty_host = IriReferenceStr::new("file:///etc/hosts").expect("valid IRI");
+    assert!(empty_host.host_eq(""));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn ip_addr_roundtrip() {
+    use std::net::IpAddr;
+
+    use iri_string::types::UriHostString;
+
+    // [(input, expected)]
+    const CASES: &[(&str, Option<&str>)] = &[
+        ("0.0.0.0", Some("0.0.0.0")),
+        ("255.255.255.255", Some("255.255.255.255")),
+        ("256.0.0.1", None),
+        ("1.2.3", None),
+        ("[::]", Some("::")),
+        ("[::1]", Some("::1")),
+        ("[2001:DB8:0:0:0:0:0:1]", Some("2001:db8::1")),
+        ("[::ffff:192.0.2.1]", Some("::ffff:192.0.2.1")),
+        ("[v7.fe80::1]", None),
+        ("", None),
+        ("localhost", None),
+    ];
+    for (input, expected) in CASES.iter().copied() {
+        let host = IriHostStr::new(input).expect("valid host");
+        let addr = host.to_ip_addr();
+        let expected: Option<IpAddr> = expected.map(|s| s.parse().expect("valid address"));
+        assert_eq!(addr, expected, "input={:?}", input);
+        if let Some(addr) = addr {
+            let roundtrip = UriHostString::from_ip(addr);
+            assert_eq!(
+                roundtrip.is_ip_literal(),
+                addr.is_ipv6(),
+                "input={:?}",
+                input
+            );
+            assert_eq!(roundtrip.to_ip_addr(), Some(addr), "input={:?}", input);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn socket_addrs() {
+    use std::io;
+    use std::net::{SocketAddr, ToSocketAddrs};
+
+    use iri_string::types::{IriAbsoluteStr, UriAbsoluteStr};
+
+    // [(input, expected)]
+    const CASES: &[(&str, Option<&str>)] = &[
+        ("http://192.0.2.1/", Some("192.0.2.1:80")),
+        ("HTTPS://192.0.2.1", Some("192.0.2.1:443")),
+        ("http://192.0.2.1:/", Some("192.0.2.1:80")),
+        ("http://user@192.0.2.1:8080/", Some("192.0.2.1:8080")),
+        ("ws://[::1]/", Some("[::1]:80")),
+        ("wss://[2001:db8::1]:9443", Some("[2001:db8::1]:9443")),
+        ("mqtt://10.0.0.1", Some("10.0.0.1:1883")),
+        ("mqtts://10.0.0.1", Some("10.0.0.1:8883")),
+        ("ftp://192.0.2.1/file", Some("192.0.2.1:21")),
+        ("foo://192.0.2.1/", None),
+        ("foo://192.0.2.1:1234/", Some("192.0.2.1:1234")),
+        ("http://192.0.2.1:65536/", None),
+        ("http://[v1.fe]/", None),
+        ("http:///path", None),
+        ("file:///etc/hosts", None),
+        ("urn:isbn:0451450523", None),
+    ];
+    for (input, expected) in CASES.iter().copied() {
+        let iri = UriAbsoluteStr::new(input).expect("valid absolute URI");
+        let expected: Option<SocketAddr> = expected.map(|s| s.parse().expect("valid address"));
+        match iri.to_socket_addrs() {
+            Ok(addrs) => assert_eq!(
+                addrs.collect::<Vec<_>>(),
+                expected.into_iter().collect::<Vec<_>>(),
+                "input={:?}",
+                input
+            ),
+            Err(e) => {
+                assert_eq!(expected, None, "input={:?}", input);
+                assert_eq!(e.kind(), io::ErrorKind::InvalidInput, "input={:?}", input);
+            }
+        }
+    }
+
+    // Via `ToSocketAddrs` trait.
+    let iri = IriAbsoluteStr::new("https://[::1]:8443/").expect("valid");
+    let addrs: Vec<_> = ToSocketAddrs::to_socket_addrs(iri)
+        .expect("IP address")
+        .collect();
+    assert_eq!(addrs, ["[::1]:8443".parse::<SocketAddr>().expect("valid")]);
+}
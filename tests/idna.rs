@@ -0,0 +1,107 @@
+//! Tests for IDNA processing.
+#![cfg(feature = "idna")]
+
+use iri_string::idna::IdnaOptions;
+use iri_string::punycode;
+use iri_string::types::{IriStr, UriStr};
+
+#[test]
+fn default_options() {
+    let options = IdnaOptions::default();
+    assert_eq!(options, IdnaOptions::new());
+    assert!(!options.is_transitional());
+    assert!(!options.uses_std3_ascii_rules());
+    assert!(!options.verifies_dns_length());
+    assert!(!options.checks_hyphens());
+
+    let strict = IdnaOptions::strict();
+    assert!(!strict.is_transitional());
+    assert!(strict.uses_std3_ascii_rules());
+    assert!(strict.verifies_dns_length());
+    assert!(strict.checks_hyphens());
+}
+
+#[test]
+fn transitional() {
+    let nontransitional = IdnaOptions::new();
+    let transitional = nontransitional.transitional(true);
+    assert_eq!(
+        transitional.domain_to_ascii("fa\u{DF}.de").as_deref(),
+        Ok("fass.de")
+    );
+    assert_eq!(
+        nontransitional.domain_to_ascii("fa\u{DF}.de").as_deref(),
+        Ok("xn--fa-hia.de")
+    );
+}
+
+#[test]
+fn std3_ascii_rules() {
+    let options = IdnaOptions::new();
+    assert_eq!(
+        options.domain_to_ascii("a_b.example").as_deref(),
+        Ok("a_b.example")
+    );
+    assert!(options
+        .use_std3_ascii_rules(true)
+        .domain_to_ascii("a_b.example")
+        .is_err());
+}
+
+#[test]
+fn dns_length() {
+    let long_label = "a".repeat(64);
+    let domain = format!("{}.example", long_label);
+    let options = IdnaOptions::new();
+    assert!(options.domain_to_ascii(&domain).is_ok());
+    assert!(options
+        .verify_dns_length(true)
+        .domain_to_ascii(&domain)
+        .is_err());
+    assert!(options.verify_dns_length(true).domain_to_ascii("").is_err());
+}
+
+#[test]
+fn hyphens() {
+    let options = IdnaOptions::new();
+    assert!(options.domain_to_ascii("-a.example").is_ok());
+    let checked = options.check_hyphens(true);
+    assert!(checked.domain_to_ascii("-a.example").is_err());
+    assert!(checked.domain_to_ascii("a-.example").is_err());
+    assert!(checked.domain_to_ascii("a-b.example").is_ok());
+}
+
+#[test]
+fn host_to_ascii() {
+    let options = IdnaOptions::new();
+    let label = format!(
+        "xn--{}",
+        punycode::encode("\u{4F8B}").expect("encodable label")
+    );
+
+    let iri = IriStr::new("http://user@\u{4F8B}.Test:8080/path?q#f").expect("valid IRI");
+    assert_eq!(
+        options.host_to_ascii(iri).expect("valid host"),
+        format!("http://user@{}.test:8080/path?q#f", label)
+    );
+
+    let uri = UriStr::new("http://%E4%BE%8B.test/").expect("valid URI");
+    assert_eq!(
+        options.host_to_ascii(uri).expect("valid host"),
+        format!("http://{}.test/", label)
+    );
+
+    for unchanged in [
+        "http://192.0.2.1/",
+        "http://[::1]/",
+        "http://example.com/",
+        "urn:example:\u{4F8B}",
+        "file:///etc/hosts",
+    ] {
+        let iri = IriStr::new(unchanged).expect("valid IRI");
+        assert_eq!(options.host_to_ascii(iri).expect("valid host"), unchanged);
+    }
+
+    let invalid = UriStr::new("http://%FF.test/").expect("valid URI");
+    assert!(options.host_to_ascii(invalid).is_err());
+}
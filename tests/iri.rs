@@ -57,6 +57,25 @@ fn iri_to_uri() {
     }
 }
 
+#[test]
+fn iri_to_uri_slice() {
+    let mut buf = [0_u8; 256];
+
+    for case in CASES.iter().copied() {
+        let iri = IriReferenceStr::new(case.iri).expect("should be valid URI reference");
+        let len = case.uri.len();
+        let encoded: &UriReferenceStr = iri
+            .encode_to_uri()
+            .write_to_slice(&mut buf[..len])
+            .expect("should have enough buffer");
+        assert_eq!(encoded, case.uri);
+        assert!(iri
+            .encode_to_uri()
+            .write_to_slice(&mut buf[..(len - 1)])
+            .is_err());
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn iri_to_uri_allocated() {
@@ -93,3 +112,211 @@ fn iri_to_uri_inline() {
         );
     }
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn try_to_absolute_rejects_fragment() {
+    use iri_string::types::IriStr;
+
+    let graph = IriStr::new("http://example.com/graph?q").expect("valid IRI");
+    assert_eq!(
+        graph.try_to_absolute().ok().map(|abs| abs.as_str()),
+        Some("http://example.com/graph?q")
+    );
+    for s in ["http://example.com/graph#me", "http://example.com/graph#"] {
+        let iri = IriStr::new(s).expect("valid IRI");
+        assert!(iri.try_to_absolute().is_err(), "{s:?}");
+        let err = iri.to_owned().try_into_absolute().expect_err(s);
+        assert_eq!(err.into_source(), s);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn well_known() {
+    use iri_string::types::{IriAbsoluteStr, UriAbsoluteStr};
+
+    let base = IriAbsoluteStr::new("https://user@éxample.com:8443/a/b?q").expect("valid IRI");
+    for (suffix, expected) in [
+        (
+            "host-meta",
+            "https://user@éxample.com:8443/.well-known/host-meta",
+        ),
+        ("café", "https://user@éxample.com:8443/.well-known/café"),
+        ("a%2Fb", "https://user@éxample.com:8443/.well-known/a%2Fb"),
+        ("...", "https://user@éxample.com:8443/.well-known/..."),
+        (
+            "%2E%2E%2E",
+            "https://user@éxample.com:8443/.well-known/%2E%2E%2E",
+        ),
+    ] {
+        assert_eq!(base.well_known(suffix).expect(suffix), expected);
+    }
+    for suffix in [
+        "", ".", "..", "%2E", "%2e%2E", ".%2E", "a/b", "/a", "a?b", "a#b", "%GG", "a b",
+    ] {
+        assert!(base.well_known(suffix).is_err(), "{suffix:?}");
+    }
+
+    let uri_base = UriAbsoluteStr::new("https://example.com").expect("valid URI");
+    assert!(uri_base.well_known("café").is_err());
+    assert_eq!(
+        uri_base.well_known("caf%C3%A9").expect("valid segment"),
+        "https://example.com/.well-known/caf%C3%A9"
+    );
+
+    let no_authority = IriAbsoluteStr::new("file:/etc/hosts").expect("valid IRI");
+    assert!(no_authority.well_known("host-meta").is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn with_suffix() {
+    use iri_string::types::IriAbsoluteStr;
+
+    // `[(base, suffix, expected)]`.
+    const CASES: &[(&str, &str, Option<&str>)] = &[
+        (
+            "https://example.com/v1/",
+            "users",
+            Some("https://example.com/v1/users"),
+        ),
+        ("https://example.com", "", Some("https://example.com")),
+        ("https://example.com", "/", Some("https://example.com/")),
+        ("https://example.com", "?q", Some("https://example.com?q")),
+        ("https://example.com", "#f", Some("https://example.com#f")),
+        ("https://example.com", "x", None),
+        ("https://example.com", ":80", None),
+        (
+            "https://example.com/",
+            "//x",
+            Some("https://example.com///x"),
+        ),
+        (
+            "https://example.com/",
+            "a/../b",
+            Some("https://example.com/a/../b"),
+        ),
+        ("https://example.com/?", "", Some("https://example.com/?")),
+        (
+            "https://example.com/?",
+            "#f",
+            Some("https://example.com/?#f"),
+        ),
+        ("https://example.com/?a", "&b", None),
+        ("https://example.com/?a", "?b", None),
+        ("foo:", "bar:baz", Some("foo:bar:baz")),
+        ("foo:", "//bar", None),
+        ("foo:/", "/bar", None),
+        ("foo:/", "bar", Some("foo:/bar")),
+        ("foo:/a", "//bar", Some("foo:/a//bar")),
+        ("foo:bar", "?q#f", Some("foo:bar?q#f")),
+        (
+            "foo:bar",
+            "/\u{03B1}?\u{E000}#\u{03B2}",
+            Some("foo:bar/\u{03B1}?\u{E000}#\u{03B2}"),
+        ),
+        ("foo:bar", "/\u{E000}", None),
+        ("foo:bar", "/a b", None),
+        ("foo:bar", "?a#b#c", None),
+    ];
+
+    for &(base, suffix, expected) in CASES {
+        let base_iri = IriAbsoluteStr::new(base).expect("valid IRI");
+        let result = base_iri.with_suffix(suffix).ok();
+        assert_eq!(
+            result.as_ref().map(|iri| iri.as_str()),
+            expected,
+            "base={base:?}, suffix={suffix:?}"
+        );
+    }
+}
+
+#[test]
+fn new_unchecked_with_valid_input() {
+    let s = "http://example.com/?q#f";
+    // SAFETY: the string is a valid IRI reference.
+    let iri = unsafe { IriReferenceStr::new_unchecked(s) };
+    assert_eq!(iri, s);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn new_unchecked_validates_in_debug_build() {
+    // The precondition is intentionally violated here, to check that it is
+    // detected by the debug assertion before the invalid value is created.
+    let _ = unsafe { IriReferenceStr::new_unchecked("%GG") };
+}
+
+#[test]
+fn uri_to_iri() {
+    use iri_string::convert::DecodeMode;
+    use iri_string::types::{IriStr, UriFragmentStr, UriQueryStr, UriStr};
+
+    // `[(uri, standard, safe display)]`.
+    const DECODE_CASES: &[(&str, &str, &str)] = &[
+        (
+            "http://%CE%B1@%CE%B2.example/%CE%B3?%CE%B4#%CE%B5",
+            "http://\u{03B1}@\u{03B2}.example/\u{03B3}?\u{03B4}#\u{03B5}",
+            "http://\u{03B1}@\u{03B2}.example/\u{03B3}?\u{03B4}#\u{03B5}",
+        ),
+        // ASCII characters and invalid UTF-8 sequences are kept.
+        (
+            "http://example.com/%41%2F%e3%81%82%E3%81%FF%C0%80",
+            "http://example.com/%41%2F\u{3042}%E3%81%FF%C0%80",
+            "http://example.com/%41%2F\u{3042}%E3%81%FF%C0%80",
+        ),
+        // Bidi formatting characters are never decoded.
+        (
+            "http://example.com/%E2%80%AEgpj.exe",
+            "http://example.com/%E2%80%AEgpj.exe",
+            "http://example.com/%E2%80%AEgpj.exe",
+        ),
+        // Private use characters are decoded only in the query.
+        (
+            "http://example.com/%EE%80%80?%EE%80%80#%EE%80%80",
+            "http://example.com/%EE%80%80?\u{E000}#%EE%80%80",
+            "http://example.com/%EE%80%80?%EE%80%80#%EE%80%80",
+        ),
+        // Spaces and invisible characters are decoded only in the standard mode.
+        (
+            "http://example.com/%C2%A0%E3%80%80%E2%80%8B%C2%AD%EF%BB%BF",
+            "http://example.com/\u{A0}\u{3000}\u{200B}\u{AD}\u{FEFF}",
+            "http://example.com/%C2%A0%E3%80%80%E2%80%8B%C2%AD%EF%BB%BF",
+        ),
+        // `?` in the fragment does not start a query.
+        (
+            "http://example.com/#?%EE%80%80",
+            "http://example.com/#?%EE%80%80",
+            "http://example.com/#?%EE%80%80",
+        ),
+    ];
+
+    let mut buf = [0_u8; 256];
+    for &(uri, standard, safe) in DECODE_CASES {
+        let uri = UriStr::new(uri).expect("valid URI");
+        let decoded: &IriStr = uri
+            .decode_to_iri()
+            .write_to_slice(&mut buf)
+            .expect("enough buffer");
+        assert_eq!(decoded, standard);
+        assert_eq_display!(uri.decode_to_iri().mode(DecodeMode::SafeDisplay), safe);
+    }
+
+    let query = UriQueryStr::new("%EE%80%80").expect("valid query");
+    assert_eq_display!(query.decode_to_iri(), "\u{E000}");
+    let fragment = UriFragmentStr::new("%EE%80%80").expect("valid fragment");
+    assert_eq_display!(fragment.decode_to_iri(), "%EE%80%80");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn iri_uri_roundtrip() {
+    for case in CASES.iter().copied() {
+        let uri = UriReferenceStr::new(case.uri).expect("should be valid URI reference");
+        let iri: IriReferenceString = uri.decode_to_iri().to_dedicated_string();
+        assert_eq!(iri, case.iri);
+        assert_eq!(iri.encode_to_uri().to_dedicated_string(), case.uri);
+    }
+}
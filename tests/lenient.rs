@@ -0,0 +1,330 @@
+//! Tests for lenient processing.
+
+use iri_string::lenient::Components;
+use iri_string::types::IriReferenceStr;
+
+#[test]
+fn opaque_special_schemes() {
+    // (input, path, query, fragment).
+    const CASES: &[(&str, &str, Option<&str>, Option<&str>)] = &[
+        ("javascript:alert(1)", "alert(1)", None, None),
+        (
+            "javascript://example.com/%0Aalert(1)",
+            "//example.com/%0Aalert(1)",
+            None,
+            None,
+        ),
+        ("JavaScript://x?y#z", "//x", Some("y"), Some("z")),
+        ("about:blank", "blank", None, None),
+        ("about://blank#", "//blank", None, Some("")),
+        (
+            "blob:https://example.com/uuid",
+            "https://example.com/uuid",
+            None,
+            None,
+        ),
+        ("data://text/plain,hello", "//text/plain,hello", None, None),
+        ("data:,", ",", None, None),
+    ];
+
+    for &(input, path, query, fragment) in CASES {
+        let iri = IriReferenceStr::new(input).expect("should be valid IRI reference");
+        let components = Components::from_iri(iri);
+        assert!(components.has_opaque_path(), "input={:?}", input);
+        assert_eq!(components.scheme(), iri.scheme_str());
+        assert_eq!(components.authority(), None, "input={:?}", input);
+        assert_eq!(components.path(), path, "input={:?}", input);
+        assert_eq!(components.query(), query, "input={:?}", input);
+        assert_eq!(components.fragment(), fragment, "input={:?}", input);
+    }
+}
+
+#[test]
+fn other_schemes_same_as_strict() {
+    const CASES: &[&str] = &[
+        "https://user@example.com:8080/path?query#frag",
+        "foo://bar/baz",
+        "mailto:foo@example.com",
+        "urn:isbn:0451450523",
+        "file:///etc/passwd",
+        "//example.com/relative",
+        "relative/path",
+        "",
+    ];
+
+    for &input in CASES {
+        let iri = IriReferenceStr::new(input).expect("should be valid IRI reference");
+        let components = Components::from_iri(iri);
+        assert_eq!(components.scheme(), iri.scheme_str(), "input={:?}", input);
+        assert_eq!(
+            components.authority(),
+            iri.authority_str(),
+            "input={:?}",
+            input
+        );
+        assert_eq!(components.path(), iri.path_str(), "input={:?}", input);
+        assert_eq!(
+            components.query(),
+            iri.query().map(|q| q.as_str()),
+            "input={:?}",
+            input
+        );
+        assert_eq!(
+            components.fragment(),
+            iri.fragment().map(|f| f.as_str()),
+            "input={:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn opaque_path_classification() {
+    let opaque = IriReferenceStr::new("mailto:foo@example.com").expect("valid");
+    assert!(Components::from_iri(opaque).has_opaque_path());
+
+    let hierarchical = IriReferenceStr::new("https://example.com/").expect("valid");
+    assert!(!Components::from_iri(hierarchical).has_opaque_path());
+
+    let relative = IriReferenceStr::new("foo/bar").expect("valid");
+    assert!(!Components::from_iri(relative).has_opaque_path());
+}
+
+mod ipv4 {
+    use iri_string::lenient::{interpret_ipv4_host, Ipv4Interpretation};
+
+    fn ipv4(host: &str) -> Option<([u8; 4], bool)> {
+        match interpret_ipv4_host(host) {
+            Ipv4Interpretation::Ipv4(v) => Some((v.octets(), v.is_canonical())),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn canonical() {
+        assert_eq!(ipv4("127.0.0.1"), Some(([127, 0, 0, 1], true)));
+        assert_eq!(ipv4("255.255.255.255"), Some(([255, 255, 255, 255], true)));
+    }
+
+    #[test]
+    fn obfuscated() {
+        let cases: &[(&str, [u8; 4])] = &[
+            ("0x7f.1", [127, 0, 0, 1]),
+            ("0177.0.0.1", [127, 0, 0, 1]),
+            ("127.1", [127, 0, 0, 1]),
+            ("127.0.1", [127, 0, 0, 1]),
+            ("2130706433", [127, 0, 0, 1]),
+            ("0x7F000001", [127, 0, 0, 1]),
+            ("127.0.0.1.", [127, 0, 0, 1]),
+            ("0127.0.0.1", [87, 0, 0, 1]),
+            ("%31%32%37.0.0.1", [127, 0, 0, 1]),
+            ("0x", [0, 0, 0, 0]),
+            ("00000000000000000000000001.2.3.4", [1, 2, 3, 4]),
+        ];
+        for (host, octets) in cases {
+            assert_eq!(ipv4(host), Some((*octets, false)), "host={:?}", host);
+        }
+    }
+
+    #[test]
+    fn not_ipv4() {
+        for host in ["", ".", "example.com", "1.2.3.example", "0x7g", "1.2.3.4a"] {
+            assert_eq!(
+                interpret_ipv4_host(host),
+                Ipv4Interpretation::NotIpv4,
+                "host={:?}",
+                host
+            );
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        for host in [
+            "256.0.0.1",
+            "1.2.3.256",
+            "1.2.65536",
+            "4294967296",
+            "0x100000000",
+            "1.2.3.4.5",
+            "09.0.0.1",
+            "1..2.3",
+            "example.0x1",
+            "99999999999999999999999",
+        ] {
+            assert_eq!(
+                interpret_ipv4_host(host),
+                Ipv4Interpretation::Invalid,
+                "host={:?}",
+                host
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        match interpret_ipv4_host("0x7f.1") {
+            Ipv4Interpretation::Ipv4(v) => assert_eq!(v.to_string(), "127.0.0.1"),
+            v => panic!("unexpected result: {:?}", v),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod input_cleanup {
+    use std::borrow::Cow;
+
+    use iri_string::lenient::InputCleanup;
+    use iri_string::types::{IriReferenceString, UriString};
+
+    #[test]
+    fn whatwg_default() {
+        // [(input, output)]
+        const CASES: &[(&str, &str)] = &[
+            ("", ""),
+            (" \t\r\n", ""),
+            ("\u{0}\u{1F} /a \u{7F}", "/a \u{7F}"),
+            ("http://exa\tmple.com/\n\rpath", "http://example.com/path"),
+            ("  ja\nva\tscript:alert(1)", "javascript:alert(1)"),
+            ("\u{A0}/a\u{A0}", "\u{A0}/a\u{A0}"),
+        ];
+        for &(input, expected) in CASES {
+            assert_eq!(
+                InputCleanup::new().apply(input),
+                expected,
+                "input={input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn borrowed_if_only_trimmed() {
+        assert!(matches!(
+            InputCleanup::new().apply("  http://example.com/  "),
+            Cow::Borrowed("http://example.com/")
+        ));
+        assert!(matches!(
+            InputCleanup::new().apply(" /a\nb "),
+            Cow::Owned(s) if s == "/ab"
+        ));
+    }
+
+    #[test]
+    fn configurable() {
+        let input = " \t/a\tb\n ";
+        assert_eq!(InputCleanup::new().trim(false).apply(input), " /ab ");
+        assert_eq!(
+            InputCleanup::new()
+                .remove_tab_and_newline(false)
+                .apply(input),
+            "/a\tb"
+        );
+        let noop = InputCleanup::new()
+            .trim(false)
+            .remove_tab_and_newline(false);
+        assert_eq!(noop.apply(input), input);
+    }
+
+    #[test]
+    fn validate_after_cleanup() {
+        let href = "\n  https://example.com/\tpath?q\r\n ";
+        assert!(UriString::try_from(href).is_err());
+        let cleaned = InputCleanup::new().apply(href);
+        let uri = UriString::try_from(&*cleaned).expect("valid after cleanup");
+        assert_eq!(uri, "https://example.com/path?q");
+
+        assert!(IriReferenceString::try_from(&*InputCleanup::new().apply(" a b ")).is_err());
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod sanitize {
+    use iri_string::lenient::{sanitize, FixupKind, InputCleanup};
+    use iri_string::spec::{IriSpec, UriSpec};
+
+    /// Sanitized string and the repairs.
+    type Sanitized = (String, Vec<(FixupKind, std::ops::Range<usize>)>);
+
+    /// Returns the sanitized string and the repairs.
+    fn run<S: iri_string::spec::Spec>(input: &str) -> Option<Sanitized> {
+        let (iri, report) = sanitize::<S>(input, &InputCleanup::new()).ok()?;
+        let fixups = report
+            .fixups()
+            .iter()
+            .map(|fixup| (fixup.kind(), fixup.span()))
+            .collect();
+        Some((iri.into(), fixups))
+    }
+
+    #[test]
+    fn valid_input_is_kept() {
+        for input in [
+            "",
+            "https://user@example.com:8080/a/b%20c?q=1#f",
+            "//example.com",
+            "../a?\u{E000}#\u{3042}",
+            "urn:isbn:0451450523",
+        ] {
+            let (iri, fixups) = run::<IriSpec>(input).expect("valid input");
+            assert_eq!(iri, input);
+            assert!(fixups.is_empty(), "input={input:?}");
+        }
+    }
+
+    #[test]
+    fn repairs() {
+        use FixupKind::*;
+
+        assert_eq!(
+            run::<IriSpec>("\n/a b\tc"),
+            Some((
+                "/a%20bc".to_owned(),
+                vec![
+                    (WhitespaceRemoved, 0..1),
+                    (CharacterEncoded, 3..4),
+                    (WhitespaceRemoved, 5..6)
+                ]
+            ))
+        );
+        assert_eq!(
+            run::<IriSpec>("\\\\host\\p?a\\b#c#d"),
+            Some((
+                "//host/p?a%5Cb#c%23d".to_owned(),
+                vec![
+                    (BackslashConverted, 0..2),
+                    (BackslashConverted, 6..7),
+                    (CharacterEncoded, 10..11),
+                    (CharacterEncoded, 14..15)
+                ]
+            ))
+        );
+        assert_eq!(
+            run::<IriSpec>("/%zz%4"),
+            Some((
+                "/%25zz%254".to_owned(),
+                vec![(CharacterEncoded, 1..2), (CharacterEncoded, 4..5)]
+            ))
+        );
+        // Private use characters are allowed only in queries of IRIs.
+        assert_eq!(
+            run::<IriSpec>("/\u{E000}?\u{E000}"),
+            Some((
+                "/%EE%80%80?\u{E000}".to_owned(),
+                vec![(CharacterEncoded, 1..4)]
+            ))
+        );
+        assert_eq!(
+            run::<UriSpec>("/\u{3042}\u{3044}"),
+            Some((
+                "/%E3%81%82%E3%81%84".to_owned(),
+                vec![(CharacterEncoded, 1..7)]
+            ))
+        );
+    }
+
+    #[test]
+    fn unrepairable() {
+        assert_eq!(run::<IriSpec>("ht tp://example.com"), None);
+        assert_eq!(run::<IriSpec>("http://[::1/"), None);
+    }
+}
@@ -0,0 +1,97 @@
+//! Tests for lists of IRI references.
+
+use core::ops::Range;
+
+use iri_string::list::{ListItems, Separator};
+use iri_string::spec::{IriSpec, UriSpec};
+
+/// Item and its span, or the span of an invalid item.
+type Item<'a> = Result<(&'a str, Range<usize>), Range<usize>>;
+
+/// Returns the items and their spans, or the spans of invalid items.
+fn items<'a, S: iri_string::spec::Spec + 'a>(list: &'a str, separator: Separator) -> Vec<Item<'a>> {
+    ListItems::<S>::new(list, separator)
+        .map(|item| {
+            item.map(|item| (item.as_iri_ref().as_str(), item.span()))
+                .map_err(|invalid| invalid.span())
+        })
+        .collect()
+}
+
+#[test]
+fn whitespace() {
+    assert_eq!(
+        items::<IriSpec>(
+            "\thttp://example.com/a,b \n /c\r\n?q ",
+            Separator::Whitespace
+        ),
+        [
+            Ok(("http://example.com/a,b", 1..23)),
+            Ok(("/c", 26..28)),
+            Ok(("?q", 30..32))
+        ]
+    );
+}
+
+#[test]
+fn comma() {
+    assert_eq!(
+        items::<IriSpec>(" /a , /b ,, /c,/d", Separator::Comma),
+        [
+            Ok(("/a", 1..3)),
+            Ok(("/b", 6..8)),
+            Ok(("/c", 12..14)),
+            Ok(("/d", 15..17))
+        ]
+    );
+    // Whitespaces inside items are not separators.
+    assert_eq!(
+        items::<IriSpec>("/a /b, /c", Separator::Comma),
+        [Err(0..5), Ok(("/c", 7..9))]
+    );
+}
+
+#[test]
+fn whitespace_or_comma() {
+    assert_eq!(
+        items::<IriSpec>("/a /b,/c , /d", Separator::WhitespaceOrComma),
+        [
+            Ok(("/a", 0..2)),
+            Ok(("/b", 3..5)),
+            Ok(("/c", 6..8)),
+            Ok(("/d", 11..13))
+        ]
+    );
+}
+
+#[test]
+fn empty() {
+    for separator in [
+        Separator::Whitespace,
+        Separator::Comma,
+        Separator::WhitespaceOrComma,
+    ] {
+        assert!(items::<IriSpec>("", separator).is_empty());
+        assert!(items::<IriSpec>(" \t ", separator).is_empty());
+    }
+    assert!(items::<IriSpec>(" , ,", Separator::Comma).is_empty());
+}
+
+#[test]
+fn invalid_items() {
+    let list = "/a %zz \u{3042} /b";
+    assert_eq!(
+        items::<IriSpec>(list, Separator::Whitespace),
+        [
+            Ok(("/a", 0..2)),
+            Err(3..6),
+            Ok(("\u{3042}", 7..10)),
+            Ok(("/b", 11..13))
+        ]
+    );
+    let invalid = ListItems::<UriSpec>::new(list, Separator::Whitespace)
+        .filter_map(Result::err)
+        .map(|invalid| invalid.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(invalid, ["%zz", "\u{3042}"]);
+}
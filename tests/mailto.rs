@@ -0,0 +1,166 @@
+//! Tests for conversion between email addresses and `mailto:` IRIs.
+#![cfg(feature = "alloc")]
+
+use iri_string::mailto::{
+    address_to_mailto, addresses_to_mailto, mailto_to_address, mailto_to_addresses, AddressEncoding,
+};
+use iri_string::types::{IriStr, IriString, UriStr, UriString};
+
+// [(address, IRI, URI)]
+const ROUNDTRIP_CASES: &[(&str, &str, &str)] = &[
+    (
+        "user@example.com",
+        "mailto:user@example.com",
+        "mailto:user@example.com",
+    ),
+    (
+        "user+tag@example.com",
+        "mailto:user+tag@example.com",
+        "mailto:user+tag@example.com",
+    ),
+    (
+        "a%b/c?d#e&f=g@example.com",
+        "mailto:a%25b%2Fc%3Fd%23e%26f%3Dg@example.com",
+        "mailto:a%25b%2Fc%3Fd%23e%26f%3Dg@example.com",
+    ),
+    (
+        "\"not@me, really\"@example.com",
+        "mailto:%22not%40me%2C%20really%22@example.com",
+        "mailto:%22not%40me%2C%20really%22@example.com",
+    ),
+    (
+        "\"a\\\"b\"@example.com",
+        "mailto:%22a%5C%22b%22@example.com",
+        "mailto:%22a%5C%22b%22@example.com",
+    ),
+    (
+        "user@[192.0.2.1]",
+        "mailto:user@%5B192.0.2.1%5D",
+        "mailto:user@%5B192.0.2.1%5D",
+    ),
+    (
+        "\u{7528}\u{6237}@\u{4F8B}\u{5B50}.\u{5E7F}\u{544A}",
+        "mailto:\u{7528}\u{6237}@\u{4F8B}\u{5B50}.\u{5E7F}\u{544A}",
+        "mailto:%E7%94%A8%E6%88%B7@%E4%BE%8B%E5%AD%90.%E5%B9%BF%E5%91%8A",
+    ),
+];
+
+#[test]
+fn roundtrip_smtputf8() {
+    for &(address, iri_expected, uri_expected) in ROUNDTRIP_CASES {
+        let iri: IriString = address_to_mailto(address, AddressEncoding::Smtputf8)
+            .unwrap_or_else(|_| panic!("address {address:?} should be convertible"));
+        assert_eq!(iri, iri_expected, "address={address:?}");
+        let uri: UriString = address_to_mailto(address, AddressEncoding::Smtputf8)
+            .unwrap_or_else(|_| panic!("address {address:?} should be convertible"));
+        assert_eq!(uri, uri_expected, "address={address:?}");
+
+        assert_eq!(
+            mailto_to_address(&iri, AddressEncoding::Smtputf8).as_deref(),
+            Ok(address)
+        );
+        assert_eq!(
+            mailto_to_address(&uri, AddressEncoding::Smtputf8).as_deref(),
+            Ok(address)
+        );
+    }
+}
+
+#[test]
+fn ascii_domain() {
+    let uri: UriString = address_to_mailto(
+        "info@\u{4F8B}\u{3048}.\u{30C6}\u{30B9}\u{30C8}",
+        AddressEncoding::Ascii,
+    )
+    .expect("ASCII local part with IDN should be convertible");
+    assert_eq!(uri, "mailto:info@xn--r8jz45g.xn--zckzah");
+
+    let iri =
+        IriStr::new("mailto:info@\u{4F8B}\u{3048}.\u{30C6}\u{30B9}\u{30C8}").expect("valid IRI");
+    assert_eq!(
+        mailto_to_address(iri, AddressEncoding::Ascii).as_deref(),
+        Ok("info@xn--r8jz45g.xn--zckzah")
+    );
+}
+
+#[test]
+fn ascii_rejects_nonascii_local_part() {
+    assert!(address_to_mailto::<iri_string::spec::UriSpec>(
+        "\u{7528}\u{6237}@example.com",
+        AddressEncoding::Ascii
+    )
+    .is_err());
+
+    let uri = UriStr::new("mailto:%E7%94%A8%E6%88%B7@example.com").expect("valid URI");
+    assert!(mailto_to_address(uri, AddressEncoding::Ascii).is_err());
+    assert!(mailto_to_address(uri, AddressEncoding::Smtputf8).is_ok());
+}
+
+#[test]
+fn multiple_addresses() {
+    let iri: IriString = addresses_to_mailto(
+        ["a@example.com", "\"b,c\"@example.com"],
+        AddressEncoding::Smtputf8,
+    )
+    .expect("valid addresses");
+    assert_eq!(iri, "mailto:a@example.com,%22b%2Cc%22@example.com");
+    assert_eq!(
+        mailto_to_addresses(&iri, AddressEncoding::Smtputf8).expect("valid IRI"),
+        ["a@example.com", "\"b,c\"@example.com"]
+    );
+    assert!(mailto_to_address(&iri, AddressEncoding::Smtputf8).is_err());
+}
+
+#[test]
+fn headers_only() {
+    let iri = IriStr::new("MAILTO:?to=a@example.com&subject=hi").expect("valid IRI");
+    assert_eq!(
+        mailto_to_addresses(iri, AddressEncoding::Smtputf8).expect("valid IRI"),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn invalid_addresses() {
+    const CASES: &[&str] = &[
+        "",
+        "example.com",
+        "@example.com",
+        "user@",
+        "a..b@example.com",
+        ".a@example.com",
+        "a.@example.com",
+        "a b@example.com",
+        "a\"b@example.com",
+        "\"unterminated@example.com",
+        "\"a\"b\"@example.com",
+        "user@exa_mple.com",
+        "user@example..com",
+        "user@[unterminated",
+    ];
+    for &address in CASES {
+        assert!(
+            address_to_mailto::<iri_string::spec::IriSpec>(address, AddressEncoding::Smtputf8)
+                .is_err(),
+            "address {address:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn invalid_mailto() {
+    const CASES: &[&str] = &[
+        "https://example.com/",
+        "mailto:example.com",
+        "mailto:a@example.com,",
+        "mailto:%FF@example.com",
+        "mailto:a%20b@example.com",
+    ];
+    for &iri in CASES {
+        let iri = IriStr::new(iri).expect("valid IRI");
+        assert!(
+            mailto_to_addresses(iri, AddressEncoding::Smtputf8).is_err(),
+            "IRI {iri:?} should be rejected"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Tests for in-place mutation of owned IRI strings.
+#![cfg(feature = "alloc")]
+
+use iri_string::types::{
+    IriAbsoluteString, IriFragmentStr, IriQueryStr, IriReferenceStr, IriReferenceString,
+    IriRelativeString, IriStr, IriString,
+};
+
+#[test]
+fn set_query() {
+    // [(iri, query, expected)]
+    const CASES: &[(&str, Option<&str>, &str)] = &[
+        ("http://example.com", Some("q"), "http://example.com?q"),
+        (
+            "http://example.com/?old#f",
+            Some(""),
+            "http://example.com/?#f",
+        ),
+        ("http://example.com/?old#f?", None, "http://example.com/#f?"),
+        ("scheme:opaque#f", Some("a?b"), "scheme:opaque?a?b#f"),
+        ("scheme:", None, "scheme:"),
+    ];
+    for (iri, query, expected) in CASES.iter().copied() {
+        let query = query.map(|q| IriQueryStr::new(q).expect("valid query"));
+
+        let mut owned = IriString::try_from(iri).expect("valid IRI");
+        owned.set_query(query);
+        assert_eq!(owned, expected);
+
+        let mut owned = IriReferenceString::try_from(iri).expect("valid IRI reference");
+        owned.set_query(query);
+        assert_eq!(owned, expected);
+    }
+
+    let mut absolute = IriAbsoluteString::try_from("foo:/bar?baz").expect("valid");
+    absolute.set_query(Some(IriQueryStr::new("qux").expect("valid query")));
+    assert_eq!(absolute, "foo:/bar?qux");
+    absolute.set_query(None);
+    assert_eq!(absolute, "foo:/bar");
+}
+
+#[test]
+fn set_path() {
+    // [(iri, path, expected)]
+    const VALID: &[(&str, &str, &str)] = &[
+        (
+            "http://example.com/old?q#f",
+            "/new",
+            "http://example.com/new?q#f",
+        ),
+        ("http://example.com/old", "", "http://example.com"),
+        ("http://example.com", "//a", "http://example.com//a"),
+        ("scheme:old?q", "a:b/c", "scheme:a:b/c?q"),
+        ("scheme:old", "", "scheme:"),
+        ("scheme:/old", "/", "scheme:/"),
+    ];
+    for (iri, path, expected) in VALID.iter().copied() {
+        let mut owned = IriString::try_from(iri).expect("valid IRI");
+        owned.set_path(path).expect("valid path in the context");
+        assert_eq!(owned, expected);
+
+        let mut owned = IriReferenceString::try_from(iri).expect("valid IRI reference");
+        owned.set_path(path).expect("valid path in the context");
+        assert_eq!(owned, expected);
+    }
+
+    // [(iri, path)]
+    const INVALID: &[(&str, &str)] = &[
+        // Path after an authority should be empty or start with a slash.
+        ("http://example.com/old", "relative"),
+        // Path without an authority should not start with `//`.
+        ("scheme:/old", "//authority"),
+        // Invalid characters.
+        ("http://example.com/old", "/a?b"),
+        ("http://example.com/old", "/a#b"),
+        ("http://example.com/old", "/a b"),
+        ("http://example.com/old", "/%zz"),
+    ];
+    for (iri, path) in INVALID.iter().copied() {
+        let mut owned = IriString::try_from(iri).expect("valid IRI");
+        assert!(
+            owned.set_path(path).is_err(),
+            "iri={:?}, path={:?}",
+            iri,
+            path
+        );
+        assert_eq!(owned, iri, "original IRI should be kept");
+    }
+
+    let mut absolute = IriAbsoluteString::try_from("foo:/bar?baz").expect("valid");
+    absolute.set_path("qux").expect("valid path");
+    assert_eq!(absolute, "foo:qux?baz");
+
+    let mut relative = IriRelativeString::try_from("old?q").expect("valid");
+    relative.set_path("new/path").expect("valid path");
+    assert_eq!(relative, "new/path?q");
+    // The first segment of a relative path without an authority should not
+    // contain a colon.
+    assert!(relative.set_path("a:b").is_err());
+    relative.set_path("./a:b").expect("valid path");
+    assert_eq!(relative, "./a:b?q");
+
+    let mut relative = IriRelativeString::try_from("//host/old").expect("valid");
+    relative.set_path("/a:b").expect("valid path");
+    assert_eq!(relative, "//host/a:b");
+}
+
+#[test]
+fn mutate_relative() {
+    let mut relative = IriRelativeString::try_from("//host/path#f").expect("valid");
+    relative.set_query(Some(IriQueryStr::new("q").expect("valid query")));
+    assert_eq!(relative, "//host/path?q#f");
+    relative.set_fragment(Some(IriFragmentStr::new("g").expect("valid fragment")));
+    assert_eq!(relative, "//host/path?q#g");
+    relative.set_fragment(None);
+    relative.set_query(None);
+    assert_eq!(relative, "//host/path");
+
+    let mut reference = IriReferenceString::try_from("../a").expect("valid");
+    reference.set_fragment(Some(IriFragmentStr::new("frag").expect("valid fragment")));
+    assert_eq!(reference, "../a#frag");
+}
+
+#[test]
+fn update_in_place() {
+    const CASES: &[(&str, &str)] = &[
+        ("http://example.com/a/b?q=1", "http://example.com/a/b?q=2"),
+        ("http://example.com/a/b", "http://example.com/a/x/b"),
+        ("http://example.com/a/x/b", "http://example.com/a/b"),
+        ("http://example.com/", "https://example.org/"),
+        ("http://example.com/aa", "http://example.com/a"),
+        ("http://example.com/\u{E9}", "http://example.com/\u{E8}"),
+        (
+            "http://example.com/\u{3042}",
+            "http://example.com/\u{3044}\u{3042}",
+        ),
+        ("foo:bar", "foo:bar"),
+    ];
+    for (old, new) in CASES.iter().copied() {
+        let mut s = IriString::try_from(old).expect("valid IRI");
+        s.reserve(16);
+        let capacity = s.capacity();
+        let new_iri = IriStr::new(new).expect("valid IRI");
+        assert_eq!(s.update_in_place(new_iri), old != new, "old={:?}", old);
+        assert_eq!(s, new);
+        assert_eq!(s.capacity(), capacity, "old={:?}", old);
+    }
+
+    let mut s = IriReferenceString::try_from("../a?b").expect("valid IRI reference");
+    let new = IriReferenceStr::new("../c?b").expect("valid IRI reference");
+    assert!(s.update_in_place(new));
+    assert_eq!(s, "../c?b");
+}
+
+#[test]
+fn set_fragment_relative() {
+    let mut relative = IriRelativeString::try_from("../path").expect("valid");
+    relative.set_fragment(Some(IriFragmentStr::new("frag").expect("valid fragment")));
+    assert_eq!(relative, "../path#frag");
+    relative.set_fragment(None);
+    assert_eq!(relative, "../path");
+}
@@ -0,0 +1,58 @@
+//! Tests for Unicode normalization (NFC).
+#![cfg(feature = "unicode-normalization")]
+
+use std::borrow::Cow;
+
+use iri_string::types::{IriReferenceStr, IriStr, UriStr};
+
+#[test]
+fn nfc_components() {
+    // (input, expected).
+    const CASES: &[(&str, &str)] = &[
+        (
+            "https://u\u{308}ser@cafe\u{301}.example/a\u{30A}?q=e\u{301}#e\u{301}",
+            "https://\u{FC}ser@caf\u{E9}.example/\u{E5}?q=\u{E9}#\u{E9}",
+        ),
+        // Hangul conjoining jamo.
+        ("foo:\u{1100}\u{1161}", "foo:\u{AC00}"),
+        // Singleton: U+212B ANGSTROM SIGN.
+        ("foo:/\u{212B}", "foo:/\u{C5}"),
+        // U+1FEF GREEK VARIA is normalized to a backquote, which is not
+        // allowed in IRIs. The component is left as is.
+        ("foo:/\u{1FEF}?e\u{301}", "foo:/\u{1FEF}?\u{E9}"),
+        // Percent-encoded characters are not decoded.
+        ("foo:/e%CC%81", "foo:/e%CC%81"),
+    ];
+    for (input, expected) in CASES {
+        let iri = IriStr::new(input).expect("should be valid");
+        let nfc = iri.to_nfc();
+        assert_eq!(nfc.as_str(), *expected, "input={:?}", input);
+        assert_eq!(
+            matches!(nfc, Cow::Borrowed(_)),
+            input == expected,
+            "input={:?}",
+            input
+        );
+        // Idempotent.
+        assert!(
+            matches!(nfc.to_nfc(), Cow::Borrowed(_)),
+            "input={:?}",
+            input
+        );
+
+        let reference = IriReferenceStr::new(input).expect("should be valid");
+        assert_eq!(reference.to_nfc().as_str(), *expected, "input={:?}", input);
+        assert_eq!(
+            iri.to_absolute().to_nfc().as_str(),
+            expected.split('#').next().expect("always exists"),
+            "input={:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn ascii_only() {
+    let uri = UriStr::new("https://example.com/e%CC%81").expect("should be valid");
+    assert!(matches!(uri.to_nfc(), Cow::Borrowed(_)));
+}
@@ -0,0 +1,60 @@
+//! Tests for `ni:` IRIs.
+#![cfg(feature = "alloc")]
+
+use iri_string::ni::{digest_to_ni, ni_to_named_info};
+use iri_string::types::{IriStr, IriString, UriStr};
+
+/// SHA-256 digest of `Hello World!`.
+const HELLO_SHA256: [u8; 32] = [
+    0x7f, 0x83, 0xb1, 0x65, 0x7f, 0xf1, 0xfc, 0x53, 0xb9, 0x2d, 0xc1, 0x81, 0x48, 0xa1, 0xd6, 0x5d,
+    0xfc, 0x2d, 0x4b, 0x1f, 0xa3, 0xd6, 0x77, 0x28, 0x4a, 0xdd, 0xd2, 0x00, 0x12, 0x6d, 0x90, 0x69,
+];
+
+#[test]
+fn roundtrip() {
+    for len in 1..=20 {
+        let digest: Vec<u8> = (0..len).map(|i| (i * 37 + 250) as u8).collect();
+        let iri: IriString =
+            digest_to_ni(Some("example.com"), "x-test", &digest).expect("valid digest");
+        let ni = ni_to_named_info(&iri).expect("valid `ni` IRI");
+        assert_eq!(ni.authority_str(), Some("example.com"));
+        assert_eq!(ni.algorithm(), "x-test");
+        assert_eq!(ni.digest().as_deref(), Ok(&digest[..]), "len={}", len);
+        assert!(ni.verify(&digest), "len={}", len);
+        assert!(!ni.verify(&digest[1..]), "len={}", len);
+    }
+}
+
+#[test]
+fn rfc6920_examples() {
+    let uri = UriStr::new("ni:///sha-256;f4OxZX_x_FO5LcGBSKHWXfwtSx-j1ncoSt3SABJtkGk")
+        .expect("valid URI");
+    let ni = ni_to_named_info(uri).expect("valid `ni` URI");
+    assert_eq!(ni.authority_str(), None);
+    assert_eq!(ni.digest().as_deref(), Ok(&HELLO_SHA256[..]));
+    assert!(ni.verify(&HELLO_SHA256));
+
+    let mut other = HELLO_SHA256;
+    other[31] ^= 1;
+    assert!(!ni.verify(&other));
+
+    let uri = UriStr::new("ni:///sha-256-32;f4OxZQ?ct=text/plain").expect("valid URI");
+    let ni = ni_to_named_info(uri).expect("valid `ni` URI");
+    assert_eq!(ni.query_str(), Some("ct=text/plain"));
+    assert!(ni.verify(&HELLO_SHA256));
+    assert!(ni.verify(&other));
+}
+
+#[test]
+fn invalid() {
+    for s in [
+        "ni://example.com/sha-256;f4OxZQ",
+        "ni:///sha-256-32;f4Ox%5AQ",
+        "ni:///sha-256-32;f4OxZQ;x",
+        "ni:///sha-256-32;f4OxZ",
+        "ni:///x-test;A",
+    ] {
+        let iri = IriStr::new(s).expect("valid IRI");
+        assert!(ni_to_named_info(iri).is_err(), "{s:?}");
+    }
+}
@@ -195,6 +195,13 @@ fn normalizedness() {
             is_normalized_rfc3986: false,
             is_normalized_whatwg_like: true,
         },
+        // Empty port is removed by the normalization.
+        Case {
+            iri: "scheme://authority:/foo",
+            is_normalized_default: false,
+            is_normalized_rfc3986: false,
+            is_normalized_whatwg_like: false,
+        },
     ];
 
     for case in CASES {
@@ -216,3 +223,157 @@ fn normalizedness() {
         );
     }
 }
+
+/// `NormalizedIri` should compare and hash IRIs by the normalized forms.
+#[cfg(feature = "std")]
+#[test]
+fn normalized_iri_eq_and_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    use iri_string::normalize::NormalizedIri;
+
+    /// Hasher that is sensitive to the boundaries of `write` calls.
+    #[derive(Default)]
+    struct ChunkSensitiveHasher(DefaultHasher);
+    impl Hasher for ChunkSensitiveHasher {
+        fn finish(&self) -> u64 {
+            self.0.finish()
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.write_usize(bytes.len());
+            self.0.write(bytes);
+        }
+    }
+
+    fn hash<T: Hash>(v: &T) -> (u64, u64) {
+        let mut default = DefaultHasher::new();
+        v.hash(&mut default);
+        let mut chunk_sensitive = ChunkSensitiveHasher::default();
+        v.hash(&mut chunk_sensitive);
+        (default.finish(), chunk_sensitive.finish())
+    }
+
+    for case in TEST_CASES
+        .iter()
+        .filter(|case| case.is_iri_class() && case.is_absolute())
+    {
+        let source = NormalizedIri::new(IriStr::new(case.composed).expect("should be valid IRI"));
+        let expected =
+            NormalizedIri::new(IriStr::new(case.normalized_iri).expect("should be valid IRI"));
+        assert_eq!(source, expected, "case={case:#?}");
+        assert_eq!(hash(&source), hash(&expected), "case={case:#?}");
+
+        let owned = NormalizedIri::new(IriString::from(*source.get()));
+        assert_eq!(owned, expected, "case={case:#?}");
+        assert_eq!(hash(&owned), hash(&expected), "case={case:#?}");
+
+        for different in case.different_iris {
+            let different =
+                NormalizedIri::new(IriStr::new(different).expect("should be valid IRI"));
+            assert_ne!(source, different, "case={case:#?}, different={different:?}");
+        }
+    }
+}
+
+/// `NormalizedIri` should compare long IRIs correctly.
+#[cfg(feature = "std")]
+#[test]
+fn normalized_iri_eq_long() {
+    use iri_string::normalize::NormalizedIri;
+
+    for len in [0, 1, 480, 492, 493, 494, 511, 512, 1024, 1500] {
+        let path = "x".repeat(len);
+        let normalized = format!("http://example.com/{path}");
+        let lhs = format!("HTTP://example.com/{path}");
+        let rhs = format!("http://EXAMPLE.com/./{path}");
+        let longer = format!("http://EXAMPLE.com/./{path}x");
+        let shorter = format!("http://EXAMPLE.com/./{}", &path[..len.saturating_sub(1)]);
+        let changed = format!("http://EXAMPLE.com/./{}y", &path[..len.saturating_sub(1)]);
+
+        let new = |s: &str| NormalizedIri::new(IriString::try_from(s).expect("valid IRI"));
+        let (lhs, rhs) = (new(&lhs), new(&rhs));
+        assert_eq!(lhs, rhs, "len={len}");
+        assert_eq!(lhs, new(&normalized), "len={len}");
+        assert_eq!(new(&normalized), rhs, "len={len}");
+        assert_ne!(lhs, new(&longer), "len={len}");
+        assert_ne!(new(&longer), lhs, "len={len}");
+        assert_ne!(new(&normalized), new(&longer), "len={len}");
+        if len != 0 {
+            assert_ne!(lhs, new(&shorter), "len={len}");
+            assert_ne!(new(&shorter), lhs, "len={len}");
+            assert_ne!(lhs, new(&changed), "len={len}");
+            assert_ne!(new(&normalized), new(&changed), "len={len}");
+        }
+    }
+}
+
+/// Normalization results can be written into byte buffers of exact sizes.
+#[test]
+fn write_normalized_to_slice() {
+    let mut buf = [0_u8; 512];
+
+    for case in TEST_CASES
+        .iter()
+        .filter(|case| case.is_iri_class() && case.is_absolute())
+    {
+        let source = IriStr::new(case.composed).expect("should be valid IRI");
+        let len = case.normalized_iri.len();
+
+        let normalized = source
+            .normalize()
+            .write_to_slice(&mut buf[..len])
+            .expect("should have enough buffer");
+        assert_eq!(normalized, case.normalized_iri, "case={case:#?}");
+        if len != 0 {
+            assert!(
+                source
+                    .normalize()
+                    .write_to_slice(&mut buf[..(len - 1)])
+                    .is_err(),
+                "case={case:#?}"
+            );
+        }
+
+        if let Ok(source) = IriAbsoluteStr::new(case.composed) {
+            let normalized = source
+                .normalize()
+                .write_to_slice(&mut buf[..len])
+                .expect("should have enough buffer");
+            assert_eq!(normalized, case.normalized_iri, "case={case:#?}");
+        }
+    }
+}
+
+#[test]
+fn stable_hash() {
+    // These values must not change across versions.
+    // [(iri, 64-bit hash, 128-bit hash)]
+    const CASES: &[(&str, u64, u128)] = &[
+        (
+            "https://example.com/a/b?c#d",
+            0xa867_1873_7e85_9e81,
+            0xb124_0a76_3094_2e87_e7d1_dc08_527b_3ef2,
+        ),
+        (
+            "urn:isbn:0451450523",
+            0x147e_f55a_1e3e_5f1f,
+            0x60d9_bc2b_6ad7_984c_5982_c037_3ab1_15b4,
+        ),
+    ];
+    for &(iri, hash64, hash128) in CASES {
+        let iri = IriStr::new(iri).expect("should be valid");
+        assert_eq!(iri.stable_hash(), hash64, "iri={:?}", iri);
+        assert_eq!(iri.stable_hash128(), hash128, "iri={:?}", iri);
+        if iri.fragment().is_none() {
+            assert_eq!(iri.to_absolute().stable_hash(), hash64, "iri={:?}", iri);
+            assert_eq!(iri.to_absolute().stable_hash128(), hash128, "iri={:?}", iri);
+        }
+    }
+
+    let iri = IriStr::new("HTTPS://Example.COM/x/../a/%62?c#d").expect("should be valid");
+    assert_eq!(iri.stable_hash(), 0xa867_1873_7e85_9e81);
+    let uri = UriStr::new("https://example.com/%C3%A9").expect("should be valid");
+    let iri = IriStr::new("https://example.com/\u{E9}").expect("should be valid");
+    assert_ne!(uri.stable_hash(), iri.stable_hash());
+}
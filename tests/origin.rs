@@ -0,0 +1,150 @@
+//! Tests for origins and sites.
+
+use iri_string::components::Origin;
+use iri_string::types::IriReferenceStr;
+
+fn origin(s: &str) -> Option<Origin<'_>> {
+    Origin::from_iri(IriReferenceStr::new(s).expect("should be valid IRI reference"))
+}
+
+#[test]
+fn origin_components() {
+    let o = origin("http://user:pass@[::1]:8080/path").expect("has authority");
+    assert_eq!(o.scheme(), "http");
+    assert_eq!(o.host(), "[::1]");
+    assert_eq!(o.port(), Some("8080"));
+
+    let o = origin("http://example.com:/").expect("has authority");
+    assert_eq!(o.port(), None);
+
+    assert_eq!(origin("//example.com/"), None);
+    assert_eq!(origin("foo:bar"), None);
+    assert_eq!(origin("/path"), None);
+}
+
+#[test]
+fn origin_eq() {
+    assert_eq!(
+        origin("http://example.com/a"),
+        origin("HTTP://EXAMPLE.com:/b")
+    );
+    assert_ne!(
+        origin("http://example.com/"),
+        origin("https://example.com/")
+    );
+    assert_ne!(
+        origin("http://example.com/"),
+        origin("http://example.com:80/")
+    );
+    assert_ne!(
+        origin("http://example.com/"),
+        origin("http://www.example.com/")
+    );
+}
+
+#[cfg(feature = "psl")]
+#[test]
+fn registrable_domain() {
+    use iri_string::components::AuthorityComponents;
+
+    const CASES: &[(&str, Option<&str>)] = &[
+        ("http://example.com/", Some("example.com")),
+        ("http://www.example.com/", Some("example.com")),
+        ("http://a.b.example.co.uk/", Some("example.co.uk")),
+        ("http://A.B.Example.CO.UK/", Some("Example.CO.UK")),
+        ("http://www.example.com./", Some("example.com")),
+        ("http://foo.github.io/", Some("foo.github.io")),
+        (
+            "http://a.b.\u{98DF}\u{72EE}.\u{4E2D}\u{56FD}/",
+            Some("\u{98DF}\u{72EE}.\u{4E2D}\u{56FD}"),
+        ),
+        ("http://co.uk/", None),
+        ("http://localhost/", None),
+        ("http://192.0.2.1/", None),
+        ("http://[2001:db8::1]/", None),
+        ("http:///", None),
+    ];
+    for &(iri, expected) in CASES {
+        let iri = IriReferenceStr::new(iri).expect("should be valid IRI reference");
+        let authority = AuthorityComponents::from_iri(iri).expect("has authority");
+        assert_eq!(authority.registrable_domain(), expected, "iri={:?}", iri);
+    }
+}
+
+#[cfg(feature = "psl")]
+#[test]
+fn site() {
+    let site = |s| origin(s).expect("has authority").site();
+
+    assert_eq!(
+        site("https://www.example.com/"),
+        site("https://static.EXAMPLE.com:8443/")
+    );
+    assert_ne!(
+        site("https://www.example.com/"),
+        site("http://www.example.com/")
+    );
+    assert_ne!(site("https://a.github.io/"), site("https://b.github.io/"));
+    assert_eq!(
+        site("https://a.example.com/").to_string(),
+        "https://example.com"
+    );
+    assert_eq!(site("https://co.uk/").domain(), "co.uk");
+    assert_eq!(site("http://[::1]:80/").domain(), "[::1]");
+}
+
+#[test]
+fn crawl_key_order() {
+    use iri_string::components::{CrawlKey, Partition};
+
+    let mut keys: Vec<CrawlKey<'_>> = [
+        "http://example.net/",
+        "https://b.example.com/",
+        "http://[::1]/",
+        "http://A.example.com:8080/",
+        "http://example.com./",
+        "http://192.0.2.1/",
+        "foo:bar",
+        "http://EXAMPLE.com/",
+        "file:///etc/hosts",
+    ]
+    .iter()
+    .filter_map(|s| {
+        let iri = IriReferenceStr::new(s).expect("should be valid IRI reference");
+        CrawlKey::from_iri(iri, Partition::Host)
+    })
+    .collect();
+    keys.sort();
+    keys.dedup();
+    let hosts: Vec<&str> = keys.iter().map(|key| key.host()).collect();
+    assert_eq!(
+        hosts,
+        [
+            "192.0.2.1",
+            "[::1]",
+            "example.com",
+            "A.example.com",
+            "b.example.com",
+            "example.net"
+        ]
+    );
+    assert!(keys[0].is_ip() && keys[1].is_ip() && !keys[2].is_ip());
+}
+
+#[cfg(feature = "psl")]
+#[test]
+fn crawl_key_registrable_domain() {
+    use iri_string::components::{CrawlKey, Partition};
+
+    let key = |s| {
+        let iri = IriReferenceStr::new(s).expect("should be valid IRI reference");
+        CrawlKey::from_iri(iri, Partition::RegistrableDomain).expect("has host")
+    };
+    assert_eq!(
+        key("http://a.example.co.uk/"),
+        key("https://B.Example.co.uk./")
+    );
+    assert_ne!(key("http://foo.github.io/"), key("http://bar.github.io/"));
+    assert_eq!(key("http://github.io/").host(), "github.io");
+    assert_eq!(key("http://192.0.2.1/").host(), "192.0.2.1");
+}
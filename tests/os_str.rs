@@ -0,0 +1,89 @@
+//! Tests for conversion from platform strings and paths.
+#![cfg(feature = "std")]
+
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+
+use iri_string::file::path_to_file_iri;
+use iri_string::types::{
+    IriReferenceString, IriString, UriAbsoluteString, UriReferenceString, UriString,
+};
+
+#[test]
+fn utf8_os_str() {
+    let iri = IriReferenceString::try_from(OsStr::new("/r\u{E9}sum\u{E9}?q"))
+        .expect("valid IRI reference");
+    assert_eq!(iri, "/r\u{E9}sum\u{E9}?q");
+    let iri = IriString::try_from(OsString::from("https://example.com/")).expect("valid IRI");
+    assert_eq!(iri, "https://example.com/");
+
+    assert!(UriReferenceString::try_from(OsStr::new("/r\u{E9}sum\u{E9}")).is_err());
+    assert!(UriAbsoluteString::try_from(OsStr::new("https://example.com/#frag")).is_err());
+    assert!(IriString::try_from(OsStr::new("not an IRI")).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_os_str_unix() {
+    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+    let s = OsStr::from_bytes(b"/a\xFFb\xE3\x81\x82\xE3\x81");
+    let iri = IriReferenceString::try_from(s).expect("invalid bytes are percent-encoded");
+    assert_eq!(iri, "/a%FFb\u{3042}%E3%81");
+    let iri = IriReferenceString::try_from(OsString::from_vec(b"/a\x80".to_vec()))
+        .expect("invalid bytes are percent-encoded");
+    assert_eq!(iri, "/a%80");
+    let uri = UriReferenceString::try_from(OsStr::from_bytes(b"/a\xC0\xAF"))
+        .expect("invalid bytes are percent-encoded");
+    assert_eq!(uri, "/a%C0%AF");
+
+    // A literal `%` is not escaped, so the text `%FF` and the raw byte 0xFF
+    // are converted to the same IRI.
+    let text = IriReferenceString::try_from(OsStr::new("/%FF")).expect("valid IRI");
+    let raw = IriReferenceString::try_from(OsStr::from_bytes(b"/\xFF"))
+        .expect("invalid bytes are percent-encoded");
+    assert_eq!(text, raw);
+}
+
+#[cfg(windows)]
+#[test]
+fn non_utf8_os_str_windows() {
+    use std::os::windows::ffi::OsStringExt;
+
+    let s = OsString::from_wide(&[0x2F, 0x61, 0xD800, 0x62]);
+    let iri = IriReferenceString::try_from(s).expect("surrogates are percent-encoded");
+    assert_eq!(iri, "/a%ED%A0%80b");
+}
+
+#[cfg(unix)]
+#[test]
+fn unix_path_to_file_iri() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = Path::new("/tmp/a b/100%/q?#[x]/\u{3042}");
+    let iri: IriString = path_to_file_iri(path).expect("absolute path");
+    assert_eq!(iri, "file:///tmp/a%20b/100%25/q%3F%23%5Bx%5D/\u{3042}");
+    let uri: UriString = path_to_file_iri(path).expect("absolute path");
+    assert_eq!(uri, "file:///tmp/a%20b/100%25/q%3F%23%5Bx%5D/%E3%81%82");
+
+    let path = Path::new(OsStr::from_bytes(b"/tmp/\xFF/;=@:"));
+    let iri: IriString = path_to_file_iri(path).expect("absolute path");
+    assert_eq!(iri, "file:///tmp/%FF/;=@:");
+
+    assert!(path_to_file_iri::<iri_string::spec::IriSpec>(Path::new("relative/path")).is_err());
+}
+
+#[cfg(windows)]
+#[test]
+fn windows_path_to_file_iri() {
+    let iri: IriString =
+        path_to_file_iri(Path::new(r"C:\Users\a b\file.txt")).expect("absolute path");
+    assert_eq!(iri, "file:///C:/Users/a%20b/file.txt");
+    let iri: IriString = path_to_file_iri(Path::new(r"C:\")).expect("absolute path");
+    assert_eq!(iri, "file:///C:/");
+    let iri: IriString =
+        path_to_file_iri(Path::new(r"\\server\share\dir\file")).expect("absolute path");
+    assert_eq!(iri, "file://server/share/dir/file");
+
+    assert!(path_to_file_iri::<iri_string::spec::IriSpec>(Path::new(r"dir\file")).is_err());
+}
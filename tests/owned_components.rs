@@ -0,0 +1,148 @@
+//! Tests for owned components.
+#![cfg(feature = "alloc")]
+
+mod components;
+
+use iri_string::types::{
+    IriFragmentStr, IriQueryStr, IriReferenceString, IriString, UriReferenceString,
+};
+
+use self::components::TEST_CASES;
+
+#[test]
+fn into_components() {
+    for case in TEST_CASES.iter().copied() {
+        let iri = IriReferenceString::try_from(case.composed).expect("should be valid");
+        let components = iri.into_components();
+        let expected = case.components;
+
+        assert_eq!(components.scheme_str(), expected.scheme, "case={:?}", case);
+        match components.authority() {
+            None => assert_eq!(expected.host, None, "case={:?}", case),
+            Some(authority) => {
+                let userinfo = authority.userinfo().map(|userinfo| {
+                    match userinfo.find(':').map(|pos| userinfo.split_at(pos)) {
+                        Some((user, password)) => (user, Some(&password[1..])),
+                        None => (userinfo, None),
+                    }
+                });
+                assert_eq!(userinfo.map(|(user, _)| user), expected.user);
+                assert_eq!(
+                    userinfo.and_then(|(_, password)| password),
+                    expected.password
+                );
+                assert_eq!(Some(authority.host()), expected.host, "case={:?}", case);
+                assert_eq!(authority.port(), expected.port, "case={:?}", case);
+            }
+        }
+        assert_eq!(components.path_str(), expected.path, "case={:?}", case);
+        assert_eq!(
+            components.query().map(|v| v.as_str()),
+            expected.query,
+            "case={:?}",
+            case
+        );
+        assert_eq!(
+            components.fragment().map(|v| v.as_str()),
+            expected.fragment,
+            "case={:?}",
+            case
+        );
+
+        if case.is_uri_class() {
+            let uri = UriReferenceString::try_from(case.composed).expect("should be valid");
+            assert_eq!(uri.into_components().path_str(), expected.path);
+        }
+
+        let iri = IriReferenceString::try_from(case.composed).expect("should be valid");
+        let rebuilt = IriReferenceString::from_components(&iri.clone().into_components())
+            .expect("unmodified components should be consistent");
+        assert_eq!(rebuilt, iri);
+    }
+}
+
+#[test]
+fn into_parts() {
+    let iri = IriString::try_from("foo://user@host:80/path?query#").expect("should be valid");
+    let (scheme, authority, path, query, fragment) = iri.into_components().into_parts();
+    assert_eq!(scheme.as_deref(), Some("foo"));
+    let (userinfo, host, port) = authority.expect("has authority").into_parts();
+    assert_eq!(userinfo.as_deref(), Some("user"));
+    assert_eq!(host, "host");
+    assert_eq!(port.as_deref(), Some("80"));
+    assert_eq!(path, "/path");
+    assert_eq!(query.expect("has query"), "query");
+    assert_eq!(fragment.expect("has fragment"), "");
+}
+
+#[test]
+fn modify_and_build() {
+    let iri = IriString::try_from("http://user@example.com:80/a?q#f").expect("should be valid");
+    let mut components = iri.into_components();
+
+    components.set_scheme(Some("https")).expect("valid scheme");
+    components.set_userinfo(None).expect("authority is present");
+    components.set_host(Some("[::1]")).expect("valid host");
+    components.set_port(Some("")).expect("valid port");
+    components.set_path("/b/\u{03B1}").expect("valid path");
+    components.set_query(Some(IriQueryStr::new("x=1").expect("valid query")));
+    components.set_fragment(None);
+    assert_eq!(
+        IriString::from_components(&components).expect("consistent"),
+        "https://[::1]:/b/\u{03B1}?x=1"
+    );
+
+    components.set_host(None).expect("removing authority");
+    assert_eq!(components.authority(), None);
+    assert_eq!(
+        IriString::from_components(&components).expect("consistent"),
+        "https:/b/\u{03B1}?x=1"
+    );
+    components.set_fragment(Some(IriFragmentStr::new("").expect("valid fragment")));
+    components.set_scheme(None).expect("removing scheme");
+    assert!(IriString::from_components(&components).is_err());
+    assert_eq!(
+        IriReferenceString::from_components(&components).expect("consistent"),
+        "/b/\u{03B1}?x=1#"
+    );
+}
+
+#[test]
+fn invalid_components() {
+    let iri = IriString::try_from("foo:bar").expect("should be valid");
+    let mut components = iri.into_components();
+
+    assert!(components.set_scheme(Some("")).is_err());
+    assert!(components.set_scheme(Some("1foo")).is_err());
+    assert!(components.set_userinfo(Some("user")).is_err());
+    assert!(components.set_port(Some("80")).is_err());
+    assert!(components.set_host(Some("exa mple")).is_err());
+    assert!(components.set_path("a b").is_err());
+    components
+        .set_host(Some("example.com"))
+        .expect("valid host");
+    assert!(components.set_userinfo(Some("us@er")).is_err());
+    assert!(components.set_port(Some("http")).is_err());
+    // Failed setters must not modify the components.
+    assert_eq!(components.scheme_str(), Some("foo"));
+    assert_eq!(components.authority().and_then(|a| a.port()), None);
+
+    // Path without leading slash cannot follow an authority.
+    assert!(IriString::from_components(&components).is_err());
+    components.set_path("//bar").expect("valid path");
+    assert_eq!(
+        IriString::from_components(&components).expect("consistent"),
+        "foo://example.com//bar"
+    );
+    // Path starting with `//` requires authority.
+    components.set_host(None).expect("removing authority");
+    assert!(IriString::from_components(&components).is_err());
+
+    // First segment with colon requires scheme.
+    components.set_path("a:b/c").expect("valid path");
+    assert!(IriReferenceString::from_components(&components).is_ok());
+    components.set_scheme(None).expect("removing scheme");
+    assert!(IriReferenceString::from_components(&components).is_err());
+    components.set_path("./a:b/c").expect("valid path");
+    assert!(IriReferenceString::from_components(&components).is_ok());
+}
@@ -0,0 +1,61 @@
+//! Tests for `oxiri` integration.
+#![cfg(feature = "oxiri")]
+
+mod components;
+
+use std::convert::TryFrom;
+
+use iri_string::types::{
+    IriAbsoluteStr, IriReferenceStr, IriReferenceString, IriRelativeStr, IriStr, IriString, UriStr,
+};
+use oxiri::{Iri, IriRef};
+
+use self::components::TEST_CASES;
+
+#[test]
+fn into_oxiri() {
+    for case in TEST_CASES.iter().copied() {
+        let iri_ref = IriReferenceStr::new(case.composed).expect("should be valid IRI reference");
+        let ox = IriRef::from(iri_ref);
+        assert_eq!(ox.as_str(), case.composed);
+        assert_eq!(ox.scheme(), iri_ref.scheme_str());
+        assert_eq!(ox.path(), iri_ref.path_str());
+        assert_eq!(ox.query(), iri_ref.query_str());
+        assert_eq!(ox.fragment(), iri_ref.fragment().map(|f| f.as_str()));
+
+        let owned = IriRef::<String>::from(iri_ref.to_owned());
+        assert_eq!(owned.as_str(), case.composed);
+
+        if let Ok(iri) = iri_ref.to_iri() {
+            assert_eq!(Iri::from(iri).as_str(), case.composed);
+        }
+    }
+
+    let relative = IriRelativeStr::new("../foo?bar").expect("valid");
+    assert_eq!(IriRef::from(relative).path(), "../foo");
+    let absolute = IriAbsoluteStr::new("http://example.com/").expect("valid");
+    assert_eq!(Iri::from(absolute).authority(), Some("example.com"));
+    let uri = UriStr::new("http://example.com/%E3%81%82").expect("valid");
+    assert_eq!(Iri::from(uri).path(), "/%E3%81%82");
+}
+
+#[test]
+fn from_oxiri() {
+    let ox = Iri::parse("http://example.com/\u{3042}?q#f").expect("valid for oxiri");
+    let iri = <&IriStr>::try_from(&ox).expect("valid IRI");
+    assert_eq!(iri, "http://example.com/\u{3042}?q#f");
+    assert!(<&UriStr>::try_from(&ox).is_err());
+    assert!(<&IriAbsoluteStr>::try_from(&ox).is_err());
+
+    let owned = IriString::try_from(Iri::parse("urn:isbn:0451450523".to_owned()).expect("valid"))
+        .expect("valid IRI");
+    assert_eq!(owned, "urn:isbn:0451450523");
+
+    let ox_ref = IriRef::parse("../a/b?c").expect("valid for oxiri");
+    let reference = <&IriReferenceStr>::try_from(&ox_ref).expect("valid IRI reference");
+    assert!(reference.to_iri().is_err());
+    assert!(<&IriRelativeStr>::try_from(&ox_ref).is_ok());
+    let owned = IriReferenceString::try_from(IriRef::parse("#frag".to_owned()).expect("valid"))
+        .expect("valid IRI reference");
+    assert_eq!(owned, "#frag");
+}
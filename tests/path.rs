@@ -0,0 +1,284 @@
+//! Tests for path-related operations.
+
+use iri_string::types::IriReferenceStr;
+#[cfg(feature = "alloc")]
+use iri_string::types::IriReferenceString;
+
+#[test]
+fn hierarchical_and_opaque() {
+    // (input, is_hierarchical, is_opaque).
+    const CASES: &[(&str, bool, bool)] = &[
+        ("http://example.com", true, false),
+        ("http://example.com/a/b", true, false),
+        ("file:///etc/hosts", true, false),
+        ("foo:/a/b", true, false),
+        ("foo:", false, true),
+        ("urn:isbn:0451450523", false, true),
+        ("mailto:user@example.com", false, true),
+        ("//example.com/a", true, false),
+        ("/a/b", true, false),
+        ("a/b", false, false),
+        ("", false, false),
+    ];
+    for &(input, hierarchical, opaque) in CASES {
+        let iri = IriReferenceStr::new(input).expect("should be valid");
+        assert_eq!(iri.is_hierarchical(), hierarchical, "input={:?}", input);
+        assert_eq!(iri.is_opaque(), opaque, "input={:?}", input);
+        if let Ok(iri) = iri.to_iri() {
+            assert_eq!(iri.is_hierarchical(), hierarchical, "input={:?}", input);
+            assert_eq!(iri.is_opaque(), opaque, "input={:?}", input);
+            assert_eq!(iri.to_absolute().is_opaque(), opaque, "input={:?}", input);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn trailing_slash_and_index_segment() {
+    // (input, ensure_trailing_slash, strip_trailing_slash, strip_index_segment).
+    const CASES: &[(&str, &str, &str, &str)] = &[
+        (
+            "http://example.com",
+            "http://example.com/",
+            "http://example.com",
+            "http://example.com",
+        ),
+        (
+            "http://example.com/",
+            "http://example.com/",
+            "http://example.com/",
+            "http://example.com/",
+        ),
+        (
+            "http://example.com/a//?q#f",
+            "http://example.com/a//?q#f",
+            "http://example.com/a/?q#f",
+            "http://example.com/a//?q#f",
+        ),
+        (
+            "http://example.com/index.html?q#f",
+            "http://example.com/index.html/?q#f",
+            "http://example.com/index.html?q#f",
+            "http://example.com/?q#f",
+        ),
+        ("foo:", "foo:", "foo:", "foo:"),
+        ("foo:/.//a/", "foo:/.//a/", "foo:/.//a", "foo:/.//a/"),
+        (
+            "mailto:index.html",
+            "mailto:index.html",
+            "mailto:index.html",
+            "mailto:index.html",
+        ),
+        ("", "/", "", ""),
+        ("?q", "/?q", "?q", "?q"),
+        ("./", "./", ".", "./"),
+        ("a/index.htm", "a/index.htm/", "a/index.htm", "a/"),
+        ("index.html#f", "index.html/#f", "index.html#f", "#f"),
+        ("a/main.html", "a/main.html/", "a/main.html", "a/main.html"),
+    ];
+    const NAMES: &[&str] = &["index.html", "index.htm"];
+    for &(input, ensured, stripped, index_stripped) in CASES {
+        let iri = IriReferenceStr::new(input).expect("should be valid");
+
+        let mut s = IriReferenceString::from(iri);
+        s.ensure_trailing_slash();
+        assert_eq!(s, ensured, "input={:?}", input);
+        let mut s = IriReferenceString::from(iri);
+        s.strip_trailing_slash();
+        assert_eq!(s, stripped, "input={:?}", input);
+        let mut s = IriReferenceString::from(iri);
+        s.strip_index_segment(NAMES);
+        assert_eq!(s, index_stripped, "input={:?}", input);
+
+        if let Ok(iri) = iri.to_iri() {
+            let mut s = iri.to_owned();
+            s.ensure_trailing_slash();
+            assert_eq!(s, ensured, "input={:?}", input);
+            let mut s = iri.to_owned();
+            s.strip_trailing_slash();
+            assert_eq!(s, stripped, "input={:?}", input);
+            let mut s = iri.to_owned();
+            s.strip_index_segment(NAMES);
+            assert_eq!(s, index_stripped, "input={:?}", input);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn collapse_empty_segments() {
+    // (input, expected).
+    const CASES: &[(&str, &str)] = &[
+        ("http://example.com", "http://example.com"),
+        ("http://example.com//", "http://example.com/"),
+        (
+            "http://example.com//a///b/c//?q//#f//",
+            "http://example.com/a/b/c/?q//#f//",
+        ),
+        ("http://example.com/a%2F%2Fb", "http://example.com/a%2F%2Fb"),
+        ("foo:/.//a", "foo:/./a"),
+        ("foo:a//b", "foo:a//b"),
+        ("mailto:a//b", "mailto:a//b"),
+        ("//example.com//a", "//example.com/a"),
+        ("a//b:c", "a/b:c"),
+        (".//a:b", "./a:b"),
+        ("", ""),
+    ];
+    for &(input, expected) in CASES {
+        let iri = IriReferenceStr::new(input).expect("should be valid");
+
+        let mut s = IriReferenceString::from(iri);
+        assert_eq!(
+            s.collapse_empty_segments(),
+            input != expected,
+            "input={:?}",
+            input
+        );
+        assert_eq!(s, expected, "input={:?}", input);
+        assert!(!s.collapse_empty_segments(), "input={:?}", input);
+
+        if let Ok(iri) = iri.to_iri() {
+            let mut s = iri.to_owned();
+            assert_eq!(
+                s.collapse_empty_segments(),
+                input != expected,
+                "input={:?}",
+                input
+            );
+            assert_eq!(s, expected, "input={:?}", input);
+        }
+        if let Ok(iri) = iri.to_relative_iri() {
+            let mut s = iri.to_owned();
+            assert_eq!(
+                s.collapse_empty_segments(),
+                input != expected,
+                "input={:?}",
+                input
+            );
+            assert_eq!(s, expected, "input={:?}", input);
+        }
+    }
+}
+
+#[test]
+fn percent_encoded_segment_eq() {
+    use iri_string::path::{path_eq, segment_eq};
+
+    // (lhs, rhs, equal).
+    const SEGMENTS: &[(&str, &str, bool)] = &[
+        ("", "", true),
+        ("%41", "A", true),
+        ("%41", "a", false),
+        ("%7e", "~", true),
+        ("%2f", "%2F", true),
+        ("%2F", "/", false),
+        ("%25", "%", true),
+        ("%", "%", true),
+        ("%4", "%4", true),
+        ("%E3%81%82", "\u{3042}", true),
+        ("a", "a%00", false),
+    ];
+    for &(lhs, rhs, equal) in SEGMENTS {
+        assert_eq!(segment_eq(lhs, rhs), equal, "lhs={:?}, rhs={:?}", lhs, rhs);
+        assert_eq!(segment_eq(rhs, lhs), equal, "lhs={:?}, rhs={:?}", lhs, rhs);
+    }
+
+    // (lhs, rhs, equal).
+    const PATHS: &[(&str, &str, bool)] = &[
+        ("/a/b", "/%61/%62", true),
+        ("/a/b", "/a%2Fb", false),
+        ("/a//b", "/a/b", false),
+        ("/a/b/", "/a/b", false),
+        ("a%2F", "a/", false),
+        ("/a/./b", "/a/b", false),
+    ];
+    for &(lhs, rhs, equal) in PATHS {
+        assert_eq!(path_eq(lhs, rhs), equal, "lhs={:?}, rhs={:?}", lhs, rhs);
+        assert_eq!(path_eq(rhs, lhs), equal, "lhs={:?}, rhs={:?}", lhs, rhs);
+    }
+}
+
+#[test]
+fn slice_path_keeps_validity() {
+    use iri_string::types::{IriPathStr, IriStr};
+
+    let iri = IriStr::new("http://example.com//a/b?q#f").expect("valid IRI");
+    let path: &IriPathStr = iri.slice_path();
+    assert_eq!(path, "//a/b");
+    assert!(path.is_absolute());
+    assert_eq!(IriPathStr::new(path.as_str()).ok(), Some(path));
+
+    let rel = IriReferenceStr::new("a:b").expect("valid IRI reference");
+    assert_eq!(rel.slice_path(), "b");
+}
+
+#[test]
+fn slice_after_authority() {
+    use iri_string::types::IriAbsoluteStr;
+
+    let cases: &[(&str, Option<&str>)] = &[
+        ("http://example.com", Some("")),
+        ("http://example.com/a?q#f", Some("/a?q#f")),
+        ("http://example.com//a", None),
+        ("file:///etc/hosts", Some("/etc/hosts")),
+        ("mailto:user@example.com", Some("user@example.com")),
+        ("urn:uuid:x", None),
+        ("foo:a/b:c?d:e", Some("a/b:c?d:e")),
+        ("//example.com/?q", Some("/?q")),
+        ("../a#b", Some("../a#b")),
+    ];
+    for &(input, expected) in cases {
+        let iri = IriReferenceStr::new(input).expect("valid IRI reference");
+        let rest = iri.slice_after_authority();
+        assert_eq!(rest.map(|r| r.as_str()), expected, "input={input:?}");
+        if let Some(rest) = rest {
+            assert_eq!(rest.path_str(), iri.path_str(), "input={input:?}");
+            assert_eq!(rest.authority_str(), None, "input={input:?}");
+        }
+    }
+
+    let abs = IriAbsoluteStr::new("http://example.com/a?q").expect("valid absolute IRI");
+    assert_eq!(
+        abs.slice_after_authority().map(|r| r.as_str()),
+        Some("/a?q")
+    );
+}
+
+#[test]
+fn decoded_search() {
+    use iri_string::types::{IriPathStr, UriStr};
+
+    // [(path, needle, position)]
+    const CASES: &[(&str, &str, Option<usize>)] = &[
+        ("/a%20b", "a b", Some(1)),
+        ("/a%20b", "%20", None),
+        ("/a%2Fb", "a/b", Some(1)),
+        ("/%252F", "%2F", Some(1)),
+        ("/%25%32%46", "%2F", Some(1)),
+        ("/%E6%97%A5%E6%9C%AC", "\u{672C}", Some(10)),
+        ("/\u{65E5}\u{672C}", "\u{672C}", Some(4)),
+        ("/%E6%97%A5", "\u{672C}", None),
+        ("/abc", "", Some(0)),
+        ("", "", Some(0)),
+        ("", "a", None),
+    ];
+    for &(path, needle, expected) in CASES {
+        let path = IriPathStr::new(path).expect("valid path");
+        assert_eq!(
+            path.find_decoded(needle),
+            expected,
+            "path={path:?}, needle={needle:?}"
+        );
+        assert_eq!(path.contains_decoded(needle), expected.is_some());
+    }
+
+    let uri = UriStr::new("http://example.com/%7Euser/My%20Files/?q=%41#Sec%2D1").expect("valid");
+    let path = uri.slice_path();
+    assert!(path.starts_with_decoded("/~user/"));
+    assert!(path.ends_with_decoded("My Files/"));
+    assert!(!path.ends_with_decoded("Files"));
+    assert!(uri.query().expect("has query").contains_decoded("q=A"));
+    let fragment = uri.fragment().expect("has fragment");
+    assert!(fragment.starts_with_decoded("Sec-"));
+    assert!(fragment.ends_with_decoded("-1"));
+}
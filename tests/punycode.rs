@@ -0,0 +1,107 @@
+//! Tests for Punycode.
+#![cfg(feature = "alloc")]
+
+use iri_string::punycode::{decode, encode, to_ascii_label, to_unicode_label};
+
+/// Sample strings from RFC 3492 section 7.1, and some IDN labels.
+// [(decoded, encoded)]
+const CASES: &[(&str, &str)] = &[
+    // (A) Arabic (Egyptian).
+    (
+        "\u{0644}\u{064A}\u{0647}\u{0645}\u{0627}\u{0628}\u{062A}\u{0643}\u{0644}\
+         \u{0645}\u{0648}\u{0634}\u{0639}\u{0631}\u{0628}\u{064A}\u{061F}",
+        "egbpdaj6bu4bxfgehfvwxn",
+    ),
+    // (B) Chinese (simplified).
+    (
+        "\u{4ED6}\u{4EEC}\u{4E3A}\u{4EC0}\u{4E48}\u{4E0D}\u{8BF4}\u{4E2D}\u{6587}",
+        "ihqwcrb4cv8a8dqg056pqjye",
+    ),
+    // (I) Russian (Cyrillic).
+    (
+        "\u{043F}\u{043E}\u{0447}\u{0435}\u{043C}\u{0443}\u{0436}\u{0435}\u{043E}\
+         \u{043D}\u{0438}\u{043D}\u{0435}\u{0433}\u{043E}\u{0432}\u{043E}\u{0440}\
+         \u{044F}\u{0442}\u{043F}\u{043E}\u{0440}\u{0443}\u{0441}\u{0441}\u{043A}\
+         \u{0438}",
+        "b1abfaaepdrnnbgefbadotcwatmq2g4l",
+    ),
+    // (L) 3<nen>B<gumi><kinpachi><sensei>.
+    (
+        "3\u{5E74}B\u{7D44}\u{91D1}\u{516B}\u{5148}\u{751F}",
+        "3B-ww4c5e180e575a65lsy2b",
+    ),
+    // (N) Hello-Another-Way-<sorezore><no><basho>.
+    (
+        "Hello-Another-Way-\u{305D}\u{308C}\u{305E}\u{308C}\u{306E}\u{5834}\u{6240}",
+        "Hello-Another-Way--fc4qua05auwb3674vfr0b",
+    ),
+    // (O) <hitotsu><yane><no><shita>2.
+    (
+        "\u{3072}\u{3068}\u{3064}\u{5C4B}\u{6839}\u{306E}\u{4E0B}2",
+        "2-u9tlzr9756bt3uc0v",
+    ),
+    // (R) <sono><supiido><de>.
+    (
+        "\u{305D}\u{306E}\u{30B9}\u{30D4}\u{30FC}\u{30C9}\u{3067}",
+        "d9juau41awczczp",
+    ),
+    // (S) -> $1.00 <-
+    ("-> $1.00 <-", "-> $1.00 <--"),
+    ("b\u{FC}cher", "bcher-kva"),
+    ("m\u{FC}nchen", "mnchen-3ya"),
+    ("\u{1F600}", "e28h"),
+    ("", ""),
+];
+
+#[test]
+fn encode_rfc3492_samples() {
+    for (decoded, encoded) in CASES.iter().copied() {
+        assert_eq!(
+            encode(decoded).as_deref(),
+            Ok(encoded),
+            "decoded={:?}",
+            decoded
+        );
+    }
+}
+
+#[test]
+fn decode_rfc3492_samples() {
+    for (decoded, encoded) in CASES.iter().copied() {
+        assert_eq!(
+            decode(encoded).as_deref(),
+            Ok(decoded),
+            "encoded={:?}",
+            encoded
+        );
+    }
+    // Digits are case-insensitive.
+    assert_eq!(decode("BCHER-KVA").as_deref(), Ok("B\u{FC}CHER"));
+}
+
+#[test]
+fn decode_invalid() {
+    for input in ["\u{FC}-kva", "bcher-kv!", "bcher-k", "99999999999"] {
+        assert!(decode(input).is_err(), "input={:?}", input);
+    }
+}
+
+#[test]
+fn labels() {
+    assert_eq!(
+        to_ascii_label("\u{4F8B}\u{3048}").as_deref(),
+        Ok("xn--r8jz45g")
+    );
+    assert_eq!(to_ascii_label("example").as_deref(), Ok("example"));
+    assert_eq!(
+        to_unicode_label("xn--r8jz45g").as_deref(),
+        Ok("\u{4F8B}\u{3048}")
+    );
+    assert_eq!(
+        to_unicode_label("Xn--R8JZ45G").as_deref(),
+        Ok("\u{4F8B}\u{3048}")
+    );
+    assert_eq!(to_unicode_label("example").as_deref(), Ok("example"));
+    assert_eq!(to_unicode_label("xn-").as_deref(), Ok("xn-"));
+    assert!(to_unicode_label("xn--r8jz45g!").is_err());
+}
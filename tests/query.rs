@@ -0,0 +1,239 @@
+//! Tests for query manipulation.
+#![cfg(feature = "alloc")]
+
+use iri_string::types::{IriQueryString, IriStr, IriString, UriQueryString, UriString};
+
+#[test]
+fn collect_query_pairs() {
+    let pairs = [("key", "value"), ("a&b", "c=d"), ("plus+", "sp ace#")];
+    let query: UriQueryString = pairs.iter().collect();
+    assert_eq!(query, "key=value&a%26b=c%3Dd&plus%2B=sp%20ace%23");
+
+    let owned_pairs = vec![(String::from("alpha"), String::from("\u{03B1}"))];
+    let iri_query: IriQueryString = owned_pairs.iter().collect();
+    assert_eq!(iri_query, "alpha=\u{03B1}");
+    let uri_query: UriQueryString = owned_pairs.into_iter().collect();
+    assert_eq!(uri_query, "alpha=%CE%B1");
+
+    let empty: UriQueryString = core::iter::empty::<(&str, &str)>().collect();
+    assert_eq!(empty, "");
+}
+
+#[test]
+fn extend_query_string() {
+    let mut query: IriQueryString = [("a", "1")].iter().collect();
+    query.extend([("b", 2), ("c", 3)]);
+    assert_eq!(query, "a=1&b=2&c=3");
+}
+
+#[test]
+fn extend_iri_query() {
+    let mut iri = IriString::try_from("http://example.com").expect("valid IRI");
+    iri.extend_query([("a", "1")]);
+    assert_eq!(iri, "http://example.com?a=1");
+
+    let mut iri = UriString::try_from("foo:bar?#frag").expect("valid IRI");
+    iri.extend_query([("a", "1"), ("b", "2")]);
+    assert_eq!(iri, "foo:bar?a=1&b=2#frag");
+
+    let mut iri = UriString::try_from("foo:bar?x#frag?/").expect("valid IRI");
+    iri.extend_query([("a", "?/")]);
+    assert_eq!(iri, "foo:bar?x&a=?/#frag?/");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn extend_iri_query_display_error() {
+    use core::fmt;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    struct Failing;
+    impl fmt::Display for Failing {
+        fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    let mut iri = IriString::try_from("foo:bar?x#frag").expect("valid IRI");
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        iri.extend_query([("a", &"1" as &dyn fmt::Display), ("b", &Failing)]);
+    }));
+    assert!(result.is_err());
+    // The fragment is kept even on failure.
+    assert_eq!(iri, "foo:bar?x&a=1#frag");
+    assert!(IriStr::new(iri.as_str()).is_ok());
+}
+
+#[test]
+fn byte_pairs() {
+    let mut query: UriQueryString = core::iter::empty::<(&str, &str)>().collect();
+    query.push_pair_bytes(b"k\xff", b"\xce\xb1\xce");
+    query.extend_bytes([(b"a&b".to_vec(), b"=".to_vec())]);
+    assert_eq!(query, "k%FF=%CE%B1%CE&a%26b=%3D");
+
+    let mut iri_query: IriQueryString = core::iter::empty::<(&str, &str)>().collect();
+    iri_query.push_pair_bytes(b"k\xff", b"\xce\xb1\xce");
+    assert_eq!(iri_query, "k%FF=\u{03B1}%CE");
+
+    let decoded: Vec<_> = query.query_pairs_bytes().collect();
+    assert_eq!(decoded.len(), 2);
+    assert_eq!(&*decoded[0].0, b"k\xff");
+    assert_eq!(&*decoded[0].1, b"\xce\xb1\xce");
+    assert_eq!(&*decoded[1].0, b"a&b");
+    assert_eq!(&*decoded[1].1, b"=");
+}
+
+#[test]
+fn decode_query_pairs() {
+    let pairs = [("a b", "c&d"), ("\u{03B1}", "="), ("+", "")];
+    let query: IriQueryString = pairs.iter().collect();
+    let decoded: Vec<(String, String)> = query
+        .query_pairs()
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    let expected: Vec<(String, String)> = pairs
+        .iter()
+        .map(|&(k, v)| (k.to_owned(), v.to_owned()))
+        .collect();
+    assert_eq!(decoded, expected);
+
+    let query = UriQueryString::try_from("&&a=1=2&b&=c&%FF=x+y").expect("valid query");
+    let decoded: Vec<_> = query.query_pairs().collect();
+    assert_eq!(
+        decoded,
+        [
+            ("a".into(), "1=2".into()),
+            ("b".into(), "".into()),
+            ("".into(), "c".into()),
+            ("\u{FFFD}".into(), "x y".into()),
+        ]
+    );
+}
+
+#[test]
+fn form_data() {
+    use iri_string::query::FormData;
+    use iri_string::types::UriQueryStr;
+
+    let query = UriQueryStr::new("a=1&b=x&&a=2&flag&c=%E3%81%82&a=three").expect("valid query");
+    let form = query.to_form_data();
+    assert_eq!(form, FormData::from(query));
+    assert_eq!(form.len(), 6);
+    assert!(!form.is_empty());
+
+    assert_eq!(form.get::<i32>("a"), Some(Ok(1)));
+    assert_eq!(
+        form.get_all("a")
+            .map(|v| v.parse::<i32>().ok())
+            .collect::<Vec<_>>(),
+        [Some(1), Some(2), None]
+    );
+    assert!(form.get::<i32>("b").expect("`b` exists").is_err());
+    assert_eq!(form.get_str("flag"), Some(""));
+    assert!(form.contains_key("flag"));
+    assert!(!form.contains_key("missing"));
+    assert_eq!(form.get_str("c"), Some("\u{3042}"));
+    assert_eq!(form.get_all("missing").next(), None);
+
+    assert_eq!(
+        form.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+        ["a", "b", "a", "flag", "c", "a"]
+    );
+    let rebuilt: UriQueryString = form.iter().collect();
+    assert_eq!(rebuilt, "a=1&b=x&a=2&flag=&c=%E3%81%82&a=three");
+
+    let empty = UriQueryStr::new("").expect("valid query").to_form_data();
+    assert!(empty.is_empty());
+    assert_eq!(empty.into_pairs(), Vec::<(String, String)>::new());
+}
+
+#[test]
+fn eq_ignore_query_order() {
+    // [(lhs, rhs, expected)]
+    const CASES: &[(&str, &str, bool)] = &[
+        ("http://example.com/", "http://example.com/", true),
+        ("http://example.com/", "http://example.com/?", true),
+        ("http://example.com/?a&&b", "http://example.com/?b&a", true),
+        (
+            "http://example.com/?a=b+c",
+            "http://example.com/?a=b%20c",
+            true,
+        ),
+        (
+            "http://example.com/?a=b=c",
+            "http://example.com/?a=b%3Dc",
+            true,
+        ),
+        (
+            "http://example.com/?a=1&b",
+            "http://example.com/?b=&a=1",
+            true,
+        ),
+        (
+            "http://example.com/?a%26b",
+            "http://example.com/?a&b",
+            false,
+        ),
+        (
+            "http://example.com/?a=1#x",
+            "http://example.com/?a=1#y",
+            false,
+        ),
+        ("http://example.com/?a=1#", "http://example.com/?a=1", false),
+        ("http://example.com/a?x", "http://example.com/b?x", false),
+    ];
+    for (lhs, rhs, expected) in CASES.iter().copied() {
+        let lhs = IriStr::new(lhs).expect("valid IRI");
+        let rhs = IriStr::new(rhs).expect("valid IRI");
+        assert_eq!(
+            lhs.eq_ignore_query_order(rhs),
+            expected,
+            "{} vs {}",
+            lhs,
+            rhs
+        );
+        assert_eq!(
+            rhs.eq_ignore_query_order(lhs),
+            expected,
+            "{} vs {}",
+            rhs,
+            lhs
+        );
+    }
+}
+
+#[test]
+fn duplicate_keys_keep_order() {
+    use iri_string::types::UriQueryStr;
+
+    let src = "sig=z&ts=1&sig=a&Sig=m&sig=&ts=0";
+    let query = UriQueryStr::new(src).expect("valid query");
+
+    let keys: Vec<_> = query
+        .query_pairs()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect();
+    assert_eq!(keys, ["sig=z", "ts=1", "sig=a", "Sig=m", "sig=", "ts=0"]);
+
+    let values: Vec<_> = (0..4).map(|n| query.nth_occurrence("sig", n)).collect();
+    assert_eq!(
+        values,
+        [Some("z".into()), Some("a".into()), Some("".into()), None]
+    );
+    let form = query.to_form_data();
+    for n in 0..4 {
+        assert_eq!(
+            form.nth_occurrence("sig", n),
+            query.nth_occurrence("sig", n).as_deref(),
+            "n={n}"
+        );
+    }
+    assert_eq!(form.nth_occurrence("ts", 1), Some("0"));
+
+    // Round trips of IRIs and components never reorder the query.
+    let iri = UriString::try_from(format!("https://example.com/?{src}")).expect("valid URI");
+    let components = iri.into_components();
+    assert_eq!(components.query().map(|q| q.as_str()), Some(src));
+    let rebuilt = UriString::from_components(&components).expect("valid URI");
+    assert_eq!(rebuilt.query_str(), Some(src));
+}
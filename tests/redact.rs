@@ -0,0 +1,79 @@
+//! Tests for redaction.
+#![cfg(feature = "alloc")]
+
+use iri_string::types::{IriReferenceStr, IriStr};
+
+#[test]
+fn userinfo() {
+    let iri = IriStr::new("https://user:pw@example.com/?q=1#f").expect("valid IRI");
+    assert_eq!(iri.redacted().to_string(), "https://***@example.com/?q=1#f");
+    let iri = IriStr::new("https://@example.com/").expect("valid IRI");
+    assert_eq!(iri.redacted().to_string(), "https://***@example.com/");
+    let iri = IriStr::new("https://example.com/@a").expect("valid IRI");
+    assert_eq!(iri.redacted().to_string(), "https://example.com/@a");
+    let iri = IriStr::new("urn:user:pw@example.com").expect("valid IRI");
+    assert_eq!(iri.redacted().to_string(), "urn:user:pw@example.com");
+}
+
+#[test]
+fn query_values() {
+    let cases = [
+        ("/p?a=1&b=&c&&d=x=y#q=1", "/p?a=***&b=&***&&d=***#q=1"),
+        ("?", "?"),
+        ("/p#a=1", "/p#a=1"),
+        ("//u@h?k=v", "//***@h?k=***"),
+    ];
+    for (input, expected) in cases {
+        let iri = IriReferenceStr::new(input).expect("valid IRI reference");
+        assert_eq!(
+            iri.redacted().mask_query_values(true).to_string(),
+            expected,
+            "input={:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn redacted_debug() {
+    let iri = IriStr::new("https://user:pw@example.com/").expect("valid IRI");
+    assert_eq!(
+        format!("{:?}", iri.redacted()),
+        "<https://***@example.com/>"
+    );
+}
+
+#[cfg(not(feature = "redact-debug"))]
+#[test]
+fn debug_without_redaction() {
+    let iri = IriStr::new("https://user:pw@example.com/").expect("valid IRI");
+    assert_eq!(
+        format!("{:?}", iri),
+        r#"RiStr("https://user:pw@example.com/")"#
+    );
+}
+
+#[cfg(feature = "redact-debug")]
+#[test]
+fn debug_userinfo() {
+    let iri = IriStr::new("https://user:pw@example.com/?k=v").expect("valid IRI");
+    let expected = if cfg!(feature = "redact-debug-query") {
+        r#"RiStr("https://***@example.com/?k=***")"#
+    } else {
+        r#"RiStr("https://***@example.com/?k=v")"#
+    };
+    assert_eq!(format!("{:?}", iri), expected);
+    assert_eq!(
+        format!("{:?}", iri.to_owned()),
+        expected.replace("RiStr", "RiString")
+    );
+}
+
+#[cfg(feature = "redact-debug-query")]
+#[test]
+fn debug_query() {
+    use iri_string::types::UriQueryStr;
+
+    let query = UriQueryStr::new("token=secret&x").expect("valid query");
+    assert_eq!(format!("{:?}", query), r#"RiQueryStr("token=***&***")"#);
+}
@@ -9,7 +9,7 @@ mod resolve_refimpl;
 use iri_string::format::write_to_slice;
 #[cfg(feature = "alloc")]
 use iri_string::format::ToDedicatedString;
-use iri_string::resolve::FixedBaseResolver;
+use iri_string::resolve::{FixedBaseResolver, ResolutionMode, ResolveError};
 use iri_string::types::*;
 
 #[cfg(feature = "alloc")]
@@ -297,6 +297,142 @@ fn fixed_base_resolver() {
     }
 }
 
+#[test]
+fn fixed_base_resolver_legacy_mode() {
+    // Results differ from the strict mode only when the reference has the same
+    // scheme as the base.
+    const LEGACY_CASES: &[(&str, &str, &str)] = &[
+        ("http://a/b/c/d;p?q", "http:g", "http://a/b/c/g"),
+        ("http://a/b/c/d;p?q", "HTTP:g?y", "http://a/b/c/g?y"),
+        ("http://a/b/c/d;p?q", "http:", "http://a/b/c/d;p?q"),
+        ("http://a/b/c/d;p?q", "http://x/y", "http://x/y"),
+        ("http://a/b/c/d;p?q", "https:g", "https:g"),
+        ("foo:/a/b", "foo:c", "foo:/a/c"),
+    ];
+
+    for (base, pairs) in TEST_CASES {
+        let base = IriAbsoluteStr::new(base).expect("should be valid base IRI");
+        let resolver = FixedBaseResolver::new(base).with_mode(ResolutionMode::Legacy);
+        assert_eq!(resolver.mode(), ResolutionMode::Legacy);
+
+        for (target, expected, _normalized_expected) in *pairs {
+            let target = IriReferenceStr::new(target).expect("should be valid IRI reference");
+            if target.scheme_str() == Some(base.scheme_str()) {
+                continue;
+            }
+            let resolved = resolver.resolve(target);
+            assert_eq_display!(resolved, expected, "base={base:?}, target={target:?}");
+        }
+    }
+
+    for (base, target, expected) in LEGACY_CASES {
+        let base = IriAbsoluteStr::new(base).expect("should be valid base IRI");
+        let target = IriReferenceStr::new(target).expect("should be valid IRI reference");
+        let resolver = FixedBaseResolver::new(base).with_mode(ResolutionMode::Legacy);
+        assert_eq_display!(
+            resolver.resolve(target),
+            expected,
+            "base={base:?}, target={target:?}"
+        );
+    }
+}
+
+#[test]
+fn try_resolve_path_above_root_by_mode() {
+    let base = IriAbsoluteStr::new("http://example.com/a/b").expect("should be valid base IRI");
+    let reference = IriReferenceStr::new("../../..").expect("should be valid IRI reference");
+
+    let strict = FixedBaseResolver::new(base);
+    assert_eq!(
+        strict.try_resolve(reference).err(),
+        Some(ResolveError::PathAboveRoot)
+    );
+
+    let legacy = strict.with_mode(ResolutionMode::Legacy);
+    let resolved = legacy
+        .try_resolve(reference)
+        .expect("the legacy mode discards excess `..`");
+    assert_eq_display!(resolved, "http://example.com/");
+    assert_eq_display!(legacy.resolve(reference), "http://example.com/");
+}
+
+/// Abnormal examples in RFC 3986 section 5.4.2, resolved in each mode.
+#[test]
+fn rfc3986_abnormal_examples_by_mode() {
+    // [(reference, strict, legacy, climbs_above_root)]
+    const CASES: &[(&str, &str, &str, bool)] = &[
+        ("../../../g", "http://a/g", "http://a/g", true),
+        ("../../../../g", "http://a/g", "http://a/g", true),
+        ("/./g", "http://a/g", "http://a/g", false),
+        ("/../g", "http://a/g", "http://a/g", true),
+        ("g.", "http://a/b/c/g.", "http://a/b/c/g.", false),
+        (".g", "http://a/b/c/.g", "http://a/b/c/.g", false),
+        ("g..", "http://a/b/c/g..", "http://a/b/c/g..", false),
+        ("..g", "http://a/b/c/..g", "http://a/b/c/..g", false),
+        ("./../g", "http://a/b/g", "http://a/b/g", false),
+        ("./g/.", "http://a/b/c/g/", "http://a/b/c/g/", false),
+        ("g/./h", "http://a/b/c/g/h", "http://a/b/c/g/h", false),
+        ("g/../h", "http://a/b/c/h", "http://a/b/c/h", false),
+        (
+            "g;x=1/./y",
+            "http://a/b/c/g;x=1/y",
+            "http://a/b/c/g;x=1/y",
+            false,
+        ),
+        ("g;x=1/../y", "http://a/b/c/y", "http://a/b/c/y", false),
+        (
+            "g?y/./x",
+            "http://a/b/c/g?y/./x",
+            "http://a/b/c/g?y/./x",
+            false,
+        ),
+        (
+            "g?y/../x",
+            "http://a/b/c/g?y/../x",
+            "http://a/b/c/g?y/../x",
+            false,
+        ),
+        (
+            "g#s/./x",
+            "http://a/b/c/g#s/./x",
+            "http://a/b/c/g#s/./x",
+            false,
+        ),
+        (
+            "g#s/../x",
+            "http://a/b/c/g#s/../x",
+            "http://a/b/c/g#s/../x",
+            false,
+        ),
+        ("http:g", "http:g", "http://a/b/c/g", false),
+    ];
+
+    let base = IriAbsoluteStr::new("http://a/b/c/d;p?q").expect("should be valid base IRI");
+    let strict = FixedBaseResolver::new(base);
+    let legacy = FixedBaseResolver::new(base).with_mode(ResolutionMode::Legacy);
+    for &(target, strict_expected, legacy_expected, climbs) in CASES {
+        let target = IriReferenceStr::new(target).expect("should be valid IRI reference");
+        assert_eq_display!(strict.resolve(target), strict_expected, "target={target:?}");
+        assert_eq_display!(legacy.resolve(target), legacy_expected, "target={target:?}");
+
+        match strict.try_resolve(target) {
+            Ok(resolved) => {
+                assert!(!climbs, "target={target:?}");
+                assert_eq_display!(resolved, strict_expected, "target={target:?}");
+            }
+            Err(e) => {
+                assert!(climbs, "target={target:?}");
+                assert_eq!(e, ResolveError::PathAboveRoot, "target={target:?}");
+            }
+        }
+        // The legacy mode silently discards the excess `..` segments.
+        let resolved = legacy
+            .try_resolve(target)
+            .expect("the legacy mode accepts excess `..`");
+        assert_eq_display!(resolved, legacy_expected, "target={target:?}");
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn same_result_as_reference_impl() {
@@ -472,3 +608,231 @@ fn iri_resolution_against_self_with_normalization() {
         );
     }
 }
+
+#[test]
+fn fixed_base_resolver_try_new() {
+    for base in ["http://example.com/", "scheme:", "foo:bar?query"] {
+        let base = IriReferenceStr::new(base).expect("should be valid IRI reference");
+        let resolver = FixedBaseResolver::try_new(base).expect("should be absolute IRI");
+        assert_eq!(resolver.base(), base);
+    }
+    for base in ["", "//example.com/", "/path", "http://example.com/#frag"] {
+        let base = IriReferenceStr::new(base).expect("should be valid IRI reference");
+        assert_eq!(
+            FixedBaseResolver::try_new(base).err(),
+            Some(ResolveError::BaseNotAbsolute),
+            "base={base:?}"
+        );
+    }
+}
+
+#[test]
+fn fixed_base_resolver_try_resolve() {
+    // [(base, reference, strict result, legacy result)]
+    #[allow(clippy::type_complexity)]
+    const CASES: &[(
+        &str,
+        &str,
+        Result<&str, ResolveError>,
+        Result<&str, ResolveError>,
+    )] = &[
+        (
+            "http://a/b/c/d;p?q",
+            "../g",
+            Ok("http://a/b/g"),
+            Ok("http://a/b/g"),
+        ),
+        (
+            "http://a/b/c/d;p?q",
+            "../../g",
+            Ok("http://a/g"),
+            Ok("http://a/g"),
+        ),
+        (
+            "http://a/b/c/d;p?q",
+            "../../../g",
+            Err(ResolveError::PathAboveRoot),
+            Ok("http://a/g"),
+        ),
+        (
+            "http://a/b/c/d;p?q",
+            "/./g/%2e%2E/../h",
+            Err(ResolveError::PathAboveRoot),
+            Ok("http://a/h"),
+        ),
+        (
+            "http://a/b/c/d;p?q",
+            "g/../..",
+            Ok("http://a/b/"),
+            Ok("http://a/b/"),
+        ),
+        (
+            "http://a/b/c/d;p?q",
+            "",
+            Ok("http://a/b/c/d;p?q"),
+            Ok("http://a/b/c/d;p?q"),
+        ),
+        (
+            "scheme:",
+            "/..//not-a-host",
+            Err(ResolveError::PathAboveRoot),
+            Err(ResolveError::Unrepresentable),
+        ),
+        (
+            "scheme:",
+            ".///not-a-host",
+            Err(ResolveError::Unrepresentable),
+            Err(ResolveError::Unrepresentable),
+        ),
+    ];
+
+    for &(base, reference, strict, legacy) in CASES {
+        let base = IriAbsoluteStr::new(base).expect("should be valid base IRI");
+        let reference = IriReferenceStr::new(reference).expect("should be valid IRI reference");
+        let resolver = FixedBaseResolver::new(base);
+        for (resolver, expected) in [
+            (resolver, strict),
+            (resolver.with_mode(ResolutionMode::Legacy), legacy),
+        ] {
+            let result = resolver.try_resolve(reference);
+            match expected {
+                Ok(expected) => {
+                    let resolved = result.expect("should be resolvable");
+                    assert_eq_display!(resolved, expected, "base={base:?}, ref={reference:?}");
+                }
+                Err(e) => assert_eq!(result.err(), Some(e), "base={base:?}, ref={reference:?}"),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_resolver_same_result_as_fixed_base_resolver() {
+    use iri_string::resolve::Resolver;
+
+    for (base, pairs) in TEST_CASES {
+        let base = IriAbsoluteStr::new(base).expect("should be valid base IRI");
+        let fixed = FixedBaseResolver::new(base);
+        let mut resolver = Resolver::new(base.to_owned()).with_cache(4);
+        assert_eq!(resolver.base(), base);
+        for (target, expected, _) in *pairs {
+            let target = IriReferenceStr::new(target).expect("should be valid IRI reference");
+            let expected_str = fixed.resolve(target).to_dedicated_string();
+            assert_eq!(expected_str, *expected);
+            assert_eq!(resolver.resolve(target).to_dedicated_string(), *expected);
+            // Miss and then hit.
+            assert_eq!(resolver.resolve_cached(target), *expected);
+            assert_eq!(resolver.resolve_cached(target), *expected);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_resolver_cache_eviction() {
+    use iri_string::resolve::Resolver;
+
+    let base = IriAbsoluteString::try_from("http://a/b/c/d;p?q").expect("valid base");
+    let mut resolver = Resolver::new(base).with_cache(2);
+    let g = IriReferenceStr::new("g").expect("valid reference");
+    let h = IriReferenceStr::new("h").expect("valid reference");
+    let i = IriReferenceStr::new("i").expect("valid reference");
+
+    assert_eq!(resolver.resolve_cached(g), "http://a/b/c/g");
+    assert_eq!(resolver.resolve_cached(h), "http://a/b/c/h");
+    assert_eq!(resolver.cache_len(), 2);
+    // `g` is now more recently used than `h`.
+    assert_eq!(resolver.resolve_cached(g), "http://a/b/c/g");
+    // `h` is evicted.
+    assert_eq!(resolver.resolve_cached(i), "http://a/b/c/i");
+    assert_eq!(resolver.cache_len(), 2);
+    assert_eq!(resolver.resolve_cached(h), "http://a/b/c/h");
+    assert_eq!(resolver.cache_len(), 2);
+
+    // Changing the mode clears the cache.
+    let mut resolver = resolver.with_mode(ResolutionMode::Legacy);
+    assert_eq!(resolver.cache_len(), 0);
+    let legacy = IriReferenceStr::new("http:g").expect("valid reference");
+    assert_eq!(resolver.resolve_cached(legacy), "http://a/b/c/g");
+    assert_eq!(
+        resolver
+            .try_resolve(legacy)
+            .map(|r| r.to_dedicated_string()),
+        Ok(IriString::try_from("http://a/b/c/g").expect("valid IRI"))
+    );
+
+    resolver.clear_cache();
+    assert_eq!(resolver.cache_len(), 0);
+
+    // Without cache.
+    let mut resolver = resolver.with_cache(0);
+    assert_eq!(resolver.resolve_cached(g), "http://a/b/c/g");
+    assert_eq!(resolver.cache_len(), 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn document_base_per_html() {
+    use iri_string::resolve::document_base;
+
+    let document = IriStr::new("http://example.com/a/b/page?q#frag").expect("valid IRI");
+    // (base_href, expected).
+    let cases: &[(Option<&str>, &str)] = &[
+        (None, "http://example.com/a/b/page?q"),
+        (Some(""), "http://example.com/a/b/page?q"),
+        (Some("#x"), "http://example.com/a/b/page?q"),
+        (Some("?other"), "http://example.com/a/b/page?other"),
+        (Some("/root/"), "http://example.com/root/"),
+        (
+            Some("\t//cdn.example.net/x\r\n"),
+            "http://cdn.example.net/x",
+        ),
+        (Some("https://cdn.example.net"), "https://cdn.example.net"),
+        (Some("a b"), "http://example.com/a/b/page?q"),
+        (Some("DATA:text/plain,x"), "http://example.com/a/b/page?q"),
+        (Some("JavaScript:void(0)"), "http://example.com/a/b/page?q"),
+        (Some("mailto:user@example.com"), "mailto:user@example.com"),
+    ];
+    for &(base_href, expected) in cases {
+        assert_eq!(
+            document_base(document, base_href),
+            expected,
+            "base_href={:?}",
+            base_href
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn join_matches_resolve() {
+    let base = UriStr::new("http://a/b/c/d;p?q#frag").expect("valid URI");
+    for (reference, expected) in [
+        ("g", "http://a/b/c/g"),
+        ("./g/", "http://a/b/c/g/"),
+        ("?y", "http://a/b/c/d;p?y"),
+        ("#s", "http://a/b/c/d;p?q#s"),
+        ("", "http://a/b/c/d;p?q"),
+        ("../../../g", "http://a/g"),
+        ("g:h", "g:h"),
+    ] {
+        assert_eq!(
+            base.join(reference).expect("valid reference"),
+            expected,
+            "reference={:?}",
+            reference
+        );
+        assert_eq!(
+            base.to_absolute().join(reference).expect("valid reference"),
+            expected,
+            "reference={:?}",
+            reference
+        );
+    }
+    assert!(base.join("%zz").is_err());
+
+    // Owned strings can be used via `Deref`.
+    let owned = UriString::try_from("http://a/b/").expect("valid URI");
+    assert_eq!(owned.join("c").expect("valid reference"), "http://a/b/c");
+}
@@ -0,0 +1,58 @@
+//! Tests for `rkyv` integration.
+#![cfg(feature = "rkyv")]
+
+use rkyv::{check_archived_root, Deserialize, Infallible};
+
+use iri_string::types::{
+    ArchivedRiString, CheckArchivedError, IriQueryString, IriStr, IriString, UriReferenceStr,
+    UriReferenceString,
+};
+
+#[test]
+fn roundtrip() {
+    let iri: IriString = IriStr::new("https://user@example.com/\u{03B1}?q#f")
+        .expect("should be valid")
+        .into();
+    let bytes = rkyv::to_bytes::<_, 256>(&iri).expect("should be serializable");
+
+    let archived: &ArchivedRiString<_> =
+        check_archived_root::<IriString>(&bytes).expect("should be valid archive");
+    assert_eq!(archived.as_str(), iri.as_str());
+    assert_eq!(*archived, iri);
+    // Deref to the borrowed IRI type.
+    assert_eq!(archived.scheme_str(), "https");
+    assert_eq!(archived.path_str(), "/\u{03B1}");
+
+    let deserialized: IriString = archived
+        .deserialize(&mut Infallible)
+        .expect("should be deserializable");
+    assert_eq!(deserialized, iri);
+}
+
+#[test]
+fn roundtrip_other_types() {
+    let reference: UriReferenceString = UriReferenceStr::new("../foo?bar")
+        .expect("should be valid")
+        .into();
+    let bytes = rkyv::to_bytes::<_, 256>(&reference).expect("should be serializable");
+    let archived =
+        check_archived_root::<UriReferenceString>(&bytes).expect("should be valid archive");
+    assert_eq!(archived.as_str(), "../foo?bar");
+    assert_eq!(archived.path_str(), "../foo");
+}
+
+#[test]
+fn validated_on_access() {
+    let bytes =
+        rkyv::to_bytes::<_, 256>(&String::from("not an IRI")).expect("should be serializable");
+    let err = check_archived_root::<IriString>(&bytes).expect_err("should be invalid IRI");
+    assert!(matches!(
+        err,
+        rkyv::validation::CheckArchiveError::CheckBytesError(CheckArchivedError::Validation(_))
+    ));
+
+    // Valid as a query, but invalid as an IRI.
+    let bytes = rkyv::to_bytes::<_, 256>(&String::from("a=b")).expect("should be serializable");
+    assert!(check_archived_root::<IriQueryString>(&bytes).is_ok());
+    assert!(check_archived_root::<IriString>(&bytes).is_err());
+}
@@ -0,0 +1,51 @@
+//! Tests for `schemars` integration.
+#![cfg(feature = "schemars")]
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SingleOrVec};
+use schemars::{schema_for, JsonSchema};
+
+use iri_string::types::*;
+
+fn assert_string_format<T: JsonSchema>(format: Option<&str>) {
+    let schema = schema_for!(T).schema;
+    assert_eq!(
+        schema.instance_type,
+        Some(SingleOrVec::Single(Box::new(InstanceType::String)))
+    );
+    assert_eq!(schema.format.as_deref(), format);
+}
+
+#[test]
+fn formats() {
+    assert_string_format::<IriString>(Some("iri"));
+    assert_string_format::<IriAbsoluteString>(Some("iri"));
+    assert_string_format::<IriReferenceString>(Some("iri-reference"));
+    assert_string_format::<IriRelativeString>(Some("iri-reference"));
+    assert_string_format::<IriQueryString>(None);
+    assert_string_format::<IriFragmentString>(None);
+    assert_string_format::<UriString>(Some("uri"));
+    assert_string_format::<UriAbsoluteString>(Some("uri"));
+    assert_string_format::<UriReferenceString>(Some("uri-reference"));
+    assert_string_format::<UriRelativeString>(Some("uri-reference"));
+    assert_string_format::<UriQueryString>(None);
+    assert_string_format::<UriFragmentString>(None);
+}
+
+#[test]
+fn borrowed_types() {
+    assert_string_format::<&IriStr>(Some("iri"));
+    assert_string_format::<&UriReferenceStr>(Some("uri-reference"));
+}
+
+#[test]
+fn not_referenced_in_fields() {
+    let mut gen = SchemaGenerator::default();
+    let schema = match gen.subschema_for::<UriReferenceString>() {
+        Schema::Object(v) => v,
+        Schema::Bool(_) => panic!("unexpected bool schema"),
+    };
+    assert!(!schema.is_ref());
+    assert_eq!(schema.format.as_deref(), Some("uri-reference"));
+    assert!(gen.definitions().is_empty());
+}
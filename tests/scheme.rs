@@ -0,0 +1,259 @@
+//! Tests for scheme-specific parser registry.
+#![cfg(feature = "alloc")]
+
+use iri_string::scheme::{self, Error, SchemeRegistry, SchemeSpecific};
+use iri_string::spec::UriSpec;
+use iri_string::types::{UriStr, UriString};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Urn {
+    nid: String,
+    nss: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct FilePath(String);
+
+fn parse_urn(iri: &UriStr) -> Result<Box<dyn SchemeSpecific>, Error> {
+    let (nid, nss) = iri.path_str().split_once(':').ok_or_else(Error::new)?;
+    Ok(Box::new(Urn {
+        nid: nid.to_owned(),
+        nss: nss.to_owned(),
+    }))
+}
+
+fn registry() -> SchemeRegistry<UriSpec> {
+    let mut registry = SchemeRegistry::new();
+    assert!(registry.register("urn", parse_urn).is_none());
+    assert!(registry
+        .register(
+            "FILE",
+            |iri: &UriStr| -> Result<Box<dyn SchemeSpecific>, Error> {
+                Ok(Box::new(FilePath(iri.path_str().to_owned())))
+            }
+        )
+        .is_none());
+    registry
+}
+
+#[test]
+fn lookup_is_case_insensitive() {
+    let registry = registry();
+    assert!(registry.contains("urn"));
+    assert!(registry.contains("URN"));
+    assert!(registry.contains("file"));
+    assert!(!registry.contains("mailto"));
+    assert_eq!(registry.schemes().collect::<Vec<_>>(), ["file", "urn"]);
+}
+
+#[test]
+fn dispatch_by_scheme() {
+    let registry = registry();
+
+    let urn = UriStr::new("Urn:ietf:rfc:8141").expect("valid URI");
+    let parsed = urn
+        .parse_scheme_specific(&registry)
+        .expect("registered")
+        .expect("valid URN");
+    assert!(parsed.is::<Urn>());
+    assert_eq!(
+        parsed.downcast_ref::<Urn>(),
+        Some(&Urn {
+            nid: "ietf".to_owned(),
+            nss: "rfc:8141".to_owned(),
+        })
+    );
+    assert_eq!(parsed.downcast_ref::<FilePath>(), None);
+
+    let file: UriString = "file:///etc/hosts".parse().expect("valid URI");
+    let parsed = registry
+        .parse(&file)
+        .expect("registered")
+        .expect("valid file URI");
+    assert_eq!(
+        parsed.downcast_ref::<FilePath>(),
+        Some(&FilePath("/etc/hosts".to_owned()))
+    );
+}
+
+#[test]
+fn handler_errors_and_unknown_schemes() {
+    let registry = registry();
+
+    let invalid_urn = UriStr::new("urn:no-nss").expect("valid URI");
+    assert!(invalid_urn
+        .parse_scheme_specific(&registry)
+        .expect("registered")
+        .is_err());
+
+    let unknown = UriStr::new("magnet:?xt=urn:btih:0000").expect("valid URI");
+    assert!(unknown.parse_scheme_specific(&registry).is_none());
+}
+
+#[test]
+fn replace_and_unregister() {
+    let mut registry = registry();
+    assert!(registry.register("Urn", parse_urn).is_some());
+    assert!(registry.unregister("URN").is_some());
+    assert!(!registry.contains("urn"));
+    assert!(registry.unregister("urn").is_none());
+}
+
+#[test]
+fn well_known_normalizations() {
+    let registry = SchemeRegistry::<UriSpec>::new().with_well_known_normalizations();
+    // (input, expected).
+    const CASES: &[(&str, &str)] = &[
+        ("http://example.com", "http://example.com/"),
+        ("HTTP://example.com:80/a", "http://example.com/a"),
+        ("https://example.com:443?q", "https://example.com/?q"),
+        ("https://example.com:80/", "https://example.com:80/"),
+        ("ws://example.com:80/chat", "ws://example.com/chat"),
+        ("wss://example.com:443#f", "wss://example.com/#f"),
+        ("wss://example.com:80/", "wss://example.com:80/"),
+        ("ftp://user@example.com:21", "ftp://user@example.com/"),
+        ("gopher://example.com:70/1", "gopher://example.com/1"),
+        ("http://example.com:/", "http://example.com/"),
+        ("http://example.com:080/", "http://example.com:080/"),
+        ("http:opaque", "http:opaque"),
+        ("mailto:user@example.com", "mailto:user@example.com"),
+    ];
+    for &(input, expected) in CASES {
+        let iri = UriStr::new(input).expect("valid URI");
+        assert_eq!(registry.normalize(iri), expected, "input={:?}", input);
+    }
+}
+
+#[test]
+fn custom_normalizations() {
+    use iri_string::scheme::SchemeNormalization;
+
+    let mut registry = SchemeRegistry::<UriSpec>::new();
+    let rule = SchemeNormalization::new().default_port(Some(6379));
+    assert_eq!(registry.register_normalization("Redis", rule), None);
+    assert_eq!(registry.normalization("REDIS"), Some(rule));
+
+    let iri = UriStr::new("redis://example.com:6379").expect("valid URI");
+    assert_eq!(registry.normalize(iri), "redis://example.com");
+    // No rules for `http` in this registry.
+    let iri = UriStr::new("http://example.com:80").expect("valid URI");
+    assert_eq!(registry.normalize(iri), "http://example.com:80");
+
+    assert_eq!(registry.unregister_normalization("redis"), Some(rule));
+    assert_eq!(registry.normalization("redis"), None);
+}
+
+/// Parses the IRI with the built-in parsers, and downcasts the result.
+fn parse_builtin<T: SchemeSpecific>(s: &str) -> Result<Box<dyn SchemeSpecific>, Error> {
+    let registry = SchemeRegistry::<UriSpec>::new().with_builtin_parsers();
+    let uri = UriStr::new(s).expect("valid URI");
+    let parsed = registry.parse(uri).expect("built-in scheme")?;
+    assert!(
+        parsed.is::<T>(),
+        "parsed to the unexpected type: {:?}",
+        parsed
+    );
+    Ok(parsed)
+}
+
+#[test]
+fn builtin_parsers_lookup() {
+    let registry = SchemeRegistry::<UriSpec>::new().with_builtin_parsers();
+    for scheme in ["data", "mailto", "file", "urn", "magnet"] {
+        assert!(registry.contains(scheme), "scheme={:?}", scheme);
+        assert!(registry.get(scheme).is_some(), "scheme={:?}", scheme);
+        assert!(
+            registry.contains(&scheme.to_ascii_uppercase()),
+            "scheme={:?}",
+            scheme
+        );
+    }
+    assert!(!registry.contains("http"));
+    assert_eq!(
+        registry.schemes().collect::<Vec<_>>(),
+        ["data", "file", "magnet", "mailto", "urn"]
+    );
+}
+
+#[test]
+fn builtin_data() {
+    let parsed = parse_builtin::<scheme::DataIri>("data:,A%20brief%20note").expect("valid");
+    let data = parsed.downcast_ref::<scheme::DataIri>().expect("data");
+    assert_eq!(data.media_type(), "text/plain;charset=US-ASCII");
+    assert!(!data.is_base64());
+    assert_eq!(data.data_str(), "A%20brief%20note");
+
+    let parsed =
+        parse_builtin::<scheme::DataIri>("data:image/gif;BASE64,R0lGOD?lh#frag").expect("valid");
+    let data = parsed.downcast_ref::<scheme::DataIri>().expect("data");
+    assert_eq!(data.media_type(), "image/gif");
+    assert!(data.is_base64());
+    assert_eq!(data.data_str(), "R0lGOD?lh");
+
+    assert!(parse_builtin::<scheme::DataIri>("data:text/plain").is_err());
+    assert!(parse_builtin::<scheme::DataIri>("data:text,foo").is_err());
+}
+
+#[test]
+fn builtin_mailto() {
+    let parsed =
+        parse_builtin::<scheme::MailtoIri>("mailto:a@example.com,b@example.org?subject=hi")
+            .expect("valid");
+    let mailto = parsed.downcast_ref::<scheme::MailtoIri>().expect("mailto");
+    assert_eq!(mailto.addresses(), ["a@example.com", "b@example.org"]);
+
+    assert!(parse_builtin::<scheme::MailtoIri>("mailto:not-an-address").is_err());
+}
+
+#[test]
+fn builtin_file() {
+    let parsed = parse_builtin::<scheme::FileIri>("file:///etc/hosts").expect("valid");
+    let file = parsed.downcast_ref::<scheme::FileIri>().expect("file");
+    assert_eq!(file.host(), None);
+    assert_eq!(file.path_str(), "/etc/hosts");
+
+    let parsed = parse_builtin::<scheme::FileIri>("file://server/share/a.txt").expect("valid");
+    let file = parsed.downcast_ref::<scheme::FileIri>().expect("file");
+    assert_eq!(file.host(), Some("server"));
+    assert_eq!(file.path_str(), "/share/a.txt");
+
+    let parsed = parse_builtin::<scheme::FileIri>("file:/tmp").expect("valid");
+    let file = parsed.downcast_ref::<scheme::FileIri>().expect("file");
+    assert_eq!(file.host(), None);
+    assert_eq!(file.path_str(), "/tmp");
+
+    assert!(parse_builtin::<scheme::FileIri>("file:relative").is_err());
+    assert!(parse_builtin::<scheme::FileIri>("file://host:8080/a").is_err());
+}
+
+#[test]
+fn builtin_urn() {
+    let parsed = parse_builtin::<scheme::Urn>("urn:isbn:0451450523").expect("valid");
+    let urn = parsed.downcast_ref::<scheme::Urn>().expect("urn");
+    assert_eq!(urn.nid(), "isbn");
+    assert_eq!(urn.nss(), "0451450523");
+
+    assert!(parse_builtin::<scheme::Urn>("urn:a:foo").is_err());
+    assert!(parse_builtin::<scheme::Urn>("urn:-ab:foo").is_err());
+    assert!(parse_builtin::<scheme::Urn>("urn:ab-:foo").is_err());
+    assert!(parse_builtin::<scheme::Urn>("urn:isbn:").is_err());
+    assert!(parse_builtin::<scheme::Urn>("urn:isbn").is_err());
+}
+
+#[test]
+fn builtin_magnet() {
+    let parsed = parse_builtin::<scheme::MagnetLink>(
+        "magnet:?xt=urn:btih:c12fe1c06bba254a9dc9f519b335aa7c1367a88a&dn=foo&tr=udp://a&tr=udp://b",
+    )
+    .expect("valid");
+    let magnet = parsed.downcast_ref::<scheme::MagnetLink>().expect("magnet");
+    assert_eq!(
+        magnet.exact_topics(),
+        ["urn:btih:c12fe1c06bba254a9dc9f519b335aa7c1367a88a"]
+    );
+    assert_eq!(magnet.display_name(), Some("foo"));
+    assert_eq!(magnet.trackers(), ["udp://a", "udp://b"]);
+
+    assert!(parse_builtin::<scheme::MagnetLink>("magnet:?dn=foo").is_err());
+    assert!(parse_builtin::<scheme::MagnetLink>("magnet:foo?xt=bar").is_err());
+}
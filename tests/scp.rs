@@ -0,0 +1,117 @@
+//! Tests for SCP-like remote strings.
+
+use iri_string::scp::ScpLike;
+#[cfg(feature = "alloc")]
+use iri_string::scp::{scp_to_ssh, RelativePath};
+#[cfg(feature = "alloc")]
+use iri_string::types::{IriString, UriString};
+
+// [(input, user, host, path)]
+const RECOGNIZED_CASES: &[(&str, Option<&str>, &str, &str)] = &[
+    (
+        "git@github.com:owner/repo.git",
+        Some("git"),
+        "github.com",
+        "owner/repo.git",
+    ),
+    ("example.com:repo", None, "example.com", "repo"),
+    ("host:/srv/git/repo.git", None, "host", "/srv/git/repo.git"),
+    ("me@host:~/repo.git", Some("me"), "host", "~/repo.git"),
+    ("a@b@host:repo", Some("a@b"), "host", "repo"),
+    ("user@[::1]:repo", Some("user"), "[::1]", "repo"),
+    ("[2001:db8::1]:/repo", None, "[2001:db8::1]", "/repo"),
+    ("host:a:b", None, "host", "a:b"),
+    ("host:dir/with space", None, "host", "dir/with space"),
+];
+
+const NOT_RECOGNIZED_CASES: &[&str] = &[
+    "",
+    "repo.git",
+    "./repo:git",
+    "/abs/path:with/colon",
+    "dir/host:path",
+    "ssh://git@example.com/repo.git",
+    "https://example.com/repo.git",
+    "file:///srv/repo.git",
+    "C:repo",
+    "c:\\repo",
+    "@host:repo",
+    "host:",
+    ":repo",
+    "user@:repo",
+    "[::1]",
+    "[::1]repo",
+    "[not-ipv6]:repo",
+];
+
+#[test]
+fn recognize() {
+    for &(input, user, host, path) in RECOGNIZED_CASES {
+        let scp = ScpLike::parse(input).unwrap_or_else(|| panic!("{input:?}"));
+        assert_eq!(scp.user(), user, "{input:?}");
+        assert_eq!(scp.host(), host, "{input:?}");
+        assert_eq!(scp.path(), path, "{input:?}");
+    }
+}
+
+#[test]
+fn not_recognized() {
+    for &input in NOT_RECOGNIZED_CASES {
+        assert_eq!(ScpLike::parse(input), None, "{input:?}");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn convert() {
+    // [(input, from root, from home)]
+    const CASES: &[(&str, &str, &str)] = &[
+        (
+            "git@github.com:owner/repo.git",
+            "ssh://git@github.com/owner/repo.git",
+            "ssh://git@github.com/~/owner/repo.git",
+        ),
+        (
+            "host:/srv/git/repo.git",
+            "ssh://host/srv/git/repo.git",
+            "ssh://host/srv/git/repo.git",
+        ),
+        (
+            "host:~user/repo.git",
+            "ssh://host/~user/repo.git",
+            "ssh://host/~user/repo.git",
+        ),
+        (
+            "a@b@[::1]:x",
+            "ssh://a%40b@[::1]/x",
+            "ssh://a%40b@[::1]/~/x",
+        ),
+        (
+            "host:a%b?c#d e",
+            "ssh://host/a%25b%3Fc%23d%20e",
+            "ssh://host/~/a%25b%3Fc%23d%20e",
+        ),
+        // The path is not parsed as an authority.
+        ("host:a:b//c", "ssh://host/a:b//c", "ssh://host/~/a:b//c"),
+    ];
+    for &(input, from_root, from_home) in CASES {
+        let uri: UriString = scp_to_ssh(input, RelativePath::FromRoot).expect(input);
+        assert_eq!(uri, from_root);
+        let uri: UriString = scp_to_ssh(input, RelativePath::FromHome).expect(input);
+        assert_eq!(uri, from_home);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn convert_non_ascii() {
+    let input = "\u{E9}@h\u{F4}te:d\u{E9}p\u{F4}t.git";
+    let iri: IriString = scp_to_ssh(input, RelativePath::FromRoot).expect(input);
+    assert_eq!(iri, "ssh://\u{E9}@h\u{F4}te/d\u{E9}p\u{F4}t.git");
+    let uri: UriString = scp_to_ssh(input, RelativePath::FromRoot).expect(input);
+    assert_eq!(uri, "ssh://%C3%A9@h%C3%B4te/d%C3%A9p%C3%B4t.git");
+    assert_eq!(
+        uri.authority_components().map(|a| a.host()),
+        Some("h%C3%B4te")
+    );
+}
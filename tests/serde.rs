@@ -24,7 +24,7 @@ macro_rules! define_tests {
                 let s = <$slice>::new(raw).expect("Should not fail: valid string");
                 assert_tokens(&s, &[Token::BorrowedStr(raw)]);
 
-                #[cfg(any(feature = "serde-alloc", feature = "serde-std"))]
+                #[cfg(feature = "alloc")]
                 {
                     let s = s.to_owned();
                     assert_tokens(&s, &[Token::BorrowedStr(raw)]);
@@ -97,3 +97,74 @@ define_tests! {
     IriRelativeStr,
     IriRelativeString,
 }
+
+#[test]
+fn components_ser() {
+    use iri_string::components::ComponentsSer;
+    use serde_test::assert_ser_tokens;
+
+    let iri = IriReferenceStr::new("https://user@example.com:8080/a?#f")
+        .expect("Should not fail: valid string");
+    assert_ser_tokens(
+        &ComponentsSer::new(iri),
+        &[
+            Token::Struct {
+                name: "Components",
+                len: 6,
+            },
+            Token::Str("iri"),
+            Token::Str("https://user@example.com:8080/a?#f"),
+            Token::Str("scheme"),
+            Token::Some,
+            Token::Str("https"),
+            Token::Str("authority"),
+            Token::Some,
+            Token::Struct {
+                name: "Authority",
+                len: 3,
+            },
+            Token::Str("userinfo"),
+            Token::Some,
+            Token::Str("user"),
+            Token::Str("host"),
+            Token::Str("example.com"),
+            Token::Str("port"),
+            Token::Some,
+            Token::Str("8080"),
+            Token::StructEnd,
+            Token::Str("path"),
+            Token::Str("/a"),
+            Token::Str("query"),
+            Token::Some,
+            Token::Str(""),
+            Token::Str("fragment"),
+            Token::Some,
+            Token::Str("f"),
+            Token::StructEnd,
+        ],
+    );
+
+    let iri = IriReferenceStr::new("../a").expect("Should not fail: valid string");
+    assert_ser_tokens(
+        &ComponentsSer::from(iri),
+        &[
+            Token::Struct {
+                name: "Components",
+                len: 6,
+            },
+            Token::Str("iri"),
+            Token::Str("../a"),
+            Token::Str("scheme"),
+            Token::None,
+            Token::Str("authority"),
+            Token::None,
+            Token::Str("path"),
+            Token::Str("../a"),
+            Token::Str("query"),
+            Token::None,
+            Token::Str("fragment"),
+            Token::None,
+            Token::StructEnd,
+        ],
+    );
+}
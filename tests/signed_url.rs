@@ -0,0 +1,128 @@
+//! Tests for signed IRIs.
+#![cfg(feature = "hmac")]
+
+use iri_string::signed_url::{UrlSigner, VerifyError};
+use iri_string::types::{IriStr, UriStr, UriString};
+
+const KEY: &[u8] = b"secret key";
+const EXPIRES: u64 = 1_700_000_000;
+
+fn signed() -> UriString {
+    let uri =
+        UriStr::new("HTTPS://Example.com/files/report.pdf?download=1#page=2").expect("valid URI");
+    UrlSigner::new(KEY).sign(uri, EXPIRES).expect("signable")
+}
+
+#[test]
+fn sign_canonical() {
+    assert_eq!(
+        signed(),
+        "https://example.com/files/report.pdf?download=1&expires=1700000000\
+         &signature=6546f0056dc143ae06a444a058444385efbe5fe1ab17d9c65359dea5c934726b#page=2"
+    );
+
+    let uri = UriStr::new("https://example.com").expect("valid URI");
+    let signed = UrlSigner::new(KEY).sign(uri, 0).expect("signable");
+    assert!(signed
+        .as_str()
+        .starts_with("https://example.com?expires=0&signature="));
+    assert_eq!(UrlSigner::new(KEY).verify(&signed, 0), Ok(()));
+}
+
+#[test]
+fn sign_conflicting_params() {
+    let signer = UrlSigner::new(KEY);
+    let uri = UriStr::new("https://example.com/?expires=1").expect("valid URI");
+    assert!(signer.sign(uri, EXPIRES).is_err());
+    let uri = UriStr::new("https://example.com/?a&signature").expect("valid URI");
+    assert!(signer.sign(uri, EXPIRES).is_err());
+
+    let uri = UriStr::new("https://example.com/").expect("valid URI");
+    assert!(signer.clone().expires_param("").sign(uri, EXPIRES).is_err());
+    assert!(signer
+        .clone()
+        .signature_param("a=b")
+        .sign(uri, EXPIRES)
+        .is_err());
+    assert!(signer
+        .expires_param("same")
+        .signature_param("same")
+        .sign(uri, EXPIRES)
+        .is_err());
+}
+
+#[test]
+fn verify_roundtrip() {
+    let signer = UrlSigner::new(KEY);
+    let signed = signed();
+    assert_eq!(signer.verify(&signed, EXPIRES), Ok(()));
+    assert_eq!(
+        signer.verify(&signed, EXPIRES + 1),
+        Err(VerifyError::Expired)
+    );
+
+    // The fragment is not signed.
+    let mut other_fragment = signed.clone();
+    other_fragment.set_fragment(None);
+    assert_eq!(signer.verify(&other_fragment, EXPIRES), Ok(()));
+
+    // Equivalent forms are accepted.
+    let denormalized = UriStr::new(
+        "HTTPS://EXAMPLE.com/files/%72eport.pdf?download=1&expires=1700000000\
+         &signature=6546f0056dc143ae06a444a058444385efbe5fe1ab17d9c65359dea5c934726b",
+    )
+    .expect("valid URI");
+    assert_eq!(signer.verify(denormalized, EXPIRES), Ok(()));
+
+    let iri = IriStr::new("https://example.com/\u{3042}?q=\u{3044}").expect("valid IRI");
+    let signed = signer.sign(iri, EXPIRES).expect("signable");
+    assert_eq!(signer.verify(&signed, EXPIRES), Ok(()));
+}
+
+#[test]
+fn verify_tampered() {
+    let signer = UrlSigner::new(KEY);
+    let signed = signed();
+    let tamper = |from: &str, to: &str| {
+        let s = signed.as_str().replacen(from, to, 1);
+        let uri = UriStr::new(&s).expect("valid URI");
+        signer.verify(uri, EXPIRES)
+    };
+    assert_eq!(
+        tamper("download=1", "download=2"),
+        Err(VerifyError::InvalidSignature)
+    );
+    assert_eq!(
+        tamper("1700000000", "1800000000"),
+        Err(VerifyError::InvalidSignature)
+    );
+    assert_eq!(
+        tamper("report.pdf", "secret.pdf"),
+        Err(VerifyError::InvalidSignature)
+    );
+    assert_eq!(tamper("=6546", "=7546"), Err(VerifyError::InvalidSignature));
+    assert_eq!(tamper("=6546", "=654"), Err(VerifyError::Malformed));
+    assert_eq!(tamper("=6546", "=zz46"), Err(VerifyError::Malformed));
+    assert_eq!(tamper("expires=", "expiry="), Err(VerifyError::Malformed));
+    assert_eq!(tamper("&signature", "&sig"), Err(VerifyError::Malformed));
+    assert_eq!(tamper("=1700000000", "=-1"), Err(VerifyError::Malformed));
+    assert_eq!(
+        tamper("download=1&expires", "expires"),
+        Err(VerifyError::InvalidSignature)
+    );
+
+    assert_eq!(
+        UrlSigner::new(b"other key").verify(&signed, EXPIRES),
+        Err(VerifyError::InvalidSignature)
+    );
+    let unsigned = UriStr::new("https://example.com/?signature=00").expect("valid URI");
+    assert_eq!(
+        signer.verify(unsigned, EXPIRES),
+        Err(VerifyError::Malformed)
+    );
+    let unsigned = UriStr::new("https://example.com/").expect("valid URI");
+    assert_eq!(
+        signer.verify(unsigned, EXPIRES),
+        Err(VerifyError::Malformed)
+    );
+}
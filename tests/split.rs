@@ -0,0 +1,58 @@
+//! Tests for low-level splitters.
+
+mod utils;
+
+use iri_string::parser::split::{split_iri_reference, split_userinfo_host_port};
+use iri_string::types::IriReferenceStr;
+
+/// Splitters should give the same results as the typed accessors for valid IRI references.
+#[test]
+fn consistent_with_typed_accessors() {
+    for kind in [
+        utils::Kind::Normal,
+        utils::Kind::Absolute,
+        utils::Kind::Reference,
+        utils::Kind::Relative,
+    ] {
+        for raw in utils::positive(utils::Spec::Iri, kind) {
+            let iri = IriReferenceStr::new(raw).expect("should be valid IRI reference");
+            let (scheme, authority, path, query, fragment) = split_iri_reference(raw);
+            assert_eq!(scheme, iri.scheme_str(), "raw={:?}", raw);
+            assert_eq!(authority, iri.authority_str(), "raw={:?}", raw);
+            assert_eq!(path, iri.path_str(), "raw={:?}", raw);
+            assert_eq!(query, iri.query().map(|q| q.as_str()), "raw={:?}", raw);
+            assert_eq!(
+                fragment,
+                iri.fragment().map(|f| f.as_str()),
+                "raw={:?}",
+                raw
+            );
+
+            if let Some(components) = iri.authority_components() {
+                assert_eq!(
+                    split_userinfo_host_port(authority.expect("authority exists")),
+                    (components.userinfo(), components.host(), components.port()),
+                    "raw={:?}",
+                    raw
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn invalid_strings() {
+    assert_eq!(
+        split_iri_reference("ht tp://a b/c d?e f#g#h"),
+        (Some("ht tp"), Some("a b"), "/c d", Some("e f"), Some("g#h"))
+    );
+    assert_eq!(
+        split_iri_reference("://x"),
+        (None, None, "://x", None, None)
+    );
+    assert_eq!(split_iri_reference(""), (None, None, "", None, None));
+    assert_eq!(
+        split_userinfo_host_port("a@b@c:1:2"),
+        (Some("a"), "b@c:1", Some("2"))
+    );
+}
@@ -0,0 +1,87 @@
+//! Tests for typo suggestions.
+#![cfg(feature = "alloc")]
+
+use iri_string::spec::{IriSpec, UriSpec};
+use iri_string::suggest::{apply, fix, suggest, SuggestionKind};
+
+#[test]
+fn suggestions() {
+    use SuggestionKind::*;
+
+    // [(input, kinds, fixed)]
+    const CASES: &[(&str, &[SuggestionKind], Option<&str>)] = &[
+        ("https://example.com/", &[], Some("https://example.com/")),
+        (
+            "https:example.com",
+            &[MissingSlashes],
+            Some("https://example.com"),
+        ),
+        (
+            "HTTP:/example.com",
+            &[MissingSlashes],
+            Some("HTTP://example.com"),
+        ),
+        ("urn:example:a", &[], Some("urn:example:a")),
+        (
+            "mailto:a b@example.com",
+            &[Space],
+            Some("mailto:a%20b@example.com"),
+        ),
+        (
+            "http://ex ample.com/a b",
+            &[Space, Space],
+            Some("http://ex%20ample.com/a%20b"),
+        ),
+        ("http://::1/", &[UnbracketedIpv6], Some("http://[::1]/")),
+        (
+            "http://user@fe80::1:8080",
+            &[UnbracketedIpv6],
+            Some("http://user@[fe80::1:8080]"),
+        ),
+        ("http://[::1]:80/", &[], Some("http://[::1]:80/")),
+        ("http://a:b:c/", &[], None),
+        (
+            "http://example.com/?a=1#sec",
+            &[],
+            Some("http://example.com/?a=1#sec"),
+        ),
+        (
+            "http://example.com/?c=#fff&s=2",
+            &[StrayHash],
+            Some("http://example.com/?c=%23fff&s=2"),
+        ),
+        (
+            "http://example.com/#a#b#c",
+            &[StrayHash, StrayHash],
+            Some("http://example.com/#a%23b%23c"),
+        ),
+        (
+            "https:example.com/?q=a b#x=1#",
+            &[MissingSlashes, Space, StrayHash, StrayHash],
+            Some("https://example.com/?q=a%20b%23x=1%23"),
+        ),
+        ("http://example.com/<>", &[], None),
+    ];
+    for (input, kinds, fixed) in CASES.iter().copied() {
+        let suggestions = suggest::<UriSpec>(input);
+        let actual_kinds = suggestions.iter().map(|s| s.kind()).collect::<Vec<_>>();
+        assert_eq!(actual_kinds, kinds, "input={:?}", input);
+        assert_eq!(
+            fix::<UriSpec>(input).map(|iri| iri.to_string()).as_deref(),
+            fixed,
+            "input={:?}",
+            input
+        );
+        if kinds.is_empty() {
+            assert_eq!(apply(input, &suggestions), input);
+        }
+    }
+}
+
+#[test]
+fn suggestions_nonascii() {
+    let input = "https:\u{E9}x.example/\u{E9} \u{E9}";
+    let fixed = fix::<IriSpec>(input).expect("fixable");
+    assert_eq!(fixed, "https://\u{E9}x.example/\u{E9}%20\u{E9}");
+    assert!(fix::<UriSpec>(input).is_none());
+}
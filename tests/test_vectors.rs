@@ -0,0 +1,73 @@
+//! Tests for the bundled test vectors.
+#![cfg(feature = "test-vectors")]
+
+use iri_string::spec::{IriSpec, Spec, UriSpec};
+use iri_string::test_vectors::{check_validation, Kind};
+use iri_string::validate;
+
+/// Validator function.
+type Validator = fn(&str) -> Result<(), validate::Error>;
+
+/// Checks the validators of this crate against the vectors.
+fn check_validators<S: Spec>() {
+    let cases: &[(Kind, Validator)] = &[
+        (Kind::Iri, validate::iri::<S>),
+        (Kind::IriReference, validate::iri_reference::<S>),
+        (Kind::AbsoluteIri, validate::absolute_iri::<S>),
+        (Kind::RelativeRef, validate::relative_ref::<S>),
+    ];
+    for (kind, validator) in cases.iter().copied() {
+        if let Err(e) = check_validation::<S, _>(kind, |s| validator(s).is_ok()) {
+            panic!("{}", e);
+        }
+    }
+}
+
+#[test]
+fn validation() {
+    check_validators::<UriSpec>();
+    check_validators::<IriSpec>();
+}
+
+#[test]
+fn validation_failure() {
+    let e = check_validation::<UriSpec, _>(Kind::IriReference, |_| true)
+        .expect_err("some vectors are invalid");
+    assert!(!e.expected_valid());
+    assert!(!e.vector().is_valid::<UriSpec>(Kind::IriReference));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn resolution() {
+    use iri_string::format::ToDedicatedString;
+    use iri_string::test_vectors::check_resolution;
+    use iri_string::types::{IriAbsoluteStr, IriReferenceStr};
+
+    let result = check_resolution(|base, reference| {
+        let base = IriAbsoluteStr::new(base).ok()?;
+        let reference = IriReferenceStr::new(reference).ok()?;
+        reference
+            .resolve_against(base)
+            .try_to_dedicated_string()
+            .ok()
+    });
+    if let Err(e) = result {
+        panic!("{}", e);
+    }
+
+    let e = check_resolution(|_, _| Some("http://a/")).expect_err("not always `http://a/`");
+    assert_ne!(e.vector().expected(), "http://a/");
+}
+
+#[test]
+fn w3c_vectors() {
+    use iri_string::test_vectors::{resolution_vectors, validation_vectors, Source};
+
+    assert!(validation_vectors()
+        .iter()
+        .any(|v| v.source() == Source::W3c));
+    assert!(resolution_vectors()
+        .iter()
+        .any(|v| v.source() == Source::W3c));
+}
@@ -0,0 +1,165 @@
+//! Tests for IRI transformations.
+#![cfg(feature = "alloc")]
+
+use iri_string::format::ToDedicatedString;
+use iri_string::spec::IriSpec;
+use iri_string::transform::{
+    EnforceHttps, Error, Normalize, Pipeline, Relativize, StripQueryParams, Transform,
+};
+use iri_string::types::{IriAbsoluteStr, IriReferenceStr, IriReferenceString};
+
+const BASES: &[&str] = &[
+    "https://example.com/a/b/c?q",
+    "https://example.com/a/b/",
+    "https://example.com",
+    "https://user@example.com:8080/a",
+    "foo:a/b",
+    "foo:/a/b",
+];
+
+const TARGETS: &[&str] = &[
+    "https://example.com/a/b/c",
+    "https://example.com/a/b/d?x#y",
+    "https://example.com/a/b/",
+    "https://example.com/a/b/c:d",
+    "https://example.com/a/b//e",
+    "https://example.com/a/x",
+    "https://example.com/",
+    "https://example.com",
+    "https://example.com?q",
+    "https://example.com//double",
+    "https://example.org/a/b/c",
+    "https://user@example.com:8080/a/b",
+    "HTTPS://example.com/a/b/c",
+    "http://example.com/a/b/c",
+    "foo:a/c",
+    "foo:/a/c",
+    "foo:c",
+    "foo:",
+    "foo:#frag",
+    "bar:a/b",
+];
+
+#[test]
+fn relativize_roundtrip() {
+    for base in BASES {
+        let base = IriAbsoluteStr::new(base).expect("valid base IRI");
+        let relativize = Relativize::new(base);
+        for target in TARGETS {
+            let target = IriReferenceStr::new(target).expect("valid IRI");
+            let relative = relativize
+                .transform(target.to_owned())
+                .expect("never rejected");
+            assert!(
+                relative.as_str().len() <= target.as_str().len() + 2,
+                "base={base:?}, target={target:?}, relative={relative:?}"
+            );
+            // Compare normalized results since the scheme is compared
+            // case-insensitively.
+            let mut resolved = relative.resolve_against(base);
+            resolved.enable_normalization();
+            let mut expected = target.resolve_against(base);
+            expected.enable_normalization();
+            let resolved = resolved.to_dedicated_string();
+            let expected = expected.to_dedicated_string();
+            assert_eq!(
+                resolved, expected,
+                "base={base:?}, target={target:?}, relative={relative:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn relativize_examples() {
+    let base = IriAbsoluteStr::new("https://example.com/a/b/c?q").expect("valid base IRI");
+    let relativize = Relativize::new(base);
+    let apply = |s: &str| {
+        relativize
+            .transform(IriReferenceString::try_from(s).expect("valid IRI"))
+            .expect("never rejected")
+    };
+    assert_eq!(apply("https://example.com/a/b/c"), "c");
+    assert_eq!(apply("https://example.com/a/b/c:d"), "./c:d");
+    assert_eq!(apply("https://example.com/a/b//e"), ".//e");
+    assert_eq!(apply("https://example.com/a/x"), "/a/x");
+    assert_eq!(apply("https://example.com"), "//example.com");
+    assert_eq!(
+        apply("https://example.com//double"),
+        "//example.com//double"
+    );
+    assert_eq!(apply("../relative"), "../relative");
+}
+
+#[test]
+fn strip_query_params() {
+    let strip = StripQueryParams::tracking();
+    let apply = |s: &str| {
+        strip
+            .transform(IriReferenceString::try_from(s).expect("valid IRI"))
+            .expect("never rejected")
+    };
+    assert_eq!(
+        apply("https://example.com/?utm_source=a&utm_medium=b&id=1&fbclid=x"),
+        "https://example.com/?id=1"
+    );
+    // Other pairs are kept as is, including empty pairs and duplicates.
+    assert_eq!(apply("/?b=2&&a=1&gclid&a=%41"), "/?b=2&&a=1&a=%41");
+    assert_eq!(apply("/?utm_=1#utm_source=2"), "/#utm_source=2");
+    assert_eq!(apply("/?"), "/?");
+    assert_eq!(apply("/?UTM_SOURCE=1"), "/?UTM_SOURCE=1");
+    assert_eq!(apply("/?&fbclid=1"), "/?");
+}
+
+#[test]
+fn pipeline_order() {
+    let append = |suffix: &'static str| {
+        move |iri: IriReferenceString| {
+            IriReferenceString::try_from(format!("{iri}{suffix}")).map_err(|_| Error::new())
+        }
+    };
+    let pipeline = Pipeline::<IriSpec>::new()
+        .then(append("a"))
+        .then(Pipeline::new().then(append("b")).then(append("c")))
+        .then(append("d"));
+    assert_eq!(pipeline.len(), 3);
+    let iri = IriReferenceStr::new("x").expect("valid IRI");
+    assert_eq!(pipeline.apply(iri).expect("allowed"), "xabcd");
+
+    let rejecting = Pipeline::<IriSpec>::new().then(|_| Err(Error::new())).then(
+        |_: IriReferenceString| -> Result<IriReferenceString, Error> {
+            unreachable!("steps after the rejection are not applied")
+        },
+    );
+    assert!(rejecting.apply(iri).is_err());
+
+    let empty = Pipeline::<IriSpec>::default();
+    assert!(empty.is_empty());
+    assert_eq!(empty.apply(iri).expect("allowed"), "x");
+}
+
+#[test]
+fn pipeline_policy() {
+    let base = IriAbsoluteStr::new("https://example.com/").expect("valid base IRI");
+    let pipeline = Pipeline::<IriSpec>::new()
+        .then(Normalize)
+        .then(StripQueryParams::tracking())
+        .then(EnforceHttps)
+        .then(Relativize::new(base));
+    let apply = |s: &str| pipeline.apply(IriReferenceStr::new(s).expect("valid IRI"));
+
+    assert_eq!(
+        apply("http://EXAMPLE.com:80/%7e/./x?utm_campaign=1").expect("allowed"),
+        "~/x"
+    );
+    assert_eq!(
+        apply("http://example.com:/p?utm_campaign=1&").expect("allowed"),
+        "p?"
+    );
+    assert_eq!(
+        apply("https://example.com:443/p").expect("allowed"),
+        "//example.com:443/p"
+    );
+    assert_eq!(apply("../p").expect("allowed"), "../p");
+    assert!(apply("ws://example.com/").is_err());
+}
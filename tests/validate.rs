@@ -0,0 +1,310 @@
+//! Tests for validators.
+
+mod utils;
+
+use iri_string::spec::{IriSpec, UriSpec};
+use iri_string::validate::{
+    iri, iri_chars, iri_reference, iri_reference_chars, quick_check_iri, quick_check_iri_reference,
+    validate_all, QuickCheck, StreamValidator,
+};
+
+/// Returns the expected indices of invalid IRIs.
+fn expected_invalid_indices(inputs: &[&str], is_valid: impl Fn(&str) -> bool) -> Vec<usize> {
+    inputs
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| !is_valid(s))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[test]
+fn batch_validation() {
+    let inputs: Vec<&str> = utils::positive(utils::Spec::Iri, utils::Kind::Normal)
+        .chain(utils::negative(utils::Spec::Iri, utils::Kind::Normal))
+        .collect();
+
+    let expected_uri =
+        expected_invalid_indices(&inputs, |s| iri_string::types::UriStr::new(s).is_ok());
+    let invalid_uri: Vec<usize> = validate_all::<UriSpec, _>(&inputs).collect();
+    assert_eq!(invalid_uri, expected_uri);
+
+    let expected_iri =
+        expected_invalid_indices(&inputs, |s| iri_string::types::IriStr::new(s).is_ok());
+    let invalid_iri: Vec<usize> = validate_all::<IriSpec, _>(&inputs).collect();
+    assert_eq!(invalid_iri, expected_iri);
+    assert!(!invalid_iri.is_empty());
+
+    #[cfg(feature = "rayon")]
+    {
+        use iri_string::validate::par_validate_all;
+
+        assert_eq!(par_validate_all::<UriSpec, _>(&inputs), expected_uri);
+        assert_eq!(par_validate_all::<IriSpec, _>(&inputs), expected_iri);
+    }
+}
+
+#[test]
+fn batch_validation_empty() {
+    let inputs: [&str; 0] = [];
+    assert_eq!(validate_all::<IriSpec, _>(&inputs).next(), None);
+}
+
+/// Extra inputs for the streaming validator, focusing on authorities.
+const STREAM_EXTRA: &[&str] = &[
+    "//[::1]",
+    "//[::1]:80/",
+    "//user@[::1]:80/",
+    "//[::1]x",
+    "//x[::1]",
+    "//[v1.x]",
+    "//[v1.]",
+    "//[v.x]",
+    "//[vg.x]",
+    "//[1:2:3:4:5:6:7:8:9]",
+    "//[ffff:ffff:ffff:ffff:ffff:ffff:255.255.255.255]",
+    "//[ffff:ffff:ffff:ffff:ffff:ffff:255.255.255.2555]",
+    "//[]",
+    "//[::1",
+    "//a:b:c",
+    "//a:b:c@host",
+    "//a:80x",
+    "//a:80x@host:1",
+    "//a@b@c",
+    "//a@b:c",
+    "//:80",
+    "//:",
+    "//@",
+    "//%41:%42@%43:44",
+    "//%4",
+    "foo://[::1]/bar?baz#qux",
+    "foo:/bar:baz",
+    "foo:/",
+    "foo:%",
+    "a:b:c",
+    "a/b:c",
+    "-:x",
+    ":x",
+    "./a:b",
+    "?a:b",
+    "#a:b",
+    "#a#b",
+    "?a#b?c",
+    "//\u{E9}x@\u{E9}:1",
+    "//h?\u{E000}",
+    "//h#\u{E000}",
+    "/\u{E000}",
+];
+
+#[test]
+fn stream_validation() {
+    let inputs = utils::positive(utils::Spec::Iri, utils::Kind::Reference)
+        .chain(utils::negative(utils::Spec::Iri, utils::Kind::Reference))
+        .chain(STREAM_EXTRA.iter().copied());
+    for s in inputs {
+        assert_eq!(
+            iri_chars::<UriSpec, _>(s.chars()),
+            iri::<UriSpec>(s),
+            "URI: {:?}",
+            s
+        );
+        assert_eq!(
+            iri_chars::<IriSpec, _>(s.chars()),
+            iri::<IriSpec>(s),
+            "IRI: {:?}",
+            s
+        );
+        assert_eq!(
+            iri_reference_chars::<UriSpec, _>(s.chars()),
+            iri_reference::<UriSpec>(s),
+            "URI reference: {:?}",
+            s
+        );
+        assert_eq!(
+            iri_reference_chars::<IriSpec, _>(s.chars()),
+            iri_reference::<IriSpec>(s),
+            "IRI reference: {:?}",
+            s
+        );
+    }
+}
+
+#[test]
+fn quick_check() {
+    /// Asserts that the quick check result does not contradict with the full validation.
+    fn assert_consistent(quick: QuickCheck, full: bool, s: &str) {
+        match quick {
+            QuickCheck::Valid => assert!(full, "quick check wrongly accepted {:?}", s),
+            QuickCheck::Invalid => assert!(!full, "quick check wrongly rejected {:?}", s),
+            QuickCheck::Indeterminate => {}
+        }
+    }
+
+    let inputs = utils::positive(utils::Spec::Iri, utils::Kind::Reference)
+        .chain(utils::negative(utils::Spec::Iri, utils::Kind::Reference))
+        .chain(STREAM_EXTRA.iter().copied())
+        .chain([
+            "",
+            "a",
+            "//",
+            "foo:",
+            "foo://a!b/c",
+            "1foo:bar",
+            "a b",
+            "a\u{7F}",
+        ]);
+    for s in inputs {
+        assert_consistent(quick_check_iri::<UriSpec>(s), iri::<UriSpec>(s).is_ok(), s);
+        assert_consistent(quick_check_iri::<IriSpec>(s), iri::<IriSpec>(s).is_ok(), s);
+        assert_consistent(
+            quick_check_iri_reference::<UriSpec>(s),
+            iri_reference::<UriSpec>(s).is_ok(),
+            s,
+        );
+        assert_consistent(
+            quick_check_iri_reference::<IriSpec>(s),
+            iri_reference::<IriSpec>(s).is_ok(),
+            s,
+        );
+    }
+
+    assert_eq!(quick_check_iri::<UriSpec>("foo://a!b/c"), QuickCheck::Valid);
+    assert_eq!(quick_check_iri::<UriSpec>("1foo:bar"), QuickCheck::Invalid);
+    assert_eq!(
+        quick_check_iri_reference::<UriSpec>("//"),
+        QuickCheck::Valid
+    );
+    assert_eq!(
+        quick_check_iri_reference::<UriSpec>("a\u{7F}"),
+        QuickCheck::Invalid
+    );
+}
+
+#[test]
+fn quick_check_non_ascii_scheme() {
+    use iri_string::validate::{
+        quick_check_iri_reference_then_validate, quick_check_iri_then_validate,
+    };
+
+    // Non-ASCII characters before the first colon.
+    for s in ["a\u{03B1}:b", "\u{03B1}:b", "a\u{3042}b:c", "a\u{03B1}/b:c"] {
+        assert_ne!(quick_check_iri::<IriSpec>(s), QuickCheck::Valid, "{:?}", s);
+        assert_ne!(quick_check_iri::<UriSpec>(s), QuickCheck::Valid, "{:?}", s);
+        assert_eq!(
+            quick_check_iri_then_validate::<IriSpec>(s).is_ok(),
+            iri::<IriSpec>(s).is_ok(),
+            "{:?}",
+            s
+        );
+        if quick_check_iri_reference::<IriSpec>(s) == QuickCheck::Invalid {
+            assert!(iri_reference::<IriSpec>(s).is_err(), "{:?}", s);
+        }
+        assert_eq!(
+            quick_check_iri_reference_then_validate::<IriSpec>(s).is_ok(),
+            iri_reference::<IriSpec>(s).is_ok(),
+            "{:?}",
+            s
+        );
+        assert_eq!(
+            quick_check_iri_reference_then_validate::<UriSpec>(s).is_ok(),
+            iri_reference::<UriSpec>(s).is_ok(),
+            "{:?}",
+            s
+        );
+    }
+}
+
+#[test]
+fn stream_validation_stays_failed() {
+    let mut validator = StreamValidator::<UriSpec>::iri_reference();
+    assert!(validator.push_str("foo bar").is_err());
+    assert!(validator.push('x').is_err());
+    assert!(validator.finish().is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_validation_reader() {
+    use iri_string::validate::{iri_reader, iri_reference_reader};
+
+    /// Reader returning one byte at a time.
+    struct ByteByByte<'a>(&'a [u8]);
+
+    impl std::io::Read for ByteByByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match (self.0.split_first(), buf.first_mut()) {
+                (Some((&b, rest)), Some(dest)) => {
+                    *dest = b;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    let long = format!("https://example.com/{}?q", "\u{E9}/".repeat(10_000));
+    assert!(iri_reader::<IriSpec, _>(long.as_bytes()).is_ok());
+    assert!(iri_reader::<IriSpec, _>(ByteByByte(long.as_bytes())).is_ok());
+    assert!(iri_reader::<UriSpec, _>(long.as_bytes()).is_err());
+
+    let err = iri_reference_reader::<IriSpec, _>(ByteByByte(b"/foo\xC3")).expect_err("incomplete");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    let err = iri_reader::<IriSpec, _>(&b"foo:%zz"[..]).expect_err("invalid IRI");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err
+        .get_ref()
+        .map_or(false, |e| e.is::<iri_string::validate::Error>()));
+}
+
+#[test]
+fn parser_budget() {
+    use iri_string::validate::{BudgetedError, ParserBudget};
+
+    let unlimited = ParserBudget::new();
+    let inputs = utils::positive(utils::Spec::Iri, utils::Kind::Reference)
+        .chain(utils::negative(utils::Spec::Iri, utils::Kind::Reference));
+    for s in inputs {
+        assert_eq!(
+            unlimited.iri_reference::<IriSpec>(s),
+            iri_reference::<IriSpec>(s).map_err(BudgetedError::Invalid),
+            "input={:?}",
+            s
+        );
+    }
+
+    let budget = ParserBudget::new().max_len(16).max_components(3);
+    // [(input, expected)]
+    const CASES: &[(&str, Option<bool>)] = &[
+        ("foo:", Some(true)),
+        ("foo:a/b/c", Some(true)),
+        ("foo:a/b/c/d", None),
+        ("foo:/a?b&c", Some(true)),
+        ("foo:/a?b&c&d", None),
+        ("foo:?&&", Some(true)),
+        ("foo:0123456789ab", Some(true)),
+        ("foo:0123456789abc", None),
+        ("<foo>", Some(false)),
+        // The length is checked before the validation.
+        ("<foo>0123456789abc", None),
+    ];
+    for (input, expected) in CASES.iter().copied() {
+        let actual = match budget.iri::<UriSpec>(input) {
+            Ok(()) => Some(true),
+            Err(BudgetedError::Invalid(_)) => Some(false),
+            Err(BudgetedError::BudgetExceeded) => None,
+            Err(_) => unreachable!("unknown error"),
+        };
+        assert_eq!(actual, expected, "input={:?}", input);
+    }
+}
+
+#[test]
+fn absolute_iri_rejects_empty_fragment() {
+    use iri_string::validate::absolute_iri;
+
+    assert!(absolute_iri::<IriSpec>("foo:bar").is_ok());
+    assert!(absolute_iri::<IriSpec>("foo:bar#").is_err());
+    assert!(absolute_iri::<UriSpec>("foo:bar#").is_err());
+    assert!(iri::<IriSpec>("foo:bar#").is_ok());
+}
@@ -0,0 +1,85 @@
+//! Tests for non-mutating component replacement.
+#![cfg(feature = "alloc")]
+
+use iri_string::types::{IriFragmentStr, IriQueryStr, IriStr, UriStr};
+
+#[test]
+fn with_scheme() {
+    let iri = IriStr::new("http://example.com/").expect("should be valid");
+    assert_eq!(
+        iri.with_scheme("https").expect("valid"),
+        "https://example.com/"
+    );
+    assert_eq!(
+        iri.with_scheme("A+b-c.d").expect("valid"),
+        "A+b-c.d://example.com/"
+    );
+    for scheme in ["", "1a", "a b", "a:"] {
+        assert!(iri.with_scheme(scheme).is_err(), "scheme={:?}", scheme);
+    }
+}
+
+#[test]
+fn with_host() {
+    // [(iri, host, expected)]
+    const CASES: &[(&str, &str, Option<&str>)] = &[
+        (
+            "http://example.com/",
+            "example.org",
+            Some("http://example.org/"),
+        ),
+        (
+            "http://u:p@example.com:80/?q#f",
+            "",
+            Some("http://u:p@:80/?q#f"),
+        ),
+        ("http://example.com", "192.0.2.1", Some("http://192.0.2.1")),
+        ("foo:", "example.com", Some("foo://example.com")),
+        ("foo:/a/b?q", "example.com", Some("foo://example.com/a/b?q")),
+        ("foo:a/b", "example.com", None),
+        ("http://example.com/", "[::1", None),
+        ("http://example.com/", "a:b", None),
+        ("http://example.com/", "a/b", None),
+    ];
+    for &(iri, host, expected) in CASES {
+        let iri = IriStr::new(iri).expect("should be valid");
+        let result = iri.with_host(host);
+        assert_eq!(
+            result.as_ref().map(|iri| iri.as_str()).ok(),
+            expected,
+            "iri={:?}, host={:?}",
+            iri,
+            host
+        );
+    }
+
+    let uri = UriStr::new("http://example.com/").expect("should be valid");
+    assert!(uri.with_host("\u{03B1}.example").is_err());
+    let iri = IriStr::new("http://example.com/").expect("should be valid");
+    assert!(iri.with_host("\u{03B1}.example").is_ok());
+}
+
+#[test]
+fn with_query_and_fragment() {
+    let iri = IriStr::new("http://example.com/?#").expect("should be valid");
+    let query = IriQueryStr::new("a=1?b").expect("valid query");
+    let fragment = IriFragmentStr::new("top").expect("valid fragment");
+
+    assert_eq!(iri.with_query(Some(query)), "http://example.com/?a=1?b#");
+    assert_eq!(iri.with_query(None), "http://example.com/#");
+    assert_eq!(
+        iri.with_fragment(Some(fragment)),
+        "http://example.com/?#top"
+    );
+    assert_eq!(iri.with_fragment(None), "http://example.com/?");
+
+    let chained = iri
+        .with_scheme("https")
+        .and_then(|iri| iri.with_host("example.org"))
+        .expect("valid")
+        .with_query(Some(query))
+        .with_fragment(None);
+    assert_eq!(chained, "https://example.org/?a=1?b");
+    // The original is not modified.
+    assert_eq!(iri, "http://example.com/?#");
+}
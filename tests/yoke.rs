@@ -0,0 +1,38 @@
+//! Tests for `yoke` integration.
+#![cfg(feature = "yoke")]
+
+use yoke::Yoke;
+
+use iri_string::components::AuthorityComponents;
+use iri_string::lenient::Components;
+use iri_string::types::{IriReferenceString, IriString};
+
+#[test]
+fn yoke_authority_components() {
+    let iri = IriString::try_from("https://user@example.com:8080/path").expect("valid IRI");
+    let yoked: Yoke<AuthorityComponents<'static>, IriString> =
+        Yoke::try_attach_to_cart(iri, |iri| iri.authority_components().ok_or(()))
+            .expect("authority should exist");
+
+    // The bundle can be moved freely.
+    let moved = Box::new(yoked);
+    let authority = moved.get();
+    assert_eq!(authority.userinfo(), Some("user"));
+    assert_eq!(authority.host(), "example.com");
+    assert_eq!(authority.port(), Some("8080"));
+    assert_eq!(moved.backing_cart(), "https://user@example.com:8080/path");
+}
+
+#[test]
+fn yoke_lenient_components() {
+    let iri = IriReferenceString::try_from("javascript://example.com/%0Aalert(1)?q#f")
+        .expect("valid IRI reference");
+    let yoked: Yoke<Components<'static>, IriReferenceString> =
+        Yoke::attach_to_cart(iri, |iri| Components::from_iri(iri));
+
+    let components = yoked.get();
+    assert!(components.has_opaque_path());
+    assert_eq!(components.path(), "//example.com/%0Aalert(1)");
+    assert_eq!(components.query(), Some("q"));
+    assert_eq!(components.fragment(), Some("f"));
+}